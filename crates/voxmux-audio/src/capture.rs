@@ -1,11 +1,13 @@
-use voxmux_core::{AudioChunk, AudioError};
+use crate::device::DeviceManager;
+use crate::format::samples_to_f32;
+use crate::tone_decode::ToneDecoder;
+use voxmux_core::{AudioChunk, AudioError, TapSender};
 use cpal::traits::DeviceTrait;
-use cpal::{Device, SampleRate, Stream, StreamConfig};
+use cpal::{Device, FromSample, SampleFormat, SampleRate, SizedSample, Stream, StreamConfig};
 use ringbuf::traits::Producer;
 use ringbuf::HeapProd;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
-use std::sync::{Arc, Mutex};
-use tokio::sync::mpsc;
+use std::sync::Arc;
 use voxmux_core::InputStatus;
 
 const STATUS_OK: u8 = 0;
@@ -52,6 +54,81 @@ impl CaptureHandle {
     }
 }
 
+// ── CaptureTaps ───────────────────────────────────────────────
+
+/// Consumers of captured audio besides the mixer.
+#[derive(Default)]
+pub struct CaptureTaps {
+    pub asr: Option<TapSender>,
+    pub tone_decoder: Option<ToneDecoder>,
+}
+
+// ── CaptureCallback ───────────────────────────────────────────
+
+/// State owned by the device callback, independent of the device sample format.
+struct CaptureCallback {
+    producer: HeapProd<f32>,
+    enabled: Arc<AtomicBool>,
+    asr_tap: Option<TapSender>,
+    tone_decoder: Option<ToneDecoder>,
+    sample_rate: u32,
+    channels: u16,
+    scratch: Vec<f32>,
+}
+
+impl CaptureCallback {
+    fn process<T>(&mut self, data: &[T])
+    where
+        T: SizedSample,
+        f32: FromSample<T>,
+    {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+        samples_to_f32(data, &mut self.scratch);
+        // Push as much as we can; overflow is silently dropped
+        self.producer.push_slice(&self.scratch);
+        if let Some(ref mut decoder) = self.tone_decoder {
+            decoder.process(&self.scratch);
+        }
+        if let Some(ref tap) = self.asr_tap {
+            let strip = self.tone_decoder.as_ref().is_some_and(ToneDecoder::should_strip);
+            let chunk = AudioChunk {
+                samples: if strip {
+                    vec![0.0; self.scratch.len()]
+                } else {
+                    self.scratch.clone()
+                },
+                sample_rate: self.sample_rate,
+                channels: self.channels,
+            };
+            // Never blocks: a full tap applies its overflow policy
+            let _ = tap.send(chunk);
+        }
+    }
+}
+
+fn build_input<T, E>(
+    device: &Device,
+    config: &StreamConfig,
+    mut callback: CaptureCallback,
+    err_callback: E,
+) -> Result<Stream, AudioError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| callback.process(data),
+            err_callback,
+            None,
+        )
+        .map_err(|e| AudioError::StreamBuild(e.to_string()))
+}
+
 // ── CaptureNode ───────────────────────────────────────────────
 
 pub struct CaptureNode {
@@ -59,13 +136,15 @@ pub struct CaptureNode {
 }
 
 impl CaptureNode {
+    /// Open `device` in its native sample format (preferring f32) and capture into
+    /// `producer`, converting to f32 in the callback. `taps` receive the same audio.
     pub fn new(
         device: &Device,
         producer: HeapProd<f32>,
         sample_rate: u32,
         channels: u16,
         buffer_size: u32,
-        asr_tap: Option<mpsc::UnboundedSender<AudioChunk>>,
+        taps: CaptureTaps,
         id: &str,
     ) -> Result<(Self, CaptureHandle), AudioError> {
         let config = StreamConfig {
@@ -73,10 +152,10 @@ impl CaptureNode {
             sample_rate: SampleRate(sample_rate),
             buffer_size: cpal::BufferSize::Fixed(buffer_size),
         };
+        let format = DeviceManager::input_sample_format(device, sample_rate, channels)?;
+        tracing::info!("input '{}' opened as {}", id, format);
 
-        let producer = Arc::new(Mutex::new(producer));
         let enabled = Arc::new(AtomicBool::new(true));
-        let enabled_flag = Arc::clone(&enabled);
         let status = Arc::new(AtomicU8::new(STATUS_OK));
         let status_flag = Arc::clone(&status);
 
@@ -85,30 +164,31 @@ impl CaptureNode {
             status_flag.store(STATUS_ERROR, Ordering::Relaxed);
         };
 
-        let stream = device
-            .build_input_stream(
-                &config,
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    if !enabled_flag.load(Ordering::Relaxed) {
-                        return;
-                    }
-                    if let Ok(mut prod) = producer.lock() {
-                        // Push as much as we can; overflow is silently dropped
-                        prod.push_slice(data);
-                    }
-                    if let Some(ref tap) = asr_tap {
-                        let chunk = AudioChunk {
-                            samples: data.to_vec(),
-                            sample_rate,
-                            channels,
-                        };
-                        let _ = tap.send(chunk);
-                    }
-                },
-                err_callback,
-                None,
-            )
-            .map_err(|e| AudioError::StreamBuild(e.to_string()))?;
+        let callback = CaptureCallback {
+            producer,
+            enabled: Arc::clone(&enabled),
+            asr_tap: taps.asr,
+            tone_decoder: taps.tone_decoder,
+            sample_rate,
+            channels,
+            scratch: Vec::with_capacity(buffer_size as usize * channels as usize),
+        };
+
+        let stream = match format {
+            SampleFormat::F32 => build_input::<f32, _>(device, &config, callback, err_callback),
+            SampleFormat::F64 => build_input::<f64, _>(device, &config, callback, err_callback),
+            SampleFormat::I8 => build_input::<i8, _>(device, &config, callback, err_callback),
+            SampleFormat::I16 => build_input::<i16, _>(device, &config, callback, err_callback),
+            SampleFormat::I32 => build_input::<i32, _>(device, &config, callback, err_callback),
+            SampleFormat::I64 => build_input::<i64, _>(device, &config, callback, err_callback),
+            SampleFormat::U8 => build_input::<u8, _>(device, &config, callback, err_callback),
+            SampleFormat::U16 => build_input::<u16, _>(device, &config, callback, err_callback),
+            SampleFormat::U32 => build_input::<u32, _>(device, &config, callback, err_callback),
+            SampleFormat::U64 => build_input::<u64, _>(device, &config, callback, err_callback),
+            other => Err(AudioError::StreamBuild(format!(
+                "unsupported sample format: {other}"
+            ))),
+        }?;
 
         let handle = CaptureHandle {
             enabled,
@@ -122,8 +202,9 @@ impl CaptureNode {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use voxmux_core::AudioChunk;
-    use tokio::sync::mpsc;
+    use ringbuf::traits::{Consumer, Split};
+    use ringbuf::{HeapCons, HeapRb};
+    use voxmux_core::{asr_tap, AudioChunk, OverflowPolicy};
 
     fn make_capture_handle(id: &str) -> CaptureHandle {
         CaptureHandle {
@@ -173,7 +254,7 @@ mod tests {
 
     #[test]
     fn test_asr_tap_send_receives_chunk() {
-        let (tx, mut rx) = mpsc::unbounded_channel::<AudioChunk>();
+        let (tx, mut rx) = asr_tap(1000, OverflowPolicy::DropOldest);
         let chunk = AudioChunk {
             samples: vec![0.1, 0.2, 0.3],
             sample_rate: 48000,
@@ -189,7 +270,7 @@ mod tests {
 
     #[test]
     fn test_asr_tap_none_does_not_panic() {
-        let tap: Option<mpsc::UnboundedSender<AudioChunk>> = None;
+        let tap: Option<TapSender> = None;
         // Simulating the callback logic
         if let Some(ref tx) = tap {
             let chunk = AudioChunk {
@@ -204,7 +285,7 @@ mod tests {
 
     #[test]
     fn test_asr_tap_dropped_receiver_does_not_panic() {
-        let (tx, rx) = mpsc::unbounded_channel::<AudioChunk>();
+        let (tx, rx) = asr_tap(1000, OverflowPolicy::DropOldest);
         drop(rx);
         let chunk = AudioChunk {
             samples: vec![0.0; 480],
@@ -214,4 +295,114 @@ mod tests {
         // `let _ = tx.send(...)` should not panic even with a dropped receiver
         let _ = tx.send(chunk);
     }
+
+    #[test]
+    fn test_asr_tap_full_does_not_block() {
+        // 10 ms of 48 kHz mono; the callback keeps sending long after it is full
+        let (tx, _rx) = asr_tap(10, OverflowPolicy::DropOldest);
+        for _ in 0..100 {
+            let chunk = AudioChunk {
+                samples: vec![0.0; 480],
+                sample_rate: 48000,
+                channels: 1,
+            };
+            tx.send(chunk).unwrap();
+        }
+        let state = tx.stats().queue_state();
+        assert_eq!(state.queued_ms, 10);
+        assert_eq!(state.dropped_chunks, 99);
+    }
+
+    fn make_callback(tap: Option<TapSender>) -> (CaptureCallback, HeapCons<f32>) {
+        let (producer, consumer) = HeapRb::<f32>::new(256).split();
+        let callback = CaptureCallback {
+            producer,
+            enabled: Arc::new(AtomicBool::new(true)),
+            asr_tap: tap,
+            tone_decoder: None,
+            sample_rate: 48000,
+            channels: 1,
+            scratch: Vec::new(),
+        };
+        (callback, consumer)
+    }
+
+    #[test]
+    fn test_capture_callback_converts_i16_for_mixer_and_tap() {
+        let (tx, mut rx) = asr_tap(1000, OverflowPolicy::DropOldest);
+        let (mut callback, mut consumer) = make_callback(Some(tx));
+
+        callback.process(&[0i16, i16::MIN, 16384]);
+
+        let mut mixed = [9.0f32; 3];
+        assert_eq!(consumer.pop_slice(&mut mixed), 3);
+        assert_eq!(mixed[0], 0.0);
+        assert_eq!(mixed[1], -1.0);
+        assert!((mixed[2] - 0.5).abs() < 1e-4);
+
+        let chunk = rx.try_recv().unwrap();
+        assert_eq!(chunk.samples, mixed.to_vec());
+        assert_eq!(chunk.sample_rate, 48000);
+    }
+
+    #[test]
+    fn test_capture_callback_converts_i32_and_u16() {
+        let (mut callback, mut consumer) = make_callback(None);
+        callback.process(&[i32::MIN, 0]);
+        callback.process(&[32768u16, 0]);
+
+        let mut out = [9.0f32; 4];
+        assert_eq!(consumer.pop_slice(&mut out), 4);
+        assert_eq!(out, [-1.0, 0.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn test_capture_callback_disabled_drops_data() {
+        let (tx, mut rx) = asr_tap(1000, OverflowPolicy::DropOldest);
+        let (mut callback, mut consumer) = make_callback(Some(tx));
+        callback.enabled.store(false, Ordering::Relaxed);
+
+        callback.process(&[0.5f32; 8]);
+
+        assert!(consumer.try_pop().is_none());
+        assert!(rx.try_recv().is_none());
+    }
+
+    #[test]
+    fn test_capture_callback_strips_dtmf_from_asr_tap() {
+        use voxmux_core::config::ToneDecodeConfig;
+
+        let (tx, mut rx) = asr_tap(5000, OverflowPolicy::DropOldest);
+        let (events_tx, mut events_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (mut callback, mut consumer) = make_callback(Some(tx));
+        let config = ToneDecodeConfig {
+            strip_dtmf: true,
+            ..Default::default()
+        };
+        callback.tone_decoder = Some(ToneDecoder::new("radio", 48000, 1, &config, events_tx));
+
+        // Key '1' (697 + 1209 Hz) in 1024-sample device buffers
+        let key: Vec<f32> = (0..4096)
+            .map(|i| {
+                let t = i as f32 / 48000.0;
+                0.25 * (std::f32::consts::TAU * 697.0 * t).sin()
+                    + 0.25 * (std::f32::consts::TAU * 1209.0 * t).sin()
+            })
+            .collect();
+        for block in key.chunks(1024) {
+            callback.process(block);
+            let mut sink = [0.0f32; 1024];
+            let n = consumer.pop_slice(&mut sink);
+            // The mixer always receives the original audio
+            assert_eq!(sink[..n], block[..n]);
+        }
+
+        let chunks: Vec<_> = std::iter::from_fn(|| rx.try_recv()).collect();
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks[0].samples.iter().any(|&s| s != 0.0));
+        assert!(chunks[3].samples.iter().all(|&s| s == 0.0));
+
+        let event = events_rx.try_recv().unwrap();
+        assert_eq!(event.kind, voxmux_core::ToneEventKind::Dtmf { digit: '1' });
+    }
 }
@@ -0,0 +1,282 @@
+use crate::player::{convert_clip, PlayerHandle};
+use crate::wav::read_wav;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use voxmux_core::config::{CueConfig, CueEventKind};
+use voxmux_core::AudioError;
+
+/// Something that happened which may have a sound cue attached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CueEvent<'a> {
+    /// Final recognition text from an input.
+    Keyword { input_id: &'a str, text: &'a str },
+    InputError { input_id: &'a str },
+    DestinationError { destination: &'a str },
+    /// An alert rule matched.
+    Alert { rule: &'a str },
+}
+
+struct LoadedCue {
+    event: CueEventKind,
+    /// Lower-cased keyword.
+    keyword: Option<String>,
+    source: Option<String>,
+    min_interval: Duration,
+    /// Mixer-format samples with the cue volume applied.
+    samples: Vec<f32>,
+    last_played: Option<Instant>,
+}
+
+impl LoadedCue {
+    fn matches(&self, event: &CueEvent) -> bool {
+        let source_ok = |id: &str| self.source.as_deref().is_none_or(|s| s == id);
+        match *event {
+            CueEvent::Keyword { input_id, text } => {
+                self.event == CueEventKind::Keyword
+                    && source_ok(input_id)
+                    && self
+                        .keyword
+                        .as_ref()
+                        .is_some_and(|k| text.to_lowercase().contains(k.as_str()))
+            }
+            CueEvent::InputError { input_id } => {
+                self.event == CueEventKind::InputError && source_ok(input_id)
+            }
+            CueEvent::DestinationError { destination } => {
+                self.event == CueEventKind::DestinationError && source_ok(destination)
+            }
+            CueEvent::Alert { rule } => self.event == CueEventKind::Alert && source_ok(rule),
+        }
+    }
+}
+
+// ── CueBank ────────────────────────────────────────────────────
+
+/// Configured `[[cues]]`, loaded into memory and played on a [`PlayerHandle`]
+/// when their event fires. Each cue is rate-limited on its own.
+#[derive(Clone)]
+pub struct CueBank {
+    cues: Arc<Mutex<Vec<LoadedCue>>>,
+    muted: Arc<AtomicBool>,
+    player: PlayerHandle,
+}
+
+impl CueBank {
+    /// Load every cue file and convert it to the mixer format.
+    pub fn load(
+        configs: &[CueConfig],
+        sample_rate: u32,
+        channels: u16,
+        player: PlayerHandle,
+    ) -> Result<Self, AudioError> {
+        let mut cues = Vec::with_capacity(configs.len());
+        for config in configs {
+            if config.event == CueEventKind::Keyword && config.keyword.is_none() {
+                return Err(AudioError::Wav(format!(
+                    "keyword cue '{}' has no keyword",
+                    config.file
+                )));
+            }
+            let (samples, rate, chans) = read_wav(Path::new(&config.file))?;
+            let samples = convert_clip(&samples, rate, chans, sample_rate, channels)
+                .into_iter()
+                .map(|s| s * config.volume)
+                .collect();
+            cues.push(LoadedCue {
+                event: config.event,
+                keyword: config.keyword.as_ref().map(|k| k.to_lowercase()),
+                source: config.source.clone(),
+                min_interval: Duration::from_millis(config.min_interval_ms as u64),
+                samples,
+                last_played: None,
+            });
+        }
+        Ok(Self {
+            cues: Arc::new(Mutex::new(cues)),
+            muted: Arc::new(AtomicBool::new(false)),
+            player,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.cues.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted.load(Ordering::Relaxed)
+    }
+
+    pub fn set_muted(&self, muted: bool) {
+        self.muted.store(muted, Ordering::Relaxed);
+    }
+
+    /// Queue every cue matching `event`. Returns the number queued.
+    pub fn trigger(&self, event: &CueEvent) -> usize {
+        self.trigger_at(event, Instant::now())
+    }
+
+    pub fn trigger_at(&self, event: &CueEvent, now: Instant) -> usize {
+        if self.is_muted() {
+            return 0;
+        }
+        let mut played = 0;
+        for cue in self.cues.lock().unwrap().iter_mut() {
+            if !cue.matches(event) {
+                continue;
+            }
+            if cue
+                .last_played
+                .is_some_and(|t| now.duration_since(t) < cue.min_interval)
+            {
+                continue;
+            }
+            cue.last_played = Some(now);
+            self.player.enqueue("cue", cue.samples.clone());
+            played += 1;
+        }
+        played
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::ClipPlayer;
+    use crate::wav::write_wav;
+    use ringbuf::traits::Split;
+    use ringbuf::HeapRb;
+
+    struct Fixture {
+        dir: std::path::PathBuf,
+    }
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir()
+                .join(format!("voxmux-cue-{}-{}", name, std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            // 10 ms beep at 8 kHz
+            write_wav(&dir.join("beep.wav"), &[0.5; 80], 8000, 1).unwrap();
+            Self { dir }
+        }
+
+        fn cue(&self, event: CueEventKind) -> CueConfig {
+            CueConfig {
+                event,
+                file: self.dir.join("beep.wav").display().to_string(),
+                volume: 0.5,
+                keyword: None,
+                source: None,
+                min_interval_ms: 1000,
+            }
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn bank(configs: &[CueConfig]) -> (CueBank, PlayerHandle) {
+        let (prod, _cons) = HeapRb::<f32>::new(64).split();
+        let player = ClipPlayer::new(16000, 2, prod).handle();
+        (CueBank::load(configs, 16000, 2, player.clone()).unwrap(), player)
+    }
+
+    #[test]
+    fn test_cue_loaded_in_mixer_format() {
+        let fx = Fixture::new("format");
+        let (cues, player) = bank(&[fx.cue(CueEventKind::InputError)]);
+        assert_eq!(cues.trigger(&CueEvent::InputError { input_id: "mic" }), 1);
+        let (label, secs) = player.now_playing().unwrap();
+        assert_eq!(label, "cue");
+        assert!((secs - 0.01).abs() < 1e-4, "{secs}");
+    }
+
+    #[test]
+    fn test_cue_keyword_match_is_case_insensitive() {
+        let fx = Fixture::new("keyword");
+        let cue = CueConfig {
+            keyword: Some("Mayday".into()),
+            ..fx.cue(CueEventKind::Keyword)
+        };
+        let (cues, _) = bank(&[cue]);
+        let event = |text| CueEvent::Keyword {
+            input_id: "rx",
+            text,
+        };
+        assert_eq!(cues.trigger(&event("all quiet")), 0);
+        assert_eq!(cues.trigger(&event("MAYDAY mayday")), 1);
+    }
+
+    #[test]
+    fn test_cue_source_filter_and_event_kind() {
+        let fx = Fixture::new("source");
+        let cue = CueConfig {
+            source: Some("discord".into()),
+            ..fx.cue(CueEventKind::DestinationError)
+        };
+        let (cues, _) = bank(&[cue]);
+        assert_eq!(cues.trigger(&CueEvent::InputError { input_id: "discord" }), 0);
+        assert_eq!(cues.trigger(&CueEvent::DestinationError { destination: "file" }), 0);
+        assert_eq!(cues.trigger(&CueEvent::DestinationError { destination: "discord" }), 1);
+    }
+
+    #[test]
+    fn test_cue_alert_rule_filter() {
+        let fx = Fixture::new("alert");
+        let any = fx.cue(CueEventKind::Alert);
+        let distress = CueConfig {
+            source: Some("distress".into()),
+            ..fx.cue(CueEventKind::Alert)
+        };
+        let (cues, _) = bank(&[any, distress]);
+        assert_eq!(cues.trigger(&CueEvent::Alert { rule: "callsign" }), 1);
+        assert_eq!(cues.trigger(&CueEvent::Alert { rule: "distress" }), 1);
+        assert_eq!(cues.trigger(&CueEvent::InputError { input_id: "distress" }), 0);
+    }
+
+    #[test]
+    fn test_cue_rate_limited() {
+        let fx = Fixture::new("rate");
+        let (cues, _) = bank(&[fx.cue(CueEventKind::InputError)]);
+        let event = CueEvent::InputError { input_id: "mic" };
+        let t0 = Instant::now();
+        assert_eq!(cues.trigger_at(&event, t0), 1);
+        assert_eq!(cues.trigger_at(&event, t0 + Duration::from_millis(500)), 0);
+        assert_eq!(cues.trigger_at(&event, t0 + Duration::from_millis(1000)), 1);
+    }
+
+    #[test]
+    fn test_cue_muted() {
+        let fx = Fixture::new("muted");
+        let (cues, player) = bank(&[fx.cue(CueEventKind::InputError)]);
+        cues.clone().set_muted(true);
+        assert!(cues.is_muted());
+        assert_eq!(cues.trigger(&CueEvent::InputError { input_id: "mic" }), 0);
+        assert!(!player.is_playing());
+    }
+
+    #[test]
+    fn test_cue_load_errors() {
+        let fx = Fixture::new("errors");
+        let (prod, _cons) = HeapRb::<f32>::new(4).split();
+        let player = ClipPlayer::new(16000, 1, prod).handle();
+
+        let keyless = fx.cue(CueEventKind::Keyword);
+        assert!(CueBank::load(&[keyless], 16000, 1, player.clone()).is_err());
+
+        let missing = CueConfig {
+            file: fx.dir.join("missing.wav").display().to_string(),
+            ..fx.cue(CueEventKind::InputError)
+        };
+        assert!(CueBank::load(&[missing], 16000, 1, player).is_err());
+    }
+}
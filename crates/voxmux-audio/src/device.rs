@@ -1,6 +1,7 @@
+use crate::format::choose_sample_format;
 use voxmux_core::AudioError;
 use cpal::traits::{DeviceTrait, HostTrait};
-use cpal::{Device, Host};
+use cpal::{Device, Host, SampleFormat, SupportedStreamConfigRange};
 
 pub struct DeviceManager {
     host: Host,
@@ -90,4 +91,42 @@ impl DeviceManager {
             name
         )))
     }
+
+    /// Query `device`'s supported input configs and pick the native sample format
+    /// to capture `sample_rate`/`channels` with (f32 preferred).
+    pub fn input_sample_format(
+        device: &Device,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<SampleFormat, AudioError> {
+        let ranges: Vec<SupportedStreamConfigRange> = device
+            .supported_input_configs()
+            .map_err(|e| AudioError::StreamBuild(e.to_string()))?
+            .collect();
+        choose_sample_format(&ranges, sample_rate, channels).ok_or_else(|| {
+            AudioError::StreamBuild(format!(
+                "input device supports no format at {} Hz, {} ch",
+                sample_rate, channels
+            ))
+        })
+    }
+
+    /// Query `device`'s supported output configs and pick the native sample format
+    /// to play `sample_rate`/`channels` with (f32 preferred).
+    pub fn output_sample_format(
+        device: &Device,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<SampleFormat, AudioError> {
+        let ranges: Vec<SupportedStreamConfigRange> = device
+            .supported_output_configs()
+            .map_err(|e| AudioError::StreamBuild(e.to_string()))?
+            .collect();
+        choose_sample_format(&ranges, sample_rate, channels).ok_or_else(|| {
+            AudioError::StreamBuild(format!(
+                "output device supports no format at {} Hz, {} ch",
+                sample_rate, channels
+            ))
+        })
+    }
 }
@@ -0,0 +1,186 @@
+use cpal::{FromSample, Sample, SampleFormat, SupportedStreamConfigRange};
+
+/// Device sample formats in order of preference. `f32` needs no conversion;
+/// after that, the widest integer formats lose the least precision.
+pub const PREFERRED_FORMATS: [SampleFormat; 10] = [
+    SampleFormat::F32,
+    SampleFormat::I32,
+    SampleFormat::I16,
+    SampleFormat::F64,
+    SampleFormat::I64,
+    SampleFormat::U32,
+    SampleFormat::U16,
+    SampleFormat::I8,
+    SampleFormat::U8,
+    SampleFormat::U64,
+];
+
+/// Pick the best sample format among `ranges` that supports `sample_rate` and `channels`.
+/// Returns `None` when no range matches the requested rate and channel count.
+pub fn choose_sample_format(
+    ranges: &[SupportedStreamConfigRange],
+    sample_rate: u32,
+    channels: u16,
+) -> Option<SampleFormat> {
+    let supported: Vec<SampleFormat> = ranges
+        .iter()
+        .filter(|r| {
+            r.channels() == channels
+                && r.min_sample_rate().0 <= sample_rate
+                && sample_rate <= r.max_sample_rate().0
+        })
+        .map(|r| r.sample_format())
+        .collect();
+    PREFERRED_FORMATS
+        .iter()
+        .copied()
+        .find(|f| supported.contains(f))
+}
+
+/// Convert device-native samples to f32, replacing the contents of `out`.
+pub fn samples_to_f32<T>(input: &[T], out: &mut Vec<f32>)
+where
+    T: Sample,
+    f32: FromSample<T>,
+{
+    out.clear();
+    out.extend(input.iter().map(|&s| s.to_sample::<f32>()));
+}
+
+/// Convert f32 samples into a device-native output buffer of the same length.
+/// Values outside `-1.0..=1.0` are clipped by the integer conversions.
+pub fn samples_from_f32<T>(input: &[f32], out: &mut [T])
+where
+    T: Sample + FromSample<f32>,
+{
+    for (dst, &src) in out.iter_mut().zip(input) {
+        *dst = src.to_sample::<T>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cpal::{SampleRate, SupportedBufferSize};
+
+    fn range(channels: u16, min: u32, max: u32, format: SampleFormat) -> SupportedStreamConfigRange {
+        SupportedStreamConfigRange::new(
+            channels,
+            SampleRate(min),
+            SampleRate(max),
+            SupportedBufferSize::Unknown,
+            format,
+        )
+    }
+
+    #[test]
+    fn test_choose_prefers_f32() {
+        let ranges = vec![
+            range(1, 8000, 48000, SampleFormat::I16),
+            range(1, 8000, 48000, SampleFormat::F32),
+        ];
+        assert_eq!(choose_sample_format(&ranges, 48000, 1), Some(SampleFormat::F32));
+    }
+
+    #[test]
+    fn test_choose_falls_back_to_integer() {
+        let ranges = vec![
+            range(1, 8000, 48000, SampleFormat::I16),
+            range(1, 8000, 48000, SampleFormat::I32),
+        ];
+        assert_eq!(choose_sample_format(&ranges, 48000, 1), Some(SampleFormat::I32));
+
+        let ranges = vec![range(2, 44100, 48000, SampleFormat::I16)];
+        assert_eq!(choose_sample_format(&ranges, 44100, 2), Some(SampleFormat::I16));
+    }
+
+    #[test]
+    fn test_choose_respects_rate_and_channels() {
+        let ranges = vec![
+            range(2, 8000, 48000, SampleFormat::F32),
+            range(1, 8000, 16000, SampleFormat::F32),
+            range(1, 8000, 48000, SampleFormat::U16),
+        ];
+        assert_eq!(choose_sample_format(&ranges, 48000, 1), Some(SampleFormat::U16));
+        assert_eq!(choose_sample_format(&ranges, 96000, 1), None);
+        assert_eq!(choose_sample_format(&ranges, 48000, 4), None);
+    }
+
+    #[test]
+    fn test_i16_to_f32() {
+        let mut out = Vec::new();
+        samples_to_f32(&[0i16, i16::MIN, i16::MAX, 16384], &mut out);
+        assert_eq!(out[0], 0.0);
+        assert_eq!(out[1], -1.0);
+        assert!((out[2] - 1.0).abs() < 1e-4);
+        assert!((out[3] - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_i32_to_f32() {
+        let mut out = Vec::new();
+        samples_to_f32(&[0i32, i32::MIN, i32::MAX / 2], &mut out);
+        assert_eq!(out[0], 0.0);
+        assert_eq!(out[1], -1.0);
+        assert!((out[2] - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_unsigned_to_f32_centres_on_midpoint() {
+        let mut out = Vec::new();
+        samples_to_f32(&[32768u16, 0, u16::MAX], &mut out);
+        assert_eq!(out[0], 0.0);
+        assert_eq!(out[1], -1.0);
+        assert!((out[2] - 1.0).abs() < 1e-4);
+
+        samples_to_f32(&[128u8, 0], &mut out);
+        assert_eq!(out, vec![0.0, -1.0]);
+    }
+
+    #[test]
+    fn test_f64_to_f32() {
+        let mut out = Vec::new();
+        samples_to_f32(&[0.25f64, -0.75], &mut out);
+        assert_eq!(out, vec![0.25, -0.75]);
+    }
+
+    #[test]
+    fn test_samples_to_f32_replaces_previous_contents() {
+        let mut out = vec![9.0; 8];
+        samples_to_f32(&[0i16; 3], &mut out);
+        assert_eq!(out, vec![0.0; 3]);
+    }
+
+    #[test]
+    fn test_f32_to_i16() {
+        let mut out = [0i16; 4];
+        samples_from_f32(&[0.0, -1.0, 0.5, 1.0], &mut out);
+        assert_eq!(out[0], 0);
+        assert_eq!(out[1], i16::MIN);
+        assert_eq!(out[2], 16384);
+        assert_eq!(out[3], i16::MAX);
+    }
+
+    #[test]
+    fn test_f32_to_integer_clips_out_of_range() {
+        let mut out = [0i16; 2];
+        samples_from_f32(&[4.0, -4.0], &mut out);
+        assert_eq!(out, [i16::MAX, i16::MIN]);
+
+        let mut out = [0u16; 2];
+        samples_from_f32(&[0.0, 2.0], &mut out);
+        assert_eq!(out, [32768, u16::MAX]);
+    }
+
+    #[test]
+    fn test_i32_roundtrip_is_close() {
+        let signal: Vec<f32> = (0..64).map(|i| (i as f32 * 0.1).sin() * 0.9).collect();
+        let mut native = vec![0i32; signal.len()];
+        samples_from_f32(&signal, &mut native);
+        let mut back = Vec::new();
+        samples_to_f32(&native, &mut back);
+        for (a, b) in signal.iter().zip(&back) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+}
@@ -1,12 +1,28 @@
 pub mod capture;
+pub mod cue;
 pub mod device;
+pub mod format;
 pub mod mixer;
 pub mod output;
+pub mod player;
+pub mod replay;
+pub mod spectrum;
+pub mod tone;
+pub mod tone_decode;
+pub mod transmission;
+pub mod wav;
 
-pub use capture::{CaptureHandle, CaptureNode};
+pub use capture::{CaptureHandle, CaptureNode, CaptureTaps};
+pub use cue::{CueBank, CueEvent};
 pub use device::DeviceManager;
-pub use mixer::{InputHandle, Mixer, MixerHandle};
+pub use mixer::{InputAnalyzer, InputHandle, Mixer, MixerHandle};
 pub use output::{OutputHandle, OutputNode};
+pub use player::{convert_clip, ClipPlayer, ClipPlayerHandle, PlayerHandle};
+pub use replay::ReplayBuffers;
+pub use spectrum::{SpectrumAnalyzer, SpectrumHandle};
+pub use tone::{ToneGenerator, ToneGeneratorHandle, ToneHandle};
+pub use tone_decode::ToneDecoder;
+pub use transmission::TransmissionDetector;
 
 use ringbuf::traits::Split;
 use ringbuf::{HeapCons, HeapProd, HeapRb};
@@ -88,11 +88,21 @@ impl InputHandle {
     }
 }
 
+// ── InputAnalyzer ──────────────────────────────────────────────
+
+/// Observes an input's raw samples (before gain and mute) on the mixer thread.
+pub trait InputAnalyzer: Send {
+    fn analyze(&mut self, samples: &[f32]);
+}
+
 // ── MixerInput ─────────────────────────────────────────────────
 
 struct MixerInput {
     consumer: HeapCons<f32>,
     controls: Arc<InputControls>,
+    analyzers: Vec<Box<dyn InputAnalyzer>>,
+    /// Set for injected audio: regular inputs are ducked to this gain while it plays.
+    duck_gain: Option<f32>,
 }
 
 // ── Mixer ──────────────────────────────────────────────────────
@@ -101,7 +111,11 @@ pub struct Mixer {
     inputs: Vec<MixerInput>,
     output: HeapProd<f32>,
     mix_buffer: Vec<f32>,
+    inject_buffer: Vec<f32>,
     read_buffer: Vec<f32>,
+    bus_analyzers: Vec<Box<dyn InputAnalyzer>>,
+    /// Gain currently applied to regular inputs.
+    duck_level: f32,
 }
 
 impl Mixer {
@@ -110,7 +124,10 @@ impl Mixer {
             inputs: Vec::new(),
             output,
             mix_buffer: vec![0.0; mix_block_size],
+            inject_buffer: vec![0.0; mix_block_size],
             read_buffer: vec![0.0; mix_block_size],
+            bus_analyzers: Vec::new(),
+            duck_level: 1.0,
         }
     }
 
@@ -123,10 +140,52 @@ impl Mixer {
     ) -> InputHandle {
         let controls = Arc::new(InputControls::new(id, volume, muted));
         let handle = InputHandle::from_arc(Arc::clone(&controls));
-        self.inputs.push(MixerInput { consumer, controls });
+        self.inputs.push(MixerInput {
+            consumer,
+            controls,
+            analyzers: Vec::new(),
+            duck_gain: None,
+        });
+        handle
+    }
+
+    /// Add an input for injected audio (replay, announcements). While it
+    /// delivers samples, all regular inputs are lowered to `duck_gain`.
+    pub fn add_ducking_input(
+        &mut self,
+        id: &str,
+        consumer: HeapCons<f32>,
+        volume: f32,
+        duck_gain: f32,
+    ) -> InputHandle {
+        let controls = Arc::new(InputControls::new(id, volume, false));
+        let handle = InputHandle::from_arc(Arc::clone(&controls));
+        self.inputs.push(MixerInput {
+            consumer,
+            controls,
+            analyzers: Vec::new(),
+            duck_gain: Some(duck_gain.clamp(0.0, 1.0)),
+        });
         handle
     }
 
+    /// Observe the mix of regular inputs, before ducking and injected audio.
+    pub fn add_bus_analyzer(&mut self, analyzer: Box<dyn InputAnalyzer>) {
+        self.bus_analyzers.push(analyzer);
+    }
+
+    /// Attach an analyzer to the input with the given id.
+    /// Returns `false` if no such input exists.
+    pub fn add_analyzer(&mut self, input_id: &str, analyzer: Box<dyn InputAnalyzer>) -> bool {
+        match self.inputs.iter_mut().find(|i| i.controls.id() == input_id) {
+            Some(input) => {
+                input.analyzers.push(analyzer);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Run one mix cycle: drain all inputs, apply gain, sum, write to output.
     /// Returns the number of samples pushed to the output.
     pub fn mix_once(&mut self) -> usize {
@@ -136,10 +195,12 @@ impl Mixer {
 
         let block = self.mix_buffer.len();
 
-        // Zero mix buffer
+        // Zero mix buffers
         self.mix_buffer.iter_mut().for_each(|s| *s = 0.0);
+        self.inject_buffer.iter_mut().for_each(|s| *s = 0.0);
 
         let mut max_read = 0usize;
+        let mut duck_target = 1.0f32;
 
         for input in &mut self.inputs {
             // Always drain to prevent stale data buildup
@@ -148,6 +209,22 @@ impl Mixer {
             if n > max_read {
                 max_read = n;
             }
+            if n > 0 {
+                for analyzer in &mut input.analyzers {
+                    analyzer.analyze(&self.read_buffer[..n]);
+                }
+            }
+
+            // Injected audio is summed separately so the bus can be ducked under it
+            let target = match input.duck_gain {
+                Some(gain) => {
+                    if n > 0 && !input.controls.is_muted() {
+                        duck_target = duck_target.min(gain);
+                    }
+                    &mut self.inject_buffer
+                }
+                None => &mut self.mix_buffer,
+            };
 
             if input.controls.is_muted() {
                 input.controls.set_peak(0.0);
@@ -155,9 +232,9 @@ impl Mixer {
                 let vol = input.controls.volume();
                 if n > 0 {
                     let mut peak: f32 = 0.0;
-                    for i in 0..n {
-                        let s = self.read_buffer[i] * vol;
-                        self.mix_buffer[i] += s;
+                    for (out, &sample) in target.iter_mut().zip(&self.read_buffer[..n]) {
+                        let s = sample * vol;
+                        *out += s;
                         let abs = s.abs();
                         if abs > peak {
                             peak = abs;
@@ -176,6 +253,24 @@ impl Mixer {
             return 0;
         }
 
+        for analyzer in &mut self.bus_analyzers {
+            analyzer.analyze(&self.mix_buffer[..max_read]);
+        }
+
+        // Ramp the ducking gain across the block to avoid clicks
+        let start = self.duck_level;
+        let step = (duck_target - start) / max_read as f32;
+        for (i, (mixed, injected)) in self
+            .mix_buffer
+            .iter_mut()
+            .zip(&self.inject_buffer)
+            .take(max_read)
+            .enumerate()
+        {
+            *mixed = *mixed * (start + step * (i + 1) as f32) + injected;
+        }
+        self.duck_level = duck_target;
+
         // Push mixed samples to output
         self.output.push_slice(&self.mix_buffer[..max_read])
     }
@@ -739,4 +834,119 @@ mod tests {
         mixer.mix_once();
         assert!((handle.peak_level() - 0.9).abs() < 1e-6);
     }
+
+    // ── Group E: Input analyzers ────────────────────────────────
+
+    struct Recorder(Arc<std::sync::Mutex<Vec<f32>>>);
+
+    impl InputAnalyzer for Recorder {
+        fn analyze(&mut self, samples: &[f32]) {
+            self.0.lock().unwrap().extend_from_slice(samples);
+        }
+    }
+
+    #[test]
+    fn test_mixer_analyzer_sees_raw_samples() {
+        let (mut mixer, _out) = make_mixer(128, 1024);
+        let _h = mixer.add_input("a", feed(&[0.5, -0.5], 256), 0.1, true);
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        assert!(mixer.add_analyzer("a", Box::new(Recorder(Arc::clone(&seen)))));
+
+        mixer.mix_once();
+
+        // Gain and mute apply to the mix only, not to analysis
+        assert_eq!(*seen.lock().unwrap(), vec![0.5, -0.5]);
+    }
+
+    #[test]
+    fn test_mixer_analyzer_only_for_its_input() {
+        let (mut mixer, _out) = make_mixer(128, 1024);
+        let _ha = mixer.add_input("a", feed(&[0.1], 256), 1.0, false);
+        let _hb = mixer.add_input("b", feed(&[0.2, 0.2], 256), 1.0, false);
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        mixer.add_analyzer("b", Box::new(Recorder(Arc::clone(&seen))));
+
+        mixer.mix_once();
+        mixer.mix_once(); // empty cycle must not call the analyzer
+
+        assert_eq!(*seen.lock().unwrap(), vec![0.2, 0.2]);
+    }
+
+    #[test]
+    fn test_mixer_add_analyzer_unknown_input() {
+        let (mut mixer, _out) = make_mixer(128, 1024);
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        assert!(!mixer.add_analyzer("nope", Box::new(Recorder(seen))));
+    }
+
+    // ── Group F: Ducking inputs & bus analyzers ─────────────────
+
+    fn drain(out: &mut HeapCons<f32>) -> Vec<f32> {
+        let mut buf = vec![0.0; 1024];
+        let n = out.pop_slice(&mut buf);
+        buf.truncate(n);
+        buf
+    }
+
+    #[test]
+    fn test_mixer_ducking_input_lowers_live_inputs() {
+        let (mut mixer, mut out) = make_mixer(4, 1024);
+        let (mut live, live_cons) = HeapRb::<f32>::new(256).split();
+        let (mut inject, inject_cons) = HeapRb::<f32>::new(256).split();
+        let _live = mixer.add_input("live", live_cons, 1.0, false);
+        let _inj = mixer.add_ducking_input("inject", inject_cons, 1.0, 0.5);
+
+        // Ducking ramps from 1.0 to 0.5 across the first block
+        live.push_slice(&[1.0; 4]);
+        inject.push_slice(&[0.1; 4]);
+        mixer.mix_once();
+        let ramp = drain(&mut out);
+        let expected = [0.975, 0.85, 0.725, 0.6];
+        for (got, want) in ramp.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-6, "{ramp:?}");
+        }
+
+        live.push_slice(&[1.0; 4]);
+        inject.push_slice(&[0.1; 4]);
+        mixer.mix_once();
+        assert!(drain(&mut out).iter().all(|&s| (s - 0.6).abs() < 1e-6));
+
+        // Injected audio ends: live audio ramps back up to full level
+        live.push_slice(&[1.0; 4]);
+        mixer.mix_once();
+        let release = drain(&mut out);
+        assert!((release[3] - 1.0).abs() < 1e-6, "{release:?}");
+        live.push_slice(&[1.0; 4]);
+        mixer.mix_once();
+        assert!(drain(&mut out).iter().all(|&s| (s - 1.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_mixer_muted_ducking_input_does_not_duck() {
+        let (mut mixer, mut out) = make_mixer(4, 1024);
+        let _live = mixer.add_input("live", feed(&[1.0; 4], 256), 1.0, false);
+        let inj = mixer.add_ducking_input("inject", feed(&[0.1; 4], 256), 1.0, 0.2);
+        inj.set_muted(true);
+        mixer.mix_once();
+        assert!(drain(&mut out).iter().all(|&s| (s - 1.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_mixer_bus_analyzer_sees_live_mix_only() {
+        let (mut mixer, _out) = make_mixer(4, 1024);
+        let _a = mixer.add_input("a", feed(&[0.25, 0.25], 256), 2.0, false);
+        let _b = mixer.add_input("b", feed(&[0.1], 256), 1.0, false);
+        let _inj = mixer.add_ducking_input("inject", feed(&[0.3, 0.3, 0.3], 256), 1.0, 0.5);
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        mixer.add_bus_analyzer(Box::new(Recorder(Arc::clone(&seen))));
+
+        mixer.mix_once();
+
+        let seen = seen.lock().unwrap();
+        let expected = [0.6, 0.5, 0.0];
+        assert_eq!(seen.len(), 3);
+        for (got, want) in seen.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-6, "{seen:?}");
+        }
+    }
 }
@@ -1,10 +1,12 @@
+use crate::device::DeviceManager;
+use crate::format::samples_from_f32;
 use voxmux_core::AudioError;
 use cpal::traits::DeviceTrait;
-use cpal::{Device, SampleRate, Stream, StreamConfig};
+use cpal::{Device, FromSample, SampleFormat, SampleRate, SizedSample, Stream, StreamConfig};
 use ringbuf::traits::Consumer;
 use ringbuf::HeapCons;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use voxmux_core::InputStatus;
 
 const STATUS_OK: u8 = 0;
@@ -35,6 +37,50 @@ impl OutputHandle {
     }
 }
 
+// ── OutputCallback ────────────────────────────────────────────
+
+/// State owned by the device callback, independent of the device sample format.
+struct OutputCallback {
+    consumer: HeapCons<f32>,
+    playing: Arc<AtomicBool>,
+    scratch: Vec<f32>,
+}
+
+impl OutputCallback {
+    fn process<T>(&mut self, data: &mut [T])
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        self.scratch.clear();
+        self.scratch.resize(data.len(), 0.0);
+        if self.playing.load(Ordering::Relaxed) {
+            // Underrun leaves the remainder as silence
+            self.consumer.pop_slice(&mut self.scratch);
+        }
+        samples_from_f32(&self.scratch, data);
+    }
+}
+
+fn build_output<T, E>(
+    device: &Device,
+    config: &StreamConfig,
+    mut callback: OutputCallback,
+    err_callback: E,
+) -> Result<Stream, AudioError>
+where
+    T: SizedSample + FromSample<f32>,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| callback.process(data),
+            err_callback,
+            None,
+        )
+        .map_err(|e| AudioError::StreamBuild(e.to_string()))
+}
+
 // ── OutputNode ────────────────────────────────────────────────
 
 pub struct OutputNode {
@@ -42,6 +88,8 @@ pub struct OutputNode {
 }
 
 impl OutputNode {
+    /// Open `device` in its native sample format (preferring f32) and play from
+    /// `consumer`, converting from f32 in the callback.
     pub fn new(
         device: &Device,
         consumer: HeapCons<f32>,
@@ -54,10 +102,10 @@ impl OutputNode {
             sample_rate: SampleRate(sample_rate),
             buffer_size: cpal::BufferSize::Fixed(buffer_size),
         };
+        let format = DeviceManager::output_sample_format(device, sample_rate, channels)?;
+        tracing::info!("output opened as {}", format);
 
-        let consumer = Arc::new(Mutex::new(consumer));
         let playing = Arc::new(AtomicBool::new(true));
-        let playing_flag = Arc::clone(&playing);
         let status = Arc::new(AtomicU8::new(STATUS_OK));
         let status_flag = Arc::clone(&status);
 
@@ -66,27 +114,27 @@ impl OutputNode {
             status_flag.store(STATUS_ERROR, Ordering::Relaxed);
         };
 
-        let stream = device
-            .build_output_stream(
-                &config,
-                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                    if !playing_flag.load(Ordering::Relaxed) {
-                        data.fill(0.0);
-                        return;
-                    }
-                    if let Ok(mut cons) = consumer.lock() {
-                        for sample in data.iter_mut() {
-                            *sample = cons.try_pop().unwrap_or(0.0);
-                        }
-                    } else {
-                        // Mutex poisoned — fill with silence
-                        data.fill(0.0);
-                    }
-                },
-                err_callback,
-                None,
-            )
-            .map_err(|e| AudioError::StreamBuild(e.to_string()))?;
+        let callback = OutputCallback {
+            consumer,
+            playing: Arc::clone(&playing),
+            scratch: Vec::with_capacity(buffer_size as usize * channels as usize),
+        };
+
+        let stream = match format {
+            SampleFormat::F32 => build_output::<f32, _>(device, &config, callback, err_callback),
+            SampleFormat::F64 => build_output::<f64, _>(device, &config, callback, err_callback),
+            SampleFormat::I8 => build_output::<i8, _>(device, &config, callback, err_callback),
+            SampleFormat::I16 => build_output::<i16, _>(device, &config, callback, err_callback),
+            SampleFormat::I32 => build_output::<i32, _>(device, &config, callback, err_callback),
+            SampleFormat::I64 => build_output::<i64, _>(device, &config, callback, err_callback),
+            SampleFormat::U8 => build_output::<u8, _>(device, &config, callback, err_callback),
+            SampleFormat::U16 => build_output::<u16, _>(device, &config, callback, err_callback),
+            SampleFormat::U32 => build_output::<u32, _>(device, &config, callback, err_callback),
+            SampleFormat::U64 => build_output::<u64, _>(device, &config, callback, err_callback),
+            other => Err(AudioError::StreamBuild(format!(
+                "unsupported sample format: {other}"
+            ))),
+        }?;
 
         let handle = OutputHandle { playing, status };
         Ok((Self { _stream: stream }, handle))
@@ -132,4 +180,41 @@ mod tests {
         let handle = make_output_handle();
         assert_eq!(handle.status(), InputStatus::Ok);
     }
+
+    fn make_callback(samples: &[f32]) -> OutputCallback {
+        use ringbuf::traits::{Producer, Split};
+        let (mut producer, consumer) = ringbuf::HeapRb::<f32>::new(256).split();
+        producer.push_slice(samples);
+        OutputCallback {
+            consumer,
+            playing: Arc::new(AtomicBool::new(true)),
+            scratch: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_output_callback_converts_to_i16() {
+        let mut callback = make_callback(&[0.5, -1.0]);
+        let mut data = [1i16; 4];
+        callback.process(&mut data);
+        // Underrun after two samples → silence
+        assert_eq!(data, [16384, i16::MIN, 0, 0]);
+    }
+
+    #[test]
+    fn test_output_callback_converts_to_u16_silence_is_midpoint() {
+        let mut callback = make_callback(&[]);
+        let mut data = [0u16; 2];
+        callback.process(&mut data);
+        assert_eq!(data, [32768, 32768]);
+    }
+
+    #[test]
+    fn test_output_callback_not_playing_outputs_silence() {
+        let mut callback = make_callback(&[0.5; 4]);
+        callback.playing.store(false, Ordering::Relaxed);
+        let mut data = [7i32; 4];
+        callback.process(&mut data);
+        assert_eq!(data, [0; 4]);
+    }
 }
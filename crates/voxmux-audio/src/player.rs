@@ -0,0 +1,303 @@
+use ringbuf::traits::Producer;
+use ringbuf::HeapProd;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const TICK: Duration = Duration::from_millis(5);
+/// Never deliver more than this much audio in one tick (e.g. after a stall).
+const MAX_BURST: Duration = Duration::from_millis(50);
+
+struct Clip {
+    label: String,
+    samples: Vec<f32>,
+    pos: usize,
+}
+
+struct Queue {
+    clips: VecDeque<Clip>,
+}
+
+// ── PlayerHandle ───────────────────────────────────────────────
+
+/// Queue control for a [`ClipPlayer`], shared with the UI and event handlers.
+#[derive(Clone)]
+pub struct PlayerHandle {
+    queue: Arc<Mutex<Queue>>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl PlayerHandle {
+    /// Append a clip (interleaved, mixer sample rate and channel count).
+    pub fn enqueue(&self, label: &str, samples: Vec<f32>) {
+        self.queue.lock().unwrap().clips.push_back(Clip {
+            label: label.to_string(),
+            samples,
+            pos: 0,
+        });
+    }
+
+    /// Drop anything queued or playing and play this clip immediately.
+    pub fn play_now(&self, label: &str, samples: Vec<f32>) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.clips.clear();
+        queue.clips.push_back(Clip {
+            label: label.to_string(),
+            samples,
+            pos: 0,
+        });
+    }
+
+    pub fn stop(&self) {
+        self.queue.lock().unwrap().clips.clear();
+    }
+
+    pub fn is_playing(&self) -> bool {
+        !self.queue.lock().unwrap().clips.is_empty()
+    }
+
+    /// Label and remaining seconds of the current clip.
+    pub fn now_playing(&self) -> Option<(String, f32)> {
+        let queue = self.queue.lock().unwrap();
+        queue.clips.front().map(|clip| {
+            let remaining = (clip.samples.len() - clip.pos) as f32
+                / (self.sample_rate as f32 * self.channels as f32);
+            (clip.label.clone(), remaining)
+        })
+    }
+}
+
+// ── Format conversion ──────────────────────────────────────────
+
+/// Convert an interleaved clip to another sample rate (linear interpolation)
+/// and channel count (downmix to mono, then copy to every channel).
+pub fn convert_clip(
+    samples: &[f32],
+    from_rate: u32,
+    from_channels: u16,
+    to_rate: u32,
+    to_channels: u16,
+) -> Vec<f32> {
+    let from_channels = from_channels.max(1) as usize;
+    let to_channels = to_channels.max(1) as usize;
+    let mono: Vec<f32> = samples
+        .chunks(from_channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+    if mono.is_empty() {
+        return Vec::new();
+    }
+
+    let out_frames = (mono.len() as u64 * to_rate as u64 / from_rate.max(1) as u64) as usize;
+    let step = from_rate as f64 / to_rate.max(1) as f64;
+    let mut out = Vec::with_capacity(out_frames * to_channels);
+    for i in 0..out_frames {
+        let pos = i as f64 * step;
+        let idx = pos as usize;
+        let frac = (pos - idx as f64) as f32;
+        let a = mono[idx.min(mono.len() - 1)];
+        let b = mono[(idx + 1).min(mono.len() - 1)];
+        let s = a + (b - a) * frac;
+        out.extend(std::iter::repeat_n(s, to_channels));
+    }
+    out
+}
+
+// ── ClipPlayer ─────────────────────────────────────────────────
+
+/// Plays queued clips into a mixer input at real-time pace.
+/// Produces nothing while idle, so a ducking input only ducks during playback.
+pub struct ClipPlayer {
+    producer: HeapProd<f32>,
+    handle: PlayerHandle,
+}
+
+impl ClipPlayer {
+    pub fn new(sample_rate: u32, channels: u16, producer: HeapProd<f32>) -> Self {
+        Self {
+            producer,
+            handle: PlayerHandle {
+                queue: Arc::new(Mutex::new(Queue {
+                    clips: VecDeque::new(),
+                })),
+                sample_rate,
+                channels: channels.max(1),
+            },
+        }
+    }
+
+    pub fn handle(&self) -> PlayerHandle {
+        self.handle.clone()
+    }
+
+    /// Deliver up to `frames` frames of queued audio.
+    /// Returns the number of samples pushed (0 when idle).
+    pub fn generate(&mut self, frames: usize) -> usize {
+        let mut budget = frames * self.handle.channels as usize;
+        let mut pushed = 0;
+        let mut queue = self.handle.queue.lock().unwrap();
+        while budget > 0 {
+            let Some(clip) = queue.clips.front_mut() else {
+                break;
+            };
+            let end = (clip.pos + budget).min(clip.samples.len());
+            let n = self.producer.push_slice(&clip.samples[clip.pos..end]);
+            clip.pos += n;
+            budget -= n;
+            pushed += n;
+            if clip.pos < clip.samples.len() {
+                break; // budget spent or ring full
+            }
+            queue.clips.pop_front();
+        }
+        pushed
+    }
+
+    /// Run the player loop until `running` is set to false.
+    pub fn run(&mut self, running: Arc<AtomicBool>) {
+        let sample_rate = self.handle.sample_rate;
+        let max_frames = sample_rate as f64 * MAX_BURST.as_secs_f64();
+        let mut last = Instant::now();
+        let mut owed = 0.0f64;
+        while running.load(Ordering::Relaxed) {
+            std::thread::sleep(TICK);
+            let now = Instant::now();
+            owed = (owed + (now - last).as_secs_f64() * sample_rate as f64).min(max_frames);
+            last = now;
+            let frames = owed.floor();
+            owed -= frames;
+            self.generate(frames as usize);
+        }
+    }
+
+    /// Spawn the player on a dedicated thread. Consumes self.
+    pub fn start(mut self, name: &str) -> ClipPlayerHandle {
+        let running = Arc::new(AtomicBool::new(true));
+        let flag = Arc::clone(&running);
+        let handle = self.handle();
+        let thread = std::thread::Builder::new()
+            .name(name.into())
+            .spawn(move || {
+                self.run(flag);
+            })
+            .expect("failed to spawn player thread");
+        ClipPlayerHandle {
+            running,
+            thread: Some(thread),
+            handle,
+        }
+    }
+}
+
+// ── ClipPlayerHandle ───────────────────────────────────────────
+
+pub struct ClipPlayerHandle {
+    running: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+    handle: PlayerHandle,
+}
+
+impl ClipPlayerHandle {
+    /// Signal the player thread to stop and wait for it to finish.
+    pub fn stop(mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(t) = self.thread.take() {
+            t.join().expect("player thread panicked");
+        }
+    }
+
+    pub fn handle(&self) -> PlayerHandle {
+        self.handle.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ringbuf::traits::{Consumer, Split};
+    use ringbuf::{HeapCons, HeapRb};
+
+    fn player(capacity: usize) -> (ClipPlayer, HeapCons<f32>) {
+        let (prod, cons) = HeapRb::<f32>::new(capacity).split();
+        (ClipPlayer::new(1000, 2, prod), cons)
+    }
+
+    fn drain(cons: &mut HeapCons<f32>) -> Vec<f32> {
+        let mut buf = vec![0.0; 4096];
+        let n = cons.pop_slice(&mut buf);
+        buf.truncate(n);
+        buf
+    }
+
+    #[test]
+    fn test_player_idle_produces_nothing() {
+        let (mut player, mut cons) = player(64);
+        assert_eq!(player.generate(10), 0);
+        assert!(drain(&mut cons).is_empty());
+        assert!(!player.handle().is_playing());
+    }
+
+    #[test]
+    fn test_player_plays_clips_in_order_at_requested_pace() {
+        let (mut player, mut cons) = player(64);
+        let handle = player.handle();
+        handle.enqueue("a", vec![1.0; 6]);
+        handle.enqueue("b", vec![2.0; 4]);
+
+        // 4 frames × 2 channels
+        assert_eq!(player.generate(4), 8);
+        assert_eq!(drain(&mut cons), vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0]);
+        assert_eq!(handle.now_playing(), Some(("b".to_string(), 0.001)));
+
+        assert_eq!(player.generate(4), 2);
+        assert!(!handle.is_playing());
+    }
+
+    #[test]
+    fn test_player_play_now_replaces_queue() {
+        let (mut player, mut cons) = player(64);
+        let handle = player.handle();
+        handle.enqueue("a", vec![1.0; 6]);
+        handle.enqueue("b", vec![2.0; 4]);
+        handle.play_now("c", vec![3.0; 2]);
+        player.generate(10);
+        assert_eq!(drain(&mut cons), vec![3.0, 3.0]);
+    }
+
+    #[test]
+    fn test_player_waits_when_ring_full() {
+        let (mut player, mut cons) = player(4);
+        let handle = player.handle();
+        handle.enqueue("a", (0..10).map(|i| i as f32).collect());
+        assert_eq!(player.generate(10), 4);
+        assert_eq!(drain(&mut cons), vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(player.generate(10), 4);
+        assert_eq!(drain(&mut cons), vec![4.0, 5.0, 6.0, 7.0]);
+    }
+
+    #[test]
+    fn test_convert_clip_resamples_and_expands_channels() {
+        // 4 mono frames at 8 kHz → 8 stereo frames at 16 kHz
+        let out = convert_clip(&[0.0, 1.0, 0.0, -1.0], 8000, 1, 16000, 2);
+        assert_eq!(out.len(), 16);
+        assert_eq!(&out[..6], &[0.0, 0.0, 0.5, 0.5, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_convert_clip_downmixes() {
+        let out = convert_clip(&[1.0, 0.0, 0.5, 0.5], 1000, 2, 1000, 1);
+        assert_eq!(out, vec![0.5, 0.5]);
+        assert!(convert_clip(&[], 22050, 1, 48000, 2).is_empty());
+    }
+
+    #[test]
+    fn test_player_stop_clears_queue() {
+        let (mut player, _cons) = player(64);
+        let handle = player.handle();
+        handle.enqueue("a", vec![1.0; 6]);
+        handle.stop();
+        assert_eq!(player.generate(10), 0);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::mixer::InputAnalyzer;
+use crate::wav::write_wav;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use voxmux_core::AudioError;
+
+/// Fixed-size ring of the most recent samples.
+struct HistoryBuffer {
+    samples: Vec<f32>,
+    /// Next write position.
+    pos: usize,
+    len: usize,
+}
+
+impl HistoryBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            samples: vec![0.0; capacity.max(1)],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, data: &[f32]) {
+        let cap = self.samples.len();
+        // Only the tail can survive if `data` is longer than the ring
+        let data = &data[data.len().saturating_sub(cap)..];
+        let first = data.len().min(cap - self.pos);
+        self.samples[self.pos..self.pos + first].copy_from_slice(&data[..first]);
+        self.samples[..data.len() - first].copy_from_slice(&data[first..]);
+        self.pos = (self.pos + data.len()) % cap;
+        self.len = (self.len + data.len()).min(cap);
+    }
+
+    /// The newest `n` samples, oldest first.
+    fn latest(&self, n: usize) -> Vec<f32> {
+        let cap = self.samples.len();
+        let n = n.min(self.len);
+        let start = (self.pos + cap - n) % cap;
+        let mut out = Vec::with_capacity(n);
+        if start + n <= cap {
+            out.extend_from_slice(&self.samples[start..start + n]);
+        } else {
+            out.extend_from_slice(&self.samples[start..]);
+            out.extend_from_slice(&self.samples[..n - (cap - start)]);
+        }
+        out
+    }
+}
+
+struct HistoryRecorder {
+    history: Arc<Mutex<HistoryBuffer>>,
+}
+
+impl InputAnalyzer for HistoryRecorder {
+    fn analyze(&mut self, samples: &[f32]) {
+        self.history.lock().unwrap().push(samples);
+    }
+}
+
+// ── ReplayBuffers ──────────────────────────────────────────────
+
+/// Rolling audio history of each input and of the mix bus, for instant
+/// replay and WAV export. Sources are keyed by input id; `None` is the mix bus.
+#[derive(Clone)]
+pub struct ReplayBuffers {
+    sample_rate: u32,
+    channels: u16,
+    seconds: u32,
+    histories: HashMap<Option<String>, Arc<Mutex<HistoryBuffer>>>,
+}
+
+impl ReplayBuffers {
+    pub fn new(sample_rate: u32, channels: u16, seconds: u32) -> Self {
+        Self {
+            sample_rate,
+            channels: channels.max(1),
+            seconds,
+            histories: HashMap::new(),
+        }
+    }
+
+    /// Length of history kept per source.
+    pub fn seconds(&self) -> u32 {
+        self.seconds
+    }
+
+    /// Create the history for `source` and return the analyzer that fills it;
+    /// attach it to the input (or to the mixer bus for `None`).
+    pub fn recorder(&mut self, source: Option<&str>) -> Box<dyn InputAnalyzer> {
+        let capacity = self.frames(self.seconds as f32) * self.channels as usize;
+        let history = Arc::new(Mutex::new(HistoryBuffer::new(capacity)));
+        self.histories
+            .insert(source.map(str::to_string), Arc::clone(&history));
+        Box::new(HistoryRecorder { history })
+    }
+
+    /// Up to the last `seconds` of a source's audio, oldest first.
+    /// Returns `None` for an unknown source.
+    pub fn snapshot(&self, source: Option<&str>, seconds: f32) -> Option<Vec<f32>> {
+        let history = self.histories.get(&source.map(str::to_string))?;
+        let n = self.frames(seconds) * self.channels as usize;
+        Some(history.lock().unwrap().latest(n))
+    }
+
+    /// Write up to the last `seconds` of a source to a WAV file.
+    /// Returns the length written, in seconds.
+    pub fn export_wav(
+        &self,
+        source: Option<&str>,
+        seconds: f32,
+        path: &Path,
+    ) -> Result<f32, AudioError> {
+        let samples = self.snapshot(source, seconds).ok_or_else(|| {
+            AudioError::Wav(format!("no history for '{}'", source.unwrap_or("mix")))
+        })?;
+        write_wav(path, &samples, self.sample_rate, self.channels)?;
+        Ok(samples.len() as f32 / (self.sample_rate as f32 * self.channels as f32))
+    }
+
+    fn frames(&self, seconds: f32) -> usize {
+        (seconds.max(0.0) * self.sample_rate as f32) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_buffer_keeps_newest() {
+        let mut h = HistoryBuffer::new(5);
+        h.push(&[1.0, 2.0, 3.0]);
+        assert_eq!(h.latest(10), vec![1.0, 2.0, 3.0]);
+        h.push(&[4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(h.latest(10), vec![3.0, 4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(h.latest(2), vec![6.0, 7.0]);
+        h.push(&(10..20).map(|i| i as f32).collect::<Vec<_>>());
+        assert_eq!(h.latest(5), vec![15.0, 16.0, 17.0, 18.0, 19.0]);
+    }
+
+    #[test]
+    fn test_replay_buffers_per_source() {
+        let mut buffers = ReplayBuffers::new(10, 1, 2);
+        let mut mic = buffers.recorder(Some("mic"));
+        let mut bus = buffers.recorder(None);
+        mic.analyze(&[0.1; 25]);
+        bus.analyze(&[0.2; 5]);
+
+        // 2 s at 10 Hz
+        assert_eq!(buffers.snapshot(Some("mic"), 30.0).unwrap().len(), 20);
+        assert_eq!(buffers.snapshot(Some("mic"), 0.5).unwrap(), vec![0.1; 5]);
+        assert_eq!(buffers.snapshot(None, 2.0).unwrap(), vec![0.2; 5]);
+        assert!(buffers.snapshot(Some("other"), 1.0).is_none());
+    }
+
+    #[test]
+    fn test_replay_buffers_clone_shares_history() {
+        let mut buffers = ReplayBuffers::new(10, 2, 1);
+        let mut rec = buffers.recorder(Some("mic"));
+        let shared = buffers.clone();
+        rec.analyze(&[0.5, -0.5]);
+        assert_eq!(shared.snapshot(Some("mic"), 1.0).unwrap(), vec![0.5, -0.5]);
+    }
+
+    #[test]
+    fn test_replay_export_wav() {
+        let dir = std::env::temp_dir().join(format!("voxmux-replay-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("mix.wav");
+
+        let mut buffers = ReplayBuffers::new(8000, 1, 1);
+        buffers.recorder(None).analyze(&[0.25; 4000]);
+        let secs = buffers.export_wav(None, 30.0, &path).unwrap();
+        assert_eq!(secs, 0.5);
+        assert_eq!(hound::WavReader::open(&path).unwrap().len(), 4000);
+
+        assert!(buffers.export_wav(Some("nope"), 1.0, &path).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
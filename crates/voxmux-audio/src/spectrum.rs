@@ -0,0 +1,300 @@
+use crate::mixer::InputAnalyzer;
+use rustfft::num_complex::Complex;
+use rustfft::{Fft, FftPlanner};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use voxmux_core::config::SpectrumConfig;
+use voxmux_core::Spectrum;
+
+/// Level reported for bins with no energy.
+pub const SPECTRUM_FLOOR_DB: f32 = -120.0;
+const MIN_FFT_SIZE: usize = 64;
+
+struct Shared {
+    enabled: AtomicBool,
+    latest: Mutex<Option<Spectrum>>,
+}
+
+// ── SpectrumHandle ─────────────────────────────────────────────
+
+/// Control and read side of a [`SpectrumAnalyzer`].
+#[derive(Clone)]
+pub struct SpectrumHandle {
+    shared: Arc<Shared>,
+}
+
+impl SpectrumHandle {
+    pub fn is_enabled(&self) -> bool {
+        self.shared.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Switch analysis on or off; disabling clears the published spectrum.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.shared.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            *self.shared.latest.lock().unwrap() = None;
+        }
+    }
+
+    /// Most recent spectrum, or `None` while disabled or warming up.
+    pub fn latest(&self) -> Option<Spectrum> {
+        self.shared.latest.lock().unwrap().clone()
+    }
+}
+
+// ── SpectrumAnalyzer ───────────────────────────────────────────
+
+/// Hann-windowed FFT of an input, decimated to a fixed number of bands.
+pub struct SpectrumAnalyzer {
+    shared: Arc<Shared>,
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    /// Amplitude correction so a full-scale sine reads 0 dBFS.
+    scale: f32,
+    /// Last `fft_size` mono samples, circular.
+    history: Vec<f32>,
+    pos: usize,
+    filled: usize,
+    hop: usize,
+    since_last: usize,
+    buffer: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    channels: usize,
+    bin_hz: f32,
+    bands: usize,
+    max_hz: f32,
+    seq: u64,
+}
+
+impl SpectrumAnalyzer {
+    pub fn new(sample_rate: u32, channels: u16, config: &SpectrumConfig) -> Self {
+        let fft_size = config.fft_size.max(MIN_FFT_SIZE);
+        let fft = FftPlanner::new().plan_fft_forward(fft_size);
+        let window: Vec<f32> = (0..fft_size)
+            .map(|i| {
+                let x = std::f32::consts::TAU * i as f32 / fft_size as f32;
+                0.5 - 0.5 * x.cos()
+            })
+            .collect();
+        let scale = 2.0 / window.iter().sum::<f32>();
+        let scratch = vec![Complex::default(); fft.get_inplace_scratch_len()];
+        let nyquist = sample_rate as f32 / 2.0;
+        Self {
+            shared: Arc::new(Shared {
+                enabled: AtomicBool::new(config.enabled),
+                latest: Mutex::new(None),
+            }),
+            fft,
+            window,
+            scale,
+            history: vec![0.0; fft_size],
+            pos: 0,
+            filled: 0,
+            hop: ((sample_rate as u64 * config.update_ms as u64 / 1000) as usize).max(1),
+            since_last: 0,
+            buffer: vec![Complex::default(); fft_size],
+            scratch,
+            channels: channels.max(1) as usize,
+            bin_hz: sample_rate as f32 / fft_size as f32,
+            bands: config.bands.max(1),
+            max_hz: config.max_hz.clamp(1.0, nyquist),
+            seq: 0,
+        }
+    }
+
+    pub fn handle(&self) -> SpectrumHandle {
+        SpectrumHandle {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+
+    fn compute(&mut self) -> Spectrum {
+        let n = self.history.len();
+        for (i, slot) in self.buffer.iter_mut().enumerate() {
+            let sample = self.history[(self.pos + i) % n];
+            *slot = Complex::new(sample * self.window[i], 0.0);
+        }
+        self.fft
+            .process_with_scratch(&mut self.buffer, &mut self.scratch);
+
+        // Bins 0..top cover 0..max_hz; each band takes the loudest of its bins
+        let top = ((self.max_hz / self.bin_hz).round() as usize).clamp(1, n / 2);
+        let bands_db = (0..self.bands)
+            .map(|b| {
+                let lo = (b * top / self.bands).min(top - 1);
+                let hi = ((b + 1) * top / self.bands).clamp(lo + 1, top);
+                let peak = self.buffer[lo..hi]
+                    .iter()
+                    .map(|c| c.norm() * self.scale)
+                    .fold(0.0f32, f32::max);
+                if peak > 0.0 {
+                    (20.0 * peak.log10()).max(SPECTRUM_FLOOR_DB)
+                } else {
+                    SPECTRUM_FLOOR_DB
+                }
+            })
+            .collect();
+        self.seq += 1;
+        Spectrum {
+            bands_db,
+            max_hz: top as f32 * self.bin_hz,
+            seq: self.seq,
+        }
+    }
+}
+
+impl InputAnalyzer for SpectrumAnalyzer {
+    fn analyze(&mut self, samples: &[f32]) {
+        if !self.shared.enabled.load(Ordering::Relaxed) {
+            // Start from a fresh window when re-enabled
+            self.filled = 0;
+            self.since_last = 0;
+            return;
+        }
+
+        let n = self.history.len();
+        for frame in samples.chunks(self.channels) {
+            self.history[self.pos] = frame.iter().sum::<f32>() / frame.len() as f32;
+            self.pos = (self.pos + 1) % n;
+            self.filled = (self.filled + 1).min(n);
+            self.since_last += 1;
+
+            if self.filled == n && self.since_last >= self.hop {
+                self.since_last = 0;
+                let spectrum = self.compute();
+                *self.shared.latest.lock().unwrap() = Some(spectrum);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 8000;
+
+    fn sine(freq: f32, amplitude: f32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| (std::f32::consts::TAU * freq * i as f32 / RATE as f32).sin() * amplitude)
+            .collect()
+    }
+
+    fn config() -> SpectrumConfig {
+        SpectrumConfig {
+            fft_size: 1024,
+            bands: 40,
+            max_hz: 4000.0,
+            update_ms: 50,
+            ..Default::default()
+        }
+    }
+
+    fn loudest_band(spectrum: &Spectrum) -> usize {
+        spectrum
+            .bands_db
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn test_spectrum_peak_at_tone_frequency() {
+        let mut analyzer = SpectrumAnalyzer::new(RATE, 1, &config());
+        analyzer.analyze(&sine(1000.0, 0.5, 4096));
+        let spectrum = analyzer.handle().latest().unwrap();
+        assert_eq!(spectrum.bands_db.len(), 40);
+        assert_eq!(spectrum.max_hz, 4000.0);
+        // 100 Hz per band
+        assert_eq!(loudest_band(&spectrum), 10);
+    }
+
+    #[test]
+    fn test_spectrum_level_is_dbfs() {
+        let mut analyzer = SpectrumAnalyzer::new(RATE, 1, &config());
+        // Bin-centred tone: 1000 Hz = bin 128 of 1024 at 8 kHz
+        analyzer.analyze(&sine(1000.0, 0.5, 4096));
+        let spectrum = analyzer.handle().latest().unwrap();
+        let peak = spectrum.bands_db[10];
+        assert!((peak - -6.0).abs() < 0.5, "peak {peak}");
+        assert!(spectrum.bands_db[30] < -60.0);
+    }
+
+    #[test]
+    fn test_spectrum_resolves_mains_hum() {
+        let mut analyzer = SpectrumAnalyzer::new(
+            RATE,
+            1,
+            &SpectrumConfig {
+                fft_size: 4096,
+                bands: 20,
+                max_hz: 200.0,
+                ..Default::default()
+            },
+        );
+        analyzer.analyze(&sine(60.0, 0.1, 8192));
+        let spectrum = analyzer.handle().latest().unwrap();
+        // ~10 Hz per band
+        assert_eq!(loudest_band(&spectrum), 6);
+    }
+
+    #[test]
+    fn test_spectrum_silence_is_floor() {
+        let mut analyzer = SpectrumAnalyzer::new(RATE, 1, &config());
+        analyzer.analyze(&[0.0; 2048]);
+        let spectrum = analyzer.handle().latest().unwrap();
+        assert!(spectrum.bands_db.iter().all(|&db| db == SPECTRUM_FLOOR_DB));
+    }
+
+    #[test]
+    fn test_spectrum_waits_for_full_window_and_hop() {
+        let mut analyzer = SpectrumAnalyzer::new(RATE, 1, &config());
+        let handle = analyzer.handle();
+        analyzer.analyze(&[0.0; 1000]);
+        assert!(handle.latest().is_none());
+        analyzer.analyze(&[0.0; 24]);
+        assert_eq!(handle.latest().unwrap().seq, 1);
+        // 50 ms hop = 400 samples
+        analyzer.analyze(&[0.0; 399]);
+        assert_eq!(handle.latest().unwrap().seq, 1);
+        analyzer.analyze(&[0.0]);
+        assert_eq!(handle.latest().unwrap().seq, 2);
+    }
+
+    #[test]
+    fn test_spectrum_disabled_publishes_nothing() {
+        let mut analyzer = SpectrumAnalyzer::new(
+            RATE,
+            1,
+            &SpectrumConfig {
+                enabled: false,
+                ..config()
+            },
+        );
+        let handle = analyzer.handle();
+        analyzer.analyze(&sine(1000.0, 0.5, 4096));
+        assert!(!handle.is_enabled());
+        assert!(handle.latest().is_none());
+
+        handle.set_enabled(true);
+        analyzer.analyze(&sine(1000.0, 0.5, 4096));
+        assert!(handle.latest().is_some());
+
+        handle.set_enabled(false);
+        assert!(handle.latest().is_none());
+    }
+
+    #[test]
+    fn test_spectrum_downmixes_channels() {
+        let mut analyzer = SpectrumAnalyzer::new(RATE, 2, &config());
+        let stereo: Vec<f32> = sine(500.0, 0.5, 4096)
+            .into_iter()
+            .flat_map(|s| [s, s])
+            .collect();
+        analyzer.analyze(&stereo);
+        let spectrum = analyzer.handle().latest().unwrap();
+        assert_eq!(loudest_band(&spectrum), 5);
+    }
+}
@@ -0,0 +1,644 @@
+use crate::mixer::InputAnalyzer;
+use ringbuf::traits::Producer;
+use ringbuf::HeapProd;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use voxmux_core::{
+    AudioChunk, LoopbackMeasurement, TapSender, ToneSignal, ToneState, ToneTarget,
+};
+
+/// Line-up level used for the 1 kHz reference and loopback bursts.
+pub const REFERENCE_LEVEL_DBFS: f32 = -20.0;
+pub const REFERENCE_FREQUENCY_HZ: f32 = 1000.0;
+
+const BURST_MS: u32 = 200;
+const BURST_PERIOD_MS: u32 = 1000;
+/// Received level above which a loopback burst counts as arrived.
+const ONSET_THRESHOLD_DBFS: f32 = -45.0;
+/// Skip the attack of a received burst before measuring its level.
+const LEVEL_SKIP_MS: u32 = 10;
+const LEVEL_WINDOW_MS: u32 = 100;
+const TICK: Duration = Duration::from_millis(5);
+/// Never generate more than this much audio in one tick (e.g. after a stall).
+const MAX_BURST: Duration = Duration::from_millis(50);
+
+/// Convert a dBFS level to a linear amplitude (0 dBFS = 1.0).
+pub fn dbfs_to_amplitude(dbfs: f32) -> f32 {
+    10f32.powf(dbfs / 20.0)
+}
+
+// ── Oscillator ─────────────────────────────────────────────────
+
+/// Sample-by-sample source for a `ToneSignal`.
+struct Oscillator {
+    sample_rate: u32,
+    phase: f32,
+    rng: u32,
+    pink: [f32; 7],
+}
+
+impl Oscillator {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            phase: 0.0,
+            rng: 0x9E37_79B9,
+            pink: [0.0; 7],
+        }
+    }
+
+    fn reset(&mut self) {
+        self.phase = 0.0;
+        self.pink = [0.0; 7];
+    }
+
+    fn sine(&mut self, frequency_hz: f32, amplitude: f32) -> f32 {
+        let s = (self.phase * std::f32::consts::TAU).sin() * amplitude;
+        self.phase = (self.phase + frequency_hz / self.sample_rate as f32).fract();
+        s
+    }
+
+    fn white(&mut self) -> f32 {
+        // xorshift32
+        let mut x = self.rng;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.rng = x;
+        (x as f32 / u32::MAX as f32) * 2.0 - 1.0
+    }
+
+    /// Pink noise via Paul Kellet's refined filter, scaled so that
+    /// `amplitude` is the approximate RMS level.
+    fn pink(&mut self, amplitude: f32) -> f32 {
+        let w = self.white();
+        let b = &mut self.pink;
+        b[0] = 0.99886 * b[0] + w * 0.0555179;
+        b[1] = 0.99332 * b[1] + w * 0.0750759;
+        b[2] = 0.96900 * b[2] + w * 0.153_852;
+        b[3] = 0.86650 * b[3] + w * 0.3104856;
+        b[4] = 0.55000 * b[4] + w * 0.5329522;
+        b[5] = -0.7616 * b[5] - w * 0.0168980;
+        let out = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + w * 0.5362;
+        b[6] = w * 0.115926;
+        (out * PINK_RMS_SCALE * amplitude).clamp(-1.0, 1.0)
+    }
+
+    fn next(&mut self, signal: &ToneSignal) -> f32 {
+        match *signal {
+            ToneSignal::Sine {
+                frequency_hz,
+                level_dbfs,
+            } => self.sine(frequency_hz, dbfs_to_amplitude(level_dbfs)),
+            ToneSignal::PinkNoise { level_dbfs } => self.pink(dbfs_to_amplitude(level_dbfs)),
+            ToneSignal::Reference1k => {
+                self.sine(REFERENCE_FREQUENCY_HZ, dbfs_to_amplitude(REFERENCE_LEVEL_DBFS))
+            }
+        }
+    }
+}
+
+/// Normalises the Kellet filter output (RMS ≈ 1.8 for uniform white input) to unit RMS.
+const PINK_RMS_SCALE: f32 = 0.56;
+
+// ── Shared state ───────────────────────────────────────────────
+
+struct Shared {
+    state: ToneState,
+    /// Set when the signal changed so the generator restarts its phase.
+    restart: bool,
+    /// When the current loopback burst was pushed towards the mix.
+    burst_emitted_at: Option<Instant>,
+}
+
+/// Control surface for a running (or not yet started) `ToneGenerator`.
+#[derive(Clone)]
+pub struct ToneHandle {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl ToneHandle {
+    pub fn start_tone(&self, signal: ToneSignal, target: ToneTarget) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.state = ToneState {
+            signal: Some(signal),
+            target,
+            measuring_input: None,
+            measurement: None,
+        };
+        shared.restart = true;
+        shared.burst_emitted_at = None;
+    }
+
+    pub fn stop_tone(&self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.state.signal = None;
+        shared.state.measuring_input = None;
+        shared.burst_emitted_at = None;
+    }
+
+    /// Emit reference bursts into the mix and measure their arrival on `input_id`.
+    pub fn measure_loopback(&self, input_id: &str) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.state = ToneState {
+            signal: Some(ToneSignal::Reference1k),
+            target: ToneTarget::Mix,
+            measuring_input: Some(input_id.to_string()),
+            measurement: None,
+        };
+        shared.restart = true;
+        shared.burst_emitted_at = None;
+    }
+
+    pub fn state(&self) -> ToneState {
+        self.shared.lock().unwrap().state.clone()
+    }
+}
+
+// ── ToneGenerator ──────────────────────────────────────────────
+
+/// Virtual input that generates test signals into the mix or an ASR tap.
+pub struct ToneGenerator {
+    sample_rate: u32,
+    channels: u16,
+    producer: HeapProd<f32>,
+    taps: HashMap<String, TapSender>,
+    oscillator: Oscillator,
+    /// Sample position within the loopback burst period.
+    burst_pos: u32,
+    handle: ToneHandle,
+}
+
+impl ToneGenerator {
+    pub fn new(sample_rate: u32, channels: u16, producer: HeapProd<f32>) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            producer,
+            taps: HashMap::new(),
+            oscillator: Oscillator::new(sample_rate),
+            burst_pos: 0,
+            handle: ToneHandle {
+                shared: Arc::new(Mutex::new(Shared {
+                    state: ToneState::default(),
+                    restart: false,
+                    burst_emitted_at: None,
+                })),
+            },
+        }
+    }
+
+    /// Register an input's ASR tap so tones can be injected into it.
+    pub fn add_tap(&mut self, input_id: &str, tap: TapSender) {
+        self.taps.insert(input_id.to_string(), tap);
+    }
+
+    pub fn handle(&self) -> ToneHandle {
+        self.handle.clone()
+    }
+
+    /// Analyzer to attach to `input_id` in the mixer for loopback measurement.
+    pub fn loopback_analyzer(&self, input_id: &str) -> Box<dyn InputAnalyzer> {
+        Box::new(LoopbackAnalyzer::new(
+            input_id,
+            self.sample_rate,
+            Arc::clone(&self.handle.shared),
+        ))
+    }
+
+    /// Generate `frames` frames of the current signal and deliver them.
+    /// Returns the number of samples generated (0 when idle).
+    pub fn generate(&mut self, frames: usize) -> usize {
+        let (state, restart) = {
+            let mut shared = self.handle.shared.lock().unwrap();
+            let restart = std::mem::take(&mut shared.restart);
+            (shared.state.clone(), restart)
+        };
+        if restart {
+            self.oscillator.reset();
+            self.burst_pos = 0;
+        }
+        let Some(signal) = state.signal else {
+            return 0;
+        };
+
+        let measuring = state.measuring_input.is_some();
+        let burst_len = self.sample_rate * BURST_MS / 1000;
+        let period = self.sample_rate * BURST_PERIOD_MS / 1000;
+        let channels = self.channels as usize;
+        let mut samples = Vec::with_capacity(frames * channels);
+        let mut burst_started = false;
+
+        for _ in 0..frames {
+            let s = if measuring {
+                if self.burst_pos == 0 {
+                    self.oscillator.reset();
+                    burst_started = true;
+                }
+                let on = self.burst_pos < burst_len;
+                self.burst_pos = (self.burst_pos + 1) % period;
+                if on {
+                    self.oscillator.next(&signal)
+                } else {
+                    0.0
+                }
+            } else {
+                self.oscillator.next(&signal)
+            };
+            samples.extend(std::iter::repeat_n(s, channels));
+        }
+
+        if burst_started {
+            self.handle.shared.lock().unwrap().burst_emitted_at = Some(Instant::now());
+        }
+
+        let n = samples.len();
+        match state.target {
+            ToneTarget::Mix => {
+                self.producer.push_slice(&samples);
+            }
+            ToneTarget::AsrTap(ref id) => {
+                if let Some(tap) = self.taps.get(id) {
+                    let _ = tap.send(AudioChunk {
+                        samples,
+                        sample_rate: self.sample_rate,
+                        channels: self.channels,
+                    });
+                }
+            }
+        }
+        n
+    }
+
+    /// Run the generator loop until `running` is set to false.
+    pub fn run(&mut self, running: Arc<AtomicBool>) {
+        let max_frames = self.sample_rate as f64 * MAX_BURST.as_secs_f64();
+        let mut last = Instant::now();
+        let mut owed = 0.0f64;
+        while running.load(Ordering::Relaxed) {
+            std::thread::sleep(TICK);
+            let now = Instant::now();
+            owed = (owed + (now - last).as_secs_f64() * self.sample_rate as f64).min(max_frames);
+            last = now;
+            let frames = owed.floor();
+            owed -= frames;
+            self.generate(frames as usize);
+        }
+    }
+
+    /// Spawn the generator on a dedicated thread. Consumes self.
+    pub fn start(mut self) -> ToneGeneratorHandle {
+        let running = Arc::new(AtomicBool::new(true));
+        let flag = Arc::clone(&running);
+        let handle = self.handle();
+        let thread = std::thread::Builder::new()
+            .name("tone".into())
+            .spawn(move || {
+                self.run(flag);
+            })
+            .expect("failed to spawn tone thread");
+        ToneGeneratorHandle {
+            running,
+            thread: Some(thread),
+            handle,
+        }
+    }
+}
+
+// ── ToneGeneratorHandle ────────────────────────────────────────
+
+pub struct ToneGeneratorHandle {
+    running: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+    handle: ToneHandle,
+}
+
+impl ToneGeneratorHandle {
+    /// Signal the generator thread to stop and wait for it to finish.
+    /// This releases any ASR tap senders the generator holds.
+    pub fn stop(mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(t) = self.thread.take() {
+            t.join().expect("tone thread panicked");
+        }
+    }
+
+    pub fn handle(&self) -> ToneHandle {
+        self.handle.clone()
+    }
+}
+
+// ── LoopbackAnalyzer ───────────────────────────────────────────
+
+enum Detect {
+    /// Waiting for a burst to arrive.
+    Idle,
+    /// Burst arrived after `latency_ms`; measuring its level.
+    Level {
+        latency_ms: f32,
+        skip: u32,
+        sum_sq: f64,
+        count: u32,
+    },
+}
+
+/// Detects loopback bursts on one input and reports latency and level.
+struct LoopbackAnalyzer {
+    input_id: String,
+    sample_rate: u32,
+    shared: Arc<Mutex<Shared>>,
+    window: u32,
+    window_sum_sq: f64,
+    window_count: u32,
+    detect: Detect,
+}
+
+impl LoopbackAnalyzer {
+    fn new(input_id: &str, sample_rate: u32, shared: Arc<Mutex<Shared>>) -> Self {
+        Self {
+            input_id: input_id.to_string(),
+            sample_rate,
+            shared,
+            window: (sample_rate / 1000).max(1),
+            window_sum_sq: 0.0,
+            window_count: 0,
+            detect: Detect::Idle,
+        }
+    }
+}
+
+impl InputAnalyzer for LoopbackAnalyzer {
+    fn analyze(&mut self, samples: &[f32]) {
+        let mut shared = self.shared.lock().unwrap();
+        if shared.state.measuring_input.as_deref() != Some(self.input_id.as_str()) {
+            self.detect = Detect::Idle;
+            return;
+        }
+
+        let now = Instant::now();
+        let threshold = dbfs_to_amplitude(ONSET_THRESHOLD_DBFS) as f64;
+        let skip_len = self.sample_rate * LEVEL_SKIP_MS / 1000;
+        let level_len = self.sample_rate * LEVEL_WINDOW_MS / 1000;
+
+        for (i, &s) in samples.iter().enumerate() {
+            let sq = s as f64 * s as f64;
+            match &mut self.detect {
+                Detect::Idle => {
+                    self.window_sum_sq += sq;
+                    self.window_count += 1;
+                    if self.window_count < self.window {
+                        continue;
+                    }
+                    let rms = (self.window_sum_sq / self.window_count as f64).sqrt();
+                    self.window_sum_sq = 0.0;
+                    self.window_count = 0;
+                    if rms < threshold {
+                        continue;
+                    }
+                    let Some(emitted) = shared.burst_emitted_at.take() else {
+                        continue;
+                    };
+                    // Back-date to the start of this window within the block
+                    let behind = (samples.len() - i - 1) as u32 + self.window;
+                    let arrived = now.checked_sub(Duration::from_secs_f64(
+                        behind as f64 / self.sample_rate as f64,
+                    ));
+                    let latency = arrived
+                        .map(|t| t.saturating_duration_since(emitted))
+                        .unwrap_or_default();
+                    self.detect = Detect::Level {
+                        latency_ms: latency.as_secs_f32() * 1000.0,
+                        skip: skip_len,
+                        sum_sq: 0.0,
+                        count: 0,
+                    };
+                }
+                Detect::Level {
+                    latency_ms,
+                    skip,
+                    sum_sq,
+                    count,
+                } => {
+                    if *skip > 0 {
+                        *skip -= 1;
+                        continue;
+                    }
+                    *sum_sq += sq;
+                    *count += 1;
+                    if *count >= level_len {
+                        let rms = (*sum_sq / *count as f64).sqrt() as f32;
+                        let emitted_rms =
+                            dbfs_to_amplitude(REFERENCE_LEVEL_DBFS) / std::f32::consts::SQRT_2;
+                        shared.state.measurement = Some(LoopbackMeasurement {
+                            latency_ms: *latency_ms,
+                            level_db: 20.0 * (rms.max(1e-9) / emitted_rms).log10(),
+                        });
+                        self.detect = Detect::Idle;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ringbuf::traits::{Consumer, Observer};
+    use ringbuf::HeapCons;
+
+    fn make_generator(rate: u32) -> (ToneGenerator, HeapCons<f32>) {
+        let (prod, cons) = crate::create_ring_buffer(rate as usize * 4);
+        (ToneGenerator::new(rate, 1, prod), cons)
+    }
+
+    fn drain(cons: &mut HeapCons<f32>) -> Vec<f32> {
+        let mut out = vec![0.0; cons.occupied_len()];
+        cons.pop_slice(&mut out);
+        out
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_dbfs_to_amplitude() {
+        assert!((dbfs_to_amplitude(0.0) - 1.0).abs() < 1e-6);
+        assert!((dbfs_to_amplitude(-20.0) - 0.1).abs() < 1e-6);
+        assert!((dbfs_to_amplitude(-6.0) - 0.501).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_idle_generator_produces_nothing() {
+        let (mut gen, mut cons) = make_generator(48000);
+        assert_eq!(gen.generate(480), 0);
+        assert!(drain(&mut cons).is_empty());
+    }
+
+    #[test]
+    fn test_sine_level_and_frequency() {
+        let (mut gen, mut cons) = make_generator(48000);
+        gen.handle().start_tone(
+            ToneSignal::Sine {
+                frequency_hz: 440.0,
+                level_dbfs: -6.0,
+            },
+            ToneTarget::Mix,
+        );
+        assert_eq!(gen.generate(48000), 48000);
+        let out = drain(&mut cons);
+
+        let peak = out.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!((peak - dbfs_to_amplitude(-6.0)).abs() < 1e-3);
+        let rising = out.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count();
+        assert!((439..=441).contains(&rising), "crossings: {}", rising);
+    }
+
+    #[test]
+    fn test_reference_is_1k_at_minus_20() {
+        let (mut gen, mut cons) = make_generator(48000);
+        gen.handle()
+            .start_tone(ToneSignal::Reference1k, ToneTarget::Mix);
+        gen.generate(4800);
+        let out = drain(&mut cons);
+        let expected = 0.1 / std::f32::consts::SQRT_2;
+        assert!((rms(&out) - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_pink_noise_level_and_bounds() {
+        let (mut gen, mut cons) = make_generator(48000);
+        gen.handle()
+            .start_tone(ToneSignal::PinkNoise { level_dbfs: -20.0 }, ToneTarget::Mix);
+        gen.generate(96000);
+        let out = drain(&mut cons);
+        assert!(out.iter().all(|s| s.abs() <= 1.0));
+        let db = 20.0 * rms(&out).log10();
+        assert!((db + 20.0).abs() < 3.0, "pink level {} dBFS", db);
+    }
+
+    #[test]
+    fn test_stop_tone() {
+        let (mut gen, mut cons) = make_generator(48000);
+        let handle = gen.handle();
+        handle.start_tone(ToneSignal::Reference1k, ToneTarget::Mix);
+        gen.generate(100);
+        handle.stop_tone();
+        assert_eq!(gen.generate(100), 0);
+        assert_eq!(drain(&mut cons).len(), 100);
+        assert!(handle.state().signal.is_none());
+    }
+
+    #[test]
+    fn test_asr_tap_target() {
+        let (mut gen, mut cons) = make_generator(16000);
+        let (tx, mut rx) = voxmux_core::asr_tap(5000, Default::default());
+        gen.add_tap("mic", tx);
+        gen.handle().start_tone(
+            ToneSignal::Reference1k,
+            ToneTarget::AsrTap("mic".to_string()),
+        );
+        gen.generate(160);
+
+        let chunk = rx.try_recv().unwrap();
+        assert_eq!(chunk.samples.len(), 160);
+        assert_eq!(chunk.sample_rate, 16000);
+        // Nothing reaches the mix
+        assert!(drain(&mut cons).is_empty());
+    }
+
+    #[test]
+    fn test_stereo_duplicates_frames() {
+        let (prod, mut cons) = crate::create_ring_buffer(1024);
+        let mut gen = ToneGenerator::new(48000, 2, prod);
+        gen.handle()
+            .start_tone(ToneSignal::Reference1k, ToneTarget::Mix);
+        assert_eq!(gen.generate(10), 20);
+        let out = drain(&mut cons);
+        assert!(out.chunks(2).all(|f| f[0] == f[1]));
+    }
+
+    #[test]
+    fn test_measure_mode_emits_bursts() {
+        let (mut gen, mut cons) = make_generator(8000);
+        gen.handle().measure_loopback("mic");
+        gen.generate(16000);
+        let out = drain(&mut cons);
+
+        // 200 ms on, 800 ms off
+        assert!(rms(&out[..1600]) > 0.05);
+        assert!(out[1600..8000].iter().all(|&s| s == 0.0));
+        assert!(rms(&out[8000..9600]) > 0.05);
+        assert!(out[9600..].iter().all(|&s| s == 0.0));
+        assert!(gen.handle.shared.lock().unwrap().burst_emitted_at.is_some());
+    }
+
+    #[test]
+    fn test_measure_state() {
+        let (gen, _cons) = make_generator(48000);
+        let handle = gen.handle();
+        handle.measure_loopback("mic");
+        let state = handle.state();
+        assert_eq!(state.signal, Some(ToneSignal::Reference1k));
+        assert_eq!(state.target, ToneTarget::Mix);
+        assert_eq!(state.measuring_input.as_deref(), Some("mic"));
+
+        handle.start_tone(ToneSignal::Reference1k, ToneTarget::Mix);
+        assert!(handle.state().measuring_input.is_none());
+    }
+
+    #[test]
+    fn test_loopback_analyzer_measures_latency_and_level() {
+        let rate = 48000;
+        let (mut gen, mut cons) = make_generator(rate);
+        let handle = gen.handle();
+        let mut analyzer = gen.loopback_analyzer("mic");
+
+        handle.measure_loopback("mic");
+        gen.generate(rate as usize / 5);
+        let burst = drain(&mut cons);
+        // Pretend the burst left 40 ms ago
+        gen.handle.shared.lock().unwrap().burst_emitted_at =
+            Some(Instant::now() - Duration::from_millis(40));
+
+        // Looped back 6 dB quieter, after some silence
+        let received: Vec<f32> = std::iter::repeat_n(0.0, 480)
+            .chain(burst.iter().map(|s| s * 0.5))
+            .collect();
+        for block in received.chunks(256) {
+            analyzer.analyze(block);
+        }
+
+        let m = handle.state().measurement.expect("no measurement");
+        assert!((m.level_db + 6.0).abs() < 0.2, "level {}", m.level_db);
+        assert!(m.latency_ms > 20.0 && m.latency_ms < 60.0, "latency {}", m.latency_ms);
+    }
+
+    #[test]
+    fn test_loopback_analyzer_ignores_other_inputs() {
+        let (gen, _cons) = make_generator(1000);
+        let handle = gen.handle();
+        let mut analyzer = gen.loopback_analyzer("other");
+        handle.measure_loopback("mic");
+        gen.handle.shared.lock().unwrap().burst_emitted_at = Some(Instant::now());
+
+        analyzer.analyze(&[0.5; 500]);
+        assert!(handle.state().measurement.is_none());
+        assert!(gen.handle.shared.lock().unwrap().burst_emitted_at.is_some());
+    }
+
+    #[test]
+    fn test_generator_thread_start_stop() {
+        let (gen, mut cons) = make_generator(48000);
+        let handle = gen.handle();
+        handle.start_tone(ToneSignal::Reference1k, ToneTarget::Mix);
+        let running = gen.start();
+        std::thread::sleep(Duration::from_millis(60));
+        running.stop();
+        let n = drain(&mut cons).len();
+        assert!(n > 0 && n < 48000, "generated {}", n);
+    }
+}
@@ -0,0 +1,810 @@
+use tokio::sync::mpsc::UnboundedSender;
+use voxmux_core::config::ToneDecodeConfig;
+use voxmux_core::{ToneEvent, ToneEventKind};
+
+pub const DTMF_ROWS: [f32; 4] = [697.0, 770.0, 852.0, 941.0];
+pub const DTMF_COLS: [f32; 4] = [1209.0, 1336.0, 1477.0, 1633.0];
+const DTMF_KEYS: [[char; 4]; 4] = [
+    ['1', '2', '3', 'A'],
+    ['4', '5', '6', 'B'],
+    ['7', '8', '9', 'C'],
+    ['*', '0', '#', 'D'],
+];
+
+/// The 50 standard EIA CTCSS tones, in Hz.
+pub const CTCSS_TONES: &[f32] = &[
+    67.0, 69.3, 71.9, 74.4, 77.0, 79.7, 82.5, 85.4, 88.5, 91.5, 94.8, 97.4, 100.0, 103.5, 107.2,
+    110.9, 114.8, 118.8, 123.0, 127.3, 131.8, 136.5, 141.3, 146.2, 151.4, 156.7, 159.8, 162.2,
+    165.5, 167.9, 171.3, 173.8, 177.3, 179.9, 183.5, 186.2, 189.9, 192.8, 196.6, 199.5, 203.5,
+    206.5, 210.7, 218.1, 225.7, 229.1, 233.6, 241.8, 250.3, 254.1,
+];
+
+/// The 104 standard DCS codes, written in octal as on radios (`0o023` is D023).
+pub const DCS_CODES: &[u16] = &[
+    0o023, 0o025, 0o026, 0o031, 0o032, 0o036, 0o043, 0o047, 0o051, 0o053, 0o054, 0o065, 0o071,
+    0o072, 0o073, 0o074, 0o114, 0o115, 0o116, 0o122, 0o125, 0o131, 0o132, 0o134, 0o143, 0o145,
+    0o152, 0o155, 0o156, 0o162, 0o165, 0o172, 0o174, 0o205, 0o212, 0o223, 0o225, 0o226, 0o243,
+    0o244, 0o245, 0o246, 0o251, 0o252, 0o255, 0o261, 0o263, 0o265, 0o266, 0o271, 0o274, 0o306,
+    0o311, 0o315, 0o325, 0o331, 0o332, 0o343, 0o346, 0o351, 0o356, 0o364, 0o365, 0o371, 0o411,
+    0o412, 0o413, 0o423, 0o431, 0o432, 0o445, 0o446, 0o452, 0o454, 0o455, 0o462, 0o464, 0o465,
+    0o466, 0o503, 0o506, 0o516, 0o523, 0o526, 0o532, 0o546, 0o565, 0o606, 0o612, 0o624, 0o627,
+    0o631, 0o632, 0o654, 0o662, 0o664, 0o703, 0o712, 0o723, 0o731, 0o732, 0o734, 0o743, 0o754,
+];
+
+/// DTMF analysis block (205 samples at 8 kHz).
+const DTMF_BLOCK_MS: f64 = 25.6;
+/// Minimum mean power of a block that may contain DTMF (-35 dBFS RMS).
+const DTMF_MIN_POWER: f64 = 3.2e-4;
+/// Share of block energy the row + column tones must carry.
+const DTMF_MIN_SHARE: f64 = 0.6;
+/// Allowed row/column level difference (±8 dB).
+const DTMF_MAX_TWIST: f64 = 6.3;
+/// Consecutive blocks a digit must persist before it is reported.
+const DTMF_CONFIRM_BLOCKS: u32 = 2;
+
+/// CTCSS analysis window; long enough to separate tones 2.3 Hz apart.
+const CTCSS_WINDOW_MS: f64 = 400.0;
+const CTCSS_LOWPASS_HZ: f64 = 300.0;
+/// Rate the low-passed signal is decimated to before analysis.
+const CTCSS_ANALYSIS_RATE: u32 = 4000;
+/// Minimum mean power of the low-passed signal (-45 dBFS RMS).
+const CTCSS_MIN_POWER: f64 = 3.2e-5;
+/// Share of low-passed energy the strongest tone must carry.
+const CTCSS_MIN_SHARE: f64 = 0.5;
+/// Consecutive windows needed to report a tone starting or ending.
+const CTCSS_CONFIRM_WINDOWS: u32 = 2;
+
+const DCS_BAUD: f64 = 134.4;
+/// Nine code bits, the `001` marker and eleven Golay check bits.
+const DCS_WORD_BITS: u32 = 23;
+/// Golay (23,12) generator, x^11 + x^10 + x^6 + x^5 + x^4 + x^2 + 1.
+const GOLAY_POLY: u32 = 0xC75;
+/// Share of the bit clock's phase error corrected at each transition.
+const DCS_CLOCK_GAIN: f64 = 0.3;
+/// Time constant of the level the bit slicer compares against.
+const DCS_SLICER_MS: f64 = 200.0;
+/// Consecutive word periods needed to report a code starting or ending.
+const DCS_CONFIRM_WORDS: u32 = 3;
+
+// ── Goertzel ───────────────────────────────────────────────────
+
+/// Single-frequency DFT power estimate over a block of samples.
+#[derive(Clone)]
+struct Goertzel {
+    coeff: f64,
+    s1: f64,
+    s2: f64,
+}
+
+impl Goertzel {
+    fn new(frequency_hz: f32, sample_rate: f64) -> Self {
+        let w = std::f64::consts::TAU * frequency_hz as f64 / sample_rate;
+        Self {
+            coeff: 2.0 * w.cos(),
+            s1: 0.0,
+            s2: 0.0,
+        }
+    }
+
+    fn push(&mut self, x: f64) {
+        let s = x + self.coeff * self.s1 - self.s2;
+        self.s2 = self.s1;
+        self.s1 = s;
+    }
+
+    /// Squared magnitude at the filter frequency; resets the filter.
+    fn take_power(&mut self) -> f64 {
+        let p = self.s1 * self.s1 + self.s2 * self.s2 - self.coeff * self.s1 * self.s2;
+        self.s1 = 0.0;
+        self.s2 = 0.0;
+        p
+    }
+}
+
+/// Fraction of a block's energy carried by a sinusoid with Goertzel power `power`.
+fn energy_share(power: f64, len: usize, energy: f64) -> f64 {
+    2.0 * power / (len as f64 * energy)
+}
+
+fn argmax(values: &[f64]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .fold(0, |best, (i, &v)| if v > values[best] { i } else { best })
+}
+
+// ── DTMF ───────────────────────────────────────────────────────
+
+struct DtmfDecoder {
+    filters: Vec<Goertzel>,
+    block: usize,
+    len: usize,
+    energy: f64,
+    candidate: Option<char>,
+    candidate_blocks: u32,
+    /// Digit currently held down (already reported).
+    current: Option<char>,
+}
+
+impl DtmfDecoder {
+    fn new(sample_rate: u32) -> Self {
+        let rate = sample_rate as f64;
+        Self {
+            filters: DTMF_ROWS
+                .iter()
+                .chain(&DTMF_COLS)
+                .map(|&f| Goertzel::new(f, rate))
+                .collect(),
+            block: (rate * DTMF_BLOCK_MS / 1000.0).round() as usize,
+            len: 0,
+            energy: 0.0,
+            candidate: None,
+            candidate_blocks: 0,
+            current: None,
+        }
+    }
+
+    /// Feed one sample. Returns a digit when a new key press is confirmed.
+    fn push(&mut self, x: f64) -> Option<char> {
+        for f in &mut self.filters {
+            f.push(x);
+        }
+        self.energy += x * x;
+        self.len += 1;
+        if self.len < self.block {
+            return None;
+        }
+
+        let powers: Vec<f64> = self.filters.iter_mut().map(Goertzel::take_power).collect();
+        let digit = self.classify(&powers);
+        self.len = 0;
+        self.energy = 0.0;
+
+        if digit == self.candidate {
+            self.candidate_blocks += 1;
+        } else {
+            self.candidate = digit;
+            self.candidate_blocks = 1;
+        }
+        match digit {
+            Some(d) if self.candidate_blocks >= DTMF_CONFIRM_BLOCKS && self.current != digit => {
+                self.current = digit;
+                Some(d)
+            }
+            None => {
+                self.current = None;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn classify(&self, powers: &[f64]) -> Option<char> {
+        if self.energy / (self.len as f64) < DTMF_MIN_POWER {
+            return None;
+        }
+        let share: Vec<f64> = powers
+            .iter()
+            .map(|&p| energy_share(p, self.len, self.energy))
+            .collect();
+        let (rows, cols) = share.split_at(4);
+        let row = argmax(rows);
+        let col = argmax(cols);
+        if rows[row] + cols[col] < DTMF_MIN_SHARE {
+            return None;
+        }
+        let twist = rows[row] / cols[col];
+        if !(1.0 / DTMF_MAX_TWIST..=DTMF_MAX_TWIST).contains(&twist) {
+            return None;
+        }
+        // The other tones in each group must be at least 6 dB down
+        let clear = |group: &[f64], peak: usize| {
+            group
+                .iter()
+                .enumerate()
+                .all(|(i, &s)| i == peak || s * 4.0 < group[peak])
+        };
+        if !clear(rows, row) || !clear(cols, col) {
+            return None;
+        }
+        Some(DTMF_KEYS[row][col])
+    }
+
+    /// `true` while a DTMF tone may be present, including before it is confirmed.
+    fn is_active(&self) -> bool {
+        self.candidate.is_some() || self.current.is_some()
+    }
+}
+
+// ── CTCSS ──────────────────────────────────────────────────────
+
+/// RBJ biquad low-pass (Butterworth Q).
+struct LowPass {
+    b: [f64; 3],
+    a: [f64; 2],
+    x: [f64; 2],
+    y: [f64; 2],
+}
+
+impl LowPass {
+    fn new(cutoff_hz: f64, sample_rate: f64) -> Self {
+        let w = std::f64::consts::TAU * cutoff_hz / sample_rate;
+        let alpha = w.sin() / std::f64::consts::SQRT_2;
+        let cos = w.cos();
+        let a0 = 1.0 + alpha;
+        Self {
+            b: [(1.0 - cos) / 2.0 / a0, (1.0 - cos) / a0, (1.0 - cos) / 2.0 / a0],
+            a: [-2.0 * cos / a0, (1.0 - alpha) / a0],
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+            - self.a[0] * self.y[0]
+            - self.a[1] * self.y[1];
+        self.x = [x, self.x[0]];
+        self.y = [y, self.y[0]];
+        y
+    }
+}
+
+struct CtcssDecoder {
+    lowpass: [LowPass; 2],
+    decimation: u32,
+    phase: u32,
+    filters: Vec<Goertzel>,
+    window: usize,
+    len: usize,
+    energy: f64,
+    candidate: Option<usize>,
+    candidate_windows: u32,
+    /// Tone currently reported as present, by index into `CTCSS_TONES`.
+    current: Option<usize>,
+}
+
+impl CtcssDecoder {
+    fn new(sample_rate: u32) -> Self {
+        let rate = sample_rate as f64;
+        let decimation = (sample_rate / CTCSS_ANALYSIS_RATE).max(1);
+        let analysis_rate = rate / decimation as f64;
+        Self {
+            lowpass: [
+                LowPass::new(CTCSS_LOWPASS_HZ, rate),
+                LowPass::new(CTCSS_LOWPASS_HZ, rate),
+            ],
+            decimation,
+            phase: 0,
+            filters: CTCSS_TONES
+                .iter()
+                .map(|&f| Goertzel::new(f, analysis_rate))
+                .collect(),
+            window: (analysis_rate * CTCSS_WINDOW_MS / 1000.0).round() as usize,
+            len: 0,
+            energy: 0.0,
+            candidate: None,
+            candidate_windows: 0,
+            current: None,
+        }
+    }
+
+    /// Feed one sample. Returns a start/end event when the detected tone changes.
+    fn push(&mut self, x: f64) -> Vec<ToneEventKind> {
+        let y = self
+            .lowpass
+            .iter_mut()
+            .fold(x, |acc, stage| stage.process(acc));
+        self.phase += 1;
+        if self.phase < self.decimation {
+            return Vec::new();
+        }
+        self.phase = 0;
+
+        for f in &mut self.filters {
+            f.push(y);
+        }
+        self.energy += y * y;
+        self.len += 1;
+        if self.len < self.window {
+            return Vec::new();
+        }
+
+        let powers: Vec<f64> = self.filters.iter_mut().map(Goertzel::take_power).collect();
+        let best = argmax(&powers);
+        let tone = (self.energy / self.len as f64 >= CTCSS_MIN_POWER
+            && energy_share(powers[best], self.len, self.energy) >= CTCSS_MIN_SHARE)
+            .then_some(best);
+        self.len = 0;
+        self.energy = 0.0;
+
+        if tone == self.candidate {
+            self.candidate_windows += 1;
+        } else {
+            self.candidate = tone;
+            self.candidate_windows = 1;
+        }
+        if self.candidate_windows < CTCSS_CONFIRM_WINDOWS || tone == self.current {
+            return Vec::new();
+        }
+
+        let mut events = Vec::new();
+        if let Some(old) = self.current {
+            events.push(ToneEventKind::CtcssEnd {
+                frequency_hz: CTCSS_TONES[old],
+            });
+        }
+        if let Some(new) = tone {
+            events.push(ToneEventKind::CtcssStart {
+                frequency_hz: CTCSS_TONES[new],
+            });
+        }
+        self.current = tone;
+        events
+    }
+}
+
+// ── DCS ────────────────────────────────────────────────────────
+
+/// The Golay check bits of 12 data bits.
+fn golay_check_bits(data: u32) -> u32 {
+    let mut r = data << 11;
+    for bit in (11..23).rev() {
+        if r & (1 << bit) != 0 {
+            r ^= GOLAY_POLY << (bit - 11);
+        }
+    }
+    r
+}
+
+/// The 23-bit word repeated for a DCS code, first transmitted bit lowest.
+fn dcs_word(code: u16) -> u32 {
+    let data = 0x800 | u32::from(code);
+    data | golay_check_bits(data) << 12
+}
+
+/// The standard code `word` carries, if it is one.
+fn dcs_code(word: u32) -> Option<u16> {
+    let code = (word & 0x1FF) as u16;
+    (word & 0xE00 == 0x800 && dcs_word(code) == word && DCS_CODES.contains(&code)).then_some(code)
+}
+
+/// Digital-Coded Squelch: a 23-bit word sent over and over as 134.4 bit/s
+/// NRZ below the voice band. Every inverted code is the same bit stream as
+/// a normal one (754I is 116N), so codes are reported by their normal name.
+struct DcsDecoder {
+    lowpass: [LowPass; 2],
+    decimation: u32,
+    phase: u32,
+    /// Slow average of the low-passed signal; bits are read against it.
+    threshold: f64,
+    threshold_alpha: f64,
+    level: bool,
+    /// Bit clock, advanced per sample; bits are read when it wraps.
+    clock: f64,
+    clock_step: f64,
+    /// The last 23 bits, the latest highest.
+    bits: u32,
+    bit_count: u32,
+    /// Lowest code found at any alignment during the current word period;
+    /// a few codes are also rotations of others.
+    best: Option<u16>,
+    candidate: Option<u16>,
+    candidate_words: u32,
+    current: Option<u16>,
+}
+
+impl DcsDecoder {
+    fn new(sample_rate: u32) -> Self {
+        let rate = sample_rate as f64;
+        let decimation = (sample_rate / CTCSS_ANALYSIS_RATE).max(1);
+        let analysis_rate = rate / decimation as f64;
+        Self {
+            lowpass: [
+                LowPass::new(CTCSS_LOWPASS_HZ, rate),
+                LowPass::new(CTCSS_LOWPASS_HZ, rate),
+            ],
+            decimation,
+            phase: 0,
+            threshold: 0.0,
+            threshold_alpha: 1000.0 / (DCS_SLICER_MS * analysis_rate),
+            level: false,
+            clock: 0.0,
+            clock_step: DCS_BAUD / analysis_rate,
+            bits: 0,
+            bit_count: 0,
+            best: None,
+            candidate: None,
+            candidate_words: 0,
+            current: None,
+        }
+    }
+
+    /// Feed one sample. Returns a start/end event when the detected code changes.
+    fn push(&mut self, x: f64) -> Vec<ToneEventKind> {
+        let y = self
+            .lowpass
+            .iter_mut()
+            .fold(x, |acc, stage| stage.process(acc));
+        self.phase += 1;
+        if self.phase < self.decimation {
+            return Vec::new();
+        }
+        self.phase = 0;
+
+        self.threshold += (y - self.threshold) * self.threshold_alpha;
+        let level = y > self.threshold;
+        if level != self.level {
+            // Transitions belong halfway between two bit readings
+            self.level = level;
+            self.clock -= (self.clock - 0.5) * DCS_CLOCK_GAIN;
+        }
+        self.clock += self.clock_step;
+        if self.clock < 1.0 {
+            return Vec::new();
+        }
+        self.clock -= 1.0;
+
+        self.bits = (self.bits >> 1) | u32::from(level) << (DCS_WORD_BITS - 1);
+        if let Some(code) = dcs_code(self.bits) {
+            self.best = Some(self.best.map_or(code, |best| best.min(code)));
+        }
+        self.bit_count += 1;
+        if self.bit_count < DCS_WORD_BITS {
+            return Vec::new();
+        }
+        self.bit_count = 0;
+
+        let code = self.best.take();
+        if code == self.candidate {
+            self.candidate_words += 1;
+        } else {
+            self.candidate = code;
+            self.candidate_words = 1;
+        }
+        if self.candidate_words < DCS_CONFIRM_WORDS || code == self.current {
+            return Vec::new();
+        }
+
+        let mut events = Vec::new();
+        if let Some(code) = self.current {
+            events.push(ToneEventKind::DcsEnd { code });
+        }
+        if let Some(code) = code {
+            events.push(ToneEventKind::DcsStart { code });
+        }
+        self.current = code;
+        events
+    }
+}
+
+// ── ToneDecoder ────────────────────────────────────────────────
+
+/// Per-input DTMF, CTCSS and DCS decoder, run on the capture path.
+pub struct ToneDecoder {
+    input_id: String,
+    sample_rate: u32,
+    channels: usize,
+    dtmf: Option<DtmfDecoder>,
+    ctcss: Option<CtcssDecoder>,
+    dcs: Option<DcsDecoder>,
+    strip_dtmf: bool,
+    frames: u64,
+    events: UnboundedSender<ToneEvent>,
+}
+
+impl ToneDecoder {
+    pub fn new(
+        input_id: &str,
+        sample_rate: u32,
+        channels: u16,
+        config: &ToneDecodeConfig,
+        events: UnboundedSender<ToneEvent>,
+    ) -> Self {
+        Self {
+            input_id: input_id.to_string(),
+            sample_rate,
+            channels: channels.max(1) as usize,
+            dtmf: config.dtmf.then(|| DtmfDecoder::new(sample_rate)),
+            ctcss: config.ctcss.then(|| CtcssDecoder::new(sample_rate)),
+            dcs: config.dcs.then(|| DcsDecoder::new(sample_rate)),
+            strip_dtmf: config.strip_dtmf,
+            frames: 0,
+            events,
+        }
+    }
+
+    /// Analyse interleaved samples, sending any detections on the event channel.
+    pub fn process(&mut self, samples: &[f32]) {
+        for frame in samples.chunks(self.channels) {
+            let x = frame.iter().map(|&s| s as f64).sum::<f64>() / frame.len() as f64;
+            self.frames += 1;
+            if let Some(digit) = self.dtmf.as_mut().and_then(|d| d.push(x)) {
+                self.emit(ToneEventKind::Dtmf { digit });
+            }
+            if let Some(ctcss) = self.ctcss.as_mut() {
+                for kind in ctcss.push(x) {
+                    self.emit(kind);
+                }
+            }
+            if let Some(dcs) = self.dcs.as_mut() {
+                for kind in dcs.push(x) {
+                    self.emit(kind);
+                }
+            }
+        }
+    }
+
+    /// `true` when the ASR copy of the last processed audio should be silenced.
+    /// Detection works on 25.6 ms blocks, so the first block of a tone still leaks through.
+    pub fn should_strip(&self) -> bool {
+        self.strip_dtmf && self.dtmf.as_ref().is_some_and(DtmfDecoder::is_active)
+    }
+
+    fn emit(&self, kind: ToneEventKind) {
+        let _ = self.events.send(ToneEvent {
+            input_id: self.input_id.clone(),
+            timestamp: self.frames as f64 / self.sample_rate as f64,
+            kind,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+    const RATE: u32 = 48000;
+
+    fn tones(freqs: &[(f32, f32)], ms: u32) -> Vec<f32> {
+        let n = (RATE * ms / 1000) as usize;
+        (0..n)
+            .map(|i| {
+                let t = i as f32 / RATE as f32;
+                freqs
+                    .iter()
+                    .map(|&(f, a)| (std::f32::consts::TAU * f * t).sin() * a)
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn silence(ms: u32) -> Vec<f32> {
+        vec![0.0; (RATE * ms / 1000) as usize]
+    }
+
+    fn key(digit: char, ms: u32) -> Vec<f32> {
+        let (r, c) = (0..16)
+            .map(|i| (i / 4, i % 4))
+            .find(|&(r, c)| DTMF_KEYS[r][c] == digit)
+            .unwrap();
+        tones(&[(DTMF_ROWS[r], 0.25), (DTMF_COLS[c], 0.25)], ms)
+    }
+
+    /// `code` as ±`amplitude` NRZ at 134.4 bit/s.
+    fn dcs(code: u16, inverted: bool, amplitude: f32, ms: u32) -> Vec<f32> {
+        let word = dcs_word(code);
+        let n = (RATE * ms / 1000) as usize;
+        (0..n)
+            .map(|i| {
+                let bit = (i as f64 * DCS_BAUD / RATE as f64) as u32 % DCS_WORD_BITS;
+                if (word >> bit & 1 == 1) != inverted {
+                    amplitude
+                } else {
+                    -amplitude
+                }
+            })
+            .collect()
+    }
+
+    fn decoder(config: ToneDecodeConfig) -> (ToneDecoder, UnboundedReceiver<ToneEvent>) {
+        let (tx, rx) = unbounded_channel();
+        (ToneDecoder::new("radio", RATE, 1, &config, tx), rx)
+    }
+
+    fn kinds(rx: &mut UnboundedReceiver<ToneEvent>) -> Vec<ToneEventKind> {
+        std::iter::from_fn(|| rx.try_recv().ok()).map(|e| e.kind).collect()
+    }
+
+    fn digits(rx: &mut UnboundedReceiver<ToneEvent>) -> String {
+        kinds(rx)
+            .into_iter()
+            .filter_map(|k| match k {
+                ToneEventKind::Dtmf { digit } => Some(digit),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_goertzel_power_of_sinusoid() {
+        let mut g = Goertzel::new(1000.0, RATE as f64);
+        let signal = tones(&[(1000.0, 0.5)], 20);
+        for &s in &signal {
+            g.push(s as f64);
+        }
+        let energy: f64 = signal.iter().map(|&s| (s * s) as f64).sum();
+        let share = energy_share(g.take_power(), signal.len(), energy);
+        assert!((share - 1.0).abs() < 0.05, "share {}", share);
+    }
+
+    #[test]
+    fn test_dtmf_decodes_all_keys() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig::default());
+        let sequence = "123A456B789C*0#D";
+        for digit in sequence.chars() {
+            dec.process(&key(digit, 80));
+            dec.process(&silence(60));
+        }
+        assert_eq!(digits(&mut rx), sequence);
+    }
+
+    #[test]
+    fn test_dtmf_long_press_reported_once() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig::default());
+        dec.process(&key('5', 500));
+        dec.process(&silence(60));
+        dec.process(&key('5', 80));
+        assert_eq!(digits(&mut rx), "55");
+    }
+
+    #[test]
+    fn test_dtmf_ignores_single_tone_and_short_blips() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig::default());
+        dec.process(&tones(&[(DTMF_ROWS[0], 0.5)], 200));
+        dec.process(&key('1', 20));
+        dec.process(&silence(100));
+        assert!(digits(&mut rx).is_empty());
+    }
+
+    #[test]
+    fn test_dtmf_rejects_quiet_and_twisted() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig::default());
+        // Below -35 dBFS
+        dec.process(&tones(&[(DTMF_ROWS[1], 0.005), (DTMF_COLS[1], 0.005)], 200));
+        // Column 20 dB below the row
+        dec.process(&tones(&[(DTMF_ROWS[1], 0.5), (DTMF_COLS[1], 0.05)], 200));
+        assert!(digits(&mut rx).is_empty());
+    }
+
+    #[test]
+    fn test_ctcss_start_and_end() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig::default());
+        dec.process(&tones(&[(88.5, 0.1)], 2000));
+        dec.process(&silence(1200));
+        assert_eq!(
+            kinds(&mut rx),
+            vec![
+                ToneEventKind::CtcssStart { frequency_hz: 88.5 },
+                ToneEventKind::CtcssEnd { frequency_hz: 88.5 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ctcss_separates_adjacent_tones() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig::default());
+        dec.process(&tones(&[(69.3, 0.1)], 1500));
+        assert_eq!(
+            kinds(&mut rx),
+            vec![ToneEventKind::CtcssStart { frequency_hz: 69.3 }]
+        );
+    }
+
+    #[test]
+    fn test_ctcss_under_voice_band_audio() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig::default());
+        // Sub-audible tone 15 dB below mid-band "speech"
+        dec.process(&tones(&[(131.8, 0.05), (800.0, 0.3), (1700.0, 0.2)], 1500));
+        assert_eq!(
+            kinds(&mut rx),
+            vec![ToneEventKind::CtcssStart { frequency_hz: 131.8 }]
+        );
+    }
+
+    #[test]
+    fn test_dcs_words_are_golay_codewords() {
+        assert_eq!(DCS_CODES.len(), 104);
+        for &code in DCS_CODES {
+            let word = dcs_word(code);
+            assert_eq!(word >> DCS_WORD_BITS, 0);
+            assert_eq!(dcs_code(word), Some(code));
+            // Any single bit error is rejected
+            for bit in 0..DCS_WORD_BITS {
+                assert_eq!(dcs_code(word ^ 1 << bit), None, "{code:o} bit {bit}");
+            }
+        }
+        assert_eq!(dcs_code(dcs_word(0o024)), None);
+    }
+
+    #[test]
+    fn test_dcs_start_and_end() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig::default());
+        dec.process(&dcs(0o023, false, 0.1, 2000));
+        dec.process(&silence(1000));
+        assert_eq!(
+            kinds(&mut rx),
+            vec![
+                ToneEventKind::DcsStart { code: 0o023 },
+                ToneEventKind::DcsEnd { code: 0o023 },
+            ]
+        );
+    }
+
+    /// 754I is reported as the identical 116N.
+    #[test]
+    fn test_dcs_inverted_under_voice_band_audio() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig::default());
+        let mut signal = dcs(0o754, true, 0.05, 2000);
+        for (s, voice) in signal.iter_mut().zip(tones(&[(800.0, 0.3), (1700.0, 0.2)], 2000)) {
+            *s += voice;
+        }
+        dec.process(&signal);
+        assert_eq!(kinds(&mut rx), vec![ToneEventKind::DcsStart { code: 0o116 }]);
+    }
+
+    #[test]
+    fn test_dcs_ignores_ctcss_and_noise() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig {
+            ctcss: false,
+            ..Default::default()
+        });
+        dec.process(&tones(&[(88.5, 0.1), (800.0, 0.3)], 2000));
+        // Deterministic pseudo-random noise
+        let mut seed = 1u32;
+        let noise: Vec<f32> = (0..RATE * 2)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 8) as f32 / (1 << 24) as f32 * 0.2 - 0.1
+            })
+            .collect();
+        dec.process(&noise);
+        assert!(kinds(&mut rx).is_empty());
+    }
+
+    #[test]
+    fn test_decoders_can_be_disabled() {
+        let (mut dec, mut rx) = decoder(ToneDecodeConfig {
+            dtmf: false,
+            ctcss: false,
+            dcs: false,
+            strip_dtmf: true,
+        });
+        dec.process(&key('9', 200));
+        dec.process(&tones(&[(100.0, 0.1)], 1000));
+        dec.process(&dcs(0o023, false, 0.1, 1000));
+        assert!(kinds(&mut rx).is_empty());
+        assert!(!dec.should_strip());
+    }
+
+    #[test]
+    fn test_strip_follows_dtmf_activity() {
+        let (mut dec, _rx) = decoder(ToneDecodeConfig {
+            strip_dtmf: true,
+            ..Default::default()
+        });
+        dec.process(&key('7', 100));
+        assert!(dec.should_strip());
+        dec.process(&silence(60));
+        assert!(!dec.should_strip());
+
+        let (mut dec, _rx) = decoder(ToneDecodeConfig::default());
+        dec.process(&key('7', 100));
+        assert!(!dec.should_strip());
+    }
+
+    #[test]
+    fn test_event_timestamp_and_stereo_downmix() {
+        let (tx, mut rx) = unbounded_channel();
+        let mut dec = ToneDecoder::new("radio", RATE, 2, &ToneDecodeConfig::default(), tx);
+        let stereo: Vec<f32> = key('0', 100).iter().flat_map(|&s| [s, s]).collect();
+        dec.process(&stereo);
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.input_id, "radio");
+        assert_eq!(event.kind, ToneEventKind::Dtmf { digit: '0' });
+        // Confirmed at the end of the second 25.6 ms block
+        assert!((event.timestamp - 0.0512).abs() < 1e-3, "ts {}", event.timestamp);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::mixer::InputAnalyzer;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc::UnboundedSender;
+use voxmux_core::config::TransmissionConfig;
+use voxmux_core::{TransmissionEvent, TransmissionPhase};
+
+/// Level measurement window.
+const WINDOW_MS: u32 = 10;
+
+enum Gate {
+    Idle,
+    /// Above threshold, but not yet for `min_duration_ms`.
+    Pending { start: u64, last_above: u64, peak: f32 },
+    Active { start: u64, last_above: u64, peak: f32 },
+}
+
+/// Level gate with minimum duration and hang time, reporting keyed-up
+/// periods of an input as [`TransmissionEvent`]s.
+pub struct TransmissionDetector {
+    input_id: String,
+    sample_rate: u32,
+    channels: usize,
+    threshold: f32,
+    min_frames: u64,
+    hang_frames: u64,
+    window_frames: u64,
+    window_sum_sq: f64,
+    window_peak: f32,
+    window_count: u64,
+    /// Frames processed so far.
+    frames: u64,
+    gate: Gate,
+    tx: UnboundedSender<TransmissionEvent>,
+}
+
+impl TransmissionDetector {
+    pub fn new(
+        input_id: &str,
+        sample_rate: u32,
+        channels: u16,
+        config: &TransmissionConfig,
+        tx: UnboundedSender<TransmissionEvent>,
+    ) -> Self {
+        let ms = |ms: u32| sample_rate as u64 * ms as u64 / 1000;
+        Self {
+            input_id: input_id.to_string(),
+            sample_rate,
+            channels: channels.max(1) as usize,
+            threshold: 10f32.powf(config.threshold_dbfs / 20.0),
+            min_frames: ms(config.min_duration_ms),
+            hang_frames: ms(config.hang_ms),
+            window_frames: ms(WINDOW_MS).max(1),
+            window_sum_sq: 0.0,
+            window_peak: 0.0,
+            window_count: 0,
+            frames: 0,
+            gate: Gate::Idle,
+            tx,
+        }
+    }
+
+    /// Process interleaved samples that finished arriving at wall-clock `now`
+    /// (seconds since the Unix epoch).
+    pub fn process(&mut self, samples: &[f32], now: f64) {
+        let end_frame = self.frames + (samples.len() / self.channels) as u64;
+        for frame in samples.chunks(self.channels) {
+            let mono = frame.iter().sum::<f32>() / frame.len() as f32;
+            self.window_sum_sq += (mono * mono) as f64;
+            self.window_peak = self.window_peak.max(mono.abs());
+            self.window_count += 1;
+            self.frames += 1;
+            if self.window_count == self.window_frames {
+                self.end_window(end_frame, now);
+            }
+        }
+    }
+
+    fn end_window(&mut self, end_frame: u64, now: f64) {
+        let rms = (self.window_sum_sq / self.window_count as f64).sqrt() as f32;
+        let peak = self.window_peak;
+        self.window_sum_sq = 0.0;
+        self.window_peak = 0.0;
+        self.window_count = 0;
+
+        let frame = self.frames;
+        let above = rms >= self.threshold;
+        let window_start = frame - self.window_frames;
+        let wall = |f: u64| now - (end_frame - f) as f64 / self.sample_rate as f64;
+
+        self.gate = match std::mem::replace(&mut self.gate, Gate::Idle) {
+            Gate::Idle if above => Gate::Pending {
+                start: window_start,
+                last_above: frame,
+                peak,
+            },
+            Gate::Idle => Gate::Idle,
+            Gate::Pending {
+                start,
+                last_above,
+                peak: p,
+            } => {
+                let last_above = if above { frame } else { last_above };
+                let peak = if above { p.max(peak) } else { p };
+                if !above && frame - last_above >= self.hang_frames {
+                    Gate::Idle
+                } else if last_above - start >= self.min_frames {
+                    self.emit(TransmissionPhase::Start, wall(start), last_above - start, peak);
+                    Gate::Active {
+                        start,
+                        last_above,
+                        peak,
+                    }
+                } else {
+                    Gate::Pending {
+                        start,
+                        last_above,
+                        peak,
+                    }
+                }
+            }
+            Gate::Active {
+                start,
+                last_above,
+                peak: p,
+            } => {
+                let last_above = if above { frame } else { last_above };
+                let peak = if above { p.max(peak) } else { p };
+                if !above && frame - last_above >= self.hang_frames {
+                    self.emit(TransmissionPhase::End, wall(start), last_above - start, peak);
+                    Gate::Idle
+                } else {
+                    Gate::Active {
+                        start,
+                        last_above,
+                        peak,
+                    }
+                }
+            }
+        };
+    }
+
+    fn emit(&self, phase: TransmissionPhase, started_at: f64, frames: u64, peak: f32) {
+        let _ = self.tx.send(TransmissionEvent {
+            input_id: self.input_id.clone(),
+            phase,
+            started_at,
+            duration_secs: frames as f64 / self.sample_rate as f64,
+            peak_dbfs: 20.0 * peak.max(1e-6).log10(),
+        });
+    }
+}
+
+impl InputAnalyzer for TransmissionDetector {
+    fn analyze(&mut self, samples: &[f32]) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or(0.0);
+        self.process(samples, now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    const RATE: u32 = 8000;
+
+    fn detector() -> (TransmissionDetector, mpsc::UnboundedReceiver<TransmissionEvent>) {
+        detector_with_hang(500)
+    }
+
+    fn detector_with_hang(
+        hang_ms: u32,
+    ) -> (TransmissionDetector, mpsc::UnboundedReceiver<TransmissionEvent>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let config = TransmissionConfig {
+            threshold_dbfs: -40.0,
+            min_duration_ms: 200,
+            hang_ms,
+        };
+        (TransmissionDetector::new("rx1", RATE, 1, &config, tx), rx)
+    }
+
+    fn carrier(ms: u32, amplitude: f32) -> Vec<f32> {
+        (0..RATE * ms / 1000)
+            .map(|i| (i as f32 * 0.3).sin() * amplitude)
+            .collect()
+    }
+
+    fn silence(ms: u32) -> Vec<f32> {
+        vec![0.0; (RATE * ms / 1000) as usize]
+    }
+
+    fn drain(rx: &mut mpsc::UnboundedReceiver<TransmissionEvent>) -> Vec<TransmissionEvent> {
+        let mut events = Vec::new();
+        while let Ok(e) = rx.try_recv() {
+            events.push(e);
+        }
+        events
+    }
+
+    #[test]
+    fn test_transmission_start_and_end() {
+        let (mut det, mut rx) = detector();
+        let signal: Vec<f32> = silence(1000)
+            .into_iter()
+            .chain(carrier(3000, 0.5))
+            .chain(silence(1000))
+            .collect();
+        // The whole buffer finished arriving at t = 1000 s
+        det.process(&signal, 1000.0);
+
+        let events = drain(&mut rx);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].phase, TransmissionPhase::Start);
+        assert_eq!(events[0].input_id, "rx1");
+        assert!((events[0].started_at - 996.0).abs() < 0.02, "{}", events[0].started_at);
+
+        let end = &events[1];
+        assert_eq!(end.phase, TransmissionPhase::End);
+        assert_eq!(end.started_at, events[0].started_at);
+        assert!((end.duration_secs - 3.0).abs() < 0.02, "{}", end.duration_secs);
+        assert!((end.peak_dbfs - -6.0).abs() < 0.2, "{}", end.peak_dbfs);
+    }
+
+    #[test]
+    fn test_transmission_ignores_short_bursts() {
+        let (mut det, mut rx) = detector();
+        det.process(&carrier(100, 0.5), 0.0);
+        det.process(&silence(1000), 1.0);
+        assert!(drain(&mut rx).is_empty());
+    }
+
+    #[test]
+    fn test_transmission_below_threshold_ignored() {
+        let (mut det, mut rx) = detector();
+        det.process(&carrier(2000, 0.005), 0.0);
+        assert!(drain(&mut rx).is_empty());
+    }
+
+    #[test]
+    fn test_transmission_hang_bridges_pauses() {
+        let (mut det, mut rx) = detector();
+        for _ in 0..3 {
+            det.process(&carrier(500, 0.5), 0.0);
+            det.process(&silence(300), 0.0);
+        }
+        assert_eq!(drain(&mut rx).len(), 1); // start only
+        det.process(&silence(600), 0.0);
+        let events = drain(&mut rx);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].phase, TransmissionPhase::End);
+        // 3 × 500 ms keyed + 2 × 300 ms pauses
+        assert!((events[0].duration_secs - 2.1).abs() < 0.02, "{}", events[0].duration_secs);
+    }
+
+    #[test]
+    fn test_transmission_zero_hang_ends_on_first_quiet_window() {
+        let (mut det, mut rx) = detector_with_hang(0);
+        det.process(&carrier(1000, 0.5), 0.0);
+        let events = drain(&mut rx);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].phase, TransmissionPhase::Start);
+
+        det.process(&silence(10), 0.0);
+        let events = drain(&mut rx);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].phase, TransmissionPhase::End);
+        assert!((events[0].duration_secs - 1.0).abs() < 0.02, "{}", events[0].duration_secs);
+    }
+
+    #[test]
+    fn test_transmission_start_reported_while_keyed() {
+        let (mut det, mut rx) = detector();
+        det.process(&carrier(300, 0.5), 0.0);
+        let events = drain(&mut rx);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].phase, TransmissionPhase::Start);
+        assert!(events[0].duration_secs >= 0.2);
+    }
+}
@@ -0,0 +1,91 @@
+use std::path::Path;
+use voxmux_core::AudioError;
+
+/// Write interleaved samples as a 16-bit PCM WAV file.
+pub fn write_wav(
+    path: &Path,
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+) -> Result<(), AudioError> {
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let wav_err = |e: hound::Error| AudioError::Wav(format!("{}: {e}", path.display()));
+    let mut writer = hound::WavWriter::create(path, spec).map_err(wav_err)?;
+    for &s in samples {
+        let v = (s.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+        writer.write_sample(v).map_err(wav_err)?;
+    }
+    writer.finalize().map_err(wav_err)
+}
+
+/// Read a WAV file as interleaved f32 samples, with its sample rate and
+/// channel count.
+pub fn read_wav(path: &Path) -> Result<(Vec<f32>, u32, u16), AudioError> {
+    let wav_err = |e: hound::Error| AudioError::Wav(format!("{}: {e}", path.display()));
+    let reader = hound::WavReader::open(path).map_err(wav_err)?;
+    let spec = reader.spec();
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader.into_samples::<f32>().collect::<Result<_, _>>(),
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|s| s.map(|s| s as f32 * scale))
+                .collect()
+        }
+    }
+    .map_err(wav_err)?;
+    Ok((samples, spec.sample_rate, spec.channels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_wav_round_trip() {
+        let dir = std::env::temp_dir().join(format!("voxmux-wav-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("out.wav");
+
+        write_wav(&path, &[0.0, 0.5, -1.0, 2.0], 8000, 2).unwrap();
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().sample_rate, 8000);
+        assert_eq!(reader.spec().channels, 2);
+        let samples: Vec<i16> = reader.samples::<i16>().map(|s| s.unwrap()).collect();
+        assert_eq!(samples, vec![0, 16384, -32767, 32767]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_wav_bad_path() {
+        let err = write_wav(Path::new("/nonexistent/dir/x.wav"), &[0.0], 8000, 1).unwrap_err();
+        assert!(matches!(err, AudioError::Wav(_)));
+    }
+
+    #[test]
+    fn test_read_wav_round_trip() {
+        let dir = std::env::temp_dir().join(format!("voxmux-wav-read-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cue.wav");
+
+        write_wav(&path, &[0.0, 0.5, -0.5, 0.25], 22050, 1).unwrap();
+        let (samples, rate, channels) = read_wav(&path).unwrap();
+        assert_eq!((rate, channels), (22050, 1));
+        assert_eq!(samples.len(), 4);
+        assert!((samples[1] - 0.5).abs() < 1e-4);
+        assert!((samples[2] + 0.5).abs() < 1e-4);
+
+        assert!(matches!(
+            read_wav(&dir.join("missing.wav")),
+            Err(AudioError::Wav(_))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
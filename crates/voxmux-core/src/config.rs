@@ -1,7 +1,9 @@
 use crate::error::ConfigError;
+use crate::tap::OverflowPolicy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::time::Duration;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
@@ -19,6 +21,27 @@ pub struct AppConfig {
 
     #[serde(default)]
     pub destinations: Option<toml::Value>,
+
+    #[serde(default)]
+    pub replay: ReplayConfig,
+
+    #[serde(default)]
+    pub tts: Option<TtsConfig>,
+
+    #[serde(default)]
+    pub cues: Vec<CueConfig>,
+
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+
+    #[serde(default)]
+    pub text: TextConfig,
+
+    #[serde(default)]
+    pub translation: Option<TranslationConfig>,
+
+    #[serde(default)]
+    pub alerts: Vec<AlertRuleConfig>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -31,6 +54,10 @@ pub struct GeneralConfig {
 
     #[serde(default = "default_buffer_size")]
     pub buffer_size: u32,
+
+    /// File that transmission start/end events are appended to.
+    #[serde(default)]
+    pub activity_log: Option<String>,
 }
 
 impl Default for GeneralConfig {
@@ -39,6 +66,7 @@ impl Default for GeneralConfig {
             log_level: default_log_level(),
             sample_rate: default_sample_rate(),
             buffer_size: default_buffer_size(),
+            activity_log: None,
         }
     }
 }
@@ -74,30 +102,666 @@ pub struct InputConfig {
     #[serde(default = "default_volume")]
     pub volume: f32,
 
-    #[serde(default)]
-    pub muted: bool,
+    #[serde(default)]
+    pub muted: bool,
+
+    #[serde(default)]
+    pub destinations: Vec<DestinationRouteConfig>,
+
+    #[serde(default)]
+    pub tones: Option<ToneDecodeConfig>,
+
+    #[serde(default)]
+    pub spectrum: Option<SpectrumConfig>,
+
+    #[serde(default)]
+    pub transmission: Option<TransmissionConfig>,
+
+    #[serde(default)]
+    pub asr: Option<InputAsrConfig>,
+
+    #[serde(default)]
+    pub text: Option<TextConfig>,
+}
+
+/// Per-input ASR overrides (`[input.asr]`). Keys other than `engine` are
+/// merged over the global engine table, e.g. `[asr.whisper]`.
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct InputAsrConfig {
+    #[serde(default)]
+    pub engine: Option<String>,
+
+    /// Scheduling priority on a shared engine; higher runs first.
+    #[serde(default)]
+    pub priority: i32,
+
+    #[serde(flatten)]
+    pub settings: toml::Table,
+}
+
+/// Chain of text processors applied to recognised text (`[text]`, `[input.text]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TextConfig {
+    /// Per input: run the global chain before this one.
+    #[serde(default = "default_true")]
+    pub inherit: bool,
+
+    #[serde(default)]
+    pub processors: Vec<TextProcessorConfig>,
+}
+
+impl Default for TextConfig {
+    fn default() -> Self {
+        Self {
+            inherit: true,
+            processors: Vec::new(),
+        }
+    }
+}
+
+/// One step of a text chain (`[[text.processors]]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TextProcessorConfig {
+    /// Processor name; `regex`, `dictionary`, `normalize` and `width` are built in.
+    pub processor: String,
+
+    /// Processor-specific settings.
+    #[serde(flatten)]
+    pub settings: toml::Table,
+}
+
+/// Per-input DTMF / CTCSS decoding (`[input.tones]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ToneDecodeConfig {
+    #[serde(default = "default_true")]
+    pub dtmf: bool,
+
+    #[serde(default = "default_true")]
+    pub ctcss: bool,
+
+    /// Digital-Coded Squelch, normal and inverted.
+    #[serde(default = "default_true")]
+    pub dcs: bool,
+
+    /// Silence the ASR tap while a DTMF tone is present.
+    #[serde(default)]
+    pub strip_dtmf: bool,
+}
+
+impl Default for ToneDecodeConfig {
+    fn default() -> Self {
+        Self {
+            dtmf: default_true(),
+            ctcss: default_true(),
+            dcs: default_true(),
+            strip_dtmf: false,
+        }
+    }
+}
+
+/// Per-input FFT spectrum analyzer (`[input.spectrum]`).
+///
+/// Without this section the analyzer still exists but starts disabled and
+/// uses the defaults; it can be switched on from the TUI.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SpectrumConfig {
+    /// Start analysing immediately.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    #[serde(default = "default_fft_size")]
+    pub fft_size: usize,
+
+    /// Number of magnitude bands published to the TUI.
+    #[serde(default = "default_spectrum_bands")]
+    pub bands: usize,
+
+    /// Upper edge of the displayed range.
+    #[serde(default = "default_spectrum_max_hz")]
+    pub max_hz: f32,
+
+    #[serde(default = "default_spectrum_update_ms")]
+    pub update_ms: u32,
+}
+
+fn default_fft_size() -> usize {
+    2048
+}
+
+fn default_spectrum_bands() -> usize {
+    64
+}
+
+fn default_spectrum_max_hz() -> f32 {
+    4000.0
+}
+
+fn default_spectrum_update_ms() -> u32 {
+    100
+}
+
+impl Default for SpectrumConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            fft_size: default_fft_size(),
+            bands: default_spectrum_bands(),
+            max_hz: default_spectrum_max_hz(),
+            update_ms: default_spectrum_update_ms(),
+        }
+    }
+}
+
+/// Per-input transmission detection (`[input.transmission]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TransmissionConfig {
+    /// Level above which the channel counts as keyed up.
+    #[serde(default = "default_transmission_threshold_dbfs")]
+    pub threshold_dbfs: f32,
+
+    /// Shorter bursts (clicks, noise spikes) are ignored.
+    #[serde(default = "default_transmission_min_duration_ms")]
+    pub min_duration_ms: u32,
+
+    /// Quiet time before a transmission is considered over. With 0 it ends
+    /// on the first quiet measurement window.
+    #[serde(default = "default_transmission_hang_ms")]
+    pub hang_ms: u32,
+}
+
+fn default_transmission_threshold_dbfs() -> f32 {
+    -40.0
+}
+
+fn default_transmission_min_duration_ms() -> u32 {
+    250
+}
+
+fn default_transmission_hang_ms() -> u32 {
+    800
+}
+
+impl Default for TransmissionConfig {
+    fn default() -> Self {
+        Self {
+            threshold_dbfs: default_transmission_threshold_dbfs(),
+            min_duration_ms: default_transmission_min_duration_ms(),
+            hang_ms: default_transmission_hang_ms(),
+        }
+    }
+}
+
+/// Instant replay history (`[replay]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ReplayConfig {
+    /// History kept per input and for the mix bus.
+    #[serde(default = "default_replay_seconds")]
+    pub seconds: u32,
+
+    /// Level of live audio while a replay plays.
+    #[serde(default = "default_replay_duck_db")]
+    pub duck_db: f32,
+
+    /// Directory that exported WAV files are written to.
+    #[serde(default = "default_replay_export_dir")]
+    pub export_dir: String,
+}
+
+fn default_replay_seconds() -> u32 {
+    30
+}
+
+fn default_replay_duck_db() -> f32 {
+    -15.0
+}
+
+fn default_replay_export_dir() -> String {
+    ".".to_string()
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            seconds: default_replay_seconds(),
+            duck_db: default_replay_duck_db(),
+            export_dir: default_replay_export_dir(),
+        }
+    }
+}
+
+/// Engines and destinations loaded from shared libraries (`[plugins]`).
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct PluginsConfig {
+    /// Directory whose `.so`/`.dylib`/`.dll` files are loaded at startup.
+    #[serde(default)]
+    pub dir: Option<String>,
+}
+
+/// Spoken announcements (`[tts]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TtsConfig {
+    pub engine: String,
+
+    /// Level of live audio while an announcement plays.
+    #[serde(default = "default_tts_duck_db")]
+    pub duck_db: f32,
+
+    #[serde(default = "default_volume")]
+    pub volume: f32,
+
+    /// Announce when an input's capture stream fails.
+    #[serde(default = "default_true")]
+    pub announce_input_errors: bool,
+
+    #[serde(default)]
+    pub exec: Option<ExecTtsConfig>,
+
+    #[serde(default)]
+    pub schedule: Vec<ScheduledAnnouncementConfig>,
+}
+
+fn default_tts_duck_db() -> f32 {
+    -12.0
+}
+
+/// A recurring announcement (`[[tts.schedule]]`): every `interval_secs`, or
+/// daily at `at` ("HH:MM", UTC).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ScheduledAnnouncementConfig {
+    pub text: String,
+
+    #[serde(default)]
+    pub interval_secs: Option<u64>,
+
+    #[serde(default)]
+    pub at: Option<String>,
+}
+
+impl ScheduledAnnouncementConfig {
+    /// Delay from `now` (Unix seconds) until the first announcement, and the
+    /// period after it.
+    pub fn timing(&self, now: f64) -> Result<(Duration, Duration), ConfigError> {
+        let invalid = |msg: &str| {
+            ConfigError::Invalid(format!("[[tts.schedule]] {:?}: {msg}", self.text))
+        };
+        match (self.interval_secs, &self.at) {
+            (Some(0), None) => Err(invalid("interval_secs must be positive")),
+            (Some(secs), None) => Ok((Duration::from_secs(secs), Duration::from_secs(secs))),
+            (None, Some(at)) => {
+                let (h, m) = at
+                    .split_once(':')
+                    .and_then(|(h, m)| Some((h.parse::<u64>().ok()?, m.parse::<u64>().ok()?)))
+                    .filter(|&(h, m)| h < 24 && m < 60)
+                    .ok_or_else(|| invalid("at must be \"HH:MM\""))?;
+                const DAY: f64 = 86_400.0;
+                let delay = ((h * 3600 + m * 60) as f64 - now.rem_euclid(DAY)).rem_euclid(DAY);
+                Ok((Duration::from_secs_f64(delay), Duration::from_secs(86_400)))
+            }
+            _ => Err(invalid("set exactly one of interval_secs and at")),
+        }
+    }
+}
+
+/// External synthesizer (`[tts.exec]`): text is written to the command's
+/// stdin and a WAV file is read from its stdout.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ExecTtsConfig {
+    #[serde(default = "default_tts_command")]
+    pub command: String,
+
+    #[serde(default = "default_tts_args")]
+    pub args: Vec<String>,
+
+    /// The command is killed if it has not finished after this long.
+    #[serde(default = "default_tts_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_tts_command() -> String {
+    "espeak-ng".to_string()
+}
+
+fn default_tts_args() -> Vec<String> {
+    vec!["--stdout".to_string()]
+}
+
+fn default_tts_timeout_ms() -> u64 {
+    30_000
+}
+
+impl Default for ExecTtsConfig {
+    fn default() -> Self {
+        Self {
+            command: default_tts_command(),
+            args: default_tts_args(),
+            timeout_ms: default_tts_timeout_ms(),
+        }
+    }
+}
+
+/// Event that plays a sound cue.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CueEventKind {
+    /// A final recognition containing `keyword`.
+    Keyword,
+    /// An input's capture stream failed.
+    InputError,
+    /// A destination failed to deliver text.
+    DestinationError,
+    /// An alert rule matched; `source` selects the rule by name.
+    Alert,
+}
+
+/// A WAV cue played into the mix on an event (`[[cues]]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CueConfig {
+    pub event: CueEventKind,
+
+    pub file: String,
+
+    #[serde(default = "default_volume")]
+    pub volume: f32,
+
+    /// Text to look for (case-insensitive); required for `keyword` cues.
+    #[serde(default)]
+    pub keyword: Option<String>,
+
+    /// Only fire for this input id (destination name for `destination_error`,
+    /// rule name for `alert`).
+    #[serde(default)]
+    pub source: Option<String>,
+
+    /// Minimum time between two plays of this cue.
+    #[serde(default = "default_cue_min_interval_ms")]
+    pub min_interval_ms: u32,
+}
+
+fn default_cue_min_interval_ms() -> u32 {
+    2000
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct DestinationRouteConfig {
+    pub plugin: String,
+
+    #[serde(default)]
+    pub prefix: String,
+
+    /// Translate results before sending (`translate = { to = "en" }`).
+    #[serde(default)]
+    pub translate: Option<RouteTranslationConfig>,
+
+    #[serde(flatten)]
+    pub extra: toml::Value,
+}
+
+/// Raises an alert when recognised text matches (`[[alerts]]`). Reloaded
+/// with the config file, except `destinations`.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AlertRuleConfig {
+    /// Identifies the rule in warnings, cues (`source`) and alert text.
+    pub name: String,
+
+    /// Case-insensitive; spaces and punctuation are ignored.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+
+    /// Regular expressions, matched against the text as recognised.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+
+    /// Letters a keyword may differ by, up to a quarter of its length.
+    #[serde(default)]
+    pub fuzzy: u32,
+
+    /// Also check partial results. An utterance alerts at most once per rule.
+    #[serde(default)]
+    pub partials: bool,
+
+    /// Only check these inputs; all when empty.
+    #[serde(default)]
+    pub inputs: Vec<String>,
+
+    /// Minimum time between two alerts from this rule.
+    #[serde(default)]
+    pub min_interval_ms: u32,
+
+    /// Highlight the recognition in the TUI.
+    #[serde(default = "default_true")]
+    pub highlight: bool,
+
+    /// Show a warning in the TUI.
+    #[serde(default = "default_true")]
+    pub warn: bool,
+
+    /// Speak the alert through `[tts]`.
+    #[serde(default)]
+    pub speak: bool,
+
+    /// Where to send the alert, in addition to the input's own routes.
+    #[serde(default)]
+    pub destinations: Vec<DestinationRouteConfig>,
+}
+
+/// A route's target language and what it sends.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RouteTranslationConfig {
+    pub to: String,
+
+    #[serde(default)]
+    pub send: TranslationSend,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationSend {
+    Original,
+    #[default]
+    Translation,
+    /// The original, then the translation.
+    Both,
+}
+
+/// Translator used by routes with `translate` (`[translation]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TranslationConfig {
+    pub translator: String,
+
+    /// Per-translator tables, e.g. `[translation.libretranslate]`.
+    #[serde(flatten)]
+    pub settings: toml::Table,
+}
+
+impl TranslationConfig {
+    /// Settings for the selected translator.
+    pub fn translator_config(&self) -> toml::Value {
+        self.settings
+            .get(&self.translator)
+            .cloned()
+            .unwrap_or_else(|| toml::Value::Table(Default::default()))
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AsrConfig {
+    pub engine: String,
+
+    #[serde(default)]
+    pub whisper: Option<WhisperConfig>,
+
+    #[serde(default)]
+    pub queue: AsrQueueConfig,
+
+    #[serde(default)]
+    pub vad: Option<VadConfig>,
+
+    #[serde(default)]
+    pub pool: AsrPoolConfig,
+
+    #[serde(default)]
+    pub supervisor: AsrSupervisorConfig,
+
+    /// Tables of other engines, e.g. `[asr.vosk]`, keyed by engine name.
+    #[serde(flatten)]
+    pub engines: toml::Table,
+}
+
+/// Engine name and configuration for one input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedAsr {
+    pub engine: String,
+    pub config: toml::Value,
+}
+
+impl AsrConfig {
+    /// Merge `input`'s `[input.asr]` overrides over the global settings.
+    pub fn resolve(&self, input: &InputConfig) -> Result<ResolvedAsr, ConfigError> {
+        let overrides = input.asr.clone().unwrap_or_default();
+        let engine = overrides.engine.unwrap_or_else(|| self.engine.clone());
+
+        let mut table = match (engine.as_str(), &self.whisper) {
+            ("whisper", Some(whisper)) => toml::Table::try_from(whisper)
+                .map_err(|e| ConfigError::Invalid(format!("[asr.whisper]: {e}")))?,
+            _ => match self.engines.get(&engine) {
+                Some(toml::Value::Table(t)) => t.clone(),
+                Some(_) => {
+                    return Err(ConfigError::Invalid(format!(
+                        "[asr.{engine}] must be a table"
+                    )))
+                }
+                None => toml::Table::new(),
+            },
+        };
+        table.extend(overrides.settings);
+
+        Ok(ResolvedAsr {
+            engine,
+            config: toml::Value::Table(table),
+        })
+    }
+}
+
+/// Bounds on the audio buffered between each capture callback and its engine.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AsrQueueConfig {
+    #[serde(default = "default_queue_capacity_ms")]
+    pub capacity_ms: u32,
+
+    #[serde(default)]
+    pub policy: OverflowPolicy,
+}
+
+impl Default for AsrQueueConfig {
+    fn default() -> Self {
+        Self {
+            capacity_ms: default_queue_capacity_ms(),
+            policy: OverflowPolicy::default(),
+        }
+    }
+}
+
+/// Worker pool for engines shared between inputs (`[asr.pool]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AsrPoolConfig {
+    /// Segments transcribed concurrently.
+    #[serde(default = "default_pool_workers")]
+    pub workers: usize,
+
+    /// Segments an input may have waiting; the oldest is dropped beyond this.
+    #[serde(default = "default_pool_max_pending")]
+    pub max_pending: usize,
+}
+
+impl Default for AsrPoolConfig {
+    fn default() -> Self {
+        Self {
+            workers: default_pool_workers(),
+            max_pending: default_pool_max_pending(),
+        }
+    }
+}
+
+/// Restart policy for failing engines (`[asr.supervisor]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AsrSupervisorConfig {
+    /// Consecutive failed engine calls before the engine is restarted.
+    #[serde(default = "default_supervisor_max_errors")]
+    pub max_errors: u32,
+
+    /// Delay before the first restart; doubled for each further attempt.
+    #[serde(default = "default_supervisor_restart_delay_ms")]
+    pub restart_delay_ms: u64,
+
+    #[serde(default = "default_supervisor_max_restart_delay_ms")]
+    pub max_restart_delay_ms: u64,
+
+    /// Restarts in a row before the engine is given up on.
+    #[serde(default = "default_supervisor_max_restarts")]
+    pub max_restarts: u32,
+
+    /// An engine that runs this long counts as recovered, resetting the
+    /// delay and the restart count.
+    #[serde(default = "default_supervisor_healthy_after_ms")]
+    pub healthy_after_ms: u64,
+}
+
+impl Default for AsrSupervisorConfig {
+    fn default() -> Self {
+        Self {
+            max_errors: default_supervisor_max_errors(),
+            restart_delay_ms: default_supervisor_restart_delay_ms(),
+            max_restart_delay_ms: default_supervisor_max_restart_delay_ms(),
+            max_restarts: default_supervisor_max_restarts(),
+            healthy_after_ms: default_supervisor_healthy_after_ms(),
+        }
+    }
+}
+
+/// Voice activity detection in front of the engine (`[asr.vad]`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct VadConfig {
+    /// Speech detector plugin; `"energy"` is built in.
+    #[serde(default = "default_vad_detector")]
+    pub detector: String,
+
+    /// Speech probability at or above which a frame counts as speech.
+    #[serde(default = "default_vad_threshold")]
+    pub threshold: f32,
+
+    /// Audio kept from before the detected start of speech.
+    #[serde(default = "default_vad_pre_roll_ms")]
+    pub pre_roll_ms: u32,
 
-    #[serde(default)]
-    pub destinations: Vec<DestinationRouteConfig>,
-}
+    /// Silence after speech before the utterance is closed.
+    #[serde(default = "default_vad_hangover_ms")]
+    pub hangover_ms: u32,
 
-#[derive(Debug, Deserialize, Clone)]
-pub struct DestinationRouteConfig {
-    pub plugin: String,
+    /// Utterances longer than this are split.
+    #[serde(default = "default_vad_max_utterance_ms")]
+    pub max_utterance_ms: u32,
 
-    #[serde(default)]
-    pub prefix: String,
+    /// Speech must last this long before an utterance starts.
+    #[serde(default = "default_vad_min_speech_ms")]
+    pub min_speech_ms: u32,
 
+    /// Detector-specific settings.
     #[serde(flatten)]
     pub extra: toml::Value,
 }
 
-#[derive(Debug, Deserialize, Clone)]
-pub struct AsrConfig {
-    pub engine: String,
-
-    #[serde(default)]
-    pub whisper: Option<WhisperConfig>,
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            detector: default_vad_detector(),
+            threshold: default_vad_threshold(),
+            pre_roll_ms: default_vad_pre_roll_ms(),
+            hangover_ms: default_vad_hangover_ms(),
+            max_utterance_ms: default_vad_max_utterance_ms(),
+            min_speech_ms: default_vad_min_speech_ms(),
+            extra: toml::Value::Table(Default::default()),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -106,6 +770,38 @@ pub struct WhisperConfig {
 
     #[serde(default = "default_language")]
     pub language: String,
+
+    /// Initial prompt to bias vocabulary and spelling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+fn default_pool_workers() -> usize {
+    1
+}
+
+fn default_pool_max_pending() -> usize {
+    8
+}
+
+fn default_supervisor_max_errors() -> u32 {
+    5
+}
+
+fn default_supervisor_restart_delay_ms() -> u64 {
+    500
+}
+
+fn default_supervisor_max_restart_delay_ms() -> u64 {
+    30_000
+}
+
+fn default_supervisor_max_restarts() -> u32 {
+    5
+}
+
+fn default_supervisor_healthy_after_ms() -> u64 {
+    60_000
 }
 
 fn default_log_level() -> String {
@@ -136,6 +832,34 @@ fn default_language() -> String {
     "ja".to_string()
 }
 
+fn default_queue_capacity_ms() -> u32 {
+    5000
+}
+
+fn default_vad_detector() -> String {
+    "energy".to_string()
+}
+
+fn default_vad_threshold() -> f32 {
+    0.5
+}
+
+fn default_vad_pre_roll_ms() -> u32 {
+    300
+}
+
+fn default_vad_hangover_ms() -> u32 {
+    600
+}
+
+fn default_vad_max_utterance_ms() -> u32 {
+    15000
+}
+
+fn default_vad_min_speech_ms() -> u32 {
+    60
+}
+
 /// Interpolate `${VAR}` patterns with environment variable values.
 fn interpolate_env_vars(input: &str) -> Result<String, ConfigError> {
     let re = Regex::new(r"\$\{([^}]+)\}").unwrap();
@@ -377,6 +1101,434 @@ model_path = "./models/ggml-base.bin"
         assert_eq!(whisper.language, "ja");
     }
 
+    #[test]
+    fn test_config_asr_queue_defaults() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[asr]
+engine = "null"
+"#,
+        )
+        .unwrap();
+        let queue = config.asr.unwrap().queue;
+        assert_eq!(queue.capacity_ms, 5000);
+        assert_eq!(queue.policy, OverflowPolicy::DropOldest);
+    }
+
+    #[test]
+    fn test_config_asr_queue_section() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[asr]
+engine = "null"
+
+[asr.queue]
+capacity_ms = 2000
+policy = "coalesce"
+"#,
+        )
+        .unwrap();
+        let queue = config.asr.unwrap().queue;
+        assert_eq!(queue.capacity_ms, 2000);
+        assert_eq!(queue.policy, OverflowPolicy::Coalesce);
+    }
+
+    #[test]
+    fn test_config_asr_pool_section() {
+        let config = AppConfig::from_toml_str("[asr]\nengine = \"null\"\n").unwrap();
+        assert_eq!(config.asr.unwrap().pool, AsrPoolConfig::default());
+
+        let config = AppConfig::from_toml_str(
+            r#"
+[asr]
+engine = "whisper"
+
+[asr.pool]
+workers = 3
+
+[[input]]
+id = "rx"
+
+[input.asr]
+priority = 5
+language = "de"
+"#,
+        )
+        .unwrap();
+        let asr = config.asr.as_ref().unwrap();
+        assert_eq!(asr.pool.workers, 3);
+        assert_eq!(asr.pool.max_pending, 8);
+        assert!(!asr.engines.contains_key("pool"));
+
+        let input_asr = config.input[0].asr.as_ref().unwrap();
+        assert_eq!(input_asr.priority, 5);
+        // Scheduling settings are not passed to the engine
+        let resolved = asr.resolve(&config.input[0]).unwrap();
+        assert!(resolved.config.get("priority").is_none());
+    }
+
+    #[test]
+    fn test_config_text_chains() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[[text.processors]]
+processor = "dictionary"
+words = { Yankee = "Y" }
+
+[[text.processors]]
+processor = "normalize"
+
+[[input]]
+id = "rx"
+
+[input.text]
+inherit = false
+
+[[input.text.processors]]
+processor = "width"
+to = "half"
+
+[[input]]
+id = "mic"
+"#,
+        )
+        .unwrap();
+        let names: Vec<_> = config.text.processors.iter().map(|p| p.processor.as_str()).collect();
+        assert_eq!(names, ["dictionary", "normalize"]);
+        assert!(config.text.processors[0].settings.contains_key("words"));
+        assert!(!config.text.processors[0].settings.contains_key("processor"));
+
+        let rx = config.input[0].text.as_ref().unwrap();
+        assert!(!rx.inherit);
+        assert_eq!(rx.processors[0].settings["to"].as_str(), Some("half"));
+        assert_eq!(config.input[1].text, None);
+        assert!(TextConfig::default().inherit);
+    }
+
+    #[test]
+    fn test_config_alert_rules() {
+        let toml_str = r#"
+[[alerts]]
+name = "distress"
+keywords = ["mayday", "emergency"]
+fuzzy = 1
+partials = true
+min_interval_ms = 5000
+
+[[alerts.destinations]]
+plugin = "file"
+path = "./alerts.txt"
+prefix = "!! "
+
+[[alerts]]
+name = "callsign"
+patterns = ['(?i)\bJA1\w+']
+inputs = ["radio1"]
+highlight = false
+warn = false
+speak = true
+
+[[cues]]
+event = "alert"
+source = "distress"
+file = "./cues/alert.wav"
+"#;
+        let config = AppConfig::from_toml_str(toml_str).unwrap();
+        let distress = &config.alerts[0];
+        assert_eq!(distress.keywords, ["mayday", "emergency"]);
+        assert_eq!((distress.fuzzy, distress.partials), (1, true));
+        assert!(distress.highlight && distress.warn && !distress.speak);
+        assert_eq!(distress.destinations[0].prefix, "!! ");
+
+        let callsign = &config.alerts[1];
+        assert_eq!(callsign.patterns, [r"(?i)\bJA1\w+"]);
+        assert_eq!(callsign.inputs, ["radio1"]);
+        assert!(!callsign.highlight && !callsign.warn && callsign.speak);
+        assert_eq!((callsign.fuzzy, callsign.min_interval_ms), (0, 0));
+        assert_eq!(config.cues[0].event, CueEventKind::Alert);
+    }
+
+    #[test]
+    fn test_config_translation_routes() {
+        let toml_str = r#"
+[translation]
+translator = "libretranslate"
+
+[translation.libretranslate]
+url = "http://localhost:5000/translate"
+
+[[input]]
+id = "rx"
+device_name = "default"
+
+[[input.destinations]]
+plugin = "discord"
+translate = { to = "en", send = "both" }
+channel_id = 1
+
+[[input.destinations]]
+plugin = "file"
+translate = { to = "de" }
+path = "./de.txt"
+
+[[input.destinations]]
+plugin = "file"
+path = "./ja.txt"
+"#;
+        let config = AppConfig::from_toml_str(toml_str).unwrap();
+        let translation = config.translation.unwrap();
+        assert_eq!(translation.translator, "libretranslate");
+        assert_eq!(
+            translation.translator_config()["url"].as_str(),
+            Some("http://localhost:5000/translate")
+        );
+
+        let routes = &config.input[0].destinations;
+        let first = routes[0].translate.as_ref().unwrap();
+        assert_eq!((first.to.as_str(), first.send), ("en", TranslationSend::Both));
+        assert!(routes[0].extra.get("translate").is_none());
+        assert_eq!(routes[1].translate.as_ref().unwrap().send, TranslationSend::Translation);
+        assert_eq!(routes[2].translate, None);
+    }
+
+    #[test]
+    fn test_config_asr_supervisor_section() {
+        let config = AppConfig::from_toml_str("[asr]\nengine = \"null\"\n").unwrap();
+        assert_eq!(config.asr.unwrap().supervisor, AsrSupervisorConfig::default());
+
+        let config = AppConfig::from_toml_str(
+            "[asr]\nengine = \"null\"\n\n[asr.supervisor]\nmax_errors = 2\nmax_restarts = 0\n",
+        )
+        .unwrap();
+        let asr = config.asr.unwrap();
+        assert_eq!(asr.supervisor.max_errors, 2);
+        assert_eq!(asr.supervisor.max_restarts, 0);
+        assert_eq!(asr.supervisor.restart_delay_ms, 500);
+        assert!(!asr.engines.contains_key("supervisor"));
+    }
+
+    #[test]
+    fn test_config_asr_vad_section() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[asr]
+engine = "null"
+
+[asr.vad]
+hangover_ms = 400
+snr_db = 12.0
+"#,
+        )
+        .unwrap();
+        let vad = config.asr.unwrap().vad.unwrap();
+        assert_eq!(vad.detector, "energy");
+        assert_eq!(vad.hangover_ms, 400);
+        assert_eq!(vad.pre_roll_ms, 300);
+        assert_eq!(vad.extra.get("snr_db").and_then(|v| v.as_float()), Some(12.0));
+
+        let config = AppConfig::from_toml_str("[asr]\nengine = \"null\"\n").unwrap();
+        assert!(config.asr.unwrap().vad.is_none());
+    }
+
+    #[test]
+    fn test_config_input_tones_section() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[[input]]
+id = "radio1"
+
+[input.tones]
+ctcss = false
+strip_dtmf = true
+
+[[input]]
+id = "mic1"
+"#,
+        )
+        .unwrap();
+        let tones = config.input[0].tones.as_ref().unwrap();
+        assert!(tones.dtmf && tones.dcs);
+        assert!(!tones.ctcss);
+        assert!(tones.strip_dtmf);
+        assert!(config.input[1].tones.is_none());
+    }
+
+    #[test]
+    fn test_config_replay_defaults_and_override() {
+        let config = AppConfig::from_toml_str("").unwrap();
+        assert_eq!(config.replay, ReplayConfig::default());
+        assert_eq!(config.replay.seconds, 30);
+
+        let config = AppConfig::from_toml_str(
+            r#"
+[replay]
+seconds = 60
+export_dir = "/tmp/replays"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.replay.seconds, 60);
+        assert_eq!(config.replay.duck_db, -15.0);
+        assert_eq!(config.replay.export_dir, "/tmp/replays");
+    }
+
+    #[test]
+    fn test_config_tts() {
+        let config = AppConfig::from_toml_str("").unwrap();
+        assert!(config.tts.is_none());
+
+        let config = AppConfig::from_toml_str(
+            r#"
+[tts]
+engine = "exec"
+duck_db = -20.0
+
+[tts.exec]
+command = "piper"
+args = ["--model", "en_US-amy-low.onnx", "--output_file", "-"]
+"#,
+        )
+        .unwrap();
+        let tts = config.tts.unwrap();
+        assert_eq!(tts.engine, "exec");
+        assert_eq!(tts.duck_db, -20.0);
+        assert_eq!(tts.volume, 1.0);
+        assert!(tts.announce_input_errors);
+        let exec = tts.exec.unwrap();
+        assert_eq!(exec.command, "piper");
+        assert_eq!(exec.args.len(), 4);
+        assert_eq!(exec.timeout_ms, 30_000);
+        assert_eq!(ExecTtsConfig::default().args, vec!["--stdout"]);
+        assert!(tts.schedule.is_empty());
+    }
+
+    #[test]
+    fn test_config_tts_schedule_timing() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[tts]
+engine = "exec"
+
+[[tts.schedule]]
+text = "This is JA1XYZ"
+interval_secs = 600
+
+[[tts.schedule]]
+text = "Net starts now"
+at = "21:30"
+"#,
+        )
+        .unwrap();
+        let schedule = &config.tts.unwrap().schedule;
+        let ten_min = Duration::from_secs(600);
+        assert_eq!(schedule[0].timing(0.0).unwrap(), (ten_min, ten_min));
+
+        let day = Duration::from_secs(86_400);
+        // 2026-10-18 21:00:00 UTC, then 22:00:00 UTC
+        let evening = 1_792_357_200.0;
+        assert_eq!(
+            schedule[1].timing(evening).unwrap(),
+            (Duration::from_secs(1800), day)
+        );
+        assert_eq!(
+            schedule[1].timing(evening + 3600.0).unwrap(),
+            (Duration::from_secs(84_600), day)
+        );
+
+        let entry = |interval_secs, at: Option<&str>| ScheduledAnnouncementConfig {
+            text: "x".to_string(),
+            interval_secs,
+            at: at.map(str::to_string),
+        };
+        assert!(entry(Some(0), None).timing(0.0).is_err());
+        assert!(entry(None, None).timing(0.0).is_err());
+        assert!(entry(Some(60), Some("12:00")).timing(0.0).is_err());
+        assert!(entry(None, Some("24:00")).timing(0.0).is_err());
+        assert!(entry(None, Some("noon")).timing(0.0).is_err());
+    }
+
+    #[test]
+    fn test_config_cues() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[[cues]]
+event = "keyword"
+keyword = "mayday"
+file = "cues/alert.wav"
+
+[[cues]]
+event = "input_error"
+source = "radio1"
+file = "cues/error.wav"
+volume = 0.5
+min_interval_ms = 10000
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.cues.len(), 2);
+        assert_eq!(config.cues[0].event, CueEventKind::Keyword);
+        assert_eq!(config.cues[0].keyword.as_deref(), Some("mayday"));
+        assert_eq!(config.cues[0].volume, 1.0);
+        assert_eq!(config.cues[0].min_interval_ms, 2000);
+        assert_eq!(config.cues[1].event, CueEventKind::InputError);
+        assert_eq!(config.cues[1].source.as_deref(), Some("radio1"));
+        assert_eq!(config.cues[1].min_interval_ms, 10000);
+
+        assert!(AppConfig::from_toml_str("[[cues]]\nevent = \"bogus\"\nfile = \"x.wav\"").is_err());
+    }
+
+    #[test]
+    fn test_config_plugins() {
+        assert_eq!(AppConfig::from_toml_str("").unwrap().plugins.dir, None);
+        let config = AppConfig::from_toml_str("[plugins]\ndir = \"./plugins\"").unwrap();
+        assert_eq!(config.plugins.dir.as_deref(), Some("./plugins"));
+    }
+
+    #[test]
+    fn test_config_transmission_detection() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[general]
+activity_log = "./activity.log"
+
+[[input]]
+id = "rx"
+device_name = "Radio"
+
+[input.transmission]
+threshold_dbfs = -30.0
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.general.activity_log.as_deref(), Some("./activity.log"));
+        let tx = config.input[0].transmission.as_ref().unwrap();
+        assert_eq!(tx.threshold_dbfs, -30.0);
+        assert_eq!(tx.min_duration_ms, 250);
+        assert_eq!(tx.hang_ms, 800);
+    }
+
+    #[test]
+    fn test_config_input_spectrum_section() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[[input]]
+id = "rx"
+device_name = "Radio"
+
+[input.spectrum]
+fft_size = 4096
+max_hz = 1000.0
+"#,
+        )
+        .unwrap();
+        let spectrum = config.input[0].spectrum.as_ref().unwrap();
+        assert!(spectrum.enabled);
+        assert_eq!(spectrum.fft_size, 4096);
+        assert_eq!(spectrum.bands, 64);
+        assert_eq!(spectrum.max_hz, 1000.0);
+        assert_eq!(spectrum.update_ms, 100);
+    }
+
     #[test]
     fn test_config_destination_route_extra_fields() {
         let toml_str = r#"
@@ -395,4 +1547,77 @@ channel_id = 123456789
         // Verify extra fields are captured via #[serde(flatten)]
         assert_eq!(dest.extra.get("channel_id").unwrap().as_integer(), Some(123456789));
     }
+
+    #[test]
+    fn test_config_input_asr_overrides_global() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[asr]
+engine = "whisper"
+
+[asr.whisper]
+model_path = "/models/base.bin"
+language = "en"
+
+[asr.vosk]
+model_path = "/models/vosk-en"
+
+[[input]]
+id = "mic1"
+
+[[input]]
+id = "rx"
+
+[input.asr]
+language = "ja"
+prompt = "callsign JA1ABC"
+
+[[input]]
+id = "scanner"
+
+[input.asr]
+engine = "vosk"
+grammar = ["alpha", "bravo"]
+"#,
+        )
+        .unwrap();
+        let asr = config.asr.as_ref().unwrap();
+
+        let mic = asr.resolve(&config.input[0]).unwrap();
+        assert_eq!(mic.engine, "whisper");
+        assert_eq!(mic.config.get("language").unwrap().as_str(), Some("en"));
+        assert!(mic.config.get("prompt").is_none());
+
+        let rx = asr.resolve(&config.input[1]).unwrap();
+        assert_eq!(rx.engine, "whisper");
+        assert_eq!(rx.config.get("model_path").unwrap().as_str(), Some("/models/base.bin"));
+        assert_eq!(rx.config.get("language").unwrap().as_str(), Some("ja"));
+        assert_eq!(rx.config.get("prompt").unwrap().as_str(), Some("callsign JA1ABC"));
+
+        let scanner = asr.resolve(&config.input[2]).unwrap();
+        assert_eq!(scanner.engine, "vosk");
+        assert_eq!(scanner.config.get("model_path").unwrap().as_str(), Some("/models/vosk-en"));
+        assert!(scanner.config.get("grammar").unwrap().is_array());
+        assert!(scanner.config.get("language").is_none());
+    }
+
+    #[test]
+    fn test_config_asr_engine_table_must_be_table() {
+        let config = AppConfig::from_toml_str(
+            r#"
+[asr]
+engine = "null"
+vosk = 3
+
+[[input]]
+id = "mic1"
+
+[input.asr]
+engine = "vosk"
+"#,
+        )
+        .unwrap();
+        let err = config.asr.as_ref().unwrap().resolve(&config.input[0]).unwrap_err();
+        assert!(matches!(err, ConfigError::Invalid(_)), "{err}");
+    }
 }
@@ -1,4 +1,4 @@
-use crate::config::AppConfig;
+use crate::config::{AlertRuleConfig, AppConfig};
 
 /// Describes runtime-safe changes between two configs.
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -6,13 +6,15 @@ pub struct ConfigDiff {
     pub volume_changes: Vec<(String, f32)>,
     pub mute_changes: Vec<(String, bool)>,
     pub play_mixed_change: Option<bool>,
+    /// The new `[[alerts]]`, if any rule changed.
+    pub alerts_change: Option<Vec<AlertRuleConfig>>,
     pub non_reloadable: Vec<String>,
 }
 
 impl ConfigDiff {
     /// Compare two configs and return the diff.
-    /// Reloadable: volume, mute, play_mixed_input.
-    /// Non-reloadable: device changes, sample_rate, buffer_size, ASR engine — logged as warnings.
+    /// Reloadable: volume, mute, play_mixed_input, alert rules.
+    /// Non-reloadable: device changes, tone decoding, transmission detection, activity log, replay, TTS, cues, plugins, text processing, translation, alert destinations, sample_rate, buffer_size, ASR engine/queue/VAD — logged as warnings.
     pub fn diff(old: &AppConfig, new: &AppConfig) -> Self {
         let mut result = Self::default();
 
@@ -30,6 +32,55 @@ impl ConfigDiff {
             ));
         }
 
+        if old.general.activity_log != new.general.activity_log {
+            result.non_reloadable.push("activity_log changed, requires restart".to_string());
+        }
+
+        if old.replay != new.replay {
+            result.non_reloadable.push("replay settings changed, requires restart".to_string());
+        }
+
+        if old.tts != new.tts {
+            result.non_reloadable.push("TTS settings changed, requires restart".to_string());
+        }
+
+        if old.cues != new.cues {
+            result.non_reloadable.push("cues changed, requires restart".to_string());
+        }
+
+        if old.plugins != new.plugins {
+            result.non_reloadable.push("plugins changed, requires restart".to_string());
+        }
+
+        if old.text != new.text {
+            result
+                .non_reloadable
+                .push("text processing changed, requires restart".to_string());
+        }
+
+        if old.translation != new.translation {
+            result
+                .non_reloadable
+                .push("translation settings changed, requires restart".to_string());
+        }
+
+        // Alert rules are reloadable, except where they are delivered
+        if old.alerts != new.alerts {
+            result.alerts_change = Some(new.alerts.clone());
+            let routed = |alerts: &[AlertRuleConfig]| {
+                alerts
+                    .iter()
+                    .filter(|a| !a.destinations.is_empty())
+                    .map(|a| (a.name.clone(), a.destinations.clone()))
+                    .collect::<Vec<_>>()
+            };
+            if routed(&old.alerts) != routed(&new.alerts) {
+                result
+                    .non_reloadable
+                    .push("alert destinations changed, requires restart".to_string());
+            }
+        }
+
         // Check output device change (non-reloadable)
         if old.output.device_name != new.output.device_name {
             result.non_reloadable.push(format!(
@@ -65,6 +116,41 @@ impl ConfigDiff {
                         new_input.id, old_input.device_name, new_input.device_name
                     ));
                 }
+                // Tone decoding change (non-reloadable)
+                if old_input.tones != new_input.tones {
+                    result.non_reloadable.push(format!(
+                        "input '{}' tone decoding changed, requires restart",
+                        new_input.id
+                    ));
+                }
+                // Transmission detection change (non-reloadable)
+                if old_input.transmission != new_input.transmission {
+                    result.non_reloadable.push(format!(
+                        "input '{}' transmission detection changed, requires restart",
+                        new_input.id
+                    ));
+                }
+                // Spectrum analyzer change (non-reloadable)
+                if old_input.spectrum != new_input.spectrum {
+                    result.non_reloadable.push(format!(
+                        "input '{}' spectrum settings changed, requires restart",
+                        new_input.id
+                    ));
+                }
+                // Per-input ASR override change (non-reloadable)
+                if old_input.asr != new_input.asr {
+                    result.non_reloadable.push(format!(
+                        "input '{}' ASR settings changed, requires restart",
+                        new_input.id
+                    ));
+                }
+                // Per-input text chain change (non-reloadable)
+                if old_input.text != new_input.text {
+                    result.non_reloadable.push(format!(
+                        "input '{}' text processing changed, requires restart",
+                        new_input.id
+                    ));
+                }
             }
         }
 
@@ -76,6 +162,26 @@ impl ConfigDiff {
                     old_asr.engine, new_asr.engine
                 ));
             }
+            (Some(old_asr), Some(new_asr)) if old_asr.queue != new_asr.queue => {
+                result
+                    .non_reloadable
+                    .push("ASR queue settings changed, requires restart".to_string());
+            }
+            (Some(old_asr), Some(new_asr)) if old_asr.vad != new_asr.vad => {
+                result
+                    .non_reloadable
+                    .push("ASR VAD settings changed, requires restart".to_string());
+            }
+            (Some(old_asr), Some(new_asr)) if old_asr.pool != new_asr.pool => {
+                result
+                    .non_reloadable
+                    .push("ASR worker pool settings changed, requires restart".to_string());
+            }
+            (Some(old_asr), Some(new_asr)) if old_asr.supervisor != new_asr.supervisor => {
+                result
+                    .non_reloadable
+                    .push("ASR supervisor settings changed, requires restart".to_string());
+            }
             _ => {}
         }
 
@@ -207,4 +313,288 @@ muted = false
         let diff = ConfigDiff::diff(&old, &new);
         assert_eq!(diff.play_mixed_change, Some(false));
     }
+
+    #[test]
+    fn test_config_diff_alert_rules_reloadable() {
+        let alerts = |extra: &str| {
+            AppConfig::from_toml_str(&format!(
+                "[[alerts]]\nname = \"mayday\"\nkeywords = [\"mayday\"]\n{extra}"
+            ))
+            .unwrap()
+            .alerts
+        };
+        let old = base_config();
+        let mut new = base_config();
+        new.alerts = alerts("");
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.alerts_change.as_deref(), Some(new.alerts.as_slice()));
+        assert!(diff.non_reloadable.is_empty());
+
+        let mut routed = new.clone();
+        routed.alerts = alerts("[[alerts.destinations]]\nplugin = \"file\"\npath = \"alerts.txt\"\n");
+        let diff = ConfigDiff::diff(&new, &routed);
+        assert!(diff.alerts_change.is_some());
+        assert_eq!(diff.non_reloadable, ["alert destinations changed, requires restart"]);
+        assert_eq!(ConfigDiff::diff(&routed, &routed), ConfigDiff::default());
+    }
+
+    #[test]
+    fn test_config_diff_asr_queue_change_non_reloadable() {
+        let old = AppConfig::from_toml_str(
+            r#"
+[asr]
+engine = "null"
+"#,
+        )
+        .unwrap();
+        let new = AppConfig::from_toml_str(
+            r#"
+[asr]
+engine = "null"
+
+[asr.queue]
+policy = "drop_newest"
+"#,
+        )
+        .unwrap();
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 1);
+        assert!(diff.non_reloadable[0].contains("queue"));
+    }
+
+    #[test]
+    fn test_config_diff_tone_decoding_non_reloadable() {
+        let old = base_config();
+        let new = AppConfig::from_toml_str(
+            r#"
+[output]
+device_name = "speakers"
+play_mixed_input = true
+
+[[input]]
+id = "mic1"
+device_name = "USB Mic"
+volume = 0.8
+muted = false
+
+[input.tones]
+strip_dtmf = true
+"#,
+        )
+        .unwrap();
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 1);
+        assert!(diff.non_reloadable[0].contains("tone decoding"));
+    }
+
+    #[test]
+    fn test_config_diff_replay_non_reloadable() {
+        let old = base_config();
+        let mut new = base_config();
+        new.replay.seconds = 120;
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 1);
+        assert!(diff.non_reloadable[0].contains("replay"));
+    }
+
+    #[test]
+    fn test_config_diff_tts_non_reloadable() {
+        let old = base_config();
+        let new = AppConfig::from_toml_str(
+            r#"
+[tts]
+engine = "null"
+"#,
+        )
+        .unwrap();
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert!(diff.non_reloadable.iter().any(|m| m.contains("TTS")));
+    }
+
+    #[test]
+    fn test_config_diff_cues_non_reloadable() {
+        let old = base_config();
+        let mut new = base_config();
+        new.cues.push(crate::config::CueConfig {
+            event: crate::config::CueEventKind::InputError,
+            file: "error.wav".to_string(),
+            volume: 1.0,
+            keyword: None,
+            source: None,
+            min_interval_ms: 2000,
+        });
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 1);
+        assert!(diff.non_reloadable[0].contains("cues"));
+    }
+
+    #[test]
+    fn test_config_diff_plugins_non_reloadable() {
+        let old = base_config();
+        let mut new = base_config();
+        new.plugins.dir = Some("plugins".to_string());
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable, vec!["plugins changed, requires restart"]);
+    }
+
+    #[test]
+    fn test_config_diff_transmission_non_reloadable() {
+        let old = base_config();
+        let new = AppConfig::from_toml_str(
+            r#"
+[general]
+activity_log = "tx.log"
+
+[output]
+device_name = "speakers"
+play_mixed_input = true
+
+[[input]]
+id = "mic1"
+device_name = "USB Mic"
+volume = 0.8
+muted = false
+
+[input.transmission]
+hang_ms = 300
+"#,
+        )
+        .unwrap();
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 2);
+        assert!(diff.non_reloadable[0].contains("activity_log"));
+        assert!(diff.non_reloadable[1].contains("transmission detection"));
+    }
+
+    #[test]
+    fn test_config_diff_spectrum_non_reloadable() {
+        let old = base_config();
+        let new = AppConfig::from_toml_str(
+            r#"
+[output]
+device_name = "speakers"
+play_mixed_input = true
+
+[[input]]
+id = "mic1"
+device_name = "USB Mic"
+volume = 0.8
+muted = false
+
+[input.spectrum]
+bands = 32
+"#,
+        )
+        .unwrap();
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 1);
+        assert!(diff.non_reloadable[0].contains("spectrum"));
+    }
+
+    #[test]
+    fn test_config_diff_asr_vad_change_non_reloadable() {
+        let old = AppConfig::from_toml_str("[asr]\nengine = \"null\"\n").unwrap();
+        let new = AppConfig::from_toml_str(
+            r#"
+[asr]
+engine = "null"
+
+[asr.vad]
+"#,
+        )
+        .unwrap();
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 1);
+        assert!(diff.non_reloadable[0].contains("VAD"));
+    }
+
+    #[test]
+    fn test_config_diff_input_asr_non_reloadable() {
+        let old = base_config();
+        let new = AppConfig::from_toml_str(
+            r#"
+[output]
+device_name = "speakers"
+play_mixed_input = true
+
+[[input]]
+id = "mic1"
+device_name = "USB Mic"
+volume = 0.8
+muted = false
+
+[input.asr]
+language = "ja"
+"#,
+        )
+        .unwrap();
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 1);
+        assert!(diff.non_reloadable[0].contains("'mic1' ASR"));
+    }
+
+    #[test]
+    fn test_config_diff_asr_pool_non_reloadable() {
+        let old = AppConfig::from_toml_str("[asr]\nengine = \"null\"\n").unwrap();
+        let new =
+            AppConfig::from_toml_str("[asr]\nengine = \"null\"\n\n[asr.pool]\nworkers = 2\n")
+                .unwrap();
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 1);
+        assert!(diff.non_reloadable[0].contains("worker pool"));
+    }
+
+    #[test]
+    fn test_config_diff_text_non_reloadable() {
+        let old = base_config();
+        let mut new = base_config();
+        new.text.processors.push(crate::config::TextProcessorConfig {
+            processor: "normalize".to_string(),
+            settings: toml::Table::new(),
+        });
+        new.input[0].text = Some(Default::default());
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 2);
+        assert!(diff.non_reloadable[0].contains("text processing"));
+        assert!(diff.non_reloadable[1].contains("'mic1' text processing"));
+    }
+
+    #[test]
+    fn test_config_diff_translation_non_reloadable() {
+        let old = base_config();
+        let mut new = base_config();
+        new.translation = Some(crate::config::TranslationConfig {
+            translator: "libretranslate".to_string(),
+            settings: toml::Table::new(),
+        });
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable, ["translation settings changed, requires restart"]);
+    }
+
+    #[test]
+    fn test_config_diff_asr_supervisor_non_reloadable() {
+        let old = AppConfig::from_toml_str("[asr]\nengine = \"null\"\n").unwrap();
+        let new = AppConfig::from_toml_str(
+            "[asr]\nengine = \"null\"\n\n[asr.supervisor]\nmax_restarts = 1\n",
+        )
+        .unwrap();
+
+        let diff = ConfigDiff::diff(&old, &new);
+        assert_eq!(diff.non_reloadable.len(), 1);
+        assert!(diff.non_reloadable[0].contains("supervisor"));
+    }
 }
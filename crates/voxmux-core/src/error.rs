@@ -10,6 +10,9 @@ pub enum ConfigError {
 
     #[error("environment variable not found: {0}")]
     EnvVarNotFound(String),
+
+    #[error("invalid configuration: {0}")]
+    Invalid(String),
 }
 
 #[derive(Debug, Error)]
@@ -25,9 +28,12 @@ pub enum AudioError {
 
     #[error("stream error: {0}")]
     StreamError(String),
+
+    #[error("WAV file error: {0}")]
+    Wav(String),
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum AsrError {
     #[error("ASR initialization failed: {0}")]
     InitializationFailed(String),
@@ -39,6 +45,18 @@ pub enum AsrError {
     EngineNotFound(String),
 }
 
+#[derive(Debug, Error)]
+pub enum TtsError {
+    #[error("TTS initialization failed: {0}")]
+    InitializationFailed(String),
+
+    #[error("speech synthesis failed: {0}")]
+    SynthesisFailed(String),
+
+    #[error("TTS engine not found: {0}")]
+    EngineNotFound(String),
+}
+
 #[derive(Debug, Error)]
 pub enum DestinationError {
     #[error("destination initialization failed: {0}")]
@@ -53,3 +71,40 @@ pub enum DestinationError {
     #[error("destination connection lost: {0}")]
     ConnectionLost(String),
 }
+
+#[derive(Debug, Error)]
+pub enum TextError {
+    #[error("text processor not found: {0}")]
+    ProcessorNotFound(String),
+
+    #[error("invalid text processor configuration: {0}")]
+    InvalidConfig(String),
+}
+
+#[derive(Debug, Error)]
+pub enum TranslationError {
+    #[error("translator not found: {0}")]
+    NotFound(String),
+
+    #[error("translator initialization failed: {0}")]
+    InitializationFailed(String),
+
+    #[error("translation failed: {0}")]
+    Failed(String),
+}
+
+#[derive(Debug, Error)]
+pub enum PluginError {
+    #[error("failed to load plugin '{path}': {reason}")]
+    LoadFailed { path: String, reason: String },
+
+    #[error("plugin '{path}' was built for ABI version {found}, expected {expected}")]
+    IncompatibleAbi {
+        path: String,
+        found: u32,
+        expected: u32,
+    },
+
+    #[error("invalid plugin '{path}': {reason}")]
+    Invalid { path: String, reason: String },
+}
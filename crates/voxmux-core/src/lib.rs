@@ -1,14 +1,26 @@
 pub mod config;
 pub mod config_diff;
 pub mod error;
+pub mod tap;
 pub mod tui_types;
 pub mod types;
 
 pub use config::AppConfig;
 pub use config_diff::ConfigDiff;
-pub use error::{AsrError, AudioError, ConfigError, DestinationError};
-pub use tui_types::{InputState, InputStatus, OutputState, RouterState, UiCommand};
-pub use types::{AudioChunk, RecognitionResult, TextMetadata};
+pub use error::{
+    AsrError, AudioError, ConfigError, DestinationError, PluginError, TextError,
+    TranslationError, TtsError,
+};
+pub use tap::{asr_tap, OverflowPolicy, TapClosed, TapReceiver, TapSender, TapStats};
+pub use tui_types::{
+    AsrPoolState, AsrQueueState, EngineHealth, EngineState, InputState, InputStatus,
+    LoopbackMeasurement, OutputState, ReplayState, RouterState, Spectrum, ToneSignal, ToneState,
+    ToneTarget, UiCommand, VadState,
+};
+pub use types::{
+    AlertEvent, AudioChunk, RecognitionResult, TextMetadata, ToneEvent, ToneEventKind,
+    TransmissionEvent, TransmissionPhase, WordInfo,
+};
 
 #[cfg(test)]
 mod tests {
@@ -33,6 +45,7 @@ mod tests {
             input_id: "mic1".to_string(),
             timestamp: 1.5,
             is_final: true,
+            ..Default::default()
         };
         assert_eq!(result.text, "hello world");
         assert_eq!(result.input_id, "mic1");
@@ -45,8 +58,79 @@ mod tests {
         let meta = TextMetadata {
             input_id: "radio1".to_string(),
             prefix: "[R1] ".to_string(),
+            ..Default::default()
         };
         assert_eq!(meta.input_id, "radio1");
         assert_eq!(meta.prefix, "[R1] ");
+        assert_eq!(meta.utterance_id, 0);
+    }
+
+    #[test]
+    fn test_text_metadata_from_result() {
+        let result = RecognitionResult {
+            text: "roger".to_string(),
+            input_id: "radio1".to_string(),
+            timestamp: 3.0,
+            is_final: true,
+            utterance_id: 7,
+            offset: 2.0,
+            started_at: Some(1_700_000_002.0),
+            ended_at: Some(1_700_000_003.0),
+            confidence: Some(0.9),
+            words: vec![WordInfo {
+                word: "roger".to_string(),
+                start: 2.0,
+                end: 3.0,
+                confidence: Some(0.9),
+            }],
+            language: Some("en".to_string()),
+            engine: "vosk".to_string(),
+            audio_clip: None,
+        };
+        let meta = TextMetadata::from_result(&result, "[R1] ");
+        assert_eq!(meta.input_id, "radio1");
+        assert_eq!(meta.prefix, "[R1] ");
+        assert_eq!((meta.utterance_id, meta.offset, meta.timestamp), (7, 2.0, 3.0));
+        assert_eq!(meta.ended_at, Some(1_700_000_003.0));
+        assert_eq!(meta.words, result.words);
+        assert_eq!(meta.language.as_deref(), Some("en"));
+        assert_eq!(meta.engine, "vosk");
+    }
+
+    #[test]
+    fn test_tone_event_display() {
+        let event = |kind| ToneEvent {
+            input_id: "radio1".to_string(),
+            timestamp: 2.0,
+            kind,
+        };
+        assert_eq!(event(ToneEventKind::Dtmf { digit: '#' }).to_string(), "DTMF #");
+        assert_eq!(
+            event(ToneEventKind::CtcssStart { frequency_hz: 88.5 }).to_string(),
+            "CTCSS 88.5 Hz start"
+        );
+        assert_eq!(
+            event(ToneEventKind::CtcssEnd { frequency_hz: 100.0 }).to_string(),
+            "CTCSS 100.0 Hz end"
+        );
+        assert_eq!(
+            event(ToneEventKind::DcsStart { code: 0o023 }).to_string(),
+            "DCS 023N start"
+        );
+    }
+
+    #[test]
+    fn test_transmission_event_display() {
+        let mut event = TransmissionEvent {
+            input_id: "radio1".to_string(),
+            phase: TransmissionPhase::Start,
+            started_at: 1_700_000_000.0,
+            duration_secs: 0.25,
+            peak_dbfs: -12.0,
+        };
+        assert_eq!(event.to_string(), "TX start");
+        event.phase = TransmissionPhase::End;
+        event.duration_secs = 12.34;
+        assert_eq!(event.to_string(), "TX end 12.3s peak -12.0 dBFS");
     }
 }
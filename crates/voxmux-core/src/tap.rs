@@ -0,0 +1,405 @@
+use crate::tui_types::AsrQueueState;
+use crate::types::AudioChunk;
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use tokio::sync::Notify;
+
+/// What a tap does when the queued audio would exceed its capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Discard the oldest queued chunks until the new chunk fits.
+    #[default]
+    DropOldest,
+    /// Discard the incoming chunk, keeping what is already queued.
+    DropNewest,
+    /// Merge everything queued into one chunk holding only the most recent audio,
+    /// so a slow engine catches up with a single large feed.
+    Coalesce,
+}
+
+#[derive(Debug, Error)]
+#[error("ASR tap receiver closed")]
+pub struct TapClosed;
+
+/// Counters shared between a tap's sender, receiver and any observers.
+#[derive(Debug, Default)]
+pub struct TapStats {
+    capacity_ms: u32,
+    queued_samples: AtomicUsize,
+    dropped_samples: AtomicU64,
+    dropped_chunks: AtomicU64,
+    /// Samples per second across all channels of the most recent chunk.
+    sample_throughput: AtomicU32,
+}
+
+impl TapStats {
+    pub fn capacity_ms(&self) -> u32 {
+        self.capacity_ms
+    }
+
+    pub fn queued_samples(&self) -> usize {
+        self.queued_samples.load(Ordering::Relaxed)
+    }
+
+    pub fn dropped_samples(&self) -> u64 {
+        self.dropped_samples.load(Ordering::Relaxed)
+    }
+
+    pub fn dropped_chunks(&self) -> u64 {
+        self.dropped_chunks.load(Ordering::Relaxed)
+    }
+
+    /// Snapshot the counters in milliseconds of audio, for TUI display.
+    pub fn queue_state(&self) -> AsrQueueState {
+        let per_second = self.sample_throughput.load(Ordering::Relaxed) as u64;
+        let to_ms = |samples: u64| (samples * 1000).checked_div(per_second).unwrap_or(0);
+        AsrQueueState {
+            queued_ms: to_ms(self.queued_samples() as u64) as u32,
+            capacity_ms: self.capacity_ms,
+            dropped_ms: to_ms(self.dropped_samples()),
+            dropped_chunks: self.dropped_chunks(),
+        }
+    }
+
+    fn record_drop(&self, samples: usize) {
+        self.dropped_samples
+            .fetch_add(samples as u64, Ordering::Relaxed);
+        self.dropped_chunks.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Default)]
+struct Queue {
+    chunks: VecDeque<AudioChunk>,
+    samples: usize,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    notify: Notify,
+    policy: OverflowPolicy,
+    stats: Arc<TapStats>,
+    senders: AtomicUsize,
+    receiver_alive: AtomicBool,
+}
+
+/// Create a bounded ASR tap holding at most `capacity_ms` of audio.
+///
+/// The capacity is converted to samples using the rate and channel count of each
+/// incoming chunk, so the tap does not need to know the stream format up front.
+pub fn asr_tap(capacity_ms: u32, policy: OverflowPolicy) -> (TapSender, TapReceiver) {
+    let shared = Arc::new(Shared {
+        queue: Mutex::new(Queue::default()),
+        notify: Notify::new(),
+        policy,
+        stats: Arc::new(TapStats {
+            capacity_ms,
+            ..Default::default()
+        }),
+        senders: AtomicUsize::new(1),
+        receiver_alive: AtomicBool::new(true),
+    });
+    (
+        TapSender {
+            shared: Arc::clone(&shared),
+        },
+        TapReceiver { shared },
+    )
+}
+
+// ── TapSender ──────────────────────────────────────────────────
+
+/// Producer half of an ASR tap. Never blocks: overflow is resolved by the policy.
+pub struct TapSender {
+    shared: Arc<Shared>,
+}
+
+impl TapSender {
+    /// Queue a chunk, applying the overflow policy if the tap is full.
+    /// Fails only when the receiver has been dropped.
+    pub fn send(&self, chunk: AudioChunk) -> Result<(), TapClosed> {
+        if !self.shared.receiver_alive.load(Ordering::Relaxed) {
+            return Err(TapClosed);
+        }
+
+        let stats = &self.shared.stats;
+        let per_second = chunk.sample_rate * chunk.channels as u32;
+        stats.sample_throughput.store(per_second, Ordering::Relaxed);
+        let capacity = stats.capacity_ms as usize * per_second as usize / 1000;
+
+        let Ok(mut queue) = self.shared.queue.lock() else {
+            return Ok(());
+        };
+        // A single chunk is always accepted into an empty queue: the bound limits
+        // backlog, not the size of one callback's worth of audio.
+        if queue.chunks.is_empty() || queue.samples + chunk.samples.len() <= capacity {
+            queue.samples += chunk.samples.len();
+            queue.chunks.push_back(chunk);
+        } else {
+            match self.shared.policy {
+                OverflowPolicy::DropOldest => drop_oldest(&mut queue, chunk, capacity, stats),
+                OverflowPolicy::DropNewest => stats.record_drop(chunk.samples.len()),
+                OverflowPolicy::Coalesce => coalesce(&mut queue, chunk, capacity, stats),
+            }
+        }
+        stats.queued_samples.store(queue.samples, Ordering::Relaxed);
+        drop(queue);
+
+        self.shared.notify.notify_one();
+        Ok(())
+    }
+
+    /// Shared counters for this tap.
+    pub fn stats(&self) -> Arc<TapStats> {
+        Arc::clone(&self.shared.stats)
+    }
+}
+
+impl Clone for TapSender {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::Relaxed);
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl Drop for TapSender {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            // Last sender gone — wake the receiver so it observes the close.
+            self.shared.notify.notify_one();
+        }
+    }
+}
+
+fn drop_oldest(queue: &mut Queue, chunk: AudioChunk, capacity: usize, stats: &TapStats) {
+    while queue.samples + chunk.samples.len() > capacity {
+        match queue.chunks.pop_front() {
+            Some(old) => {
+                queue.samples -= old.samples.len();
+                stats.record_drop(old.samples.len());
+            }
+            None => break,
+        }
+    }
+    queue.samples += chunk.samples.len();
+    queue.chunks.push_back(chunk);
+}
+
+fn coalesce(queue: &mut Queue, chunk: AudioChunk, capacity: usize, stats: &TapStats) {
+    let same_format = queue
+        .chunks
+        .iter()
+        .all(|c| c.sample_rate == chunk.sample_rate && c.channels == chunk.channels);
+    if !same_format {
+        drop_oldest(queue, chunk, capacity, stats);
+        return;
+    }
+
+    // Never trim into the incoming chunk itself — only older audio is dropped.
+    let channels = chunk.channels.max(1) as usize;
+    let keep = (capacity / channels * channels).max(chunk.samples.len());
+    let mut samples = Vec::with_capacity(queue.samples + chunk.samples.len());
+    for old in queue.chunks.drain(..) {
+        samples.extend_from_slice(&old.samples);
+    }
+    samples.extend_from_slice(&chunk.samples);
+
+    let excess = samples.len().saturating_sub(keep);
+    if excess > 0 {
+        samples.drain(..excess);
+        stats.record_drop(excess);
+    }
+    queue.samples = samples.len();
+    queue.chunks.push_back(AudioChunk { samples, ..chunk });
+}
+
+// ── TapReceiver ────────────────────────────────────────────────
+
+/// Consumer half of an ASR tap.
+pub struct TapReceiver {
+    shared: Arc<Shared>,
+}
+
+impl TapReceiver {
+    /// Wait for the next chunk. Returns `None` once every sender is dropped
+    /// and the queue has been drained.
+    pub async fn recv(&mut self) -> Option<AudioChunk> {
+        loop {
+            if let Some(chunk) = self.try_recv() {
+                return Some(chunk);
+            }
+            if self.shared.senders.load(Ordering::Acquire) == 0 {
+                return None;
+            }
+            self.shared.notify.notified().await;
+        }
+    }
+
+    /// Take the next chunk without waiting.
+    pub fn try_recv(&mut self) -> Option<AudioChunk> {
+        let mut queue = self.shared.queue.lock().ok()?;
+        let chunk = queue.chunks.pop_front()?;
+        queue.samples -= chunk.samples.len();
+        self.shared
+            .stats
+            .queued_samples
+            .store(queue.samples, Ordering::Relaxed);
+        Some(chunk)
+    }
+
+    /// Shared counters for this tap.
+    pub fn stats(&self) -> Arc<TapStats> {
+        Arc::clone(&self.shared.stats)
+    }
+}
+
+impl Drop for TapReceiver {
+    fn drop(&mut self) {
+        self.shared.receiver_alive.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10 samples per millisecond, mono.
+    fn chunk(len: usize, value: f32) -> AudioChunk {
+        AudioChunk {
+            samples: vec![value; len],
+            sample_rate: 10_000,
+            channels: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tap_delivers_in_order() {
+        let (tx, mut rx) = asr_tap(1000, OverflowPolicy::DropOldest);
+        tx.send(chunk(10, 1.0)).unwrap();
+        tx.send(chunk(10, 2.0)).unwrap();
+        assert_eq!(rx.recv().await.unwrap().samples[0], 1.0);
+        assert_eq!(rx.recv().await.unwrap().samples[0], 2.0);
+        assert_eq!(rx.stats().queued_samples(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_tap_recv_returns_none_after_senders_dropped() {
+        let (tx, mut rx) = asr_tap(1000, OverflowPolicy::DropOldest);
+        let tx2 = tx.clone();
+        tx.send(chunk(4, 0.5)).unwrap();
+        drop(tx);
+        drop(tx2);
+        assert!(rx.recv().await.is_some());
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tap_recv_wakes_on_send() {
+        let (tx, mut rx) = asr_tap(1000, OverflowPolicy::DropOldest);
+        let handle = tokio::spawn(async move { rx.recv().await });
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        tx.send(chunk(3, 0.1)).unwrap();
+        let received = tokio::time::timeout(std::time::Duration::from_secs(2), handle)
+            .await
+            .expect("timed out")
+            .unwrap();
+        assert_eq!(received.unwrap().samples.len(), 3);
+    }
+
+    #[test]
+    fn test_tap_send_after_receiver_dropped_fails() {
+        let (tx, rx) = asr_tap(1000, OverflowPolicy::DropOldest);
+        drop(rx);
+        assert!(tx.send(chunk(1, 0.0)).is_err());
+    }
+
+    #[test]
+    fn test_tap_drop_oldest_keeps_newest() {
+        // 5 ms capacity = 50 samples
+        let (tx, mut rx) = asr_tap(5, OverflowPolicy::DropOldest);
+        for i in 0..7 {
+            tx.send(chunk(10, i as f32)).unwrap();
+        }
+        let stats = tx.stats();
+        assert_eq!(stats.queued_samples(), 50);
+        assert_eq!(stats.dropped_samples(), 20);
+        assert_eq!(stats.dropped_chunks(), 2);
+        assert_eq!(rx.try_recv().unwrap().samples[0], 2.0);
+    }
+
+    #[test]
+    fn test_tap_drop_newest_keeps_oldest() {
+        let (tx, mut rx) = asr_tap(5, OverflowPolicy::DropNewest);
+        for i in 0..7 {
+            tx.send(chunk(10, i as f32)).unwrap();
+        }
+        let stats = tx.stats();
+        assert_eq!(stats.queued_samples(), 50);
+        assert_eq!(stats.dropped_samples(), 20);
+        assert_eq!(rx.try_recv().unwrap().samples[0], 0.0);
+    }
+
+    #[test]
+    fn test_tap_coalesce_merges_into_single_chunk() {
+        let (tx, mut rx) = asr_tap(5, OverflowPolicy::Coalesce);
+        for i in 0..7 {
+            tx.send(chunk(10, i as f32)).unwrap();
+        }
+        let merged = rx.try_recv().unwrap();
+        assert!(rx.try_recv().is_none());
+        assert_eq!(merged.samples.len(), 50);
+        assert_eq!(merged.samples[0], 2.0);
+        assert_eq!(*merged.samples.last().unwrap(), 6.0);
+        assert_eq!(tx.stats().dropped_samples(), 20);
+    }
+
+    #[test]
+    fn test_tap_oversized_chunk_accepted_when_empty() {
+        let (tx, mut rx) = asr_tap(5, OverflowPolicy::DropNewest);
+        tx.send(chunk(80, 1.0)).unwrap();
+        tx.send(chunk(10, 2.0)).unwrap();
+        assert_eq!(rx.try_recv().unwrap().samples.len(), 80);
+        assert!(rx.try_recv().is_none());
+        assert_eq!(tx.stats().dropped_samples(), 10);
+    }
+
+    #[test]
+    fn test_tap_drop_oldest_oversized_chunk_evicts_backlog() {
+        let (tx, mut rx) = asr_tap(5, OverflowPolicy::DropOldest);
+        tx.send(chunk(10, 1.0)).unwrap();
+        tx.send(chunk(80, 2.0)).unwrap();
+        assert_eq!(rx.try_recv().unwrap().samples[0], 2.0);
+        assert_eq!(tx.stats().dropped_samples(), 10);
+    }
+
+    #[test]
+    fn test_tap_queue_state_in_milliseconds() {
+        let (tx, _rx) = asr_tap(5, OverflowPolicy::DropOldest);
+        for _ in 0..6 {
+            tx.send(chunk(10, 0.0)).unwrap();
+        }
+        let state = tx.stats().queue_state();
+        assert_eq!(state.capacity_ms, 5);
+        assert_eq!(state.queued_ms, 5);
+        assert_eq!(state.dropped_ms, 1);
+        assert_eq!(state.dropped_chunks, 1);
+    }
+
+    #[test]
+    fn test_overflow_policy_deserialize() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            policy: OverflowPolicy,
+        }
+        let w: Wrapper = toml::from_str("policy = \"coalesce\"").unwrap();
+        assert_eq!(w.policy, OverflowPolicy::Coalesce);
+        let w: Wrapper = toml::from_str("policy = \"drop_newest\"").unwrap();
+        assert_eq!(w.policy, OverflowPolicy::DropNewest);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::types::{AlertEvent, TransmissionEvent};
+
 /// Health status for an input or output device.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum InputStatus {
@@ -7,6 +9,93 @@ pub enum InputStatus {
     Disabled,
 }
 
+/// Fill level and loss counters of an input's ASR tap queue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AsrQueueState {
+    pub queued_ms: u32,
+    pub capacity_ms: u32,
+    pub dropped_ms: u64,
+    pub dropped_chunks: u64,
+}
+
+impl AsrQueueState {
+    /// `true` once more than half the queue is occupied, i.e. the engine is
+    /// consuming audio slower than real time.
+    pub fn is_falling_behind(&self) -> bool {
+        self.capacity_ms > 0 && self.queued_ms * 2 > self.capacity_ms
+    }
+}
+
+/// Load and latency of the shared inference worker pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AsrPoolState {
+    pub workers: usize,
+    /// Workers running inference right now.
+    pub busy: usize,
+    /// Segments waiting for a worker, across all inputs.
+    pub queued: usize,
+    pub completed: u64,
+    /// Segments discarded because an input's backlog was full.
+    pub dropped: u64,
+    /// Time from submission to result, for the last and all segments.
+    pub last_latency_ms: u32,
+    pub avg_latency_ms: u32,
+    pub max_latency_ms: u32,
+}
+
+/// Voice activity on an input's ASR path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VadState {
+    /// No VAD configured; all audio goes to the engine.
+    #[default]
+    Off,
+    Silence,
+    Speech,
+}
+
+/// Supervisor's view of an input's ASR engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EngineState {
+    #[default]
+    Running,
+    /// Recent calls failed; the engine is restarted if failures continue.
+    Degraded,
+    /// Waiting to recreate the engine.
+    Restarting,
+    /// Restarts exhausted; the input's audio is discarded.
+    Failed,
+}
+
+impl std::fmt::Display for EngineState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            EngineState::Running => "running",
+            EngineState::Degraded => "degraded",
+            EngineState::Restarting => "restarting",
+            EngineState::Failed => "failed",
+        })
+    }
+}
+
+/// Health of an input's ASR engine, for TUI display.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EngineHealth {
+    pub state: EngineState,
+    /// Restarts since startup.
+    pub restarts: u32,
+    pub last_error: Option<String>,
+}
+
+/// Decimated magnitude spectrum of an input.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Spectrum {
+    /// Band magnitudes in dBFS, lowest frequency first, evenly spaced up to `max_hz`.
+    pub bands_db: Vec<f32>,
+    pub max_hz: f32,
+    /// Increments with each new analysis frame.
+    pub seq: u64,
+}
+
 /// State of a single audio input, for TUI display.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct InputState {
@@ -17,6 +106,24 @@ pub struct InputState {
     pub muted: bool,
     pub peak_level: f32,
     pub status: InputStatus,
+    pub asr_queue: AsrQueueState,
+    /// Segments from this input waiting in the inference pool.
+    pub asr_segments: usize,
+    pub vad: VadState,
+    /// Present when the input feeds an ASR engine.
+    pub asr_engine: Option<EngineHealth>,
+    /// CTCSS tone currently present on the input, if decoding is enabled.
+    pub ctcss_hz: Option<f32>,
+    /// DCS code currently present (normal-polarity name, octal), if decoding is enabled.
+    pub dcs_code: Option<u16>,
+    /// Most recently decoded DTMF digits, oldest first.
+    pub dtmf_digits: String,
+    /// Latest spectrum, if the analyzer is enabled.
+    pub spectrum: Option<Spectrum>,
+    /// Channel is keyed up right now.
+    pub transmitting: bool,
+    /// Most recent completed transmission.
+    pub last_heard: Option<TransmissionEvent>,
 }
 
 /// State of the audio output, for TUI display.
@@ -35,6 +142,66 @@ impl Default for OutputState {
     }
 }
 
+/// Calibration signal produced by the built-in tone generator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToneSignal {
+    Sine { frequency_hz: f32, level_dbfs: f32 },
+    PinkNoise { level_dbfs: f32 },
+    /// 1 kHz sine at the -20 dBFS line-up level.
+    Reference1k,
+}
+
+impl std::fmt::Display for ToneSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ToneSignal::Sine {
+                frequency_hz,
+                level_dbfs,
+            } => write!(f, "sine {frequency_hz} Hz @ {level_dbfs} dBFS"),
+            ToneSignal::PinkNoise { level_dbfs } => write!(f, "pink noise @ {level_dbfs} dBFS"),
+            ToneSignal::Reference1k => write!(f, "1 kHz reference @ -20 dBFS"),
+        }
+    }
+}
+
+/// Where the tone generator's output is injected.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ToneTarget {
+    /// The speaker mix, as a virtual input.
+    #[default]
+    Mix,
+    /// The ASR tap of the given input, bypassing the mix.
+    AsrTap(String),
+}
+
+/// Result of a loopback measurement: output → cable → input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoopbackMeasurement {
+    pub latency_ms: f32,
+    /// Received level relative to the emitted level.
+    pub level_db: f32,
+}
+
+/// State of the tone generator, for TUI display.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ToneState {
+    pub signal: Option<ToneSignal>,
+    pub target: ToneTarget,
+    /// Input being measured in loopback mode, if any.
+    pub measuring_input: Option<String>,
+    pub measurement: Option<LoopbackMeasurement>,
+}
+
+/// Instant replay status, for TUI display.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayState {
+    /// Seconds of history kept per source.
+    pub history_secs: u32,
+    /// Source being replayed (input id, or `"mix"`).
+    pub playing: Option<String>,
+    pub remaining_secs: f32,
+}
+
 /// Aggregate router state broadcast to the TUI via watch channel.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct RouterState {
@@ -43,6 +210,29 @@ pub struct RouterState {
     pub latest_recognitions: Vec<String>,
     pub warnings: Vec<String>,
     pub is_running: bool,
+    pub tone: ToneState,
+    pub replay: ReplayState,
+    /// Text of the announcement currently being spoken.
+    pub announcement: Option<String>,
+    /// Number of configured sound cues, and whether they are muted.
+    pub cue_count: usize,
+    pub cues_muted: bool,
+    /// Present when any input runs on a shared engine.
+    pub asr_pool: Option<AsrPoolState>,
+    /// Recent alerts from rules with `highlight`, oldest first.
+    pub alerts: Vec<AlertEvent>,
+}
+
+impl RouterState {
+    /// Whether a `latest_recognitions` line matched a highlighting alert.
+    pub fn is_alert(&self, recognition: &str) -> bool {
+        self.alerts.iter().any(|a| {
+            a.is_final
+                && recognition
+                    .strip_prefix(&format!("[{}] ", a.input_id))
+                    .is_some_and(|text| text == a.text)
+        })
+    }
 }
 
 /// Commands sent from TUI → main via mpsc channel.
@@ -52,6 +242,19 @@ pub enum UiCommand {
     SetMuted { input_id: String, muted: bool },
     SetEnabled { input_id: String, enabled: bool },
     SetPlayMixedInput(bool),
+    StartTone { signal: ToneSignal, target: ToneTarget },
+    StopTone,
+    /// Emit 1 kHz bursts into the mix and measure them arriving on `input_id`.
+    MeasureLoopback { input_id: String },
+    SetSpectrum { input_id: String, enabled: bool },
+    /// Play back the last `seconds` of an input (`None` = mix bus), ducking live audio.
+    Replay { input_id: Option<String>, seconds: f32 },
+    StopReplay,
+    /// Write the last `seconds` of an input (`None` = mix bus) to a WAV file.
+    ExportReplay { input_id: Option<String>, seconds: f32 },
+    /// Speak `text` into the mix through the TTS engine.
+    Announce { text: String },
+    SetCuesMuted(bool),
     Quit,
 }
 
@@ -91,6 +294,36 @@ mod tests {
         assert_eq!(input.status, InputStatus::Ok);
     }
 
+    #[test]
+    fn test_asr_queue_falling_behind() {
+        let mut queue = AsrQueueState {
+            capacity_ms: 5000,
+            ..Default::default()
+        };
+        assert!(!queue.is_falling_behind());
+        queue.queued_ms = 2600;
+        assert!(queue.is_falling_behind());
+        assert!(!AsrQueueState::default().is_falling_behind());
+    }
+
+    #[test]
+    fn test_tone_state_default_idle() {
+        let state = RouterState::default();
+        assert!(state.tone.signal.is_none());
+        assert_eq!(state.tone.target, ToneTarget::Mix);
+        assert!(state.tone.measurement.is_none());
+    }
+
+    #[test]
+    fn test_tone_signal_display() {
+        let sine = ToneSignal::Sine {
+            frequency_hz: 440.0,
+            level_dbfs: -12.0,
+        };
+        assert_eq!(sine.to_string(), "sine 440 Hz @ -12 dBFS");
+        assert!(ToneSignal::Reference1k.to_string().contains("1 kHz"));
+    }
+
     #[test]
     fn test_router_state_has_warnings() {
         let state = RouterState::default();
@@ -107,6 +340,14 @@ mod tests {
         assert_eq!(cmd, cloned);
     }
 
+    #[test]
+    fn test_engine_state_display() {
+        assert_eq!(EngineState::default(), EngineState::Running);
+        assert_eq!(EngineState::Degraded.to_string(), "degraded");
+        assert_eq!(EngineState::Restarting.to_string(), "restarting");
+        assert_eq!(EngineState::Failed.to_string(), "failed");
+    }
+
     #[test]
     fn test_router_state_is_clone() {
         let state = RouterState {
@@ -118,6 +359,35 @@ mod tests {
                 muted: false,
                 peak_level: 0.5,
                 status: InputStatus::Ok,
+                asr_queue: AsrQueueState {
+                    queued_ms: 120,
+                    capacity_ms: 5000,
+                    dropped_ms: 0,
+                    dropped_chunks: 0,
+                },
+                asr_segments: 1,
+                vad: VadState::Speech,
+                asr_engine: Some(EngineHealth {
+                    state: EngineState::Degraded,
+                    restarts: 1,
+                    last_error: Some("timeout".to_string()),
+                }),
+                ctcss_hz: Some(88.5),
+                dcs_code: None,
+                dtmf_digits: "12#".to_string(),
+                spectrum: Some(Spectrum {
+                    bands_db: vec![-60.0, -12.5],
+                    max_hz: 4000.0,
+                    seq: 3,
+                }),
+                transmitting: false,
+                last_heard: Some(TransmissionEvent {
+                    input_id: "mic1".to_string(),
+                    phase: crate::types::TransmissionPhase::End,
+                    started_at: 1_700_000_000.0,
+                    duration_secs: 4.5,
+                    peak_dbfs: -3.0,
+                }),
             }],
             output: OutputState {
                 device_name: "speakers".to_string(),
@@ -126,8 +396,53 @@ mod tests {
             latest_recognitions: vec!["hello".to_string()],
             warnings: Vec::new(),
             is_running: true,
+            tone: ToneState::default(),
+            replay: ReplayState {
+                history_secs: 30,
+                playing: Some("mic1".to_string()),
+                remaining_secs: 12.5,
+            },
+            announcement: Some("channel 3 lost signal".to_string()),
+            cue_count: 2,
+            cues_muted: true,
+            asr_pool: Some(AsrPoolState {
+                workers: 2,
+                busy: 1,
+                queued: 1,
+                completed: 10,
+                dropped: 0,
+                last_latency_ms: 800,
+                avg_latency_ms: 750,
+                max_latency_ms: 1200,
+            }),
+            alerts: vec![AlertEvent {
+                rule: "distress".to_string(),
+                input_id: "mic1".to_string(),
+                text: "hello".to_string(),
+                matched: "hello".to_string(),
+                is_final: true,
+            }],
         };
         let cloned = state.clone();
         assert_eq!(state, cloned);
     }
+
+    #[test]
+    fn test_router_state_is_alert() {
+        let alert = |text: &str, is_final| AlertEvent {
+            rule: "distress".to_string(),
+            input_id: "radio1".to_string(),
+            text: text.to_string(),
+            matched: "mayday".to_string(),
+            is_final,
+        };
+        let state = RouterState {
+            alerts: vec![alert("mayday mayday", true), alert("mayday", false)],
+            ..Default::default()
+        };
+        assert!(state.is_alert("[radio1] mayday mayday"));
+        assert!(!state.is_alert("[radio1] mayday"));
+        assert!(!state.is_alert("[radio2] mayday mayday"));
+        assert!(!RouterState::default().is_alert("[radio1] mayday mayday"));
+    }
 }
@@ -1,3 +1,5 @@
+use serde::Serialize;
+
 #[derive(Debug, Clone)]
 pub struct AudioChunk {
     pub samples: Vec<f32>,
@@ -5,16 +7,175 @@ pub struct AudioChunk {
     pub channels: u16,
 }
 
-#[derive(Debug, Clone)]
+/// Timing and confidence of one recognised word.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WordInfo {
+    pub word: String,
+    /// Seconds of input audio since the stream started.
+    pub start: f64,
+    pub end: f64,
+    /// 0.0 to 1.0, if the engine reports one.
+    pub confidence: Option<f32>,
+}
+
+/// Engines fill in what they know; `AsrHost` completes the utterance ID,
+/// timing, language and engine name.
+#[derive(Debug, Clone, Default)]
 pub struct RecognitionResult {
     pub text: String,
     pub input_id: String,
+    /// Seconds of input audio since the stream started, at the end of the
+    /// recognised speech.
     pub timestamp: f64,
     pub is_final: bool,
+    /// Shared by an utterance's partial results and its final. Counts from 1
+    /// per input.
+    pub utterance_id: u64,
+    /// Seconds of input audio since the stream started, at the start of the
+    /// recognised speech.
+    pub offset: f64,
+    /// Wall-clock start and end of the speech, in seconds since the Unix epoch.
+    pub started_at: Option<f64>,
+    pub ended_at: Option<f64>,
+    /// 0.0 to 1.0, if the engine reports one.
+    pub confidence: Option<f32>,
+    pub words: Vec<WordInfo>,
+    /// Detected or configured language code, e.g. `"en"`.
+    pub language: Option<String>,
+    /// Name of the engine that produced the result.
+    pub engine: String,
+    /// Path or URL of a recording of the speech, if one was kept.
+    pub audio_clip: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+/// What a tone decoder detected on an input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToneEventKind {
+    /// A DTMF key press: `0`-`9`, `*`, `#` or `A`-`D`.
+    Dtmf { digit: char },
+    /// A CTCSS sub-audible tone appeared.
+    CtcssStart { frequency_hz: f32 },
+    /// The CTCSS tone went away.
+    CtcssEnd { frequency_hz: f32 },
+    /// A DCS code appeared. `code` is its normal-polarity name in octal,
+    /// e.g. `0o023` for D023N.
+    DcsStart { code: u16 },
+    /// The DCS code went away.
+    DcsEnd { code: u16 },
+}
+
+/// A tone detection, emitted alongside `RecognitionResult`s.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToneEvent {
+    pub input_id: String,
+    /// Seconds of input audio since the capture stream started.
+    pub timestamp: f64,
+    pub kind: ToneEventKind,
+}
+
+impl std::fmt::Display for ToneEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ToneEventKind::Dtmf { digit } => write!(f, "DTMF {digit}"),
+            ToneEventKind::CtcssStart { frequency_hz } => {
+                write!(f, "CTCSS {frequency_hz:.1} Hz start")
+            }
+            ToneEventKind::CtcssEnd { frequency_hz } => write!(f, "CTCSS {frequency_hz:.1} Hz end"),
+            ToneEventKind::DcsStart { code } => write!(f, "DCS {code:03o}N start"),
+            ToneEventKind::DcsEnd { code } => write!(f, "DCS {code:03o}N end"),
+        }
+    }
+}
+
+/// An alert rule matching recognised text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AlertEvent {
+    pub rule: String,
+    pub input_id: String,
+    pub text: String,
+    /// The keyword or pattern that matched.
+    pub matched: String,
+    pub is_final: bool,
+}
+
+impl std::fmt::Display for AlertEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ALERT {} [{}] {}", self.rule, self.input_id, self.text)
+    }
+}
+
+/// Whether a transmission began or ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransmissionPhase {
+    Start,
+    End,
+}
+
+/// A keyed-up period on an input, detected from its signal level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransmissionEvent {
+    pub input_id: String,
+    pub phase: TransmissionPhase,
+    /// Wall-clock start, in seconds since the Unix epoch.
+    pub started_at: f64,
+    /// Length so far (`Start`) or in total (`End`).
+    pub duration_secs: f64,
+    /// Loudest sample so far, in dBFS.
+    pub peak_dbfs: f32,
+}
+
+impl std::fmt::Display for TransmissionEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.phase {
+            TransmissionPhase::Start => write!(f, "TX start"),
+            TransmissionPhase::End => write!(
+                f,
+                "TX end {:.1}s peak {:.1} dBFS",
+                self.duration_secs, self.peak_dbfs
+            ),
+        }
+    }
+}
+
+/// What a destination knows about the text it is sent. Recognition fields
+/// are left at their defaults for text that did not come from an engine,
+/// such as tone events.
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct TextMetadata {
     pub input_id: String,
     pub prefix: String,
+    pub utterance_id: u64,
+    pub offset: f64,
+    pub timestamp: f64,
+    pub started_at: Option<f64>,
+    pub ended_at: Option<f64>,
+    pub confidence: Option<f32>,
+    pub words: Vec<WordInfo>,
+    pub language: Option<String>,
+    pub engine: String,
+    pub audio_clip: Option<String>,
+    /// For a translation: the recognised text it was made from. `language`
+    /// is then the target language.
+    pub original: Option<String>,
+}
+
+impl TextMetadata {
+    /// Metadata for `result`'s text, sent with `prefix`.
+    pub fn from_result(result: &RecognitionResult, prefix: &str) -> Self {
+        Self {
+            input_id: result.input_id.clone(),
+            prefix: prefix.to_string(),
+            utterance_id: result.utterance_id,
+            offset: result.offset,
+            timestamp: result.timestamp,
+            started_at: result.started_at,
+            ended_at: result.ended_at,
+            confidence: result.confidence,
+            words: result.words.clone(),
+            language: result.language.clone(),
+            engine: result.engine.clone(),
+            audio_clip: result.audio_clip.clone(),
+            original: None,
+        }
+    }
 }
@@ -128,6 +128,7 @@ mod tests {
         let metadata = TextMetadata {
             input_id: "mic1".to_string(),
             prefix: "[M1] ".to_string(),
+            ..Default::default()
         };
         let result = dest.send_text("hello", &metadata).await;
         assert!(result.is_ok());
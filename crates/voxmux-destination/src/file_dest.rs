@@ -1,12 +1,30 @@
 use crate::dest_trait::Destination;
 use async_trait::async_trait;
+use serde::Serialize;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 use voxmux_core::{DestinationError, TextMetadata};
 
+/// How each text is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// The prefixed text on its own line.
+    Text,
+    /// One JSON object per line with the text and all its metadata.
+    Jsonl,
+}
+
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    text: &'a str,
+    #[serde(flatten)]
+    metadata: &'a TextMetadata,
+}
+
 pub struct FileDestination {
     output_path: Mutex<Option<PathBuf>>,
+    format: Format,
     send_count: AtomicUsize,
 }
 
@@ -14,6 +32,7 @@ impl FileDestination {
     pub fn new() -> Self {
         Self {
             output_path: Mutex::new(None),
+            format: Format::Text,
             send_count: AtomicUsize::new(0),
         }
     }
@@ -42,6 +61,15 @@ impl Destination for FileDestination {
             .ok_or_else(|| {
                 DestinationError::InitializationFailed("missing 'path' in config".to_string())
             })?;
+        self.format = match config.get("format").and_then(|v| v.as_str()) {
+            None | Some("text") => Format::Text,
+            Some("jsonl") => Format::Jsonl,
+            Some(other) => {
+                return Err(DestinationError::InitializationFailed(format!(
+                    "unknown format '{other}', expected 'text' or 'jsonl'"
+                )))
+            }
+        };
         *self.output_path.lock().unwrap() = Some(PathBuf::from(path));
         Ok(())
     }
@@ -59,8 +87,12 @@ impl Destination for FileDestination {
             .open(path)
             .map_err(|e| DestinationError::SendFailed(e.to_string()))?;
 
-        writeln!(file, "{}{}", metadata.prefix, text)
-            .map_err(|e| DestinationError::SendFailed(e.to_string()))?;
+        let line = match self.format {
+            Format::Text => format!("{}{}", metadata.prefix, text),
+            Format::Jsonl => serde_json::to_string(&JsonRecord { text, metadata })
+                .map_err(|e| DestinationError::SendFailed(e.to_string()))?,
+        };
+        writeln!(file, "{line}").map_err(|e| DestinationError::SendFailed(e.to_string()))?;
 
         self.send_count.fetch_add(1, Ordering::Relaxed);
         Ok(())
@@ -133,6 +165,7 @@ mod tests {
         let metadata = TextMetadata {
             input_id: "mic1".to_string(),
             prefix: "[M1] ".to_string(),
+            ..Default::default()
         };
         dest.send_text("hello world", &metadata).await.unwrap();
 
@@ -163,6 +196,7 @@ mod tests {
         let metadata = TextMetadata {
             input_id: "mic1".to_string(),
             prefix: "".to_string(),
+            ..Default::default()
         };
         dest.send_text("line one", &metadata).await.unwrap();
         dest.send_text("line two", &metadata).await.unwrap();
@@ -179,6 +213,7 @@ mod tests {
         let metadata = TextMetadata {
             input_id: "mic1".to_string(),
             prefix: "".to_string(),
+            ..Default::default()
         };
         let result = dest.send_text("test", &metadata).await;
         match result {
@@ -227,6 +262,7 @@ mod tests {
         let metadata = TextMetadata {
             input_id: "mic1".to_string(),
             prefix: "".to_string(),
+            ..Default::default()
         };
         for _ in 0..3 {
             dest.send_text("msg", &metadata).await.unwrap();
@@ -235,4 +271,52 @@ mod tests {
 
         std::fs::remove_dir_all(&dir).unwrap();
     }
+
+    #[tokio::test]
+    async fn test_file_dest_jsonl_writes_metadata() {
+        let dir = std::env::temp_dir().join("voxmux_file_dest_jsonl");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("output.jsonl");
+        let _ = std::fs::remove_file(&path);
+
+        let mut dest = FileDestination::new();
+        let config: toml::Value = toml::from_str(&format!(
+            "path = {:?}\nformat = \"jsonl\"",
+            path.to_string_lossy()
+        ))
+        .unwrap();
+        dest.initialize(config).await.unwrap();
+
+        let metadata = TextMetadata {
+            input_id: "mic1".to_string(),
+            utterance_id: 3,
+            confidence: Some(0.5),
+            language: Some("en".to_string()),
+            engine: "null".to_string(),
+            ..Default::default()
+        };
+        dest.send_text("hello", &metadata).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let record: serde_json::Value = serde_json::from_str(contents.trim()).unwrap();
+        assert_eq!(record["text"], "hello");
+        assert_eq!(record["input_id"], "mic1");
+        assert_eq!(record["utterance_id"], 3);
+        assert_eq!(record["confidence"], 0.5);
+        assert_eq!(record["language"], "en");
+        assert_eq!(record["engine"], "null");
+        assert!(record["words"].as_array().unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_dest_unknown_format_fails() {
+        let mut dest = FileDestination::new();
+        let config: toml::Value = toml::from_str("path = \"/tmp/x\"\nformat = \"xml\"").unwrap();
+        assert!(matches!(
+            dest.initialize(config).await,
+            Err(DestinationError::InitializationFailed(msg)) if msg.contains("xml")
+        ));
+    }
 }
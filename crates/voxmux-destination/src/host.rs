@@ -1,19 +1,39 @@
 use crate::dest_trait::Destination;
 use crate::registry::DestinationRegistry;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::mpsc;
-use voxmux_core::{DestinationError, RecognitionResult, TextMetadata};
+use voxmux_core::config::{RouteTranslationConfig, TranslationSend};
+use voxmux_core::{AlertEvent, DestinationError, RecognitionResult, TextMetadata, ToneEvent};
+use voxmux_text::Translator;
 
 struct Route {
     destination: Box<dyn Destination>,
     prefix: String,
+    translate: Option<RouteTranslationConfig>,
+}
+
+/// A `send_text` call that failed, reported to the failure sender.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DestinationFailure {
+    pub input_id: String,
+    pub destination: String,
+    pub error: String,
 }
 
 pub struct DestinationHost {
     registry: DestinationRegistry,
     routes: HashMap<String, Vec<Route>>,
+    /// Routes for alerts, by rule name.
+    alert_routes: HashMap<String, Vec<Route>>,
     result_rx: Option<mpsc::UnboundedReceiver<RecognitionResult>>,
+    tone_rx: Option<mpsc::UnboundedReceiver<ToneEvent>>,
+    alert_rx: Option<mpsc::UnboundedReceiver<AlertEvent>>,
+    failure_tx: Option<mpsc::UnboundedSender<DestinationFailure>>,
+    translator: Option<Arc<dyn Translator>>,
     task_handle: Option<tokio::task::JoinHandle<()>>,
+    tone_task_handle: Option<tokio::task::JoinHandle<()>>,
+    alert_task_handle: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl DestinationHost {
@@ -21,11 +41,46 @@ impl DestinationHost {
         Self {
             registry: DestinationRegistry::new(),
             routes: HashMap::new(),
+            alert_routes: HashMap::new(),
             result_rx: Some(result_rx),
+            tone_rx: None,
+            alert_rx: None,
+            failure_tx: None,
+            translator: None,
             task_handle: None,
+            tone_task_handle: None,
+            alert_task_handle: None,
         }
     }
 
+    /// Also route decoded tone events to each input's destinations, as text.
+    /// Must be called before `start()`.
+    pub fn set_tone_receiver(&mut self, tone_rx: mpsc::UnboundedReceiver<ToneEvent>) {
+        self.tone_rx = Some(tone_rx);
+    }
+
+    /// Send alerts to the routes added with `add_alert_route`, as text. Must
+    /// be called before `start()`.
+    pub fn set_alert_receiver(&mut self, alert_rx: mpsc::UnboundedReceiver<AlertEvent>) {
+        self.alert_rx = Some(alert_rx);
+    }
+
+    /// Report failed sends on this channel. Must be called before `start()`.
+    pub fn set_failure_sender(&mut self, failure_tx: mpsc::UnboundedSender<DestinationFailure>) {
+        self.failure_tx = Some(failure_tx);
+    }
+
+    /// Translator for routes added with `add_route_with_translation`. Must be
+    /// called before those routes are added.
+    pub fn set_translator(&mut self, translator: Arc<dyn Translator>) {
+        self.translator = Some(translator);
+    }
+
+    /// Make a loaded plugin's destinations available to `add_route`.
+    pub fn register_plugin(&mut self, plugin: &voxmux_plugin::host::Plugin) {
+        self.registry.register_plugin(plugin);
+    }
+
     pub async fn add_route(
         &mut self,
         input_id: &str,
@@ -33,51 +88,137 @@ impl DestinationHost {
         prefix: &str,
         config: toml::Value,
     ) -> Result<(), DestinationError> {
-        let mut dest = self.registry.create(plugin_name)?;
-        dest.initialize(config).await?;
+        self.push_route(input_id, plugin_name, prefix, None, config)
+            .await
+    }
 
-        let route = Route {
-            destination: dest,
-            prefix: prefix.to_string(),
-        };
+    /// Like `add_route`, but results are translated as `translate` says
+    /// before they are sent. Tone events are sent untranslated.
+    pub async fn add_route_with_translation(
+        &mut self,
+        input_id: &str,
+        plugin_name: &str,
+        prefix: &str,
+        translate: RouteTranslationConfig,
+        config: toml::Value,
+    ) -> Result<(), DestinationError> {
+        if translate.send != TranslationSend::Original && self.translator.is_none() {
+            return Err(DestinationError::InitializationFailed(format!(
+                "route to '{plugin_name}' translates to '{}' but no translator is configured",
+                translate.to
+            )));
+        }
+        self.push_route(input_id, plugin_name, prefix, Some(translate), config)
+            .await
+    }
 
+    /// Route alerts raised by rule `rule` to a destination.
+    pub async fn add_alert_route(
+        &mut self,
+        rule: &str,
+        plugin_name: &str,
+        prefix: &str,
+        config: toml::Value,
+    ) -> Result<(), DestinationError> {
+        let route = self.create_route(plugin_name, prefix, None, config).await?;
+        self.alert_routes
+            .entry(rule.to_string())
+            .or_default()
+            .push(route);
+        Ok(())
+    }
+
+    async fn push_route(
+        &mut self,
+        input_id: &str,
+        plugin_name: &str,
+        prefix: &str,
+        translate: Option<RouteTranslationConfig>,
+        config: toml::Value,
+    ) -> Result<(), DestinationError> {
+        let route = self
+            .create_route(plugin_name, prefix, translate, config)
+            .await?;
         self.routes
             .entry(input_id.to_string())
             .or_default()
             .push(route);
-
         Ok(())
     }
 
+    async fn create_route(
+        &self,
+        plugin_name: &str,
+        prefix: &str,
+        translate: Option<RouteTranslationConfig>,
+        config: toml::Value,
+    ) -> Result<Route, DestinationError> {
+        let mut dest = self.registry.create(plugin_name)?;
+        dest.initialize(config).await?;
+        Ok(Route {
+            destination: dest,
+            prefix: prefix.to_string(),
+            translate,
+        })
+    }
+
     pub fn start(&mut self) {
         let mut rx = self
             .result_rx
             .take()
             .expect("start() called but receiver already taken");
-        let routes = std::mem::take(&mut self.routes);
+        let routes = Arc::new(std::mem::take(&mut self.routes));
+        let failure_tx = self.failure_tx.take();
+        let translator = self.translator.take();
+
+        if let Some(mut tone_rx) = self.tone_rx.take() {
+            let routes = Arc::clone(&routes);
+            let failure_tx = failure_tx.clone();
+            self.tone_task_handle = Some(tokio::spawn(async move {
+                while let Some(event) = tone_rx.recv().await {
+                    let metadata = TextMetadata {
+                        input_id: event.input_id.clone(),
+                        ..Default::default()
+                    };
+                    send_to_routes(
+                        &routes,
+                        &event.input_id,
+                        &event.to_string(),
+                        metadata,
+                        &failure_tx,
+                    )
+                    .await;
+                }
+            }));
+        }
+
+        if let Some(mut alert_rx) = self.alert_rx.take() {
+            let alert_routes = std::mem::take(&mut self.alert_routes);
+            let failure_tx = failure_tx.clone();
+            self.alert_task_handle = Some(tokio::spawn(async move {
+                while let Some(event) = alert_rx.recv().await {
+                    let metadata = TextMetadata {
+                        input_id: event.input_id.clone(),
+                        ..Default::default()
+                    };
+                    send_to_routes(
+                        &alert_routes,
+                        &event.rule,
+                        &event.to_string(),
+                        metadata,
+                        &failure_tx,
+                    )
+                    .await;
+                }
+            }));
+        }
 
         let handle = tokio::spawn(async move {
             while let Some(result) = rx.recv().await {
-                if !result.is_final {
+                if !result.is_final || result.text.is_empty() {
                     continue;
                 }
-
-                if let Some(input_routes) = routes.get(&result.input_id) {
-                    for route in input_routes {
-                        let metadata = TextMetadata {
-                            input_id: result.input_id.clone(),
-                            prefix: route.prefix.clone(),
-                        };
-                        if let Err(e) = route.destination.send_text(&result.text, &metadata).await
-                        {
-                            tracing::error!(
-                                input_id = %result.input_id,
-                                destination = %route.destination.name(),
-                                "send_text failed: {e}"
-                            );
-                        }
-                    }
-                }
+                send_result(&routes, &result, translator.as_deref(), &failure_tx).await;
             }
         });
 
@@ -85,7 +226,14 @@ impl DestinationHost {
     }
 
     pub async fn shutdown(&mut self) {
-        if let Some(handle) = self.task_handle.take() {
+        for handle in [
+            self.task_handle.take(),
+            self.tone_task_handle.take(),
+            self.alert_task_handle.take(),
+        ]
+        .into_iter()
+        .flatten()
+        {
             if let Err(e) = handle.await {
                 tracing::error!("destination routing task panicked: {e}");
             }
@@ -93,6 +241,113 @@ impl DestinationHost {
     }
 }
 
+/// Send `text` to each route under `key`, with the route's prefix set on
+/// `metadata`.
+async fn send_to_routes(
+    routes: &HashMap<String, Vec<Route>>,
+    key: &str,
+    text: &str,
+    mut metadata: TextMetadata,
+    failure_tx: &Option<mpsc::UnboundedSender<DestinationFailure>>,
+) {
+    let Some(key_routes) = routes.get(key) else {
+        return;
+    };
+    for route in key_routes {
+        metadata.prefix.clone_from(&route.prefix);
+        send_to_route(route, text, &metadata, failure_tx).await;
+    }
+}
+
+/// Send a result to each of its input's routes, translated as each route
+/// asks. Each target language is translated once. Routes fall back to the
+/// original text when translation fails or is not needed.
+async fn send_result(
+    routes: &HashMap<String, Vec<Route>>,
+    result: &RecognitionResult,
+    translator: Option<&dyn Translator>,
+    failure_tx: &Option<mpsc::UnboundedSender<DestinationFailure>>,
+) {
+    let Some(input_routes) = routes.get(&result.input_id) else {
+        return;
+    };
+    let mut translations: HashMap<&str, Option<String>> = HashMap::new();
+    for route in input_routes {
+        let metadata = TextMetadata::from_result(result, &route.prefix);
+        let translation = match (&route.translate, translator) {
+            (Some(translate), Some(translator)) if translate.send != TranslationSend::Original => {
+                let target = translate.to.as_str();
+                if !translations.contains_key(target) {
+                    let text = translate_result(translator, result, target).await;
+                    translations.insert(target, text);
+                }
+                translations[target].clone().map(|text| (target, text))
+            }
+            _ => None,
+        };
+        let send = route.translate.as_ref().map(|t| t.send);
+
+        let Some((target, text)) = translation else {
+            send_to_route(route, &result.text, &metadata, failure_tx).await;
+            continue;
+        };
+        if send == Some(TranslationSend::Both) {
+            send_to_route(route, &result.text, &metadata, failure_tx).await;
+        }
+        let metadata = TextMetadata {
+            language: Some(target.to_string()),
+            original: Some(result.text.clone()),
+            ..metadata
+        };
+        send_to_route(route, &text, &metadata, failure_tx).await;
+    }
+}
+
+/// `None` if the result is already in `target` or translation failed.
+async fn translate_result(
+    translator: &dyn Translator,
+    result: &RecognitionResult,
+    target: &str,
+) -> Option<String> {
+    let source = result.language.as_deref();
+    if voxmux_text::translate::same_language(source, target) {
+        return None;
+    }
+    match translator.translate(&result.text, source, target).await {
+        Ok(text) => Some(text),
+        Err(e) => {
+            tracing::warn!(
+                input_id = %result.input_id,
+                translator = %translator.name(),
+                "translation to '{target}' failed, sending original: {e}"
+            );
+            None
+        }
+    }
+}
+
+async fn send_to_route(
+    route: &Route,
+    text: &str,
+    metadata: &TextMetadata,
+    failure_tx: &Option<mpsc::UnboundedSender<DestinationFailure>>,
+) {
+    if let Err(e) = route.destination.send_text(text, metadata).await {
+        tracing::error!(
+            input_id = %metadata.input_id,
+            destination = %route.destination.name(),
+            "send_text failed: {e}"
+        );
+        if let Some(tx) = failure_tx {
+            let _ = tx.send(DestinationFailure {
+                input_id: metadata.input_id.clone(),
+                destination: route.destination.name().to_string(),
+                error: e.to_string(),
+            });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,6 +365,7 @@ mod tests {
             input_id: input_id.to_string(),
             timestamp: 0.0,
             is_final,
+            ..Default::default()
         }
     }
 
@@ -341,6 +597,7 @@ mod tests {
         host.start();
 
         tx.send(make_result("mic1", "partial", false)).unwrap();
+        tx.send(make_result("mic1", "", true)).unwrap();
         tx.send(make_result("mic1", "final", true)).unwrap();
         drop(tx);
 
@@ -353,4 +610,315 @@ mod tests {
 
         std::fs::remove_dir_all(&dir).unwrap();
     }
+
+    #[tokio::test]
+    async fn test_host_routes_tone_events_as_text() {
+        let (tx, rx) = make_channel();
+        let (tone_tx, tone_rx) = mpsc::unbounded_channel();
+        let mut host = DestinationHost::new(rx);
+        let dir = std::env::temp_dir().join("voxmux_host_tone_events");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("radio.txt");
+        let _ = std::fs::remove_file(&path);
+
+        host.add_route("radio1", "file", "[R1] ", file_config(&path.to_string_lossy()))
+            .await
+            .unwrap();
+        host.set_tone_receiver(tone_rx);
+        host.start();
+
+        tone_tx
+            .send(ToneEvent {
+                input_id: "radio1".to_string(),
+                timestamp: 1.0,
+                kind: voxmux_core::ToneEventKind::Dtmf { digit: '7' },
+            })
+            .unwrap();
+        tone_tx
+            .send(ToneEvent {
+                input_id: "other".to_string(),
+                timestamp: 1.0,
+                kind: voxmux_core::ToneEventKind::Dtmf { digit: '8' },
+            })
+            .unwrap();
+        drop(tone_tx);
+        drop(tx);
+
+        tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+            .await
+            .expect("shutdown timed out");
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "[R1] DTMF 7\n");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_host_routes_alerts_by_rule() {
+        let (tx, rx) = make_channel();
+        let (alert_tx, alert_rx) = mpsc::unbounded_channel();
+        let mut host = DestinationHost::new(rx);
+        let dir = std::env::temp_dir().join("voxmux_host_alert_events");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("alerts.txt");
+        let _ = std::fs::remove_file(&path);
+
+        host.add_alert_route("distress", "file", "!! ", file_config(&path.to_string_lossy()))
+            .await
+            .unwrap();
+        host.set_alert_receiver(alert_rx);
+        host.start();
+
+        alert_tx
+            .send(AlertEvent {
+                rule: "distress".to_string(),
+                input_id: "radio1".to_string(),
+                text: "mayday mayday".to_string(),
+                matched: "mayday".to_string(),
+                is_final: true,
+            })
+            .unwrap();
+        alert_tx
+            .send(AlertEvent {
+                rule: "unrouted".to_string(),
+                input_id: "radio1".to_string(),
+                text: "ignored".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        drop(alert_tx);
+        drop(tx);
+
+        tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+            .await
+            .expect("shutdown timed out");
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "!! ALERT distress [radio1] mayday mayday\n");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    struct FailingDestination;
+
+    #[async_trait::async_trait]
+    impl Destination for FailingDestination {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), DestinationError> {
+            Ok(())
+        }
+
+        async fn send_text(
+            &self,
+            _text: &str,
+            _metadata: &TextMetadata,
+        ) -> Result<(), DestinationError> {
+            Err(DestinationError::ConnectionLost("gateway closed".to_string()))
+        }
+
+        fn is_healthy(&self) -> bool {
+            false
+        }
+
+        async fn shutdown(&self) -> Result<(), DestinationError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_host_reports_send_failures() {
+        let (tx, rx) = make_channel();
+        let (failure_tx, mut failure_rx) = mpsc::unbounded_channel();
+        let mut host = DestinationHost::new(rx);
+        host.registry.register("failing", || Box::new(FailingDestination));
+        host.add_route("mic1", "failing", "", toml::Value::Table(Default::default()))
+            .await
+            .unwrap();
+        host.set_failure_sender(failure_tx);
+        host.start();
+
+        tx.send(make_result("mic1", "hello", true)).unwrap();
+        drop(tx);
+        tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+            .await
+            .expect("shutdown timed out");
+
+        let failure = failure_rx.try_recv().unwrap();
+        assert_eq!(failure.input_id, "mic1");
+        assert_eq!(failure.destination, "failing");
+        assert!(failure.error.contains("gateway closed"));
+        assert!(failure_rx.try_recv().is_err());
+    }
+
+    /// Tags text with the target language, counting calls; fails for "xx".
+    struct MockTranslator {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Translator for MockTranslator {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        async fn initialize(
+            &mut self,
+            _config: toml::Value,
+        ) -> Result<(), voxmux_core::TranslationError> {
+            Ok(())
+        }
+
+        async fn translate(
+            &self,
+            text: &str,
+            source: Option<&str>,
+            target: &str,
+        ) -> Result<String, voxmux_core::TranslationError> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if target == "xx" {
+                return Err(voxmux_core::TranslationError::Failed("unsupported".to_string()));
+            }
+            Ok(format!("{}>{target}: {text}", source.unwrap_or("auto")))
+        }
+    }
+
+    fn translate(to: &str, send: TranslationSend) -> RouteTranslationConfig {
+        RouteTranslationConfig {
+            to: to.to_string(),
+            send,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_host_translates_per_route() {
+        let (tx, rx) = make_channel();
+        let mut host = DestinationHost::new(rx);
+        let translator = Arc::new(MockTranslator {
+            calls: Default::default(),
+        });
+        host.set_translator(translator.clone());
+        let dir = std::env::temp_dir().join("voxmux_host_translate");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+
+        host.add_route("mic1", "file", "", file_config(&path("original.txt")))
+            .await
+            .unwrap();
+        host.add_route_with_translation(
+            "mic1",
+            "file",
+            "[EN] ",
+            translate("en", TranslationSend::Translation),
+            file_config(&path("en.txt")),
+        )
+        .await
+        .unwrap();
+        host.add_route_with_translation(
+            "mic1",
+            "file",
+            "",
+            translate("en", TranslationSend::Both),
+            toml::from_str(&format!("path = '{}'\nformat = 'jsonl'", path("both.jsonl")))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+        host.start();
+
+        tx.send(RecognitionResult {
+            language: Some("ja".to_string()),
+            ..make_result("mic1", "了解", true)
+        })
+        .unwrap();
+        drop(tx);
+        tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+            .await
+            .expect("shutdown timed out");
+
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).unwrap();
+        assert_eq!(read("original.txt"), "了解\n");
+        assert_eq!(read("en.txt"), "[EN] ja>en: 了解\n");
+        let both: Vec<serde_json::Value> = read("both.jsonl")
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(both[0]["text"], "了解");
+        assert_eq!(both[0]["language"], "ja");
+        assert!(both[0]["original"].is_null());
+        assert_eq!(both[1]["text"], "ja>en: 了解");
+        assert_eq!(both[1]["language"], "en");
+        assert_eq!(both[1]["original"], "了解");
+        // Both English routes share one translation
+        assert_eq!(translator.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_host_translation_falls_back_to_original() {
+        let (tx, rx) = make_channel();
+        let mut host = DestinationHost::new(rx);
+        assert!(matches!(
+            host.add_route_with_translation(
+                "mic1",
+                "file",
+                "",
+                translate("en", TranslationSend::Translation),
+                toml::Value::Table(Default::default()),
+            )
+            .await,
+            Err(DestinationError::InitializationFailed(_))
+        ));
+
+        let translator = Arc::new(MockTranslator {
+            calls: Default::default(),
+        });
+        host.set_translator(translator.clone());
+        let dir = std::env::temp_dir().join("voxmux_host_translate_fallback");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let failing = dir.join("xx.txt");
+        let same = dir.join("en.txt");
+        host.add_route_with_translation(
+            "mic1",
+            "file",
+            "",
+            translate("xx", TranslationSend::Both),
+            file_config(&failing.to_string_lossy()),
+        )
+        .await
+        .unwrap();
+        host.add_route_with_translation(
+            "mic1",
+            "file",
+            "",
+            translate("en", TranslationSend::Translation),
+            file_config(&same.to_string_lossy()),
+        )
+        .await
+        .unwrap();
+        host.start();
+
+        tx.send(RecognitionResult {
+            language: Some("en-US".to_string()),
+            ..make_result("mic1", "roger", true)
+        })
+        .unwrap();
+        drop(tx);
+        tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+            .await
+            .expect("shutdown timed out");
+
+        assert_eq!(std::fs::read_to_string(&failing).unwrap(), "roger\n");
+        assert_eq!(std::fs::read_to_string(&same).unwrap(), "roger\n");
+        // Only the failing target was attempted
+        assert_eq!(translator.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -3,11 +3,17 @@ pub mod dest_trait;
 pub mod discord_dest;
 pub mod file_dest;
 pub mod host;
+pub mod plugin_dest;
 pub mod registry;
+#[cfg(feature = "wasm")]
+pub mod wasm_dest;
 
 pub use dest_trait::Destination;
 #[cfg(feature = "discord")]
 pub use discord_dest::DiscordDestination;
 pub use file_dest::FileDestination;
-pub use host::DestinationHost;
+pub use host::{DestinationFailure, DestinationHost};
+pub use plugin_dest::LibraryDestination;
 pub use registry::DestinationRegistry;
+#[cfg(feature = "wasm")]
+pub use wasm_dest::WasmDestination;
@@ -0,0 +1,70 @@
+use crate::dest_trait::Destination;
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use voxmux_core::{DestinationError, TextMetadata};
+use voxmux_plugin::host::{DestinationInstance, PluginDestination};
+
+/// A destination loaded from a plugin library.
+///
+/// Plugin calls are blocking, so sends run on tokio's blocking pool, one at
+/// a time.
+pub struct LibraryDestination {
+    destination: PluginDestination,
+    instance: Arc<Mutex<Option<DestinationInstance>>>,
+}
+
+impl LibraryDestination {
+    pub fn new(destination: PluginDestination) -> Self {
+        Self {
+            destination,
+            instance: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+#[async_trait]
+impl Destination for LibraryDestination {
+    fn name(&self) -> &str {
+        self.destination.name()
+    }
+
+    /// The config is handed to the plugin as a TOML document.
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), DestinationError> {
+        let config = toml::to_string(&config)
+            .map_err(|e| DestinationError::InitializationFailed(e.to_string()))?;
+        let destination = self.destination.clone();
+        let instance = tokio::task::spawn_blocking(move || destination.create(&config))
+            .await
+            .map_err(|e| DestinationError::InitializationFailed(e.to_string()))?
+            .map_err(|e| {
+                DestinationError::InitializationFailed(format!("{}: {e}", self.destination.name()))
+            })?;
+        *self.instance.lock().unwrap() = Some(instance);
+        Ok(())
+    }
+
+    async fn send_text(&self, text: &str, metadata: &TextMetadata) -> Result<(), DestinationError> {
+        let instance = self.instance.clone();
+        let text = text.to_string();
+        let metadata = metadata.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut guard = instance.lock().unwrap();
+            let instance = guard
+                .as_mut()
+                .ok_or_else(|| "not initialized".to_string())?;
+            instance.send_text(&text, &metadata.input_id, &metadata.prefix)
+        })
+        .await
+        .map_err(|e| DestinationError::SendFailed(e.to_string()))?
+        .map_err(DestinationError::SendFailed)
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.instance.lock().unwrap().is_some()
+    }
+
+    async fn shutdown(&self) -> Result<(), DestinationError> {
+        self.instance.lock().unwrap().take();
+        Ok(())
+    }
+}
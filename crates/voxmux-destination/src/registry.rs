@@ -1,21 +1,27 @@
 use crate::dest_trait::Destination;
+use crate::plugin_dest::LibraryDestination;
 use std::collections::HashMap;
 use voxmux_core::DestinationError;
+use voxmux_plugin::host::{Plugin, PluginDestination};
 
 pub struct DestinationRegistry {
     factories: HashMap<String, fn() -> Box<dyn Destination>>,
+    plugins: HashMap<String, PluginDestination>,
 }
 
 impl DestinationRegistry {
     pub fn new() -> Self {
         let mut registry = Self {
             factories: HashMap::new(),
+            plugins: HashMap::new(),
         };
         registry.register("file", || Box::new(crate::file_dest::FileDestination::new()));
         #[cfg(feature = "discord")]
         registry.register("discord", || {
             Box::new(crate::discord_dest::DiscordDestination::new())
         });
+        #[cfg(feature = "wasm")]
+        registry.register("wasm", || Box::new(crate::wasm_dest::WasmDestination::new()));
         registry
     }
 
@@ -23,7 +29,19 @@ impl DestinationRegistry {
         self.factories.insert(name.to_string(), factory);
     }
 
+    /// Register the destinations of a loaded plugin library. They take
+    /// precedence over compiled-in destinations of the same name.
+    pub fn register_plugin(&mut self, plugin: &Plugin) {
+        for destination in plugin.destinations() {
+            self.plugins
+                .insert(destination.name().to_string(), destination.clone());
+        }
+    }
+
     pub fn create(&self, name: &str) -> Result<Box<dyn Destination>, DestinationError> {
+        if let Some(destination) = self.plugins.get(name) {
+            return Ok(Box::new(LibraryDestination::new(destination.clone())));
+        }
         self.factories
             .get(name)
             .map(|f| f())
@@ -31,7 +49,11 @@ impl DestinationRegistry {
     }
 
     pub fn list_destinations(&self) -> Vec<&str> {
-        self.factories.keys().map(|s| s.as_str()).collect()
+        self.factories
+            .keys()
+            .chain(self.plugins.keys())
+            .map(|s| s.as_str())
+            .collect()
     }
 }
 
@@ -0,0 +1,677 @@
+//! Destinations implemented as sandboxed WebAssembly modules.
+//!
+//! A guest module exports `memory`, `alloc(len) -> ptr` and
+//! `send_text(text_ptr, text_len, input_ptr, input_len, prefix_ptr,
+//! prefix_len) -> i32` (0 = success), and optionally `init() -> i32`, called
+//! once after loading. It can import these functions from module `voxmux`:
+//!
+//! | Import | Description |
+//! |--------|-------------|
+//! | `log(level, ptr, len)` | Log a message (0 error, 1 warn, 2 info, 3 debug) |
+//! | `config(key_ptr, key_len, out_ptr, out_cap) -> len` | Copy a guest setting; -1 if unset |
+//! | `fail(ptr, len)` | Set the error message of the current call |
+//! | `http_post(url_ptr, url_len, body_ptr, body_len) -> status` | POST through the host; -1 if the host is not allowed, -2 on failure |
+//!
+//! Every call runs with a fuel budget, and memory is capped.
+
+use crate::dest_trait::Destination;
+use async_trait::async_trait;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use voxmux_core::{DestinationError, TextMetadata};
+use wasmi::{
+    Caller, Config, Engine, Extern, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+    TypedFunc,
+};
+
+/// Default fuel per call, roughly one unit per instruction.
+const DEFAULT_FUEL: u64 = 10_000_000;
+const DEFAULT_MAX_MEMORY_BYTES: u64 = 16 * 1024 * 1024;
+const DEFAULT_HTTP_TIMEOUT_MS: u64 = 5000;
+
+/// Returned by `http_post` when the URL's host is not in `allow_http`.
+const HTTP_DENIED: i32 = -1;
+/// Returned by `http_post` when the request failed.
+const HTTP_FAILED: i32 = -2;
+
+type SendTextFn = TypedFunc<(i32, i32, i32, i32, i32, i32), i32>;
+
+/// Capabilities and limits from the destination config.
+struct Settings {
+    fuel: u64,
+    max_memory_bytes: usize,
+    allow_http: Vec<String>,
+    http_timeout: Duration,
+    /// The `config` table, readable by the guest.
+    guest_config: toml::Table,
+}
+
+/// Host side of the store, reachable from imports.
+struct HostState {
+    settings: Arc<Settings>,
+    limits: StoreLimits,
+    http: reqwest::Client,
+    runtime: tokio::runtime::Handle,
+    /// Message passed to `fail` during the current call.
+    error: Option<String>,
+}
+
+impl HostState {
+    fn http_allowed(&self, url: &str) -> bool {
+        let Ok(url) = reqwest::Url::parse(url) else {
+            return false;
+        };
+        matches!(url.scheme(), "http" | "https")
+            && url
+                .host_str()
+                .is_some_and(|host| self.settings.allow_http.iter().any(|h| h == host))
+    }
+}
+
+/// A running instance of the guest.
+struct Guest {
+    store: Store<HostState>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    send_text: SendTextFn,
+}
+
+/// The loaded module, re-instantiated after a trap.
+struct Sandbox {
+    engine: Engine,
+    module: Module,
+    linker: Linker<HostState>,
+    settings: Arc<Settings>,
+    runtime: tokio::runtime::Handle,
+    http: reqwest::Client,
+    guest: Option<Guest>,
+}
+
+fn wasm_error(e: impl std::fmt::Display) -> wasmi::Error {
+    wasmi::Error::new(e.to_string())
+}
+
+fn guest_bytes(
+    caller: &Caller<'_, HostState>,
+    ptr: i32,
+    len: i32,
+) -> Result<Vec<u8>, wasmi::Error> {
+    let memory = caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasm_error("guest exports no memory"))?;
+    let len = usize::try_from(len).map_err(wasm_error)?;
+    let mut buf = vec![0; len];
+    memory
+        .read(caller, ptr as u32 as usize, &mut buf)
+        .map_err(wasm_error)?;
+    Ok(buf)
+}
+
+fn guest_str(caller: &Caller<'_, HostState>, ptr: i32, len: i32) -> Result<String, wasmi::Error> {
+    String::from_utf8(guest_bytes(caller, ptr, len)?).map_err(wasm_error)
+}
+
+fn linker(engine: &Engine) -> Result<Linker<HostState>, wasmi::Error> {
+    let mut linker = Linker::new(engine);
+    linker.func_wrap(
+        "voxmux",
+        "log",
+        |caller: Caller<'_, HostState>, level: i32, ptr: i32, len: i32| {
+            let msg = guest_str(&caller, ptr, len)?;
+            match level {
+                0 => tracing::error!(target: "voxmux::wasm", "{}", msg),
+                1 => tracing::warn!(target: "voxmux::wasm", "{}", msg),
+                2 => tracing::info!(target: "voxmux::wasm", "{}", msg),
+                _ => tracing::debug!(target: "voxmux::wasm", "{}", msg),
+            }
+            Ok(())
+        },
+    )?;
+    linker.func_wrap(
+        "voxmux",
+        "config",
+        |mut caller: Caller<'_, HostState>,
+         key_ptr: i32,
+         key_len: i32,
+         out_ptr: i32,
+         out_cap: i32|
+         -> Result<i32, wasmi::Error> {
+            let key = guest_str(&caller, key_ptr, key_len)?;
+            let value = match caller.data().settings.guest_config.get(&key) {
+                None => return Ok(-1),
+                Some(toml::Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+            };
+            let n = value.len().min(out_cap.max(0) as usize);
+            let memory = caller
+                .get_export("memory")
+                .and_then(Extern::into_memory)
+                .ok_or_else(|| wasm_error("guest exports no memory"))?;
+            memory
+                .write(&mut caller, out_ptr as u32 as usize, &value.as_bytes()[..n])
+                .map_err(wasm_error)?;
+            i32::try_from(value.len()).map_err(wasm_error)
+        },
+    )?;
+    linker.func_wrap(
+        "voxmux",
+        "fail",
+        |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+            let msg = guest_str(&caller, ptr, len)?;
+            caller.data_mut().error = Some(msg);
+            Ok(())
+        },
+    )?;
+    linker.func_wrap(
+        "voxmux",
+        "http_post",
+        |caller: Caller<'_, HostState>,
+         url_ptr: i32,
+         url_len: i32,
+         body_ptr: i32,
+         body_len: i32|
+         -> Result<i32, wasmi::Error> {
+            let url = guest_str(&caller, url_ptr, url_len)?;
+            let body = guest_bytes(&caller, body_ptr, body_len)?;
+            let state = caller.data();
+            if !state.http_allowed(&url) {
+                tracing::warn!(url = %url, "wasm destination: HTTP to this host is not allowed");
+                return Ok(HTTP_DENIED);
+            }
+            let request = state
+                .http
+                .post(&url)
+                .timeout(state.settings.http_timeout)
+                .header("content-type", "text/plain; charset=utf-8")
+                .body(body)
+                .send();
+            Ok(match state.runtime.block_on(request) {
+                Ok(response) => i32::from(response.status().as_u16()),
+                Err(e) => {
+                    tracing::warn!(url = %url, error = %e, "wasm destination: HTTP request failed");
+                    HTTP_FAILED
+                }
+            })
+        },
+    )?;
+    Ok(linker)
+}
+
+impl Sandbox {
+    fn instantiate(&self) -> Result<Guest, String> {
+        let state = HostState {
+            settings: self.settings.clone(),
+            limits: StoreLimitsBuilder::new()
+                .memory_size(self.settings.max_memory_bytes)
+                .build(),
+            http: self.http.clone(),
+            runtime: self.runtime.clone(),
+            error: None,
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store
+            .set_fuel(self.settings.fuel)
+            .map_err(|e| e.to_string())?;
+
+        let instance = self
+            .linker
+            .instantiate(&mut store, &self.module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| e.to_string())?;
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or("module does not export 'memory'")?;
+        let alloc = instance
+            .get_typed_func(&store, "alloc")
+            .map_err(|e| format!("export 'alloc': {e}"))?;
+        let send_text = instance
+            .get_typed_func(&store, "send_text")
+            .map_err(|e| format!("export 'send_text': {e}"))?;
+        let mut guest = Guest {
+            store,
+            memory,
+            alloc,
+            send_text,
+        };
+        if let Ok(init) = instance.get_typed_func::<(), i32>(&guest.store, "init") {
+            guest
+                .call(|store| init.call(store, ()))
+                .map_err(CallError::into_message)?;
+        }
+        Ok(guest)
+    }
+
+    /// Run `send_text` on the guest, starting a fresh instance after a trap
+    /// so a guest left in a broken state cannot fail every later call.
+    fn send_text(&mut self, text: &str, input_id: &str, prefix: &str) -> Result<(), String> {
+        let mut guest = match self.guest.take() {
+            Some(guest) => guest,
+            None => self.instantiate()?,
+        };
+        let result = guest.send_text(text, input_id, prefix);
+        match &result {
+            Err(CallError::Trap(_)) => {}
+            _ => self.guest = Some(guest),
+        }
+        result.map_err(CallError::into_message)
+    }
+}
+
+enum CallError {
+    /// The guest trapped or ran out of fuel.
+    Trap(String),
+    /// The guest reported an error.
+    Failed(String),
+}
+
+impl CallError {
+    fn into_message(self) -> String {
+        match self {
+            Self::Trap(msg) | Self::Failed(msg) => msg,
+        }
+    }
+}
+
+impl Guest {
+    /// Call into the guest with a fresh fuel budget.
+    fn call(
+        &mut self,
+        f: impl FnOnce(&mut Store<HostState>) -> Result<i32, wasmi::Error>,
+    ) -> Result<(), CallError> {
+        let fuel = self.store.data().settings.fuel;
+        self.store
+            .set_fuel(fuel)
+            .map_err(|e| CallError::Trap(e.to_string()))?;
+        self.store.data_mut().error = None;
+        match f(&mut self.store) {
+            Ok(0) => Ok(()),
+            Ok(code) => Err(CallError::Failed(
+                self.store
+                    .data_mut()
+                    .error
+                    .take()
+                    .unwrap_or_else(|| format!("guest returned {code}")),
+            )),
+            Err(e) if e.as_trap_code() == Some(wasmi::core::TrapCode::OutOfFuel) => Err(
+                CallError::Trap(format!("guest ran out of fuel ({fuel} units)")),
+            ),
+            Err(e) => Err(CallError::Trap(format!("guest trapped: {e}"))),
+        }
+    }
+
+    /// Copy `s` into guest memory.
+    fn write_str(&mut self, s: &str) -> Result<(i32, i32), CallError> {
+        let len = i32::try_from(s.len()).map_err(|e| CallError::Failed(e.to_string()))?;
+        let alloc = self.alloc;
+        let ptr = alloc
+            .call(&mut self.store, len)
+            .map_err(|e| CallError::Trap(format!("guest 'alloc' trapped: {e}")))?;
+        self.memory
+            .write(&mut self.store, ptr as u32 as usize, s.as_bytes())
+            .map_err(|e| CallError::Trap(format!("guest 'alloc' returned bad memory: {e}")))?;
+        Ok((ptr, len))
+    }
+
+    fn send_text(&mut self, text: &str, input_id: &str, prefix: &str) -> Result<(), CallError> {
+        // Copying the arguments in gets its own budget
+        let fuel = self.store.data().settings.fuel;
+        self.store
+            .set_fuel(fuel)
+            .map_err(|e| CallError::Trap(e.to_string()))?;
+        let (text_ptr, text_len) = self.write_str(text)?;
+        let (input_ptr, input_len) = self.write_str(input_id)?;
+        let (prefix_ptr, prefix_len) = self.write_str(prefix)?;
+        let send_text = self.send_text;
+        self.call(|store| {
+            send_text.call(
+                store,
+                (
+                    text_ptr, text_len, input_ptr, input_len, prefix_ptr, prefix_len,
+                ),
+            )
+        })
+    }
+}
+
+fn parse_settings(config: &toml::Value) -> Result<(String, Settings), DestinationError> {
+    let invalid = |msg: &str| DestinationError::InitializationFailed(msg.to_string());
+    let module = config
+        .get("module")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| invalid("missing 'module' in wasm config"))?;
+    let positive = |key: &str, default: u64| match config.get(key) {
+        None => Ok(default),
+        Some(v) => v
+            .as_integer()
+            .filter(|&n| n > 0)
+            .map(|n| n as u64)
+            .ok_or_else(|| invalid(&format!("wasm '{key}' must be a positive integer"))),
+    };
+    let allow_http = match config.get("allow_http") {
+        None => Vec::new(),
+        Some(toml::Value::Array(hosts)) => hosts
+            .iter()
+            .map(|h| {
+                h.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| invalid("wasm 'allow_http' must be a list of host names"))
+            })
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err(invalid("wasm 'allow_http' must be a list of host names")),
+    };
+    let guest_config = match config.get("config") {
+        None => toml::Table::new(),
+        Some(toml::Value::Table(t)) => t.clone(),
+        Some(_) => return Err(invalid("wasm 'config' must be a table")),
+    };
+    Ok((
+        module.to_string(),
+        Settings {
+            fuel: positive("fuel", DEFAULT_FUEL)?,
+            max_memory_bytes: positive("max_memory_bytes", DEFAULT_MAX_MEMORY_BYTES)? as usize,
+            allow_http,
+            http_timeout: Duration::from_millis(positive(
+                "http_timeout_ms",
+                DEFAULT_HTTP_TIMEOUT_MS,
+            )?),
+            guest_config,
+        },
+    ))
+}
+
+// ── WasmDestination ────────────────────────────────────────────
+
+/// Forwards text to a WebAssembly guest running in a sandbox.
+///
+/// Config: `module` (a `.wasm` or `.wat` file), optional `fuel` per call,
+/// `max_memory_bytes`, `allow_http` (host names the guest may POST to),
+/// `http_timeout_ms` and a `config` table of guest settings.
+pub struct WasmDestination {
+    sandbox: Arc<Mutex<Option<Sandbox>>>,
+}
+
+impl WasmDestination {
+    pub fn new() -> Self {
+        Self {
+            sandbox: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl Default for WasmDestination {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Destination for WasmDestination {
+    fn name(&self) -> &str {
+        "wasm"
+    }
+
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), DestinationError> {
+        let (module_path, settings) = parse_settings(&config)?;
+        let runtime = tokio::runtime::Handle::current();
+        let sandbox = tokio::task::spawn_blocking(move || -> Result<Sandbox, String> {
+            let wasm = wat::parse_file(Path::new(&module_path))
+                .map_err(|e| format!("{module_path}: {e}"))?;
+            let mut engine_config = Config::default();
+            engine_config.consume_fuel(true);
+            let engine = Engine::new(&engine_config);
+            let module = Module::new(&engine, &wasm).map_err(|e| format!("{module_path}: {e}"))?;
+            let mut sandbox = Sandbox {
+                linker: linker(&engine).map_err(|e| e.to_string())?,
+                engine,
+                module,
+                settings: Arc::new(settings),
+                runtime,
+                http: reqwest::Client::new(),
+                guest: None,
+            };
+            sandbox.guest = Some(
+                sandbox
+                    .instantiate()
+                    .map_err(|e| format!("{module_path}: {e}"))?,
+            );
+            tracing::info!(module = %module_path, "WasmDestination initialized");
+            Ok(sandbox)
+        })
+        .await
+        .map_err(|e| DestinationError::InitializationFailed(e.to_string()))?
+        .map_err(DestinationError::InitializationFailed)?;
+        *self.sandbox.lock().unwrap() = Some(sandbox);
+        Ok(())
+    }
+
+    async fn send_text(&self, text: &str, metadata: &TextMetadata) -> Result<(), DestinationError> {
+        let sandbox = self.sandbox.clone();
+        let text = text.to_string();
+        let metadata = metadata.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut guard = sandbox.lock().unwrap();
+            let sandbox = guard
+                .as_mut()
+                .ok_or_else(|| "not initialized".to_string())?;
+            sandbox.send_text(&text, &metadata.input_id, &metadata.prefix)
+        })
+        .await
+        .map_err(|e| DestinationError::SendFailed(e.to_string()))?
+        .map_err(DestinationError::SendFailed)
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.sandbox.lock().unwrap().is_some()
+    }
+
+    async fn shutdown(&self) -> Result<(), DestinationError> {
+        self.sandbox.lock().unwrap().take();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WEBHOOK: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/webhook.wat");
+
+    /// Spins forever on texts longer than three bytes.
+    const SPIN: &str = r#"(module
+      (memory (export "memory") 1)
+      (func (export "alloc") (param i32) (result i32) (i32.const 0))
+      (func (export "send_text") (param i32 i32 i32 i32 i32 i32) (result i32)
+        (if (i32.gt_u (local.get 1) (i32.const 3)) (then (loop $spin (br $spin))))
+        (i32.const 0)))"#;
+
+    fn write_module(name: &str, wat: &str) -> String {
+        let dir = std::env::temp_dir().join("voxmux_wasm_dest_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, wat).unwrap();
+        path.display().to_string()
+    }
+
+    fn config(module: &str, extra: &[(&str, toml::Value)]) -> toml::Value {
+        let mut table = toml::Table::new();
+        table.insert("module".into(), module.into());
+        for (k, v) in extra {
+            table.insert(k.to_string(), v.clone());
+        }
+        toml::Value::Table(table)
+    }
+
+    fn metadata() -> TextMetadata {
+        TextMetadata {
+            input_id: "mic1".to_string(),
+            prefix: "[A] ".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wasm_guest_errors_are_reported() {
+        let mut dest = WasmDestination::new();
+        assert!(!dest.is_healthy());
+        dest.initialize(config(WEBHOOK, &[])).await.unwrap();
+        assert!(dest.is_healthy());
+        match dest.send_text("hello", &metadata()).await {
+            Err(DestinationError::SendFailed(msg)) => assert_eq!(msg, "missing url"),
+            other => panic!("expected SendFailed, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wasm_http_requires_allowed_host() {
+        let mut guest_config = toml::Table::new();
+        guest_config.insert("url".into(), "http://127.0.0.1:9/hook".into());
+        let mut dest = WasmDestination::new();
+        dest.initialize(config(WEBHOOK, &[("config", guest_config.into())]))
+            .await
+            .unwrap();
+        // Denied before any connection is attempted
+        match dest.send_text("hello", &metadata()).await {
+            Err(DestinationError::SendFailed(msg)) => assert_eq!(msg, "http_post failed"),
+            other => panic!("expected SendFailed, got {other:?}"),
+        }
+    }
+
+    /// Answers one request per status on a local port, returning the port and
+    /// a receiver for each raw request it read.
+    fn mock_server(statuses: &'static [u16]) -> (u16, std::sync::mpsc::Receiver<String>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for &status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Mock\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                )
+                .unwrap();
+                tx.send(request).unwrap();
+            }
+        });
+        (port, rx)
+    }
+
+    #[tokio::test]
+    async fn test_wasm_http_post_to_allowed_host() {
+        let (port, requests) = mock_server(&[201, 503]);
+        let mut guest_config = toml::Table::new();
+        guest_config.insert("url".into(), format!("http://127.0.0.1:{port}/hook").into());
+        let allow = toml::Value::Array(vec!["127.0.0.1".into()]);
+        let mut dest = WasmDestination::new();
+        dest.initialize(config(
+            WEBHOOK,
+            &[("allow_http", allow), ("config", guest_config.into())],
+        ))
+        .await
+        .unwrap();
+
+        // A 2xx status is a successful send
+        dest.send_text("hello", &metadata()).await.unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /hook HTTP/1.1\r\n"), "{request}");
+        assert!(request.contains("content-type: text/plain; charset=utf-8\r\n"));
+        assert!(request.ends_with("\r\n\r\n[A] hello"), "{request}");
+
+        // Any other status reaches the guest, which reports the failure
+        match dest.send_text("again", &metadata()).await {
+            Err(DestinationError::SendFailed(msg)) => assert_eq!(msg, "http_post failed"),
+            other => panic!("expected SendFailed, got {other:?}"),
+        }
+        assert!(requests.recv().unwrap().ends_with("[A] again"));
+    }
+
+    #[tokio::test]
+    async fn test_wasm_fuel_limit_and_recovery() {
+        let module = write_module("spin.wat", SPIN);
+        let mut dest = WasmDestination::new();
+        dest.initialize(config(&module, &[("fuel", 100_000.into())]))
+            .await
+            .unwrap();
+        match dest.send_text("spin!", &metadata()).await {
+            Err(DestinationError::SendFailed(msg)) => assert!(msg.contains("out of fuel")),
+            other => panic!("expected SendFailed, got {other:?}"),
+        }
+        // A fresh instance serves the next call
+        dest.send_text("ok", &metadata()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wasm_memory_limit() {
+        let module = write_module(
+            "big.wat",
+            r#"(module (memory (export "memory") 64)
+              (func (export "alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "send_text") (param i32 i32 i32 i32 i32 i32) (result i32) (i32.const 0)))"#,
+        );
+        let mut dest = WasmDestination::new();
+        let limited = config(&module, &[("max_memory_bytes", (1024 * 1024).into())]);
+        assert!(matches!(
+            dest.initialize(limited).await,
+            Err(DestinationError::InitializationFailed(_))
+        ));
+        // 64 pages are 4 MiB, within the default limit
+        dest.initialize(config(&module, &[])).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wasm_config_validation() {
+        let mut dest = WasmDestination::new();
+        match dest
+            .initialize(toml::Value::Table(Default::default()))
+            .await
+        {
+            Err(DestinationError::InitializationFailed(msg)) => assert!(msg.contains("module")),
+            _ => panic!("expected InitializationFailed"),
+        }
+        for bad in [
+            config(WEBHOOK, &[("fuel", 0.into())]),
+            config(WEBHOOK, &[("allow_http", "localhost".into())]),
+            config(WEBHOOK, &[("config", "url".into())]),
+            config("/nonexistent/guest.wasm", &[]),
+        ] {
+            assert!(dest.initialize(bad).await.is_err());
+        }
+
+        let no_export = write_module("no_export.wat", r#"(module (memory (export "memory") 1))"#);
+        match dest.initialize(config(&no_export, &[])).await {
+            Err(DestinationError::InitializationFailed(msg)) => assert!(msg.contains("alloc")),
+            _ => panic!("expected InitializationFailed"),
+        }
+    }
+
+    #[test]
+    fn test_wasm_registered() {
+        let registry = crate::DestinationRegistry::new();
+        assert_eq!(registry.create("wasm").unwrap().name(), "wasm");
+    }
+}
@@ -0,0 +1,58 @@
+;; Example voxmux `wasm` destination: POSTs "<prefix><text>" to the `url`
+;; guest setting and logs what it sent. See README, "WASM destinations".
+(module
+  (import "voxmux" "log" (func $log (param i32 i32 i32)))
+  (import "voxmux" "config" (func $config (param i32 i32 i32 i32) (result i32)))
+  (import "voxmux" "fail" (func $fail (param i32 i32)))
+  (import "voxmux" "http_post" (func $http_post (param i32 i32 i32 i32) (result i32)))
+
+  ;; 0..64 constants, 256..1280 the url, 4096.. a bump heap reset per call
+  (memory (export "memory") 1)
+  (data (i32.const 0) "url")
+  (data (i32.const 16) "missing url")
+  (data (i32.const 32) "http_post failed")
+  (global $heap (mut i32) (i32.const 4096))
+
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $heap))
+    (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+    (local.get $ptr))
+
+  (func (export "send_text")
+    (param $text i32) (param $text_len i32)
+    (param $input i32) (param $input_len i32)
+    (param $prefix i32) (param $prefix_len i32)
+    (result i32)
+    (local $url_len i32) (local $body i32) (local $body_len i32) (local $status i32)
+
+    (local.set $url_len
+      (call $config (i32.const 0) (i32.const 3) (i32.const 256) (i32.const 1024)))
+    (if (i32.lt_s (local.get $url_len) (i32.const 0))
+      (then
+        (global.set $heap (i32.const 4096))
+        (call $fail (i32.const 16) (i32.const 11))
+        (return (i32.const 1))))
+
+    ;; body = prefix ++ text, placed after the arguments
+    (local.set $body (global.get $heap))
+    (local.set $body_len (i32.add (local.get $prefix_len) (local.get $text_len)))
+    (memory.copy (local.get $body) (local.get $prefix) (local.get $prefix_len))
+    (memory.copy
+      (i32.add (local.get $body) (local.get $prefix_len))
+      (local.get $text)
+      (local.get $text_len))
+    (call $log (i32.const 2) (local.get $body) (local.get $body_len))
+
+    (local.set $status
+      (call $http_post
+        (i32.const 256) (local.get $url_len)
+        (local.get $body) (local.get $body_len)))
+    (global.set $heap (i32.const 4096))
+    (if (i32.or
+          (i32.lt_s (local.get $status) (i32.const 200))
+          (i32.ge_s (local.get $status) (i32.const 300)))
+      (then
+        (call $fail (i32.const 32) (i32.const 16))
+        (return (i32.const 1))))
+    (i32.const 0)))
@@ -1,6 +1,8 @@
 use tokio::sync::mpsc;
 use voxmux_core::RecognitionResult;
-use voxmux_destination::DestinationHost;
+use voxmux_destination::{DestinationHost, DestinationRegistry};
+use voxmux_plugin::host::Plugin;
+use voxmux_plugin::test_support;
 
 fn make_result(input_id: &str, text: &str, is_final: bool) -> RecognitionResult {
     RecognitionResult {
@@ -8,6 +10,7 @@ fn make_result(input_id: &str, text: &str, is_final: bool) -> RecognitionResult
         input_id: input_id.to_string(),
         timestamp: 0.0,
         is_final,
+        ..Default::default()
     }
 }
 
@@ -141,3 +144,120 @@ async fn test_full_pipeline_shutdown_after_sender_drop() {
 
     std::fs::remove_dir_all(&dir).unwrap();
 }
+
+// ── Plugins ────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_full_pipeline_plugin_destination() {
+    let library = test_support::build_example_plugin(env!("CARGO_TARGET_TMPDIR"));
+    let plugin = Plugin::load(&library).unwrap();
+    let mut registry = DestinationRegistry::new();
+    registry.register_plugin(&plugin);
+    assert!(registry.list_destinations().contains(&"example-log"));
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut host = DestinationHost::new(rx);
+    host.register_plugin(&plugin);
+
+    let dir = std::env::temp_dir().join("voxmux_integ_plugin");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("out.txt");
+    let _ = std::fs::remove_file(&path);
+
+    host.add_route("mic1", "example-log", "[M1] ", file_config(&path.to_string_lossy()))
+        .await
+        .unwrap();
+    // Plugin errors surface as initialization failures
+    assert!(host
+        .add_route("mic1", "example-log", "", toml::Value::Table(Default::default()))
+        .await
+        .is_err());
+    host.start();
+
+    tx.send(make_result("mic1", "hello plugin", true)).unwrap();
+    drop(tx);
+
+    tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+        .await
+        .expect("shutdown timed out");
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(contents, "mic1\t[M1] hello plugin\n");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── WASM ───────────────────────────────────────────────────────
+
+/// Accept one HTTP request, answer 200 and return its body.
+#[cfg(feature = "wasm")]
+async fn serve_one_request(listener: tokio::net::TcpListener) -> String {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    let (mut socket, _) = listener.accept().await.unwrap();
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let length = head
+                .lines()
+                .find_map(|l| {
+                    l.to_ascii_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|v| v.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+            if body.len() >= length {
+                socket
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                    .await
+                    .unwrap();
+                return body.to_string();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[tokio::test]
+async fn test_full_pipeline_wasm_destination() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!(
+        "http://127.0.0.1:{}/hook",
+        listener.local_addr().unwrap().port()
+    );
+    let server = tokio::spawn(serve_one_request(listener));
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut host = DestinationHost::new(rx);
+    let mut guest_config = toml::Table::new();
+    guest_config.insert("url".into(), url.into());
+    let mut config = toml::Table::new();
+    config.insert(
+        "module".into(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/webhook.wat").into(),
+    );
+    config.insert(
+        "allow_http".into(),
+        toml::Value::Array(vec!["127.0.0.1".into()]),
+    );
+    config.insert("config".into(), guest_config.into());
+    host.add_route("mic1", "wasm", "[M1] ", toml::Value::Table(config))
+        .await
+        .unwrap();
+    host.start();
+
+    tx.send(make_result("mic1", "hello guest", true)).unwrap();
+    let body = tokio::time::timeout(std::time::Duration::from_secs(5), server)
+        .await
+        .expect("no request received")
+        .unwrap();
+    assert_eq!(body, "[M1] hello guest");
+
+    drop(tx);
+    tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+        .await
+        .expect("shutdown timed out");
+}
@@ -0,0 +1,72 @@
+/// How an engine consumes audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessingMode {
+    /// Incremental decoding. Audio is delivered in chunks of `chunk_ms`
+    /// (0 = as captured).
+    Streaming { chunk_ms: u32 },
+    /// Whole-segment decoding. Audio is buffered and delivered once per
+    /// utterance, or every `max_segment_ms` without VAD.
+    Batch,
+}
+
+/// What an engine wants from its input, used by `AsrHost` to resample,
+/// downmix and buffer audio per input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EngineCapabilities {
+    /// Preferred sample rate; `None` accepts audio as captured.
+    pub sample_rate: Option<u32>,
+    /// Preferred channel count; `None` accepts audio as captured.
+    pub channels: Option<u16>,
+    pub mode: ProcessingMode,
+    /// Whether the engine emits non-final results.
+    pub partial_results: bool,
+    /// Longest segment the engine accepts in one piece.
+    pub max_segment_ms: Option<u32>,
+    /// Supported language codes; empty means any.
+    pub languages: Vec<String>,
+}
+
+impl EngineCapabilities {
+    /// Whether `language` is supported.
+    pub fn supports_language(&self, language: &str) -> bool {
+        self.languages.is_empty() || self.languages.iter().any(|l| l == language)
+    }
+}
+
+impl Default for EngineCapabilities {
+    /// Accepts anything, streamed as captured.
+    fn default() -> Self {
+        Self {
+            sample_rate: None,
+            channels: None,
+            mode: ProcessingMode::Streaming { chunk_ms: 0 },
+            partial_results: false,
+            max_segment_ms: None,
+            languages: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_default_is_passthrough() {
+        let caps = EngineCapabilities::default();
+        assert_eq!(caps.sample_rate, None);
+        assert_eq!(caps.channels, None);
+        assert_eq!(caps.mode, ProcessingMode::Streaming { chunk_ms: 0 });
+        assert!(caps.supports_language("ja"));
+    }
+
+    #[test]
+    fn test_capabilities_language_list() {
+        let caps = EngineCapabilities {
+            languages: vec!["en".into(), "ja".into()],
+            ..Default::default()
+        };
+        assert!(caps.supports_language("ja"));
+        assert!(!caps.supports_language("de"));
+    }
+}
@@ -0,0 +1,280 @@
+use crate::capabilities::{EngineCapabilities, ProcessingMode};
+use voxmux_core::AudioChunk;
+
+/// Segment length used for batch engines without `max_segment_ms`.
+const DEFAULT_BATCH_SEGMENT_MS: u32 = 30_000;
+
+/// Streaming linear-interpolation resampler for mono audio.
+struct Resampler {
+    /// Input samples per output sample.
+    step: f64,
+    /// Position of the next output sample, relative to the start of the
+    /// next input block; -1.0 refers to `last`.
+    pos: f64,
+    last: f32,
+}
+
+impl Resampler {
+    fn new(from_rate: u32, to_rate: u32) -> Self {
+        Self {
+            step: from_rate as f64 / to_rate as f64,
+            pos: 0.0,
+            last: 0.0,
+        }
+    }
+
+    fn process(&mut self, input: &[f32], out: &mut Vec<f32>) {
+        if input.is_empty() {
+            return;
+        }
+        let n = input.len() as f64;
+        let at = |i: isize| if i < 0 { self.last } else { input[i as usize] };
+        while self.pos <= n - 1.0 {
+            let idx = self.pos.floor();
+            let frac = (self.pos - idx) as f32;
+            let a = at(idx as isize);
+            // At exactly n - 1, frac is 0 and the next sample is irrelevant
+            let b = at((idx as isize + 1).min(input.len() as isize - 1));
+            out.push(a + (b - a) * frac);
+            self.pos += self.step;
+        }
+        self.pos -= n;
+        self.last = input[input.len() - 1];
+    }
+}
+
+/// Adapts captured audio to an engine's [`EngineCapabilities`]: downmix,
+/// resample, then re-chunk (streaming) or accumulate (batch).
+pub struct AudioConditioner {
+    caps: EngineCapabilities,
+    /// Source format, learned from the first chunk.
+    source: Option<(u32, u16)>,
+    resamplers: Vec<Resampler>,
+    /// Converted, interleaved audio not yet delivered.
+    pending: Vec<f32>,
+}
+
+impl AudioConditioner {
+    pub fn new(caps: &EngineCapabilities) -> Self {
+        Self {
+            caps: caps.clone(),
+            source: None,
+            resamplers: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn out_rate(&self) -> u32 {
+        let (rate, _) = self.source.unwrap_or_default();
+        self.caps.sample_rate.unwrap_or(rate)
+    }
+
+    fn out_channels(&self) -> u16 {
+        let (_, channels) = self.source.unwrap_or((0, 1));
+        self.caps.channels.unwrap_or(channels).max(1)
+    }
+
+    /// Frames per delivered chunk, or `None` to deliver everything at once.
+    fn block_frames(&self) -> Option<usize> {
+        let ms = match self.caps.mode {
+            ProcessingMode::Streaming { chunk_ms: 0 } => return None,
+            ProcessingMode::Streaming { chunk_ms } => chunk_ms,
+            ProcessingMode::Batch => self.caps.max_segment_ms.unwrap_or(DEFAULT_BATCH_SEGMENT_MS),
+        };
+        Some(((self.out_rate() as u64 * ms as u64 / 1000) as usize).max(1))
+    }
+
+    /// Convert a captured chunk. Returns the chunks ready for the engine.
+    pub fn process(&mut self, chunk: AudioChunk) -> Vec<AudioChunk> {
+        let source = (chunk.sample_rate, chunk.channels.max(1));
+        if self.source != Some(source) {
+            if !self.pending.is_empty() {
+                tracing::warn!("input format changed mid-stream; dropping buffered audio");
+            }
+            self.source = Some(source);
+            self.pending.clear();
+            let out_channels = self.out_channels() as usize;
+            self.resamplers = (0..out_channels)
+                .map(|_| Resampler::new(source.0, self.out_rate()))
+                .collect();
+        }
+        self.convert(&chunk.samples);
+
+        let channels = self.out_channels() as usize;
+        let mut ready = Vec::new();
+        match self.block_frames() {
+            None => ready.extend(self.take(self.pending.len())),
+            Some(frames) => {
+                while self.pending.len() >= frames * channels {
+                    ready.extend(self.take(frames * channels));
+                }
+            }
+        }
+        ready
+    }
+
+    /// Deliver whatever is buffered (utterance end or end of stream).
+    pub fn finish(&mut self) -> Option<AudioChunk> {
+        self.take(self.pending.len())
+    }
+
+    fn convert(&mut self, samples: &[f32]) {
+        let (rate, in_channels) = self.source.expect("source format set");
+        let in_channels = in_channels as usize;
+        let out_channels = self.out_channels() as usize;
+
+        // Per output channel: pass through matching channels, otherwise downmix
+        let planes: Vec<Vec<f32>> = if out_channels == in_channels {
+            (0..in_channels)
+                .map(|c| samples.iter().skip(c).step_by(in_channels).copied().collect())
+                .collect()
+        } else {
+            let mono: Vec<f32> = samples
+                .chunks(in_channels)
+                .map(|f| f.iter().sum::<f32>() / f.len() as f32)
+                .collect();
+            vec![mono; out_channels]
+        };
+
+        let planes: Vec<Vec<f32>> = if rate == self.out_rate() {
+            planes
+        } else {
+            planes
+                .iter()
+                .zip(self.resamplers.iter_mut())
+                .map(|(plane, resampler)| {
+                    let mut out = Vec::with_capacity(plane.len());
+                    resampler.process(plane, &mut out);
+                    out
+                })
+                .collect()
+        };
+
+        let frames = planes.iter().map(Vec::len).min().unwrap_or(0);
+        self.pending.reserve(frames * out_channels);
+        for i in 0..frames {
+            for plane in &planes {
+                self.pending.push(plane[i]);
+            }
+        }
+    }
+
+    fn take(&mut self, n: usize) -> Option<AudioChunk> {
+        if n == 0 {
+            return None;
+        }
+        let rest = self.pending.split_off(n);
+        let samples = std::mem::replace(&mut self.pending, rest);
+        Some(AudioChunk {
+            samples,
+            sample_rate: self.out_rate(),
+            channels: self.out_channels(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(samples: Vec<f32>, sample_rate: u32, channels: u16) -> AudioChunk {
+        AudioChunk {
+            samples,
+            sample_rate,
+            channels,
+        }
+    }
+
+    fn whisper_caps() -> EngineCapabilities {
+        EngineCapabilities {
+            sample_rate: Some(16000),
+            channels: Some(1),
+            mode: ProcessingMode::Batch,
+            max_segment_ms: Some(1000),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_conditioner_passthrough() {
+        let mut cond = AudioConditioner::new(&EngineCapabilities::default());
+        let out = cond.process(chunk(vec![0.1, 0.2, 0.3, 0.4], 48000, 2));
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].samples, vec![0.1, 0.2, 0.3, 0.4]);
+        assert_eq!((out[0].sample_rate, out[0].channels), (48000, 2));
+        assert!(cond.finish().is_none());
+    }
+
+    #[test]
+    fn test_conditioner_downmixes_and_resamples() {
+        let mut cond = AudioConditioner::new(&EngineCapabilities {
+            sample_rate: Some(16000),
+            channels: Some(1),
+            ..Default::default()
+        });
+        // 48 kHz stereo, 10 ms per chunk, left = 0.5, right = -0.1
+        let stereo: Vec<f32> = (0..480).flat_map(|_| [0.5, -0.1]).collect();
+        let mut total = 0;
+        for _ in 0..10 {
+            for out in cond.process(chunk(stereo.clone(), 48000, 2)) {
+                assert_eq!((out.sample_rate, out.channels), (16000, 1));
+                assert!(out.samples.iter().skip(1).all(|&s| (s - 0.2).abs() < 1e-6));
+                total += out.samples.len();
+            }
+        }
+        // 100 ms at 16 kHz
+        assert_eq!(total, 1600);
+    }
+
+    #[test]
+    fn test_resampler_is_continuous_across_chunks() {
+        let ramp: Vec<f32> = (0..300).map(|i| i as f32).collect();
+        let mut whole = Vec::new();
+        Resampler::new(3, 2).process(&ramp, &mut whole);
+
+        let mut split = Vec::new();
+        let mut resampler = Resampler::new(3, 2);
+        for part in ramp.chunks(7) {
+            resampler.process(part, &mut split);
+        }
+        assert_eq!(whole, split);
+        assert_eq!(whole.len(), 200);
+        assert_eq!(&whole[..4], &[0.0, 1.5, 3.0, 4.5]);
+    }
+
+    #[test]
+    fn test_conditioner_streaming_chunks() {
+        let mut cond = AudioConditioner::new(&EngineCapabilities {
+            sample_rate: Some(8000),
+            mode: ProcessingMode::Streaming { chunk_ms: 20 },
+            ..Default::default()
+        });
+        // 25 ms at 8 kHz → one 20 ms chunk, 5 ms left over
+        let out = cond.process(chunk(vec![0.0; 200], 8000, 1));
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].samples.len(), 160);
+        assert_eq!(cond.finish().unwrap().samples.len(), 40);
+    }
+
+    #[test]
+    fn test_conditioner_batch_accumulates_until_finish() {
+        let mut cond = AudioConditioner::new(&whisper_caps());
+        for _ in 0..5 {
+            // 100 ms at 48 kHz
+            assert!(cond.process(chunk(vec![0.0; 4800], 48000, 1)).is_empty());
+        }
+        let segment = cond.finish().unwrap();
+        assert_eq!(segment.samples.len(), 8000);
+        assert!(cond.finish().is_none());
+    }
+
+    #[test]
+    fn test_conditioner_batch_splits_at_max_segment() {
+        let mut cond = AudioConditioner::new(&whisper_caps());
+        let out = cond.process(chunk(vec![0.0; 48000 * 5 / 2], 48000, 1));
+        // 2.5 s → two 1 s segments, 0.5 s buffered
+        assert_eq!(out.len(), 2);
+        assert!(out.iter().all(|c| c.samples.len() == 16000));
+        assert_eq!(cond.finish().unwrap().samples.len(), 8000);
+    }
+}
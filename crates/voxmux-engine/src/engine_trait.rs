@@ -1,3 +1,4 @@
+use crate::capabilities::EngineCapabilities;
 use voxmux_core::{AsrError, AudioChunk, RecognitionResult};
 use async_trait::async_trait;
 use tokio::sync::mpsc;
@@ -7,14 +8,29 @@ use tokio::sync::mpsc;
 /// Implementations receive audio chunks via [`feed_audio`](Self::feed_audio) and
 /// send [`RecognitionResult`]s through the channel provided by
 /// [`set_result_sender`](Self::set_result_sender).
+///
+/// New engines should implement [`AsrSession`](crate::AsrSession) instead; the
+/// host runs `AsrEngine`s through [`EngineAdapter`](crate::EngineAdapter).
 #[async_trait]
 pub trait AsrEngine: Send + Sync {
     /// Returns the engine's plugin name (e.g. `"null"`, `"whisper"`).
     fn name(&self) -> &str;
+    /// Audio format and buffering this engine wants.
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities::default()
+    }
     /// One-time initialisation with engine-specific TOML configuration.
     async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError>;
     /// Feed a chunk of audio samples to the engine for recognition.
     async fn feed_audio(&self, chunk: AudioChunk) -> Result<(), AsrError>;
+    /// Called when voice activity detection opens an utterance.
+    async fn begin_utterance(&self) -> Result<(), AsrError> {
+        Ok(())
+    }
+    /// Called when the current utterance ends; engines may finalise here.
+    async fn end_utterance(&self) -> Result<(), AsrError> {
+        Ok(())
+    }
     /// Set the channel where recognition results will be sent.
     fn set_result_sender(&mut self, sender: mpsc::UnboundedSender<RecognitionResult>);
     /// Gracefully shut down the engine, releasing resources.
@@ -0,0 +1,482 @@
+use crate::capabilities::{EngineCapabilities, ProcessingMode};
+use crate::session::{AsrSession, ResultStream};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::mpsc;
+use voxmux_core::{AsrError, AudioChunk, RecognitionResult};
+
+/// Version sent in the hello message; bumped on incompatible changes.
+pub const EXEC_PROTOCOL_VERSION: u32 = 1;
+
+/// How long a child gets to exit after `shutdown` before it is killed.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+/// `[asr.exec]` settings. The whole table is also forwarded to the child.
+#[derive(Debug, Clone, Deserialize)]
+struct ExecEngineConfig {
+    command: String,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default = "default_sample_rate")]
+    sample_rate: u32,
+    #[serde(default = "default_channels")]
+    channels: u16,
+    #[serde(default = "default_chunk_ms")]
+    chunk_ms: u32,
+    #[serde(default = "default_restart_delay_ms")]
+    restart_delay_ms: u64,
+    /// Restarts allowed after the child exits unexpectedly.
+    #[serde(default = "default_max_restarts")]
+    max_restarts: u32,
+}
+
+fn default_sample_rate() -> u32 {
+    16000
+}
+
+fn default_channels() -> u16 {
+    1
+}
+
+fn default_chunk_ms() -> u32 {
+    100
+}
+
+fn default_restart_delay_ms() -> u64 {
+    1000
+}
+
+fn default_max_restarts() -> u32 {
+    5
+}
+
+/// A result line on the child's stdout.
+#[derive(Deserialize)]
+struct OutputLine {
+    text: String,
+    #[serde(rename = "final", default = "default_final")]
+    is_final: bool,
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    language: Option<String>,
+}
+
+fn default_final() -> bool {
+    true
+}
+
+struct Running {
+    child: Child,
+    stdin: ChildStdin,
+}
+
+/// Runs recognition in a child process speaking JSON lines: control
+/// messages and framed PCM on stdin, results on stdout. The child is
+/// restarted if it exits unexpectedly.
+pub struct ExecSession {
+    config: Option<ExecEngineConfig>,
+    /// Raw configuration, forwarded in the hello message.
+    raw_config: serde_json::Value,
+    running: Option<Running>,
+    restarts: u32,
+    tx: mpsc::UnboundedSender<RecognitionResult>,
+    rx: Option<ResultStream>,
+}
+
+impl ExecSession {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            config: None,
+            raw_config: serde_json::Value::Null,
+            running: None,
+            restarts: 0,
+            tx,
+            rx: Some(rx),
+        }
+    }
+
+    /// Times the child has been restarted after exiting unexpectedly.
+    pub fn restarts(&self) -> u32 {
+        self.restarts
+    }
+
+    fn config(&self) -> Result<&ExecEngineConfig, AsrError> {
+        self.config
+            .as_ref()
+            .ok_or_else(|| AsrError::ProcessingFailed("exec engine not initialized".to_string()))
+    }
+
+    async fn spawn(&mut self) -> Result<(), AsrError> {
+        let config = self.config()?.clone();
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .envs(&config.env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                AsrError::InitializationFailed(format!("failed to run '{}': {e}", config.command))
+            })?;
+
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<OutputLine>(&line) {
+                    Ok(out) if !out.text.is_empty() => {
+                        let _ = tx.send(RecognitionResult {
+                            text: out.text,
+                            input_id: String::new(),
+                            timestamp: 0.0,
+                            is_final: out.is_final,
+                            confidence: out.confidence,
+                            language: out.language,
+                            ..Default::default()
+                        });
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("exec engine: ignoring invalid output line ({e}): {line}"),
+                }
+            }
+        });
+
+        let stderr = child.stderr.take().expect("stderr is piped");
+        let command = config.command.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                tracing::info!(command = %command, "{line}");
+            }
+        });
+
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let hello = serde_json::json!({
+            "type": "hello",
+            "protocol": EXEC_PROTOCOL_VERSION,
+            "sample_rate": config.sample_rate,
+            "channels": config.channels,
+            "config": self.raw_config,
+        });
+        write_message(&mut stdin, &hello, &[])
+            .await
+            .map_err(|e| AsrError::InitializationFailed(format!("exec engine hello failed: {e}")))?;
+        self.running = Some(Running { child, stdin });
+        Ok(())
+    }
+
+    /// Whether the child is gone, logging how it ended.
+    fn has_exited(&mut self) -> bool {
+        let Some(running) = self.running.as_mut() else {
+            return true;
+        };
+        match running.child.try_wait() {
+            Ok(None) => false,
+            Ok(Some(status)) => {
+                tracing::warn!("exec engine exited with {status}");
+                true
+            }
+            Err(e) => {
+                tracing::warn!("exec engine status unavailable: {e}");
+                true
+            }
+        }
+    }
+
+    /// Start a new child after a crash, if restarts remain.
+    async fn restart(&mut self) -> Result<(), AsrError> {
+        let config = self.config()?;
+        if self.restarts >= config.max_restarts {
+            return Err(AsrError::ProcessingFailed(format!(
+                "exec engine '{}' exited and {} restart(s) are used up",
+                config.command, config.max_restarts
+            )));
+        }
+        let delay = Duration::from_millis(config.restart_delay_ms);
+        self.running = None;
+        self.restarts += 1;
+        tracing::warn!("restarting exec engine (attempt {}) in {delay:?}", self.restarts);
+        tokio::time::sleep(delay).await;
+        self.spawn().await
+    }
+
+    /// Send a message, restarting the child first if it has exited.
+    async fn send(&mut self, header: serde_json::Value, payload: &[u8]) -> Result<(), AsrError> {
+        // A second attempt covers a child that died since the last check
+        for _ in 0..2 {
+            if self.has_exited() {
+                self.restart().await?;
+            }
+            let running = self.running.as_mut().expect("child running");
+            match write_message(&mut running.stdin, &header, payload).await {
+                Ok(()) => return Ok(()),
+                Err(e) => tracing::warn!("exec engine write failed: {e}"),
+            }
+            // Reap the child so the next check sees it has exited
+            let _ = running.child.wait().await;
+        }
+        Err(AsrError::ProcessingFailed(
+            "exec engine is not accepting input".to_string(),
+        ))
+    }
+
+    async fn control(&mut self, kind: &str) -> Result<(), AsrError> {
+        self.send(serde_json::json!({ "type": kind }), &[]).await
+    }
+}
+
+impl Default for ExecSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One JSON line, then the raw payload.
+async fn write_message(
+    stdin: &mut ChildStdin,
+    header: &serde_json::Value,
+    payload: &[u8],
+) -> std::io::Result<()> {
+    let mut message = serde_json::to_vec(header)?;
+    message.push(b'\n');
+    message.extend_from_slice(payload);
+    stdin.write_all(&message).await?;
+    stdin.flush().await
+}
+
+#[async_trait]
+impl AsrSession for ExecSession {
+    fn name(&self) -> &str {
+        "exec"
+    }
+
+    /// The configured format, streamed in `chunk_ms` pieces.
+    fn capabilities(&self) -> EngineCapabilities {
+        let (sample_rate, channels, chunk_ms) = match &self.config {
+            Some(c) => (c.sample_rate, c.channels, c.chunk_ms),
+            None => (default_sample_rate(), default_channels(), default_chunk_ms()),
+        };
+        EngineCapabilities {
+            sample_rate: Some(sample_rate),
+            channels: Some(channels),
+            mode: ProcessingMode::Streaming { chunk_ms },
+            partial_results: true,
+            ..Default::default()
+        }
+    }
+
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError> {
+        self.raw_config = serde_json::to_value(&config)
+            .map_err(|e| AsrError::InitializationFailed(e.to_string()))?;
+        self.config = Some(
+            config
+                .try_into()
+                .map_err(|e| AsrError::InitializationFailed(format!("invalid [asr.exec]: {e}")))?,
+        );
+        self.spawn().await
+    }
+
+    fn take_results(&mut self) -> Option<ResultStream> {
+        self.rx.take()
+    }
+
+    async fn push_audio(&mut self, chunk: AudioChunk) -> Result<(), AsrError> {
+        let payload: Vec<u8> = chunk
+            .samples
+            .iter()
+            .flat_map(|&s| ((s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes())
+            .collect();
+        let header = serde_json::json!({ "type": "audio", "samples": chunk.samples.len() });
+        self.send(header, &payload).await
+    }
+
+    async fn begin_utterance(&mut self) -> Result<(), AsrError> {
+        self.control("begin").await
+    }
+
+    async fn end_utterance(&mut self) -> Result<(), AsrError> {
+        self.control("end").await
+    }
+
+    async fn flush(&mut self) -> Result<(), AsrError> {
+        self.control("flush").await
+    }
+
+    async fn reset(&mut self) -> Result<(), AsrError> {
+        self.control("reset").await
+    }
+
+    async fn shutdown(&mut self) -> Result<(), AsrError> {
+        let Some(mut running) = self.running.take() else {
+            return Ok(());
+        };
+        let _ = write_message(&mut running.stdin, &serde_json::json!({ "type": "shutdown" }), &[])
+            .await;
+        drop(running.stdin);
+        match tokio::time::timeout(SHUTDOWN_GRACE, running.child.wait()).await {
+            Ok(_) => Ok(()),
+            Err(_) => {
+                tracing::warn!("exec engine did not exit, killing it");
+                running
+                    .child
+                    .kill()
+                    .await
+                    .map_err(|e| AsrError::ProcessingFailed(e.to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The fixture engine is a Python script, so these tests need `python3`.
+    fn script_config(extra: &str) -> toml::Value {
+        let python = std::process::Command::new("python3")
+            .arg("--version")
+            .output();
+        assert!(
+            python.is_ok_and(|o| o.status.success()),
+            "the exec engine tests need python3 on PATH"
+        );
+        let script = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/exec_engine.py");
+        toml::from_str(&format!(
+            "command = \"python3\"\nargs = [\"{script}\"]\nrestart_delay_ms = 10\n{extra}"
+        ))
+        .unwrap()
+    }
+
+    fn chunk(len: usize) -> AudioChunk {
+        AudioChunk {
+            samples: vec![0.5; len],
+            sample_rate: 16000,
+            channels: 1,
+        }
+    }
+
+    async fn next(results: &mut ResultStream) -> (String, bool) {
+        let r = tokio::time::timeout(Duration::from_secs(5), results.recv())
+            .await
+            .expect("timed out")
+            .expect("stream closed");
+        (r.text, r.is_final)
+    }
+
+    #[test]
+    fn test_exec_output_line_optional_fields() {
+        let line: OutputLine =
+            serde_json::from_str(r#"{"text": "hi", "confidence": 0.8, "language": "en"}"#).unwrap();
+        assert!(line.is_final);
+        assert_eq!(line.confidence, Some(0.8));
+        assert_eq!(line.language.as_deref(), Some("en"));
+
+        let line: OutputLine = serde_json::from_str(r#"{"text": "h", "final": false}"#).unwrap();
+        assert!(!line.is_final);
+        assert_eq!((line.confidence, line.language), (None, None));
+    }
+
+    #[tokio::test]
+    async fn test_exec_engine_partials_and_finals() {
+        let mut session = ExecSession::new();
+        let mut results = session.take_results().unwrap();
+        session.initialize(script_config("")).await.unwrap();
+
+        session.begin_utterance().await.unwrap();
+        session.push_audio(chunk(1600)).await.unwrap();
+        session.push_audio(chunk(800)).await.unwrap();
+        session.end_utterance().await.unwrap();
+        assert_eq!(next(&mut results).await, ("1600 samples".to_string(), false));
+        assert_eq!(next(&mut results).await, ("2400 samples".to_string(), false));
+        assert_eq!(next(&mut results).await, ("2400 samples".to_string(), true));
+
+        session.push_audio(chunk(160)).await.unwrap();
+        session.flush().await.unwrap();
+        session.shutdown().await.unwrap();
+        drop(session);
+        assert_eq!(next(&mut results).await, ("160 samples".to_string(), false));
+        assert_eq!(next(&mut results).await, ("160 samples".to_string(), true));
+        assert!(results.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exec_engine_restarts_after_crash() {
+        let mut session = ExecSession::new();
+        let mut results = session.take_results().unwrap();
+        session
+            .initialize(script_config("max_restarts = 1\ncrash_after = 2"))
+            .await
+            .unwrap();
+
+        session.push_audio(chunk(100)).await.unwrap();
+        assert_eq!(next(&mut results).await.0, "100 samples");
+        // The second frame kills the child
+        session.push_audio(chunk(100)).await.unwrap();
+        let mut sent = 0;
+        while session.restarts() == 0 && sent < 50 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            session.push_audio(chunk(100)).await.unwrap();
+            sent += 1;
+        }
+        assert_eq!(session.restarts(), 1);
+        // The restarted child starts counting from zero
+        assert_eq!(next(&mut results).await.0, "100 samples");
+
+        // It crashes again and no restarts are left
+        let mut failed = false;
+        for _ in 0..50 {
+            if session.push_audio(chunk(100)).await.is_err() {
+                failed = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(failed, "expected the exhausted engine to fail");
+    }
+
+    #[tokio::test]
+    async fn test_exec_engine_config_errors() {
+        let mut session = ExecSession::new();
+        assert!(matches!(
+            session.initialize(toml::Value::Table(Default::default())).await,
+            Err(AsrError::InitializationFailed(_))
+        ));
+        let missing: toml::Value = toml::from_str("command = \"voxmux-no-such-engine\"").unwrap();
+        match session.initialize(missing).await {
+            Err(AsrError::InitializationFailed(msg)) => {
+                assert!(msg.contains("voxmux-no-such-engine"))
+            }
+            _ => panic!("expected InitializationFailed"),
+        }
+    }
+
+    #[test]
+    fn test_exec_engine_capabilities_follow_config() {
+        let mut session = ExecSession::new();
+        assert_eq!(session.capabilities().sample_rate, Some(16000));
+        session.config = Some(
+            toml::Value::try_into(
+                toml::from_str::<toml::Value>("command = \"x\"\nsample_rate = 8000\nchunk_ms = 20")
+                    .unwrap(),
+            )
+            .unwrap(),
+        );
+        let caps = session.capabilities();
+        assert_eq!(caps.sample_rate, Some(8000));
+        assert_eq!(caps.mode, ProcessingMode::Streaming { chunk_ms: 20 });
+    }
+}
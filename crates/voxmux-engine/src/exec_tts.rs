@@ -0,0 +1,254 @@
+use crate::tts::TtsEngine;
+use async_trait::async_trait;
+use std::io::Cursor;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use voxmux_core::config::ExecTtsConfig;
+use voxmux_core::{AudioChunk, TtsError};
+
+/// Runs a local synthesizer such as `espeak-ng --stdout` or
+/// `piper --model <voice> --output_file -`: text goes to stdin, a WAV file
+/// comes back on stdout.
+pub struct ExecTts {
+    config: ExecTtsConfig,
+}
+
+impl ExecTts {
+    pub fn new() -> Self {
+        Self {
+            config: ExecTtsConfig::default(),
+        }
+    }
+}
+
+impl Default for ExecTts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TtsEngine for ExecTts {
+    fn name(&self) -> &str {
+        "exec"
+    }
+
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), TtsError> {
+        self.config = config
+            .try_into()
+            .map_err(|e| TtsError::InitializationFailed(format!("invalid [tts.exec]: {e}")))?;
+        Ok(())
+    }
+
+    async fn synthesize(&self, text: &str) -> Result<AudioChunk, TtsError> {
+        let mut child = Command::new(&self.config.command)
+            .args(&self.config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                TtsError::SynthesisFailed(format!("failed to run '{}': {e}", self.config.command))
+            })?;
+
+        // Write concurrently with reading so a large reply cannot deadlock the pipe
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let input = format!("{text}\n");
+        let writer = tokio::spawn(async move {
+            // The command may ignore stdin (and close it early); that is not an error
+            let _ = stdin.write_all(input.as_bytes()).await;
+        });
+
+        // Dropping the child on timeout kills it (`kill_on_drop`)
+        let timeout = Duration::from_millis(self.config.timeout_ms);
+        let output = match tokio::time::timeout(timeout, child.wait_with_output()).await {
+            Ok(output) => output.map_err(|e| TtsError::SynthesisFailed(e.to_string()))?,
+            Err(_) => {
+                writer.abort();
+                return Err(TtsError::SynthesisFailed(format!(
+                    "'{}' timed out after {} ms",
+                    self.config.command, self.config.timeout_ms
+                )));
+            }
+        };
+        let _ = writer.await;
+
+        if !output.status.success() {
+            return Err(TtsError::SynthesisFailed(format!(
+                "'{}' exited with {}: {}",
+                self.config.command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        decode_wav(output.stdout)
+    }
+}
+
+/// Decode a WAV file to interleaved f32 samples.
+fn decode_wav(mut bytes: Vec<u8>) -> Result<AudioChunk, TtsError> {
+    fix_streamed_sizes(&mut bytes);
+    let reader = hound::WavReader::new(Cursor::new(bytes))
+        .map_err(|e| TtsError::SynthesisFailed(format!("invalid WAV output: {e}")))?;
+    let spec = reader.spec();
+    let samples: Result<Vec<f32>, _> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.into_samples::<f32>().collect(),
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|s| s.map(|s| s as f32 * scale))
+                .collect()
+        }
+    };
+    Ok(AudioChunk {
+        samples: samples
+            .map_err(|e| TtsError::SynthesisFailed(format!("invalid WAV output: {e}")))?,
+        sample_rate: spec.sample_rate,
+        channels: spec.channels,
+    })
+}
+
+/// WAV written to a pipe cannot be seeked back to fill in its sizes, so
+/// synthesizers leave placeholders there. Rewrite them from the actual length.
+fn fix_streamed_sizes(bytes: &mut [u8]) {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return;
+    }
+    let len = bytes.len();
+    bytes[4..8].copy_from_slice(&((len - 8) as u32).to_le_bytes());
+
+    let mut offset = 12;
+    while offset + 8 <= len {
+        let size_bytes: [u8; 4] = bytes[offset + 4..offset + 8].try_into().unwrap();
+        if &bytes[offset..offset + 4] == b"data" {
+            let actual = (len - offset - 8) as u32;
+            let declared = u32::from_le_bytes(size_bytes);
+            if declared > actual {
+                bytes[offset + 4..offset + 8].copy_from_slice(&actual.to_le_bytes());
+            }
+            return;
+        }
+        let size = u32::from_le_bytes(size_bytes) as usize;
+        offset += 8 + size + (size & 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav_bytes(samples: &[i16], sample_rate: u32) -> Vec<u8> {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut cursor = Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+        for &s in samples {
+            writer.write_sample(s).unwrap();
+        }
+        writer.finalize().unwrap();
+        cursor.into_inner()
+    }
+
+    #[test]
+    fn test_decode_wav_int16() {
+        let chunk = decode_wav(wav_bytes(&[0, 16384, -32768], 22050)).unwrap();
+        assert_eq!(chunk.sample_rate, 22050);
+        assert_eq!(chunk.channels, 1);
+        assert_eq!(chunk.samples, vec![0.0, 0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_decode_wav_streamed_header() {
+        let mut bytes = wav_bytes(&[100; 10], 16000);
+        // As written to a pipe: unknown RIFF and data sizes
+        bytes[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        bytes[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(decode_wav(bytes).unwrap().samples.len(), 10);
+    }
+
+    #[test]
+    fn test_decode_wav_rejects_garbage() {
+        assert!(matches!(
+            decode_wav(b"hello\n".to_vec()),
+            Err(TtsError::SynthesisFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_exec_tts_reads_wav_from_stdout() {
+        let path = std::env::temp_dir().join(format!("voxmux-exec-tts-{}.wav", std::process::id()));
+        std::fs::write(&path, wav_bytes(&[0; 800], 8000)).unwrap();
+
+        let mut tts = ExecTts::new();
+        let mut config = toml::map::Map::new();
+        config.insert("command".into(), "cat".into());
+        config.insert(
+            "args".into(),
+            toml::Value::Array(vec![path.display().to_string().into()]),
+        );
+        tts.initialize(toml::Value::Table(config)).await.unwrap();
+
+        let chunk = tts.synthesize("ignored").await.unwrap();
+        assert_eq!(chunk.sample_rate, 8000);
+        assert_eq!(chunk.samples.len(), 800);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_exec_tts_missing_command() {
+        let mut tts = ExecTts::new();
+        let mut config = toml::map::Map::new();
+        config.insert("command".into(), "voxmux-no-such-tts".into());
+        tts.initialize(toml::Value::Table(config)).await.unwrap();
+        match tts.synthesize("hello").await {
+            Err(TtsError::SynthesisFailed(msg)) => assert!(msg.contains("voxmux-no-such-tts")),
+            other => panic!("expected SynthesisFailed, got {:?}", other.map(|c| c.samples.len())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exec_tts_nonzero_exit() {
+        let mut tts = ExecTts::new();
+        let mut config = toml::map::Map::new();
+        config.insert("command".into(), "false".into());
+        config.insert("args".into(), toml::Value::Array(Vec::new()));
+        tts.initialize(toml::Value::Table(config)).await.unwrap();
+        assert!(tts.synthesize("hello").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_exec_tts_times_out() {
+        let mut tts = ExecTts::new();
+        let mut config = toml::map::Map::new();
+        config.insert("command".into(), "sleep".into());
+        config.insert("args".into(), toml::Value::Array(vec!["10".into()]));
+        config.insert("timeout_ms".into(), 100.into());
+        tts.initialize(toml::Value::Table(config)).await.unwrap();
+
+        let start = std::time::Instant::now();
+        match tts.synthesize("hello").await {
+            Err(TtsError::SynthesisFailed(msg)) => assert!(msg.contains("timed out"), "{msg}"),
+            other => panic!("expected a timeout, got {:?}", other.map(|c| c.samples.len())),
+        }
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_exec_tts_invalid_config() {
+        let mut tts = ExecTts::new();
+        let mut config = toml::map::Map::new();
+        config.insert("args".into(), "not-a-list".into());
+        assert!(matches!(
+            tts.initialize(toml::Value::Table(config)).await,
+            Err(TtsError::InitializationFailed(_))
+        ));
+    }
+}
@@ -1,33 +1,99 @@
-use crate::engine_trait::AsrEngine;
+use crate::capabilities::EngineCapabilities;
+use crate::pool::InferencePool;
 use crate::registry::PluginRegistry;
-use voxmux_core::{AsrError, AudioChunk, RecognitionResult};
+use crate::shared::SharedEngine;
+use crate::supervisor::{EngineMonitor, EngineRecipe, EngineSource, SupervisedEngine};
+use crate::vad::{Vad, VadEvent, VadMonitor, VadRegistry};
+use std::collections::HashMap;
+use std::sync::Arc;
+use voxmux_core::config::{AsrPoolConfig, AsrQueueConfig, AsrSupervisorConfig, VadConfig};
+use voxmux_core::{AsrError, RecognitionResult, TapReceiver, TapSender};
 use tokio::sync::mpsc;
 
 struct PendingInput {
     id: String,
-    engine: Box<dyn AsrEngine>,
-    tap_rx: mpsc::UnboundedReceiver<AudioChunk>,
-    engine_result_rx: mpsc::UnboundedReceiver<RecognitionResult>,
+    engine: SupervisedEngine,
+    tap_rx: TapReceiver,
+    vad: Option<Vad>,
 }
 
 pub struct AsrHost {
     inputs: Vec<PendingInput>,
+    queue: AsrQueueConfig,
+    vad: Option<(VadConfig, VadRegistry)>,
+    vad_monitors: HashMap<String, VadMonitor>,
+    supervisor: AsrSupervisorConfig,
+    engine_monitors: HashMap<String, EngineMonitor>,
+    pool: InferencePool,
+    /// Shared engines keyed by engine name and configuration.
+    shared: HashMap<String, Arc<dyn SharedEngine>>,
     result_tx: mpsc::UnboundedSender<RecognitionResult>,
     result_rx: Option<mpsc::UnboundedReceiver<RecognitionResult>>,
     task_handles: Vec<tokio::task::JoinHandle<()>>,
+    pool_handles: Vec<tokio::task::JoinHandle<()>>,
 }
 
 impl AsrHost {
     pub fn new() -> Self {
+        Self::with_queue_config(AsrQueueConfig::default())
+    }
+
+    /// Create a host whose per-input taps are bounded by `queue`.
+    pub fn with_queue_config(queue: AsrQueueConfig) -> Self {
         let (result_tx, result_rx) = mpsc::unbounded_channel();
         Self {
             inputs: Vec::new(),
+            queue,
+            vad: None,
+            vad_monitors: HashMap::new(),
+            supervisor: AsrSupervisorConfig::default(),
+            engine_monitors: HashMap::new(),
+            pool: InferencePool::new(AsrPoolConfig::default()),
+            shared: HashMap::new(),
             result_tx,
             result_rx: Some(result_rx),
             task_handles: Vec::new(),
+            pool_handles: Vec::new(),
         }
     }
 
+    /// Size the worker pool used by shared engines. Call before adding inputs.
+    pub fn set_pool(&mut self, config: AsrPoolConfig) {
+        self.pool = InferencePool::new(config);
+    }
+
+    /// The shared engines' worker pool, for metrics.
+    pub fn pool(&self) -> InferencePool {
+        self.pool.clone()
+    }
+
+    /// Schedule `input_id`'s segments on a shared engine ahead of inputs with
+    /// a lower priority.
+    pub fn set_priority(&self, input_id: &str, priority: i32) {
+        self.pool.set_priority(input_id, priority);
+    }
+
+    /// Segment each input added after this call with voice activity
+    /// detection; engines then only see speech.
+    pub fn set_vad(&mut self, config: VadConfig, registry: VadRegistry) {
+        self.vad = Some((config, registry));
+    }
+
+    /// VAD state for an input, if VAD is enabled.
+    pub fn vad_monitor(&self, input_id: &str) -> Option<VadMonitor> {
+        self.vad_monitors.get(input_id).cloned()
+    }
+
+    /// Restart policy for engines of inputs added after this call.
+    pub fn set_supervisor(&mut self, config: AsrSupervisorConfig) {
+        self.supervisor = config;
+    }
+
+    /// Engine health for an input.
+    pub fn engine_monitor(&self, input_id: &str) -> Option<EngineMonitor> {
+        self.engine_monitors.get(input_id).cloned()
+    }
+
     pub fn take_result_receiver(&mut self) -> Option<mpsc::UnboundedReceiver<RecognitionResult>> {
         self.result_rx.take()
     }
@@ -38,34 +104,92 @@ impl AsrHost {
         engine_name: &str,
         config: toml::Value,
         registry: &PluginRegistry,
-    ) -> Result<mpsc::UnboundedSender<AudioChunk>, AsrError> {
-        let mut engine = registry.create(engine_name)?;
+    ) -> Result<TapSender, AsrError> {
+        let source = match self.shared_engine(engine_name, &config, registry).await? {
+            Some(shared) => EngineSource::Shared(shared, self.pool.register(id)),
+            None => EngineSource::Registry(Arc::new(registry.clone())),
+        };
+        let recipe = EngineRecipe {
+            engine_name: engine_name.to_string(),
+            config,
+            source,
+        };
+        let vad = match &self.vad {
+            Some((vad_config, vad_registry)) => {
+                let vad = Vad::new(vad_config, vad_registry.create(vad_config)?);
+                self.vad_monitors.insert(id.to_string(), vad.monitor());
+                Some(vad)
+            }
+            None => None,
+        };
 
-        // Create per-engine result channel
-        let (engine_result_tx, engine_result_rx) = mpsc::unbounded_channel();
-        engine.set_result_sender(engine_result_tx);
-        engine.initialize(config).await?;
+        let (engine, engine_result_rx) = recipe.build().await?;
 
-        // Create tap channel for audio input
-        let (tap_tx, tap_rx) = mpsc::unbounded_channel();
+        // Capabilities may depend on the configuration
+        let caps = engine.capabilities();
+        tracing::debug!(
+            input_id = %id,
+            sample_rate = ?caps.sample_rate,
+            channels = ?caps.channels,
+            mode = ?caps.mode,
+            "ASR input conditioning"
+        );
 
+        // Create bounded tap for audio input
+        let (tap_tx, tap_rx) = voxmux_core::asr_tap(self.queue.capacity_ms, self.queue.policy);
+
+        let monitor = EngineMonitor::default();
+        self.engine_monitors.insert(id.to_string(), monitor.clone());
         self.inputs.push(PendingInput {
             id: id.to_string(),
-            engine,
+            engine: SupervisedEngine::new(
+                id,
+                recipe,
+                engine,
+                engine_result_rx,
+                &self.supervisor,
+                monitor,
+            ),
             tap_rx,
-            engine_result_rx,
+            vad,
         });
 
         Ok(tap_tx)
     }
 
+    /// The instance of a shareable engine for `config`, created and
+    /// initialised on first use. `None` if the engine is not shareable.
+    async fn shared_engine(
+        &mut self,
+        engine_name: &str,
+        config: &toml::Value,
+        registry: &PluginRegistry,
+    ) -> Result<Option<Arc<dyn SharedEngine>>, AsrError> {
+        let key = format!("{engine_name}\n{config}");
+        if let Some(engine) = self.shared.get(&key) {
+            return Ok(Some(engine.clone()));
+        }
+        let Some(mut engine) = registry.create_shared(engine_name) else {
+            return Ok(None);
+        };
+        check_language(engine_name, &engine.capabilities(), config)?;
+        engine.initialize(config.clone()).await?;
+        tracing::info!(engine = %engine_name, "shared ASR engine loaded");
+        let engine: Arc<dyn SharedEngine> = Arc::from(engine);
+        self.shared.insert(key, engine.clone());
+        Ok(Some(engine))
+    }
+
     pub fn start(&mut self) {
+        if self.pool.is_used() {
+            self.pool_handles = self.pool.start();
+        }
         let inputs = std::mem::take(&mut self.inputs);
         for input in inputs {
             let input_id = input.id;
-            let engine = input.engine;
+            let mut engine = input.engine;
             let mut tap_rx = input.tap_rx;
-            let mut engine_result_rx = input.engine_result_rx;
+            let mut vad = input.vad;
             let shared_tx = self.result_tx.clone();
 
             let handle = tokio::spawn(async move {
@@ -74,11 +198,19 @@ impl AsrHost {
                         chunk = tap_rx.recv() => {
                             match chunk {
                                 Some(audio) => {
-                                    if let Err(e) = engine.feed_audio(audio).await {
-                                        tracing::error!(
-                                            input_id = %input_id,
-                                            "engine feed error: {e}"
-                                        );
+                                    engine.receive(&audio);
+                                    let events = match vad.as_mut() {
+                                        Some(vad) => vad.process(audio),
+                                        None => vec![VadEvent::Audio(audio)],
+                                    };
+                                    let mut failing = None;
+                                    for event in events {
+                                        failing = engine.dispatch(event).await.or(failing);
+                                    }
+                                    if let Some(reason) = failing {
+                                        if !engine.restart(&mut tap_rx, &reason).await {
+                                            break;
+                                        }
                                     }
                                 }
                                 None => {
@@ -87,20 +219,28 @@ impl AsrHost {
                                         input_id = %input_id,
                                         "tap sender dropped, shutting down"
                                     );
-                                    let _ = engine.shutdown().await;
+                                    if let Some(vad) = vad.as_mut() {
+                                        for event in vad.flush() {
+                                            engine.dispatch(event).await;
+                                        }
+                                    }
+                                    // Deliver whatever the flush produced
+                                    for r in engine.finish().await {
+                                        let _ = shared_tx.send(r);
+                                    }
                                     break;
                                 }
                             }
                         }
-                        result = engine_result_rx.recv() => {
+                        result = engine.next_result() => {
                             match result {
-                                Some(mut r) => {
-                                    r.input_id = input_id.clone();
+                                Some(r) => {
                                     let _ = shared_tx.send(r);
                                 }
                                 None => {
-                                    // Engine result channel closed
-                                    break;
+                                    if !engine.restart(&mut tap_rx, "result stream closed").await {
+                                        break;
+                                    }
                                 }
                             }
                         }
@@ -116,7 +256,27 @@ impl AsrHost {
         for handle in handles {
             let _ = handle.await;
         }
+        // Inputs have drained their segments; let the workers go
+        self.pool.close();
+        for handle in std::mem::take(&mut self.pool_handles) {
+            let _ = handle.await;
+        }
+    }
+}
+
+pub(crate) fn check_language(
+    engine_name: &str,
+    caps: &EngineCapabilities,
+    config: &toml::Value,
+) -> Result<(), AsrError> {
+    if let Some(language) = config.get("language").and_then(|v| v.as_str()) {
+        if !caps.supports_language(language) {
+            return Err(AsrError::InitializationFailed(format!(
+                "engine '{engine_name}' does not support language '{language}'"
+            )));
+        }
     }
+    Ok(())
 }
 
 impl Default for AsrHost {
@@ -128,6 +288,10 @@ impl Default for AsrHost {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine_trait::AsrEngine;
+    use crate::session::{AsrSession, ResultStream};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use voxmux_core::{AudioChunk, EngineState, OverflowPolicy};
 
     fn test_registry() -> PluginRegistry {
         PluginRegistry::new()
@@ -303,4 +467,709 @@ mod tests {
             .expect("closed");
         assert_eq!(result.input_id, "radio1");
     }
+
+    #[tokio::test]
+    async fn test_host_completes_result_details() {
+        let mut host = AsrHost::new();
+        let registry = test_registry();
+        let mut rx = host.take_result_receiver().unwrap();
+        let config: toml::Value = toml::from_str("language = \"en\"").unwrap();
+        let tx = host.add_input("radio1", "null", config, &registry).await.unwrap();
+        host.start();
+
+        let mut results = Vec::new();
+        for _ in 0..2 {
+            tx.send(AudioChunk {
+                samples: vec![0.0; 4800],
+                sample_rate: 48000,
+                channels: 1,
+            })
+            .unwrap();
+            results.push(
+                tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+                    .await
+                    .expect("timed out")
+                    .expect("closed"),
+            );
+        }
+        assert_eq!(results[0].utterance_id, 1);
+        assert_eq!(results[1].utterance_id, 2);
+        assert_eq!((results[1].offset, results[1].timestamp), (0.1, 0.2));
+        assert_eq!(results[0].engine, "null");
+        assert_eq!(results[0].language.as_deref(), Some("en"));
+        assert!(results[1].ended_at.unwrap() > results[1].started_at.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_host_tap_is_bounded_by_queue_config() {
+        let mut host = AsrHost::with_queue_config(AsrQueueConfig {
+            capacity_ms: 100,
+            policy: OverflowPolicy::DropNewest,
+        });
+        let registry = test_registry();
+        let tx = host
+            .add_input("mic1", "null", toml::Value::Table(Default::default()), &registry)
+            .await
+            .unwrap();
+
+        // Not started: nothing drains the tap, so 50 ms chunks pile up
+        for _ in 0..4 {
+            tx.send(AudioChunk {
+                samples: vec![0.0; 2400],
+                sample_rate: 48000,
+                channels: 1,
+            })
+            .unwrap();
+        }
+
+        let state = tx.stats().queue_state();
+        assert_eq!(state.queued_ms, 100);
+        assert_eq!(state.dropped_ms, 100);
+        assert_eq!(state.dropped_chunks, 2);
+    }
+
+    /// Reports utterance boundaries and audio as results, in order.
+    struct SegmentEngine {
+        tx: Option<mpsc::UnboundedSender<RecognitionResult>>,
+    }
+
+    impl SegmentEngine {
+        fn report(&self, text: String) {
+            let _ = self.tx.as_ref().unwrap().send(RecognitionResult {
+                text,
+                input_id: String::new(),
+                timestamp: 0.0,
+                is_final: true,
+                ..Default::default()
+            });
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AsrEngine for SegmentEngine {
+        fn name(&self) -> &str {
+            "segment"
+        }
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn feed_audio(&self, chunk: AudioChunk) -> Result<(), AsrError> {
+            self.report(format!("audio {}", chunk.samples.len()));
+            Ok(())
+        }
+        async fn begin_utterance(&self) -> Result<(), AsrError> {
+            self.report("begin".to_string());
+            Ok(())
+        }
+        async fn end_utterance(&self) -> Result<(), AsrError> {
+            self.report("end".to_string());
+            Ok(())
+        }
+        fn set_result_sender(&mut self, sender: mpsc::UnboundedSender<RecognitionResult>) {
+            self.tx = Some(sender);
+        }
+        async fn shutdown(&self) -> Result<(), AsrError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_host_vad_delivers_utterances_with_boundaries() {
+        let mut registry = test_registry();
+        registry.register("segment", || Box::new(SegmentEngine { tx: None }));
+        let mut host = AsrHost::new();
+        host.set_vad(
+            VadConfig {
+                pre_roll_ms: 0,
+                hangover_ms: 100,
+                min_speech_ms: 20,
+                ..Default::default()
+            },
+            VadRegistry::new(),
+        );
+        let mut rx = host.take_result_receiver().unwrap();
+        let tx = host
+            .add_input("mic1", "segment", toml::Value::Table(Default::default()), &registry)
+            .await
+            .unwrap();
+        let monitor = host.vad_monitor("mic1").expect("monitor");
+        assert!(host.vad_monitor("mic2").is_none());
+        host.start();
+
+        let speech: Vec<f32> = (0..3200).map(|i| (i as f32 * 0.12).sin() * 0.3).collect();
+        for samples in [vec![0.0; 3200], speech, vec![0.0; 3200]] {
+            tx.send(AudioChunk {
+                samples,
+                sample_rate: 16000,
+                channels: 1,
+            })
+            .unwrap();
+        }
+        let mut texts = Vec::new();
+        for _ in 0..4 {
+            let r = tokio::time::timeout(std::time::Duration::from_secs(2), rx.recv())
+                .await
+                .expect("timed out")
+                .expect("closed");
+            texts.push(r.text);
+        }
+        drop(tx);
+        host.shutdown().await;
+        // 200 ms of speech, then the 100 ms hangover in the trailing chunk
+        assert_eq!(texts, vec!["begin", "audio 3200", "audio 1600", "end"]);
+        assert_eq!(monitor.state(), voxmux_core::VadState::Silence);
+    }
+
+    #[tokio::test]
+    async fn test_host_vad_unknown_detector_fails() {
+        let mut host = AsrHost::new();
+        host.set_vad(
+            VadConfig {
+                detector: "nope".to_string(),
+                ..Default::default()
+            },
+            VadRegistry::new(),
+        );
+        let result = host
+            .add_input("mic1", "null", toml::Value::Table(Default::default()), &test_registry())
+            .await;
+        assert!(matches!(result, Err(AsrError::InitializationFailed(_))));
+    }
+
+    /// Native session: buffers audio and only reports on utterance end or flush.
+    struct BufferingSession {
+        tx: mpsc::UnboundedSender<RecognitionResult>,
+        rx: Option<ResultStream>,
+        buffered: usize,
+    }
+
+    impl BufferingSession {
+        fn new() -> Self {
+            let (tx, rx) = mpsc::unbounded_channel();
+            Self {
+                tx,
+                rx: Some(rx),
+                buffered: 0,
+            }
+        }
+
+        fn emit(&mut self, what: &str) {
+            let _ = self.tx.send(RecognitionResult {
+                text: format!("{what} {}", self.buffered),
+                input_id: String::new(),
+                timestamp: 0.0,
+                is_final: true,
+                ..Default::default()
+            });
+            self.buffered = 0;
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AsrSession for BufferingSession {
+        fn name(&self) -> &str {
+            "buffering"
+        }
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), AsrError> {
+            Ok(())
+        }
+        fn take_results(&mut self) -> Option<ResultStream> {
+            self.rx.take()
+        }
+        async fn push_audio(&mut self, chunk: AudioChunk) -> Result<(), AsrError> {
+            self.buffered += chunk.samples.len();
+            Ok(())
+        }
+        async fn end_utterance(&mut self) -> Result<(), AsrError> {
+            self.emit("utterance");
+            Ok(())
+        }
+        async fn flush(&mut self) -> Result<(), AsrError> {
+            if self.buffered > 0 {
+                self.emit("flushed");
+            }
+            Ok(())
+        }
+        async fn reset(&mut self) -> Result<(), AsrError> {
+            self.buffered = 0;
+            Ok(())
+        }
+        async fn shutdown(&mut self) -> Result<(), AsrError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_host_flushes_native_session_on_close() {
+        let mut registry = test_registry();
+        registry.register_session("buffering", || Box::new(BufferingSession::new()));
+        let mut host = AsrHost::new();
+        let mut rx = host.take_result_receiver().unwrap();
+        let tx = host
+            .add_input("mic1", "buffering", toml::Value::Table(Default::default()), &registry)
+            .await
+            .unwrap();
+        host.start();
+
+        for _ in 0..3 {
+            tx.send(AudioChunk {
+                samples: vec![0.0; 160],
+                sample_rate: 16000,
+                channels: 1,
+            })
+            .unwrap();
+        }
+        drop(tx);
+        tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+            .await
+            .expect("shutdown timed out");
+
+        let result = rx.try_recv().expect("flushed result");
+        assert_eq!(result.text, "flushed 480");
+        assert_eq!(result.input_id, "mic1");
+    }
+
+    #[tokio::test]
+    async fn test_buffering_session_reset_discards_audio() {
+        let mut session = BufferingSession::new();
+        let mut results = session.take_results().unwrap();
+        session
+            .push_audio(AudioChunk {
+                samples: vec![0.0; 100],
+                sample_rate: 16000,
+                channels: 1,
+            })
+            .await
+            .unwrap();
+        session.reset().await.unwrap();
+        session.flush().await.unwrap();
+        session.end_utterance().await.unwrap();
+        assert_eq!(results.try_recv().unwrap().text, "utterance 0");
+        assert!(results.try_recv().is_err());
+    }
+
+    /// Reports the format and size of every chunk it receives.
+    struct FormatEngine {
+        tx: Option<mpsc::UnboundedSender<RecognitionResult>>,
+    }
+
+    #[async_trait::async_trait]
+    impl AsrEngine for FormatEngine {
+        fn name(&self) -> &str {
+            "format"
+        }
+        fn capabilities(&self) -> crate::EngineCapabilities {
+            crate::EngineCapabilities {
+                sample_rate: Some(16000),
+                channels: Some(1),
+                mode: crate::ProcessingMode::Streaming { chunk_ms: 20 },
+                languages: vec!["en".to_string()],
+                ..Default::default()
+            }
+        }
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn feed_audio(&self, chunk: AudioChunk) -> Result<(), AsrError> {
+            let _ = self.tx.as_ref().unwrap().send(RecognitionResult {
+                text: format!("{} Hz {} ch {}", chunk.sample_rate, chunk.channels, chunk.samples.len()),
+                input_id: String::new(),
+                timestamp: 0.0,
+                is_final: true,
+                ..Default::default()
+            });
+            Ok(())
+        }
+        fn set_result_sender(&mut self, sender: mpsc::UnboundedSender<RecognitionResult>) {
+            self.tx = Some(sender);
+        }
+        async fn shutdown(&self) -> Result<(), AsrError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_host_converts_audio_to_engine_capabilities() {
+        let mut registry = test_registry();
+        registry.register("format", || Box::new(FormatEngine { tx: None }));
+        let mut host = AsrHost::new();
+        let mut rx = host.take_result_receiver().unwrap();
+        let tx = host
+            .add_input("mic1", "format", toml::Value::Table(Default::default()), &registry)
+            .await
+            .unwrap();
+        host.start();
+
+        // 50 ms of 48 kHz stereo → two 20 ms chunks at 16 kHz mono, 10 ms left
+        tx.send(AudioChunk {
+            samples: vec![0.1; 4800],
+            sample_rate: 48000,
+            channels: 2,
+        })
+        .unwrap();
+        drop(tx);
+        tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+            .await
+            .expect("shutdown timed out");
+
+        let mut texts = Vec::new();
+        while let Ok(r) = rx.try_recv() {
+            texts.push(r.text);
+        }
+        assert_eq!(texts, vec!["16000 Hz 1 ch 320", "16000 Hz 1 ch 320", "16000 Hz 1 ch 160"]);
+    }
+
+    #[tokio::test]
+    async fn test_host_rejects_unsupported_language() {
+        let mut registry = test_registry();
+        registry.register("format", || Box::new(FormatEngine { tx: None }));
+        let mut host = AsrHost::new();
+        let mut config = toml::map::Map::new();
+        config.insert("language".to_string(), toml::Value::String("ja".to_string()));
+        let result = host
+            .add_input("mic1", "format", toml::Value::Table(config), &registry)
+            .await;
+        match result {
+            Err(AsrError::InitializationFailed(msg)) => assert!(msg.contains("'ja'")),
+            _ => panic!("expected InitializationFailed"),
+        }
+    }
+
+    static COUNTING_INITS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    /// Shareable engine that counts initialisations and echoes its language.
+    struct CountingEngine {
+        language: String,
+    }
+
+    #[async_trait::async_trait]
+    impl SharedEngine for CountingEngine {
+        fn name(&self) -> &str {
+            "counting"
+        }
+        async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError> {
+            COUNTING_INITS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.language = config
+                .get("language")
+                .and_then(|v| v.as_str())
+                .unwrap_or("?")
+                .to_string();
+            Ok(())
+        }
+        async fn transcribe(&self, segment: AudioChunk) -> Result<String, AsrError> {
+            Ok(format!("{} {}", self.language, segment.samples.len()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_host_shares_engine_per_config() {
+        let mut registry = test_registry();
+        registry.register_shared("counting", || {
+            Box::new(CountingEngine {
+                language: String::new(),
+            })
+        });
+        let mut host = AsrHost::new();
+        host.set_pool(AsrPoolConfig {
+            workers: 2,
+            max_pending: 4,
+        });
+        let mut rx = host.take_result_receiver().unwrap();
+        let config = |lang: &str| {
+            let mut table = toml::map::Map::new();
+            table.insert("language".to_string(), toml::Value::String(lang.to_string()));
+            toml::Value::Table(table)
+        };
+
+        let mut taps = Vec::new();
+        for (id, lang) in [("a", "en"), ("b", "en"), ("c", "ja")] {
+            taps.push(host.add_input(id, "counting", config(lang), &registry).await.unwrap());
+        }
+        assert_eq!(COUNTING_INITS.load(std::sync::atomic::Ordering::SeqCst), 2);
+        host.set_priority("c", 1);
+        host.start();
+
+        for tap in &taps {
+            tap.send(AudioChunk {
+                samples: vec![0.0; 160],
+                sample_rate: 16000,
+                channels: 1,
+            })
+            .unwrap();
+        }
+        drop(taps);
+        tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+            .await
+            .expect("shutdown timed out");
+
+        let mut results = Vec::new();
+        while let Ok(r) = rx.try_recv() {
+            results.push(format!("{}: {}", r.input_id, r.text));
+        }
+        results.sort();
+        assert_eq!(results, ["a: en 160", "b: en 160", "c: ja 160"]);
+        let metrics = host.pool().metrics();
+        assert_eq!((metrics.workers, metrics.completed, metrics.queued), (2, 3, 0));
+    }
+
+    // ── Supervision ────────────────────────────────────────────
+
+    static FLAKY_CREATED: AtomicUsize = AtomicUsize::new(0);
+    static CLOSING_CREATED: AtomicUsize = AtomicUsize::new(0);
+
+    /// Echoes pushes as results, except that the first instance created by
+    /// `created` misbehaves: it fails every push, or with `close` drops its
+    /// result stream on the first push.
+    struct MisbehavingSession {
+        tx: Option<mpsc::UnboundedSender<RecognitionResult>>,
+        rx: Option<ResultStream>,
+        broken: bool,
+        close: bool,
+    }
+
+    impl MisbehavingSession {
+        fn new(created: &AtomicUsize, close: bool) -> Self {
+            let (tx, rx) = mpsc::unbounded_channel();
+            Self {
+                tx: Some(tx),
+                rx: Some(rx),
+                broken: created.fetch_add(1, Ordering::SeqCst) == 0,
+                close,
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AsrSession for MisbehavingSession {
+        fn name(&self) -> &str {
+            "misbehaving"
+        }
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), AsrError> {
+            Ok(())
+        }
+        fn take_results(&mut self) -> Option<ResultStream> {
+            self.rx.take()
+        }
+        async fn push_audio(&mut self, chunk: AudioChunk) -> Result<(), AsrError> {
+            match (self.broken, self.close) {
+                (true, false) => Err(AsrError::ProcessingFailed("broken".to_string())),
+                (true, true) => {
+                    self.tx = None;
+                    Ok(())
+                }
+                (false, _) => {
+                    if let Some(tx) = &self.tx {
+                        let _ = tx.send(RecognitionResult {
+                            text: format!("ok {}", chunk.samples.len()),
+                            input_id: String::new(),
+                            timestamp: 0.0,
+                            is_final: true,
+                            ..Default::default()
+                        });
+                    }
+                    Ok(())
+                }
+            }
+        }
+        async fn flush(&mut self) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn reset(&mut self) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn shutdown(&mut self) -> Result<(), AsrError> {
+            Ok(())
+        }
+    }
+
+    struct BrokenSession(MisbehavingSession);
+
+    #[async_trait::async_trait]
+    impl AsrSession for BrokenSession {
+        fn name(&self) -> &str {
+            "broken"
+        }
+        async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError> {
+            self.0.initialize(config).await
+        }
+        fn take_results(&mut self) -> Option<ResultStream> {
+            self.0.take_results()
+        }
+        async fn push_audio(&mut self, _chunk: AudioChunk) -> Result<(), AsrError> {
+            Err(AsrError::ProcessingFailed("broken".to_string()))
+        }
+        async fn flush(&mut self) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn reset(&mut self) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn shutdown(&mut self) -> Result<(), AsrError> {
+            Ok(())
+        }
+    }
+
+    fn fast_supervisor(max_restarts: u32) -> AsrSupervisorConfig {
+        AsrSupervisorConfig {
+            max_errors: 2,
+            restart_delay_ms: 1,
+            max_restart_delay_ms: 4,
+            max_restarts,
+            ..Default::default()
+        }
+    }
+
+    /// Feed audio until `done` holds, or give up after a second.
+    async fn feed_until(tx: &TapSender, mut done: impl FnMut() -> bool) {
+        for _ in 0..200 {
+            if done() {
+                return;
+            }
+            let _ = tx.send(AudioChunk {
+                samples: vec![0.0; 160],
+                sample_rate: 16000,
+                channels: 1,
+            });
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        panic!("condition not reached");
+    }
+
+    #[tokio::test]
+    async fn test_host_restarts_engine_after_repeated_errors() {
+        let mut registry = test_registry();
+        registry.register_session("flaky", || {
+            Box::new(MisbehavingSession::new(&FLAKY_CREATED, false))
+        });
+        let mut host = AsrHost::new();
+        host.set_supervisor(fast_supervisor(3));
+        let mut rx = host.take_result_receiver().unwrap();
+        let tx = host
+            .add_input("mic1", "flaky", toml::Value::Table(Default::default()), &registry)
+            .await
+            .unwrap();
+        let monitor = host.engine_monitor("mic1").unwrap();
+        assert_eq!(monitor.health().state, EngineState::Running);
+        host.start();
+
+        let mut result = None;
+        feed_until(&tx, || {
+            result = rx.try_recv().ok();
+            result.is_some()
+        })
+        .await;
+        assert_eq!(result.unwrap().text, "ok 160");
+
+        let health = monitor.health();
+        assert_eq!(health.state, EngineState::Running);
+        assert_eq!(health.restarts, 1);
+        assert!(health.last_error.unwrap().contains("broken"));
+        drop(tx);
+        host.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_host_restarts_engine_when_results_close() {
+        let mut registry = test_registry();
+        registry.register_session("closing", || {
+            Box::new(MisbehavingSession::new(&CLOSING_CREATED, true))
+        });
+        let mut host = AsrHost::new();
+        host.set_supervisor(fast_supervisor(3));
+        let mut rx = host.take_result_receiver().unwrap();
+        let tx = host
+            .add_input("mic1", "closing", toml::Value::Table(Default::default()), &registry)
+            .await
+            .unwrap();
+        let monitor = host.engine_monitor("mic1").unwrap();
+        host.start();
+
+        let mut result = None;
+        feed_until(&tx, || {
+            result = rx.try_recv().ok();
+            result.is_some()
+        })
+        .await;
+        assert_eq!(result.unwrap().input_id, "mic1");
+        assert_eq!(monitor.health().restarts, 1);
+        assert_eq!(monitor.health().state, EngineState::Running);
+        drop(tx);
+        host.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_host_marks_engine_failed_after_max_restarts() {
+        static CREATED: AtomicUsize = AtomicUsize::new(0);
+        let mut registry = test_registry();
+        registry.register_session("broken", || {
+            Box::new(BrokenSession(MisbehavingSession::new(&CREATED, false)))
+        });
+        let mut host = AsrHost::new();
+        host.set_supervisor(fast_supervisor(2));
+        let _rx = host.take_result_receiver().unwrap();
+        let tx = host
+            .add_input("mic1", "broken", toml::Value::Table(Default::default()), &registry)
+            .await
+            .unwrap();
+        let monitor = host.engine_monitor("mic1").unwrap();
+        host.start();
+
+        feed_until(&tx, || monitor.health().state == EngineState::Failed).await;
+        assert_eq!(monitor.health().restarts, 2);
+        assert_eq!(CREATED.load(Ordering::SeqCst), 3);
+
+        // A failed input keeps draining its tap and still shuts down
+        feed_until(&tx, || tx.stats().queue_state().queued_ms == 0).await;
+        drop(tx);
+        tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+            .await
+            .expect("shutdown timed out");
+    }
+
+    /// Shareable engine whose every transcription fails, fed 10 ms segments.
+    struct FailingSharedEngine;
+
+    #[async_trait::async_trait]
+    impl SharedEngine for FailingSharedEngine {
+        fn name(&self) -> &str {
+            "failing"
+        }
+        fn capabilities(&self) -> crate::EngineCapabilities {
+            crate::EngineCapabilities {
+                mode: crate::ProcessingMode::Batch,
+                max_segment_ms: Some(10),
+                ..Default::default()
+            }
+        }
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn transcribe(&self, _segment: AudioChunk) -> Result<String, AsrError> {
+            Err(AsrError::ProcessingFailed("model crashed".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_host_supervises_pooled_engine_errors() {
+        let mut registry = test_registry();
+        registry.register_shared("failing", || Box::new(FailingSharedEngine));
+        let mut host = AsrHost::new();
+        host.set_supervisor(fast_supervisor(1));
+        let _rx = host.take_result_receiver().unwrap();
+        let tx = host
+            .add_input("mic1", "failing", toml::Value::Table(Default::default()), &registry)
+            .await
+            .unwrap();
+        let monitor = host.engine_monitor("mic1").unwrap();
+        host.start();
+
+        feed_until(&tx, || monitor.health().state == EngineState::Failed).await;
+        let health = monitor.health();
+        assert_eq!(health.restarts, 1);
+        assert!(health.last_error.unwrap().contains("model crashed"));
+        drop(tx);
+        tokio::time::timeout(std::time::Duration::from_secs(2), host.shutdown())
+            .await
+            .expect("shutdown timed out");
+    }
 }
@@ -0,0 +1,424 @@
+use crate::capabilities::{EngineCapabilities, ProcessingMode};
+use crate::shared::SharedEngine;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::time::Duration;
+use voxmux_core::{AsrError, AudioChunk};
+
+/// How a segment is uploaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadFormat {
+    /// `multipart/form-data` with a `file` part, as expected by OpenAI's
+    /// `/v1/audio/transcriptions` and whisper.cpp's `/inference`.
+    #[default]
+    Multipart,
+    /// The WAV file as the request body; other settings go in the query string.
+    Wav,
+}
+
+/// `[asr.http]` settings.
+#[derive(Debug, Clone, Deserialize)]
+struct HttpEngineConfig {
+    url: String,
+    #[serde(default)]
+    format: UploadFormat,
+    /// Sent as `Authorization: Bearer <api_key>`.
+    #[serde(default)]
+    api_key: Option<String>,
+    /// Extra request headers.
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    prompt: Option<String>,
+    #[serde(default = "default_timeout_ms")]
+    timeout_ms: u64,
+    /// Further attempts after a failed request.
+    #[serde(default = "default_retries")]
+    retries: u32,
+    /// Delay before the first retry; doubles with each attempt.
+    #[serde(default = "default_retry_backoff_ms")]
+    retry_backoff_ms: u64,
+}
+
+fn default_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_retries() -> u32 {
+    2
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    500
+}
+
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+}
+
+/// Why a request failed, and whether trying again may help.
+struct RequestError {
+    message: String,
+    retryable: bool,
+}
+
+/// Transcribes segments on a remote server with an OpenAI-compatible API,
+/// e.g. whisper.cpp `server` or faster-whisper.
+pub struct HttpEngine {
+    config: Option<HttpEngineConfig>,
+    client: reqwest::Client,
+}
+
+impl HttpEngine {
+    pub fn new() -> Self {
+        Self {
+            config: None,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn config(&self) -> Result<&HttpEngineConfig, AsrError> {
+        self.config
+            .as_ref()
+            .ok_or_else(|| AsrError::ProcessingFailed("http engine not initialized".to_string()))
+    }
+
+    async fn request(&self, config: &HttpEngineConfig, wav: &[u8]) -> Result<String, RequestError> {
+        let fields = [
+            ("model", &config.model),
+            ("language", &config.language),
+            ("prompt", &config.prompt),
+        ];
+        let mut request = self.client.post(&config.url);
+        request = match config.format {
+            UploadFormat::Multipart => {
+                let file = reqwest::multipart::Part::bytes(wav.to_vec())
+                    .file_name("segment.wav")
+                    .mime_str("audio/wav")
+                    .expect("valid mime type");
+                let mut form = reqwest::multipart::Form::new()
+                    .part("file", file)
+                    .text("response_format", "json");
+                for (name, value) in fields {
+                    if let Some(value) = value {
+                        form = form.text(name, value.clone());
+                    }
+                }
+                request.multipart(form)
+            }
+            UploadFormat::Wav => {
+                let query: Vec<(&str, &String)> = fields
+                    .iter()
+                    .filter_map(|(name, value)| value.as_ref().map(|v| (*name, v)))
+                    .collect();
+                request
+                    .query(&query)
+                    .header(reqwest::header::CONTENT_TYPE, "audio/wav")
+                    .body(wav.to_vec())
+            }
+        };
+        if let Some(key) = &config.api_key {
+            request = request.bearer_auth(key);
+        }
+        for (name, value) in &config.headers {
+            request = request.header(name, value);
+        }
+
+        let response = request.send().await.map_err(|e| RequestError {
+            message: e.to_string(),
+            retryable: true,
+        })?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(|e| RequestError {
+            message: e.to_string(),
+            retryable: true,
+        })?;
+        if !status.is_success() {
+            return Err(RequestError {
+                message: format!("{status}: {}", String::from_utf8_lossy(&body).trim()),
+                retryable: status.is_server_error()
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS,
+            });
+        }
+        let parsed: TranscriptionResponse =
+            serde_json::from_slice(&body).map_err(|e| RequestError {
+                message: format!("invalid response: {e}"),
+                retryable: false,
+            })?;
+        Ok(parsed.text.trim().to_string())
+    }
+}
+
+impl Default for HttpEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SharedEngine for HttpEngine {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    /// 16 kHz mono, one utterance per request.
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            sample_rate: Some(16000),
+            channels: Some(1),
+            mode: ProcessingMode::Batch,
+            partial_results: false,
+            max_segment_ms: Some(30_000),
+            languages: Vec::new(),
+        }
+    }
+
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError> {
+        let config: HttpEngineConfig = config
+            .try_into()
+            .map_err(|e| AsrError::InitializationFailed(format!("invalid [asr.http]: {e}")))?;
+        self.client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .map_err(|e| AsrError::InitializationFailed(e.to_string()))?;
+        tracing::info!(url = %config.url, format = ?config.format, "HttpEngine initialized");
+        self.config = Some(config);
+        Ok(())
+    }
+
+    async fn transcribe(&self, segment: AudioChunk) -> Result<String, AsrError> {
+        let config = self.config()?;
+        let wav = encode_wav(&segment)?;
+        let mut backoff = Duration::from_millis(config.retry_backoff_ms);
+        let mut attempt = 0;
+        loop {
+            match self.request(config, &wav).await {
+                Ok(text) => return Ok(text),
+                Err(e) if e.retryable && attempt < config.retries => {
+                    attempt += 1;
+                    tracing::warn!(
+                        "transcription request failed ({}), retry {attempt}/{}",
+                        e.message,
+                        config.retries
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(e) => {
+                    return Err(AsrError::ProcessingFailed(format!(
+                        "{}: {}",
+                        config.url, e.message
+                    )))
+                }
+            }
+        }
+    }
+}
+
+/// Encode a segment as a 16-bit PCM WAV file.
+fn encode_wav(segment: &AudioChunk) -> Result<Vec<u8>, AsrError> {
+    let spec = hound::WavSpec {
+        channels: segment.channels.max(1),
+        sample_rate: segment.sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut cursor = Cursor::new(Vec::new());
+    let encode = |cursor: &mut Cursor<Vec<u8>>| -> Result<(), hound::Error> {
+        let mut writer = hound::WavWriter::new(cursor, spec)?;
+        for &s in &segment.samples {
+            writer.write_sample((s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)?;
+        }
+        writer.finalize()
+    };
+    encode(&mut cursor).map_err(|e| AsrError::ProcessingFailed(format!("WAV encoding failed: {e}")))?;
+    Ok(cursor.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Raw request as received by the mock server.
+    struct Request {
+        head: String,
+        body: Vec<u8>,
+    }
+
+    /// Serves the canned `(status, body)` replies in order, one per
+    /// connection, and records each request. `None` never replies.
+    async fn mock_server(
+        replies: Vec<Option<(u16, &'static str)>>,
+    ) -> (String, Arc<Mutex<Vec<Request>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1/audio/transcriptions", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            for reply in replies {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let request = read_request(&mut stream).await;
+                recorded.lock().unwrap().push(request);
+                let Some((status, body)) = reply else {
+                    // Hold the connection open without answering
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    continue;
+                };
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\ncontent-type: application/json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    async fn read_request(stream: &mut tokio::net::TcpStream) -> Request {
+        let mut data = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            data.extend_from_slice(&buf[..n]);
+            if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+                let head = String::from_utf8_lossy(&data[..end]).to_string();
+                let length: usize = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse().unwrap())
+                    })
+                    .unwrap_or(0);
+                while data.len() < end + 4 + length {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    data.extend_from_slice(&buf[..n]);
+                }
+                return Request {
+                    head,
+                    body: data[end + 4..end + 4 + length].to_vec(),
+                };
+            }
+        }
+    }
+
+    async fn engine(url: &str, extra: &str) -> HttpEngine {
+        let config: toml::Value =
+            toml::from_str(&format!("url = \"{url}\"\nretry_backoff_ms = 10\n{extra}")).unwrap();
+        let mut engine = HttpEngine::new();
+        engine.initialize(config).await.unwrap();
+        engine
+    }
+
+    fn segment() -> AudioChunk {
+        AudioChunk {
+            samples: vec![0.25; 1600],
+            sample_rate: 16000,
+            channels: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_multipart_upload() {
+        let (url, requests) = mock_server(vec![Some((200, r#"{"text": " Roger that. "}"#))]).await;
+        let engine = engine(
+            &url,
+            "api_key = \"sk-test\"\nmodel = \"whisper-1\"\nlanguage = \"en\"\nprompt = \"QSL\"\n\
+             [headers]\nX-Team = \"ops\"",
+        )
+        .await;
+
+        assert_eq!(engine.transcribe(segment()).await.unwrap(), "Roger that.");
+
+        let requests = requests.lock().unwrap();
+        let head = requests[0].head.to_ascii_lowercase();
+        assert!(head.starts_with("post /v1/audio/transcriptions"), "{head}");
+        assert!(head.contains("authorization: bearer sk-test"));
+        assert!(head.contains("x-team: ops"));
+        assert!(head.contains("content-type: multipart/form-data"));
+        let body = String::from_utf8_lossy(&requests[0].body);
+        for field in ["file", "model", "language", "prompt", "response_format"] {
+            assert!(body.contains(&format!("name=\"{field}\"")), "missing {field}:\n{body}");
+        }
+        assert!(body.contains("whisper-1") && body.contains("QSL"));
+        assert!(body.contains("RIFF"));
+    }
+
+    #[tokio::test]
+    async fn test_http_wav_upload() {
+        let (url, requests) = mock_server(vec![Some((200, r#"{"text": "hello"}"#))]).await;
+        let engine = engine(&url, "format = \"wav\"\nlanguage = \"de\"").await;
+
+        assert_eq!(engine.transcribe(segment()).await.unwrap(), "hello");
+
+        let requests = requests.lock().unwrap();
+        let head = requests[0].head.to_ascii_lowercase();
+        assert!(head.starts_with("post /v1/audio/transcriptions?language=de "), "{head}");
+        assert!(head.contains("content-type: audio/wav"));
+        let wav = hound::WavReader::new(Cursor::new(requests[0].body.clone())).unwrap();
+        assert_eq!(wav.spec().sample_rate, 16000);
+        assert_eq!(wav.len(), 1600);
+    }
+
+    #[tokio::test]
+    async fn test_http_retries_server_errors() {
+        let (url, requests) = mock_server(vec![
+            Some((503, "busy")),
+            Some((429, "slow down")),
+            Some((200, r#"{"text": "third time"}"#)),
+        ])
+        .await;
+        let engine = engine(&url, "retries = 2").await;
+        assert_eq!(engine.transcribe(segment()).await.unwrap(), "third time");
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_http_client_error_is_not_retried() {
+        let (url, requests) = mock_server(vec![Some((401, "bad key"))]).await;
+        let engine = engine(&url, "retries = 3").await;
+        match engine.transcribe(segment()).await {
+            Err(AsrError::ProcessingFailed(msg)) => assert!(msg.contains("401"), "{msg}"),
+            _ => panic!("expected ProcessingFailed"),
+        }
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_http_timeout() {
+        let (url, _) = mock_server(vec![None]).await;
+        let engine = engine(&url, "timeout_ms = 100\nretries = 0").await;
+        let started = std::time::Instant::now();
+        assert!(engine.transcribe(segment()).await.is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_http_invalid_json_response() {
+        let (url, _) = mock_server(vec![Some((200, "<html>not json</html>"))]).await;
+        let engine = engine(&url, "").await;
+        assert!(engine.transcribe(segment()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_config_validation() {
+        let mut engine = HttpEngine::new();
+        assert!(matches!(
+            engine.initialize(toml::Value::Table(Default::default())).await,
+            Err(AsrError::InitializationFailed(_))
+        ));
+        assert!(engine.transcribe(segment()).await.is_err());
+    }
+}
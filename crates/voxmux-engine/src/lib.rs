@@ -1,13 +1,46 @@
+pub mod capabilities;
+pub mod conditioner;
 pub mod engine_trait;
+pub mod exec_engine;
+pub mod exec_tts;
 pub mod host;
+#[cfg(feature = "http")]
+pub mod http_engine;
 pub mod null_engine;
+pub mod plugin_engine;
+pub mod pool;
 pub mod registry;
+pub mod script_engine;
+pub mod session;
+pub mod shared;
+pub mod supervisor;
+pub mod tts;
+mod utterance;
+pub mod vad;
+#[cfg(feature = "vosk")]
+pub mod vosk_engine;
 #[cfg(feature = "whisper")]
 pub mod whisper_engine;
 
+pub use capabilities::{EngineCapabilities, ProcessingMode};
+pub use conditioner::AudioConditioner;
 pub use engine_trait::AsrEngine;
+pub use exec_engine::ExecSession;
+pub use exec_tts::ExecTts;
 pub use host::AsrHost;
+#[cfg(feature = "http")]
+pub use http_engine::HttpEngine;
 pub use null_engine::NullEngine;
+pub use plugin_engine::PluginSession;
+pub use pool::{InferencePool, PoolClient, PoolReply};
 pub use registry::PluginRegistry;
+pub use script_engine::ScriptSession;
+pub use session::{AsrSession, EngineAdapter, ResultStream};
+pub use shared::{PooledSession, SharedEngine};
+pub use supervisor::EngineMonitor;
+pub use tts::{NullTts, TtsEngine, TtsRegistry};
+pub use vad::{SpeechDetector, Vad, VadEvent, VadMonitor, VadRegistry};
+#[cfg(feature = "vosk")]
+pub use vosk_engine::VoskSession;
 #[cfg(feature = "whisper")]
 pub use whisper_engine::WhisperEngine;
@@ -46,6 +46,7 @@ impl AsrEngine for NullEngine {
             input_id: String::new(),
             timestamp: 0.0,
             is_final: true,
+            ..Default::default()
         };
         if let Ok(sender) = self.result_sender.lock() {
             if let Some(tx) = sender.as_ref() {
@@ -0,0 +1,161 @@
+use crate::capabilities::{EngineCapabilities, ProcessingMode};
+use crate::session::{AsrSession, ResultStream};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use voxmux_core::{AsrError, AudioChunk, RecognitionResult};
+use voxmux_plugin::host::{EngineInstance, PluginEngine};
+use voxmux_plugin::Capabilities;
+
+type Outcome = (Result<(), String>, Vec<(String, bool)>);
+
+/// A session on an engine loaded from a plugin library.
+///
+/// Plugin calls are blocking, so they run on tokio's blocking pool; results
+/// are polled after every call.
+pub struct PluginSession {
+    engine: PluginEngine,
+    instance: Option<Arc<Mutex<EngineInstance>>>,
+    capabilities: EngineCapabilities,
+    tx: mpsc::UnboundedSender<RecognitionResult>,
+    rx: Option<ResultStream>,
+}
+
+impl PluginSession {
+    pub fn new(engine: PluginEngine) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            engine,
+            instance: None,
+            capabilities: EngineCapabilities::default(),
+            tx,
+            rx: Some(rx),
+        }
+    }
+
+    /// Run `op` on the instance and forward the results it produced.
+    async fn call(
+        &mut self,
+        op: impl FnOnce(&mut EngineInstance) -> Result<(), String> + Send + 'static,
+    ) -> Result<(), AsrError> {
+        let instance = self.instance.clone().ok_or_else(|| {
+            AsrError::ProcessingFailed(format!(
+                "plugin engine '{}' not initialized",
+                self.engine.name()
+            ))
+        })?;
+        let (status, results): Outcome = tokio::task::spawn_blocking(move || {
+            let mut instance = instance.lock().unwrap();
+            let status = op(&mut instance);
+            let results = std::iter::from_fn(|| instance.poll_result()).collect();
+            (status, results)
+        })
+        .await
+        .map_err(|e| AsrError::ProcessingFailed(e.to_string()))?;
+
+        for (text, is_final) in results {
+            let _ = self.tx.send(RecognitionResult {
+                text,
+                input_id: String::new(),
+                timestamp: 0.0,
+                is_final,
+                ..Default::default()
+            });
+        }
+        status.map_err(|e| AsrError::ProcessingFailed(format!("{}: {e}", self.engine.name())))
+    }
+}
+
+fn convert(caps: Capabilities) -> EngineCapabilities {
+    EngineCapabilities {
+        sample_rate: (caps.sample_rate > 0).then_some(caps.sample_rate),
+        channels: (caps.channels > 0).then_some(caps.channels),
+        mode: if caps.batch {
+            ProcessingMode::Batch
+        } else {
+            ProcessingMode::Streaming {
+                chunk_ms: caps.chunk_ms,
+            }
+        },
+        partial_results: caps.partial_results,
+        ..Default::default()
+    }
+}
+
+#[async_trait]
+impl AsrSession for PluginSession {
+    fn name(&self) -> &str {
+        self.engine.name()
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        self.capabilities.clone()
+    }
+
+    /// The config is handed to the plugin as a TOML document.
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError> {
+        let config =
+            toml::to_string(&config).map_err(|e| AsrError::InitializationFailed(e.to_string()))?;
+        let engine = self.engine.clone();
+        let instance = tokio::task::spawn_blocking(move || engine.create(&config))
+            .await
+            .map_err(|e| AsrError::InitializationFailed(e.to_string()))?
+            .map_err(|e| AsrError::InitializationFailed(format!("{}: {e}", self.engine.name())))?;
+        self.capabilities = convert(instance.capabilities());
+        self.instance = Some(Arc::new(Mutex::new(instance)));
+        Ok(())
+    }
+
+    fn take_results(&mut self) -> Option<ResultStream> {
+        self.rx.take()
+    }
+
+    async fn push_audio(&mut self, chunk: AudioChunk) -> Result<(), AsrError> {
+        self.call(move |i| i.push_audio(&chunk.samples, chunk.sample_rate, chunk.channels))
+            .await
+    }
+
+    async fn end_utterance(&mut self) -> Result<(), AsrError> {
+        self.call(EngineInstance::end_utterance).await
+    }
+
+    async fn flush(&mut self) -> Result<(), AsrError> {
+        self.call(EngineInstance::flush).await
+    }
+
+    async fn reset(&mut self) -> Result<(), AsrError> {
+        self.call(EngineInstance::reset).await
+    }
+
+    async fn shutdown(&mut self) -> Result<(), AsrError> {
+        self.instance = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_capabilities() {
+        let caps = convert(Capabilities {
+            sample_rate: 16000,
+            channels: 0,
+            batch: false,
+            chunk_ms: 100,
+            partial_results: true,
+        });
+        assert_eq!(caps.sample_rate, Some(16000));
+        assert_eq!(caps.channels, None);
+        assert_eq!(caps.mode, ProcessingMode::Streaming { chunk_ms: 100 });
+        assert!(caps.partial_results);
+
+        let batch = convert(Capabilities {
+            batch: true,
+            ..Default::default()
+        });
+        assert_eq!(batch.mode, ProcessingMode::Batch);
+        assert_eq!(batch.sample_rate, None);
+    }
+}
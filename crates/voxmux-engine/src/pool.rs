@@ -0,0 +1,466 @@
+use crate::shared::SharedEngine;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::{mpsc, Notify};
+use voxmux_core::config::AsrPoolConfig;
+use voxmux_core::{AsrError, AsrPoolState, AudioChunk, RecognitionResult};
+
+/// What a worker sends back for one segment: the final result, with empty
+/// text if nothing was recognised, or the engine's error.
+pub type PoolReply = Result<RecognitionResult, AsrError>;
+
+/// One segment waiting for a worker.
+struct Job {
+    engine: Arc<dyn SharedEngine>,
+    segment: AudioChunk,
+    reply: mpsc::UnboundedSender<PoolReply>,
+    submitted: Instant,
+}
+
+struct InputQueue {
+    input_id: String,
+    priority: i32,
+    jobs: VecDeque<Job>,
+    /// A segment from this input is being transcribed. Inputs run one
+    /// segment at a time so their results stay in order.
+    busy: bool,
+}
+
+#[derive(Default)]
+struct PoolState {
+    queues: Vec<InputQueue>,
+    /// Round-robin position among inputs of equal priority.
+    cursor: usize,
+    closed: bool,
+    busy: usize,
+    completed: u64,
+    dropped: u64,
+    last_latency_ms: u32,
+    total_latency_ms: u64,
+    max_latency_ms: u32,
+}
+
+impl PoolState {
+    /// Take the next job: highest priority first, round-robin within a
+    /// priority, skipping inputs that already have a segment running.
+    fn next_job(&mut self) -> Option<(usize, Job)> {
+        let n = self.queues.len();
+        let mut best: Option<usize> = None;
+        for offset in 0..n {
+            let i = (self.cursor + offset) % n;
+            let queue = &self.queues[i];
+            if queue.busy || queue.jobs.is_empty() {
+                continue;
+            }
+            if best.is_none_or(|b| queue.priority > self.queues[b].priority) {
+                best = Some(i);
+            }
+        }
+        let i = best?;
+        self.cursor = (i + 1) % n;
+        self.busy += 1;
+        let queue = &mut self.queues[i];
+        queue.busy = true;
+        queue.jobs.pop_front().map(|job| (i, job))
+    }
+
+    fn has_jobs(&self) -> bool {
+        self.queues.iter().any(|q| !q.jobs.is_empty())
+    }
+
+    fn record_latency(&mut self, ms: u32) {
+        self.completed += 1;
+        self.last_latency_ms = ms;
+        self.total_latency_ms += ms as u64;
+        self.max_latency_ms = self.max_latency_ms.max(ms);
+    }
+}
+
+struct PoolInner {
+    config: AsrPoolConfig,
+    state: Mutex<PoolState>,
+    notify: Notify,
+}
+
+// ── InferencePool ──────────────────────────────────────────────
+
+/// Bounded set of workers that transcribe segments from every input using
+/// a [`SharedEngine`]. Cloning yields another handle to the same pool.
+#[derive(Clone)]
+pub struct InferencePool {
+    inner: Arc<PoolInner>,
+}
+
+impl InferencePool {
+    pub fn new(config: AsrPoolConfig) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                config,
+                state: Mutex::new(PoolState::default()),
+                notify: Notify::new(),
+            }),
+        }
+    }
+
+    /// Add a queue for `input_id`.
+    pub fn register(&self, input_id: &str) -> PoolClient {
+        let mut state = self.inner.state.lock().unwrap();
+        state.queues.push(InputQueue {
+            input_id: input_id.to_string(),
+            priority: 0,
+            jobs: VecDeque::new(),
+            busy: false,
+        });
+        PoolClient {
+            inner: self.inner.clone(),
+            slot: state.queues.len() - 1,
+        }
+    }
+
+    /// Segments from inputs with a higher priority are transcribed first.
+    pub fn set_priority(&self, input_id: &str, priority: i32) {
+        let mut state = self.inner.state.lock().unwrap();
+        for queue in state.queues.iter_mut().filter(|q| q.input_id == input_id) {
+            queue.priority = priority;
+        }
+    }
+
+    /// Whether any input has been registered.
+    pub fn is_used(&self) -> bool {
+        !self.inner.state.lock().unwrap().queues.is_empty()
+    }
+
+    /// Segments from `input_id` waiting for a worker.
+    pub fn pending(&self, input_id: &str) -> usize {
+        let state = self.inner.state.lock().unwrap();
+        state
+            .queues
+            .iter()
+            .filter(|q| q.input_id == input_id)
+            .map(|q| q.jobs.len())
+            .sum()
+    }
+
+    /// Snapshot the load and latency counters, for TUI display.
+    pub fn metrics(&self) -> AsrPoolState {
+        let state = self.inner.state.lock().unwrap();
+        AsrPoolState {
+            workers: self.inner.config.workers.max(1),
+            busy: state.busy,
+            queued: state.queues.iter().map(|q| q.jobs.len()).sum(),
+            completed: state.completed,
+            dropped: state.dropped,
+            last_latency_ms: state.last_latency_ms,
+            avg_latency_ms: state
+                .total_latency_ms
+                .checked_div(state.completed)
+                .unwrap_or(0) as u32,
+            max_latency_ms: state.max_latency_ms,
+        }
+    }
+
+    /// Spawn the workers.
+    pub fn start(&self) -> Vec<tokio::task::JoinHandle<()>> {
+        (0..self.inner.config.workers.max(1))
+            .map(|_| tokio::spawn(worker(self.inner.clone())))
+            .collect()
+    }
+
+    /// Let the workers exit once every queued segment has been transcribed.
+    pub fn close(&self) {
+        self.inner.state.lock().unwrap().closed = true;
+        self.inner.notify.notify_waiters();
+    }
+}
+
+async fn worker(inner: Arc<PoolInner>) {
+    loop {
+        let notified = inner.notify.notified();
+        let next = {
+            let mut state = inner.state.lock().unwrap();
+            match state.next_job() {
+                Some(next) => Some(next),
+                None if state.closed && !state.has_jobs() => return,
+                None => None,
+            }
+        };
+        let Some((slot, job)) = next else {
+            notified.await;
+            continue;
+        };
+
+        let input_id = inner.state.lock().unwrap().queues[slot].input_id.clone();
+        let reply = job
+            .engine
+            .transcribe(job.segment)
+            .await
+            .map(|text| RecognitionResult {
+                text,
+                input_id,
+                timestamp: 0.0,
+                is_final: true,
+                ..Default::default()
+            });
+        let _ = job.reply.send(reply);
+        let latency_ms = job.submitted.elapsed().as_millis().min(u32::MAX as u128) as u32;
+        drop(job.reply);
+
+        {
+            let mut state = inner.state.lock().unwrap();
+            state.busy -= 1;
+            state.queues[slot].busy = false;
+            state.record_latency(latency_ms);
+        }
+        // This input may have more work, and the pool may be closing
+        inner.notify.notify_waiters();
+    }
+}
+
+// ── PoolClient ─────────────────────────────────────────────────
+
+/// An input's handle for submitting segments to the pool. Clones share the
+/// input's queue.
+#[derive(Clone)]
+pub struct PoolClient {
+    inner: Arc<PoolInner>,
+    slot: usize,
+}
+
+impl PoolClient {
+    /// Queue `segment` for transcription; its result or error is sent on
+    /// `reply`. Drops the input's oldest waiting segment if its backlog is full.
+    pub fn submit(
+        &self,
+        engine: Arc<dyn SharedEngine>,
+        segment: AudioChunk,
+        reply: mpsc::UnboundedSender<PoolReply>,
+    ) {
+        {
+            let mut state = self.inner.state.lock().unwrap();
+            let max_pending = self.inner.config.max_pending.max(1);
+            let queue = &mut state.queues[self.slot];
+            let mut dropped = 0;
+            while queue.jobs.len() >= max_pending {
+                queue.jobs.pop_front();
+                dropped += 1;
+            }
+            if dropped > 0 {
+                tracing::warn!(
+                    input_id = %queue.input_id,
+                    "ASR pool backlog full, dropped {dropped} segment(s)"
+                );
+            }
+            queue.jobs.push_back(Job {
+                engine,
+                segment,
+                reply,
+                submitted: Instant::now(),
+            });
+            state.dropped += dropped;
+        }
+        self.inner.notify.notify_one();
+    }
+
+    /// Discard this input's waiting segments.
+    pub fn cancel(&self) {
+        self.inner.state.lock().unwrap().queues[self.slot].jobs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use voxmux_core::AsrError;
+
+    /// Echoes the segment length after a short delay, tracking concurrency.
+    /// Fails segments with no samples.
+    struct SlowEngine {
+        running: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl SlowEngine {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                running: AtomicUsize::new(0),
+                peak: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl SharedEngine for SlowEngine {
+        fn name(&self) -> &str {
+            "slow"
+        }
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn transcribe(&self, segment: AudioChunk) -> Result<String, AsrError> {
+            let now = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            match segment.samples.len() {
+                0 => Err(AsrError::ProcessingFailed("empty segment".to_string())),
+                n => Ok(n.to_string()),
+            }
+        }
+    }
+
+    fn segment(len: usize) -> AudioChunk {
+        AudioChunk {
+            samples: vec![0.0; len],
+            sample_rate: 16000,
+            channels: 1,
+        }
+    }
+
+    fn pool(workers: usize, max_pending: usize) -> InferencePool {
+        InferencePool::new(AsrPoolConfig {
+            workers,
+            max_pending,
+        })
+    }
+
+    async fn collect(rx: &mut mpsc::UnboundedReceiver<PoolReply>) -> Vec<String> {
+        let mut out = Vec::new();
+        while let Some(reply) = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("timed out")
+        {
+            out.push(match reply {
+                Ok(r) => format!("{}:{}", r.input_id, r.text),
+                Err(e) => format!("error:{e}"),
+            });
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn test_pool_preserves_order_per_input() {
+        let pool = pool(4, 8);
+        let engine = SlowEngine::new();
+        let client = pool.register("mic1");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        for len in 1..=5 {
+            client.submit(engine.clone(), segment(len), tx.clone());
+        }
+        drop(tx);
+        let workers = pool.start();
+
+        assert_eq!(collect(&mut rx).await, ["mic1:1", "mic1:2", "mic1:3", "mic1:4", "mic1:5"]);
+        // One segment at a time per input, even with idle workers
+        assert_eq!(engine.peak.load(Ordering::SeqCst), 1);
+        pool.close();
+        for w in workers {
+            w.await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_returns_engine_errors() {
+        let pool = pool(1, 8);
+        let engine = SlowEngine::new();
+        let client = pool.register("mic1");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        for len in [1, 0, 2] {
+            client.submit(engine.clone(), segment(len), tx.clone());
+        }
+        drop(tx);
+        pool.start();
+
+        assert_eq!(
+            collect(&mut rx).await,
+            ["mic1:1", "error:ASR processing failed: empty segment", "mic1:2"]
+        );
+        assert_eq!(pool.metrics().completed, 3);
+    }
+
+    #[tokio::test]
+    async fn test_pool_bounds_concurrency() {
+        let pool = pool(2, 8);
+        let engine = SlowEngine::new();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        for id in ["a", "b", "c", "d"] {
+            pool.register(id).submit(engine.clone(), segment(1), tx.clone());
+        }
+        drop(tx);
+        pool.start();
+
+        assert_eq!(collect(&mut rx).await.len(), 4);
+        assert_eq!(engine.peak.load(Ordering::SeqCst), 2);
+        let metrics = pool.metrics();
+        assert_eq!(metrics.workers, 2);
+        assert_eq!(metrics.completed, 4);
+        assert_eq!(metrics.queued, 0);
+        assert!(metrics.max_latency_ms >= 20);
+        assert!(metrics.avg_latency_ms <= metrics.max_latency_ms);
+    }
+
+    #[tokio::test]
+    async fn test_pool_round_robin_and_priority() {
+        let pool = pool(1, 8);
+        let engine = SlowEngine::new();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let a = pool.register("a");
+        let b = pool.register("b");
+        let urgent = pool.register("urgent");
+        pool.set_priority("urgent", 10);
+        for _ in 0..2 {
+            a.submit(engine.clone(), segment(1), tx.clone());
+        }
+        for _ in 0..2 {
+            b.submit(engine.clone(), segment(2), tx.clone());
+        }
+        urgent.submit(engine.clone(), segment(9), tx.clone());
+        assert_eq!(pool.pending("a"), 2);
+        assert_eq!(pool.metrics().queued, 5);
+        drop(tx);
+        pool.start();
+
+        assert_eq!(
+            collect(&mut rx).await,
+            ["urgent:9", "a:1", "b:2", "a:1", "b:2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pool_drops_oldest_beyond_max_pending() {
+        let pool = pool(1, 2);
+        let engine = SlowEngine::new();
+        let client = pool.register("mic1");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        for len in 1..=4 {
+            client.submit(engine.clone(), segment(len), tx.clone());
+        }
+        drop(tx);
+        assert_eq!(pool.metrics().dropped, 2);
+        pool.start();
+        assert_eq!(collect(&mut rx).await, ["mic1:3", "mic1:4"]);
+    }
+
+    #[tokio::test]
+    async fn test_pool_cancel_and_close() {
+        let pool = pool(1, 8);
+        let engine = SlowEngine::new();
+        let client = pool.register("mic1");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        client.submit(engine.clone(), segment(1), tx.clone());
+        client.cancel();
+        drop(tx);
+        assert!(rx.recv().await.is_none());
+
+        pool.close();
+        for w in pool.start() {
+            tokio::time::timeout(Duration::from_secs(2), w)
+                .await
+                .expect("worker did not exit")
+                .unwrap();
+        }
+    }
+}
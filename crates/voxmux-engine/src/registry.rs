@@ -1,21 +1,40 @@
 use crate::engine_trait::AsrEngine;
+use crate::plugin_engine::PluginSession;
+use crate::session::{AsrSession, EngineAdapter};
+use crate::shared::SharedEngine;
 use voxmux_core::AsrError;
+use voxmux_plugin::host::{Plugin, PluginEngine};
 use std::collections::HashMap;
 
+#[derive(Clone)]
 pub struct PluginRegistry {
     factories: HashMap<String, fn() -> Box<dyn AsrEngine>>,
+    session_factories: HashMap<String, fn() -> Box<dyn AsrSession>>,
+    shared_factories: HashMap<String, fn() -> Box<dyn SharedEngine>>,
+    plugin_engines: HashMap<String, PluginEngine>,
 }
 
 impl PluginRegistry {
     pub fn new() -> Self {
         let mut registry = Self {
             factories: HashMap::new(),
+            session_factories: HashMap::new(),
+            shared_factories: HashMap::new(),
+            plugin_engines: HashMap::new(),
         };
         registry.register("null", || Box::new(crate::null_engine::NullEngine::new()));
+        registry.register_session("exec", || Box::new(crate::exec_engine::ExecSession::new()));
+        registry.register_session("script", || {
+            Box::new(crate::script_engine::ScriptSession::new())
+        });
         #[cfg(feature = "whisper")]
-        registry.register("whisper", || {
+        registry.register_shared("whisper", || {
             Box::new(crate::whisper_engine::WhisperEngine::new())
         });
+        #[cfg(feature = "http")]
+        registry.register_shared("http", || Box::new(crate::http_engine::HttpEngine::new()));
+        #[cfg(feature = "vosk")]
+        registry.register_session("vosk", || Box::new(crate::vosk_engine::VoskSession::new()));
         registry
     }
 
@@ -23,6 +42,48 @@ impl PluginRegistry {
         self.factories.insert(name.to_string(), factory);
     }
 
+    /// Register an engine implementing the session API directly.
+    /// Takes precedence over an [`AsrEngine`] registered under the same name.
+    pub fn register_session(&mut self, name: &str, factory: fn() -> Box<dyn AsrSession>) {
+        self.session_factories.insert(name.to_string(), factory);
+    }
+
+    /// Register an engine whose instances serve many inputs through the
+    /// inference pool. Session factories take precedence over it.
+    pub fn register_shared(&mut self, name: &str, factory: fn() -> Box<dyn SharedEngine>) {
+        self.shared_factories.insert(name.to_string(), factory);
+    }
+
+    /// Register the engines of a loaded plugin library. They take precedence
+    /// over compiled-in engines of the same name.
+    pub fn register_plugin(&mut self, plugin: &Plugin) {
+        for engine in plugin.engines() {
+            self.plugin_engines
+                .insert(engine.name().to_string(), engine.clone());
+        }
+    }
+
+    /// Create a shareable engine for `name`, or `None` if `name` is
+    /// registered as a per-input session or engine.
+    pub fn create_shared(&self, name: &str) -> Option<Box<dyn SharedEngine>> {
+        if self.session_factories.contains_key(name) || self.plugin_engines.contains_key(name) {
+            return None;
+        }
+        self.shared_factories.get(name).map(|f| f())
+    }
+
+    /// Create a session for `name`, adapting an [`AsrEngine`] if needed.
+    pub fn create_session(&self, name: &str) -> Result<Box<dyn AsrSession>, AsrError> {
+        if let Some(engine) = self.plugin_engines.get(name) {
+            return Ok(Box::new(PluginSession::new(engine.clone())));
+        }
+        if let Some(factory) = self.session_factories.get(name) {
+            return Ok(factory());
+        }
+        self.create(name)
+            .map(|engine| Box::new(EngineAdapter::new(engine)) as Box<dyn AsrSession>)
+    }
+
     pub fn create(&self, name: &str) -> Result<Box<dyn AsrEngine>, AsrError> {
         self.factories
             .get(name)
@@ -31,7 +92,17 @@ impl PluginRegistry {
     }
 
     pub fn list_engines(&self) -> Vec<&str> {
-        self.factories.keys().map(|s| s.as_str()).collect()
+        let mut names: Vec<&str> = self
+            .factories
+            .keys()
+            .chain(self.session_factories.keys())
+            .chain(self.shared_factories.keys())
+            .chain(self.plugin_engines.keys())
+            .map(|s| s.as_str())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
     }
 }
 
@@ -94,4 +165,100 @@ mod tests {
         let engine = registry.create("null").unwrap();
         assert_eq!(engine.name(), "null");
     }
+
+    #[test]
+    fn test_registry_create_session_adapts_engine() {
+        let registry = PluginRegistry::new();
+        let session = registry.create_session("null").unwrap();
+        assert_eq!(session.name(), "null");
+        assert!(matches!(
+            registry.create_session("nope"),
+            Err(AsrError::EngineNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_registry_session_factory_takes_precedence() {
+        let mut registry = PluginRegistry::new();
+        registry.register_session("null", || {
+            Box::new(crate::session::EngineAdapter::new(Box::new(RenamedEngine)))
+        });
+        registry.register_session("native", || {
+            Box::new(crate::session::EngineAdapter::new(Box::new(RenamedEngine)))
+        });
+        assert_eq!(registry.create_session("null").unwrap().name(), "renamed");
+        // Legacy lookups are unaffected
+        assert_eq!(registry.create("null").unwrap().name(), "null");
+        assert!(registry.create("native").is_err());
+        // Exactly the built-in engines plus "native", with "null" listed once
+        let builtin = PluginRegistry::new();
+        let mut expected = builtin.list_engines();
+        expected.push("native");
+        expected.sort_unstable();
+        assert_eq!(registry.list_engines(), expected);
+    }
+
+    #[test]
+    fn test_registry_shared_engines() {
+        let mut registry = PluginRegistry::new();
+        registry.register_shared("echo", || Box::new(EchoEngine));
+        assert_eq!(registry.create_shared("echo").unwrap().name(), "echo");
+        assert!(registry.create_shared("null").is_none());
+        assert!(registry.list_engines().contains(&"echo"));
+
+        // A session registered under the same name wins
+        registry.register_session("echo", || {
+            Box::new(crate::session::EngineAdapter::new(Box::new(RenamedEngine)))
+        });
+        assert!(registry.create_shared("echo").is_none());
+        assert_eq!(registry.list_engines().iter().filter(|&&e| e == "echo").count(), 1);
+    }
+
+    #[cfg(feature = "vosk")]
+    #[test]
+    fn test_registry_vosk_is_a_streaming_session() {
+        let registry = PluginRegistry::new();
+        assert!(registry.create_shared("vosk").is_none());
+        let session = registry.create_session("vosk").unwrap();
+        assert_eq!(session.name(), "vosk");
+        assert!(session.capabilities().partial_results);
+    }
+
+    struct EchoEngine;
+
+    #[async_trait::async_trait]
+    impl SharedEngine for EchoEngine {
+        fn name(&self) -> &str {
+            "echo"
+        }
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn transcribe(&self, _segment: voxmux_core::AudioChunk) -> Result<String, AsrError> {
+            Ok(String::new())
+        }
+    }
+
+    struct RenamedEngine;
+
+    #[async_trait::async_trait]
+    impl AsrEngine for RenamedEngine {
+        fn name(&self) -> &str {
+            "renamed"
+        }
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn feed_audio(&self, _chunk: voxmux_core::AudioChunk) -> Result<(), AsrError> {
+            Ok(())
+        }
+        fn set_result_sender(
+            &mut self,
+            _sender: tokio::sync::mpsc::UnboundedSender<voxmux_core::RecognitionResult>,
+        ) {
+        }
+        async fn shutdown(&self) -> Result<(), AsrError> {
+            Ok(())
+        }
+    }
 }
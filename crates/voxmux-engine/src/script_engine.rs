@@ -0,0 +1,438 @@
+use crate::capabilities::EngineCapabilities;
+use crate::session::{AsrSession, ResultStream};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::path::Path;
+use tokio::sync::mpsc;
+use voxmux_core::{AsrError, AudioChunk, RecognitionResult};
+
+/// One scripted result, emitted once `end` seconds of audio have been fed.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ScriptCue {
+    #[serde(default)]
+    pub start: Option<f64>,
+    pub end: f64,
+    pub text: String,
+    #[serde(rename = "final", default = "default_final")]
+    pub is_final: bool,
+    #[serde(default)]
+    pub confidence: Option<f32>,
+}
+
+fn default_final() -> bool {
+    true
+}
+
+fn script_error(path: &str, line: usize, msg: impl std::fmt::Display) -> AsrError {
+    AsrError::InitializationFailed(format!("{path}:{line}: {msg}"))
+}
+
+/// Parse a JSON-lines script: one cue object per line, `#` comments allowed.
+pub fn parse_jsonl(path: &str, content: &str) -> Result<Vec<ScriptCue>, AsrError> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| serde_json::from_str(line).map_err(|e| script_error(path, i + 1, e)))
+        .collect()
+}
+
+/// `HH:MM:SS,mmm` (or `.mmm`) in seconds. The fraction may have any number
+/// of digits, so `01.5` is one and a half seconds.
+fn parse_srt_time(s: &str) -> Option<f64> {
+    let (hms, fraction) = s.trim().split_once([',', '.'])?;
+    let mut parts = hms.split(':').map(|p| p.parse::<u64>().ok());
+    let (h, m, sec) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some()
+        || fraction.is_empty()
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let whole = h as f64 * 3600.0 + m as f64 * 60.0 + sec as f64;
+    Some(whole + format!("0.{fraction}").parse::<f64>().ok()?)
+}
+
+/// Parse an SRT script. Each cue is a final result at its end time; tags at
+/// the start of its text mark a partial (`{partial}`) or set the confidence
+/// (`{confidence=0.8}`).
+pub fn parse_srt(path: &str, content: &str) -> Result<Vec<ScriptCue>, AsrError> {
+    let mut cues = Vec::new();
+    let mut lines = content.lines().enumerate().peekable();
+    while let Some((_, line)) = lines.peek() {
+        if line.trim().is_empty() {
+            lines.next();
+            continue;
+        }
+        // Index line, then timing line
+        let (index_line, _) = lines.next().unwrap();
+        let (timing_line, timing) = lines
+            .next()
+            .ok_or_else(|| script_error(path, index_line + 1, "cue has no timing line"))?;
+        let (start, end) = timing
+            .split_once("-->")
+            .and_then(|(a, b)| Some((parse_srt_time(a)?, parse_srt_time(b)?)))
+            .ok_or_else(|| script_error(path, timing_line + 1, "invalid timing line"))?;
+
+        let mut text_lines = Vec::new();
+        while let Some((_, line)) = lines.next_if(|(_, l)| !l.trim().is_empty()) {
+            text_lines.push(line.trim());
+        }
+        let mut text = text_lines.join(" ");
+        let mut cue = ScriptCue {
+            start: Some(start),
+            end,
+            text: String::new(),
+            is_final: true,
+            confidence: None,
+        };
+        while let Some(rest) = text.strip_prefix('{') {
+            let Some((tag, after)) = rest.split_once('}') else {
+                break;
+            };
+            match tag.split_once('=') {
+                None if tag == "partial" => cue.is_final = false,
+                Some(("confidence", value)) => {
+                    cue.confidence = Some(value.parse().map_err(|_| {
+                        script_error(path, timing_line + 2, format!("invalid confidence '{value}'"))
+                    })?)
+                }
+                _ => break,
+            }
+            text = after.trim_start().to_string();
+        }
+        cue.text = text;
+        cues.push(cue);
+    }
+    Ok(cues)
+}
+
+// ── ScriptSession ──────────────────────────────────────────────
+
+/// Replays a transcript file for deterministic tests of routing, formatting
+/// and the TUI.
+///
+/// A cue is emitted when the audio fed to the session reaches its `end`
+/// time, so results arrive at realistic moments relative to the input (with
+/// VAD on, only speech is fed).
+///
+/// Config: `path` to a `.jsonl` or `.srt` script, optional `format`
+/// (`"jsonl"` or `"srt"`, by default from the extension), `loop` to start
+/// over when the script ends, and `min_confidence` to drop cues below it.
+pub struct ScriptSession {
+    cues: Vec<ScriptCue>,
+    /// Index of the next cue to emit.
+    next: usize,
+    looped: bool,
+    /// Added to cue times on each pass through a looping script.
+    offset: f64,
+    /// Seconds of audio fed so far.
+    position: f64,
+    tx: mpsc::UnboundedSender<RecognitionResult>,
+    rx: Option<ResultStream>,
+}
+
+impl ScriptSession {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            cues: Vec::new(),
+            next: 0,
+            looped: false,
+            offset: 0.0,
+            position: 0.0,
+            tx,
+            rx: Some(rx),
+        }
+    }
+
+    /// Seconds of audio fed so far.
+    pub fn position(&self) -> f64 {
+        self.position
+    }
+
+    /// Emit every cue due at the current position.
+    fn emit_due(&mut self) {
+        let Some(length) = self.cues.last().map(|c| c.end) else {
+            return;
+        };
+        loop {
+            if self.next == self.cues.len() {
+                // A script of zero length would loop forever
+                if !self.looped || length <= 0.0 {
+                    return;
+                }
+                self.next = 0;
+                self.offset += length;
+            }
+            let cue = &self.cues[self.next];
+            let at = self.offset + cue.end;
+            // Tolerate rounding from summing chunk durations
+            if at > self.position + 1e-9 {
+                return;
+            }
+            // Without a start time the cue follows the previous one
+            let start = cue.start.unwrap_or_else(|| match self.next {
+                0 => 0.0,
+                i => self.cues[i - 1].end,
+            });
+            let _ = self.tx.send(RecognitionResult {
+                text: cue.text.clone(),
+                input_id: String::new(),
+                timestamp: at,
+                is_final: cue.is_final,
+                offset: self.offset + start,
+                confidence: cue.confidence,
+                ..Default::default()
+            });
+            self.next += 1;
+        }
+    }
+}
+
+impl Default for ScriptSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl AsrSession for ScriptSession {
+    fn name(&self) -> &str {
+        "script"
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            partial_results: self.cues.iter().any(|c| !c.is_final),
+            ..Default::default()
+        }
+    }
+
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError> {
+        let path = config
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                AsrError::InitializationFailed("missing 'path' in script config".to_string())
+            })?;
+        let format = match config.get("format").and_then(|v| v.as_str()) {
+            Some(format) => format.to_string(),
+            None => Path::new(path)
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or_default()
+                .to_ascii_lowercase(),
+        };
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| AsrError::InitializationFailed(format!("{path}: {e}")))?;
+        let mut cues = match format.as_str() {
+            "jsonl" => parse_jsonl(path, &content)?,
+            "srt" => parse_srt(path, &content)?,
+            other => {
+                return Err(AsrError::InitializationFailed(format!(
+                    "unknown script format '{other}' (expected \"jsonl\" or \"srt\")"
+                )))
+            }
+        };
+        if let Some(min) = config.get("min_confidence").and_then(|v| v.as_float()) {
+            cues.retain(|c| c.confidence.is_none_or(|conf| f64::from(conf) >= min));
+        }
+        cues.sort_by(|a, b| a.end.total_cmp(&b.end));
+
+        tracing::info!(path = %path, cues = cues.len(), "ScriptSession initialized");
+        self.cues = cues;
+        self.looped = config.get("loop").and_then(|v| v.as_bool()).unwrap_or(false);
+        Ok(())
+    }
+
+    fn take_results(&mut self) -> Option<ResultStream> {
+        self.rx.take()
+    }
+
+    async fn push_audio(&mut self, chunk: AudioChunk) -> Result<(), AsrError> {
+        if chunk.sample_rate > 0 && chunk.channels > 0 {
+            let frames = chunk.samples.len() / chunk.channels as usize;
+            self.position += frames as f64 / f64::from(chunk.sample_rate);
+        }
+        self.emit_due();
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<(), AsrError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSONL: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/script.jsonl");
+    const SRT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/script.srt");
+
+    fn config(entries: &[(&str, toml::Value)]) -> toml::Value {
+        toml::Value::Table(
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+        )
+    }
+
+    /// `seconds` of mono audio at 16 kHz.
+    fn audio(seconds: f64) -> AudioChunk {
+        AudioChunk {
+            samples: vec![0.0; (seconds * 16000.0) as usize],
+            sample_rate: 16000,
+            channels: 1,
+        }
+    }
+
+    fn drain(results: &mut ResultStream) -> Vec<(String, bool, f64)> {
+        let mut out = Vec::new();
+        while let Ok(r) = results.try_recv() {
+            out.push((r.text, r.is_final, r.timestamp));
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn test_script_jsonl_is_timed_against_audio() {
+        let mut session = ScriptSession::new();
+        let mut results = session.take_results().unwrap();
+        session
+            .initialize(config(&[("path", JSONL.into())]))
+            .await
+            .unwrap();
+        assert!(session.capabilities().partial_results);
+
+        session.push_audio(audio(0.5)).await.unwrap();
+        assert!(drain(&mut results).is_empty());
+        session.push_audio(audio(1.0)).await.unwrap();
+        assert_eq!(
+            drain(&mut results),
+            vec![
+                ("CQ CQ".to_string(), false, 1.0),
+                ("CQ CQ this is".to_string(), false, 1.4),
+            ]
+        );
+        session.push_audio(audio(2.0)).await.unwrap();
+        assert_eq!(
+            drain(&mut results),
+            vec![("CQ CQ this is Kilo Seven".to_string(), true, 2.0)]
+        );
+        assert_eq!(session.position(), 3.5);
+    }
+
+    #[tokio::test]
+    async fn test_script_reports_offset_and_confidence() {
+        let mut session = ScriptSession::new();
+        let mut results = session.take_results().unwrap();
+        session
+            .initialize(config(&[("path", SRT.into()), ("loop", true.into())]))
+            .await
+            .unwrap();
+        session.push_audio(audio(6.0)).await.unwrap();
+        let mut spans = Vec::new();
+        while let Ok(r) = results.try_recv() {
+            spans.push((r.text, r.offset, r.timestamp, r.confidence));
+        }
+        assert_eq!(spans[0], ("Net control".to_string(), 1.0, 1.5, Some(0.8)));
+        assert_eq!(spans[1].3, Some(0.3));
+        assert_eq!(spans[2], ("Mayday mayday".to_string(), 3.0, 4.0, None));
+        // Repeats are offset by the script's length
+        assert_eq!(spans[3].1, 5.0);
+    }
+
+    #[tokio::test]
+    async fn test_script_srt_with_tags() {
+        let mut session = ScriptSession::new();
+        let mut results = session.take_results().unwrap();
+        session
+            .initialize(config(&[("path", SRT.into())]))
+            .await
+            .unwrap();
+        // Stereo at 48 kHz: time is counted in frames
+        session
+            .push_audio(AudioChunk {
+                samples: vec![0.0; 48000 * 2 * 5],
+                sample_rate: 48000,
+                channels: 2,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            drain(&mut results),
+            vec![
+                ("Net control".to_string(), false, 1.5),
+                ("Net control, go ahead".to_string(), true, 2.25),
+                ("Mayday mayday".to_string(), true, 4.0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_script_min_confidence_and_loop() {
+        let mut session = ScriptSession::new();
+        let mut results = session.take_results().unwrap();
+        session
+            .initialize(config(&[
+                ("path", SRT.into()),
+                ("min_confidence", 0.5.into()),
+                ("loop", true.into()),
+            ]))
+            .await
+            .unwrap();
+        // The 0.3-confidence cue is dropped; the script repeats every 4 s
+        session.push_audio(audio(8.0)).await.unwrap();
+        let texts: Vec<(String, f64)> = drain(&mut results)
+            .into_iter()
+            .map(|(t, _, at)| (t, at))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                ("Net control".to_string(), 1.5),
+                ("Mayday mayday".to_string(), 4.0),
+                ("Net control".to_string(), 5.5),
+                ("Mayday mayday".to_string(), 8.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let err = parse_jsonl("s.jsonl", "{\"end\": 1, \"text\": \"a\"}\n\n{\"text\": \"b\"}")
+            .unwrap_err();
+        assert!(err.to_string().contains("s.jsonl:3"), "{err}");
+
+        let err = parse_srt("s.srt", "1\n00:00:01,000 -> 00:00:02,000\nhello\n").unwrap_err();
+        assert!(err.to_string().contains("s.srt:2"), "{err}");
+        assert_eq!(parse_srt_time("01:02:03,450"), Some(3723.45));
+        assert_eq!(parse_srt_time("1:2"), None);
+        assert_eq!(parse_srt_time("00:00:01.5"), Some(1.5));
+        assert_eq!(parse_srt_time("00:00:01,05"), Some(1.05));
+        assert_eq!(parse_srt_time("00:00:01,"), None);
+        assert_eq!(parse_srt_time("00:00:01,+5"), None);
+        // Past u32 seconds without overflowing
+        assert_eq!(parse_srt_time("4294967296:00:00,000"), Some(4294967296.0 * 3600.0));
+    }
+
+    #[tokio::test]
+    async fn test_script_config_validation() {
+        let mut session = ScriptSession::new();
+        assert!(session.initialize(config(&[])).await.is_err());
+        assert!(session
+            .initialize(config(&[("path", "/nonexistent/script.jsonl".into())]))
+            .await
+            .is_err());
+        match session
+            .initialize(config(&[("path", JSONL.into()), ("format", "vtt".into())]))
+            .await
+        {
+            Err(AsrError::InitializationFailed(msg)) => assert!(msg.contains("vtt")),
+            _ => panic!("expected InitializationFailed"),
+        }
+    }
+}
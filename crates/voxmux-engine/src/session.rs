@@ -0,0 +1,156 @@
+use crate::capabilities::EngineCapabilities;
+use crate::engine_trait::AsrEngine;
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use voxmux_core::{AsrError, AudioChunk, RecognitionResult};
+
+/// Recognition results of one session, in the order they were produced.
+pub type ResultStream = mpsc::UnboundedReceiver<RecognitionResult>;
+
+/// A streaming recognition session over a single input.
+///
+/// The host owns the session exclusively and calls its methods in order, so
+/// implementations keep their state in plain fields. Results are delivered on
+/// the stream returned by [`take_results`](Self::take_results), which may be
+/// fed from a background thread.
+#[async_trait]
+pub trait AsrSession: Send {
+    /// Returns the engine's plugin name (e.g. `"null"`, `"whisper"`).
+    fn name(&self) -> &str;
+    /// Audio format and buffering this engine wants; the host converts
+    /// captured audio accordingly before `push_audio`.
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities::default()
+    }
+    /// One-time initialisation with engine-specific TOML configuration.
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError>;
+    /// Hand over the result stream. Returns `None` once taken.
+    fn take_results(&mut self) -> Option<ResultStream>;
+    /// Append captured audio to the current utterance.
+    async fn push_audio(&mut self, chunk: AudioChunk) -> Result<(), AsrError>;
+    /// Called when voice activity detection opens an utterance.
+    async fn begin_utterance(&mut self) -> Result<(), AsrError> {
+        Ok(())
+    }
+    /// The current utterance is complete; emit its final result.
+    async fn end_utterance(&mut self) -> Result<(), AsrError> {
+        Ok(())
+    }
+    /// Finalise any buffered audio now (end of stream).
+    async fn flush(&mut self) -> Result<(), AsrError> {
+        Ok(())
+    }
+    /// Discard buffered audio and partial hypotheses without emitting them.
+    async fn reset(&mut self) -> Result<(), AsrError> {
+        Ok(())
+    }
+    /// Release resources. No other method is called afterwards.
+    async fn shutdown(&mut self) -> Result<(), AsrError>;
+}
+
+// ── EngineAdapter ──────────────────────────────────────────────
+
+/// Runs an [`AsrEngine`] as an [`AsrSession`].
+pub struct EngineAdapter {
+    engine: Box<dyn AsrEngine>,
+    results: Option<ResultStream>,
+}
+
+impl EngineAdapter {
+    pub fn new(mut engine: Box<dyn AsrEngine>) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        engine.set_result_sender(tx);
+        Self {
+            engine,
+            results: Some(rx),
+        }
+    }
+}
+
+#[async_trait]
+impl AsrSession for EngineAdapter {
+    fn name(&self) -> &str {
+        self.engine.name()
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        self.engine.capabilities()
+    }
+
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError> {
+        self.engine.initialize(config).await
+    }
+
+    fn take_results(&mut self) -> Option<ResultStream> {
+        self.results.take()
+    }
+
+    async fn push_audio(&mut self, chunk: AudioChunk) -> Result<(), AsrError> {
+        self.engine.feed_audio(chunk).await
+    }
+
+    async fn begin_utterance(&mut self) -> Result<(), AsrError> {
+        self.engine.begin_utterance().await
+    }
+
+    async fn end_utterance(&mut self) -> Result<(), AsrError> {
+        self.engine.end_utterance().await
+    }
+
+    async fn shutdown(&mut self) -> Result<(), AsrError> {
+        self.engine.shutdown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NullEngine;
+
+    fn chunk(len: usize) -> AudioChunk {
+        AudioChunk {
+            samples: vec![0.0; len],
+            sample_rate: 16000,
+            channels: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_adapter_forwards_audio_and_results() {
+        let mut session = EngineAdapter::new(Box::new(NullEngine::new()));
+        assert_eq!(session.name(), "null");
+        session
+            .initialize(toml::Value::Table(Default::default()))
+            .await
+            .unwrap();
+        let mut results = session.take_results().expect("stream");
+        assert!(session.take_results().is_none());
+
+        session.push_audio(chunk(160)).await.unwrap();
+        assert_eq!(results.recv().await.unwrap().text, "[null] 160 samples");
+    }
+
+    #[tokio::test]
+    async fn test_adapter_session_defaults_succeed() {
+        let mut session = EngineAdapter::new(Box::new(NullEngine::new()));
+        session.begin_utterance().await.unwrap();
+        session.end_utterance().await.unwrap();
+        session.flush().await.unwrap();
+        session.reset().await.unwrap();
+        session.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_adapter_stream_ends_with_session() {
+        let mut session = EngineAdapter::new(Box::new(NullEngine::new()));
+        let mut results = session.take_results().unwrap();
+        drop(session);
+        assert!(results.recv().await.is_none());
+    }
+
+    #[test]
+    fn test_adapter_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<EngineAdapter>();
+    }
+}
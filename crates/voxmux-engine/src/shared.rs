@@ -0,0 +1,219 @@
+use crate::capabilities::{EngineCapabilities, ProcessingMode};
+use crate::pool::{PoolClient, PoolReply};
+use crate::session::{AsrSession, ResultStream};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use voxmux_core::{AsrError, AudioChunk};
+
+/// A segment-at-a-time engine that can serve many inputs at once.
+///
+/// The host creates one instance per distinct (engine, configuration) pair,
+/// so a model is loaded only once, and runs segments from every input using
+/// it on the shared [`InferencePool`](crate::InferencePool).
+#[async_trait]
+pub trait SharedEngine: Send + Sync {
+    /// Returns the engine's plugin name (e.g. `"whisper"`).
+    fn name(&self) -> &str;
+    /// Audio format the engine wants. Shared engines are normally batch
+    /// engines, so each segment is one utterance.
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            mode: ProcessingMode::Batch,
+            ..Default::default()
+        }
+    }
+    /// One-time initialisation with engine-specific TOML configuration.
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError>;
+    /// Transcribe one segment. An empty string means nothing was recognised.
+    async fn transcribe(&self, segment: AudioChunk) -> Result<String, AsrError>;
+}
+
+// ── PooledSession ──────────────────────────────────────────────
+
+/// An input's session on a [`SharedEngine`]: each delivered segment is
+/// queued on the pool, and results arrive on the session's stream.
+///
+/// Segments are transcribed after `push_audio` returns, so an engine error
+/// is reported by the following calls instead, until a segment succeeds.
+pub struct PooledSession {
+    engine: Arc<dyn SharedEngine>,
+    client: PoolClient,
+    tx: mpsc::UnboundedSender<PoolReply>,
+    rx: Option<ResultStream>,
+    /// The error of the last transcribed segment, if it failed.
+    failure: Arc<Mutex<Option<AsrError>>>,
+}
+
+impl PooledSession {
+    /// Must be called within a Tokio runtime: a task relays the pool's
+    /// replies to the result stream.
+    pub fn new(engine: Arc<dyn SharedEngine>, client: PoolClient) -> Self {
+        let (tx, mut replies) = mpsc::unbounded_channel::<PoolReply>();
+        let (results_tx, rx) = mpsc::unbounded_channel();
+        let failure = Arc::new(Mutex::new(None));
+        let last = failure.clone();
+        tokio::spawn(async move {
+            while let Some(reply) = replies.recv().await {
+                let result = match reply {
+                    Ok(result) => result,
+                    Err(e) => {
+                        *last.lock().unwrap() = Some(e);
+                        continue;
+                    }
+                };
+                *last.lock().unwrap() = None;
+                if !result.text.is_empty() {
+                    let _ = results_tx.send(result);
+                }
+            }
+        });
+        Self {
+            engine,
+            client,
+            tx,
+            rx: Some(rx),
+            failure,
+        }
+    }
+
+    fn check(&self) -> Result<(), AsrError> {
+        match self.failure.lock().unwrap().clone() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl AsrSession for PooledSession {
+    fn name(&self) -> &str {
+        self.engine.name()
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        self.engine.capabilities()
+    }
+
+    /// The shared engine is initialised once by the host.
+    async fn initialize(&mut self, _config: toml::Value) -> Result<(), AsrError> {
+        Ok(())
+    }
+
+    fn take_results(&mut self) -> Option<ResultStream> {
+        self.rx.take()
+    }
+
+    async fn push_audio(&mut self, chunk: AudioChunk) -> Result<(), AsrError> {
+        self.client
+            .submit(self.engine.clone(), chunk, self.tx.clone());
+        self.check()
+    }
+
+    async fn end_utterance(&mut self) -> Result<(), AsrError> {
+        self.check()
+    }
+
+    async fn flush(&mut self) -> Result<(), AsrError> {
+        self.check()
+    }
+
+    async fn reset(&mut self) -> Result<(), AsrError> {
+        self.client.cancel();
+        Ok(())
+    }
+
+    /// Queued segments still complete; the stream ends after the last one.
+    async fn shutdown(&mut self) -> Result<(), AsrError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InferencePool;
+    use voxmux_core::config::AsrPoolConfig;
+
+    /// Reports the segment length; fails segments of one sample.
+    struct LengthEngine;
+
+    #[async_trait]
+    impl SharedEngine for LengthEngine {
+        fn name(&self) -> &str {
+            "length"
+        }
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), AsrError> {
+            Ok(())
+        }
+        async fn transcribe(&self, segment: AudioChunk) -> Result<String, AsrError> {
+            match segment.samples.len() {
+                0 => Ok(String::new()),
+                1 => Err(AsrError::ProcessingFailed("too short".to_string())),
+                n => Ok(format!("{n} samples")),
+            }
+        }
+    }
+
+    fn segment(len: usize) -> AudioChunk {
+        AudioChunk {
+            samples: vec![0.0; len],
+            sample_rate: 16000,
+            channels: 1,
+        }
+    }
+
+    #[test]
+    fn test_shared_engine_defaults_to_batch() {
+        assert_eq!(LengthEngine.capabilities().mode, ProcessingMode::Batch);
+    }
+
+    #[tokio::test]
+    async fn test_pooled_session_results_and_end_of_stream() {
+        let pool = InferencePool::new(AsrPoolConfig::default());
+        let workers = pool.start();
+        let mut session = PooledSession::new(Arc::new(LengthEngine), pool.register("mic1"));
+        assert_eq!(session.name(), "length");
+        let mut results = session.take_results().unwrap();
+
+        session.push_audio(segment(0)).await.unwrap();
+        session.push_audio(segment(320)).await.unwrap();
+        session.shutdown().await.unwrap();
+        drop(session);
+
+        let r = results.recv().await.unwrap();
+        assert_eq!((r.text.as_str(), r.input_id.as_str()), ("320 samples", "mic1"));
+        // Empty transcriptions are not reported
+        assert!(results.recv().await.is_none());
+
+        pool.close();
+        for w in workers {
+            w.await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pooled_session_reports_errors_until_a_segment_succeeds() {
+        let pool = InferencePool::new(AsrPoolConfig::default());
+        pool.start();
+        let mut session = PooledSession::new(Arc::new(LengthEngine), pool.register("mic1"));
+        let mut results = session.take_results().unwrap();
+
+        session.push_audio(segment(1)).await.unwrap();
+        for _ in 0..100 {
+            if session.flush().await.is_err() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        match session.end_utterance().await {
+            Err(AsrError::ProcessingFailed(msg)) => assert_eq!(msg, "too short"),
+            other => panic!("expected ProcessingFailed, got {other:?}"),
+        }
+        // The error stays until a later segment is transcribed
+        assert!(session.push_audio(segment(320)).await.is_err());
+        assert_eq!(results.recv().await.unwrap().text, "320 samples");
+        session.flush().await.unwrap();
+        pool.close();
+    }
+}
@@ -0,0 +1,352 @@
+use crate::conditioner::AudioConditioner;
+use crate::host::check_language;
+use crate::pool::PoolClient;
+use crate::registry::PluginRegistry;
+use crate::session::{AsrSession, ResultStream};
+use crate::shared::{PooledSession, SharedEngine};
+use crate::utterance::UtteranceClock;
+use crate::vad::VadEvent;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use voxmux_core::config::AsrSupervisorConfig;
+use voxmux_core::{
+    AsrError, AudioChunk, EngineHealth, EngineState, RecognitionResult, TapReceiver,
+};
+
+// ── EngineMonitor ──────────────────────────────────────────────
+
+/// Read-only view of an input's engine health, for the TUI.
+#[derive(Clone, Default)]
+pub struct EngineMonitor {
+    health: Arc<Mutex<EngineHealth>>,
+}
+
+impl EngineMonitor {
+    pub fn health(&self) -> EngineHealth {
+        self.health.lock().unwrap().clone()
+    }
+
+    fn update(&self, f: impl FnOnce(&mut EngineHealth)) {
+        f(&mut self.health.lock().unwrap());
+    }
+}
+
+// ── EngineRecipe ───────────────────────────────────────────────
+
+pub(crate) enum EngineSource {
+    /// A per-input session created by the registry.
+    Registry(Arc<PluginRegistry>),
+    /// A session on an already loaded shared engine.
+    Shared(Arc<dyn SharedEngine>, PoolClient),
+}
+
+/// Everything needed to create an input's engine again.
+pub(crate) struct EngineRecipe {
+    pub engine_name: String,
+    pub config: toml::Value,
+    pub source: EngineSource,
+}
+
+impl EngineRecipe {
+    /// Create and initialise a session, returning it with its result stream.
+    pub(crate) async fn build(&self) -> Result<(Box<dyn AsrSession>, ResultStream), AsrError> {
+        let mut engine: Box<dyn AsrSession> = match &self.source {
+            EngineSource::Registry(registry) => registry.create_session(&self.engine_name)?,
+            EngineSource::Shared(shared, client) => {
+                Box::new(PooledSession::new(shared.clone(), client.clone()))
+            }
+        };
+        check_language(&self.engine_name, &engine.capabilities(), &self.config)?;
+        let results = engine.take_results().ok_or_else(|| {
+            AsrError::InitializationFailed(format!(
+                "engine '{}' has no result stream",
+                self.engine_name
+            ))
+        })?;
+        engine.initialize(self.config.clone()).await?;
+        Ok((engine, results))
+    }
+}
+
+// ── Backoff ────────────────────────────────────────────────────
+
+/// Exponentially growing restart delays, bounded in number.
+pub(crate) struct Backoff {
+    initial: Duration,
+    max: Duration,
+    max_attempts: u32,
+    attempts: u32,
+}
+
+impl Backoff {
+    pub(crate) fn new(config: &AsrSupervisorConfig) -> Self {
+        Self {
+            initial: Duration::from_millis(config.restart_delay_ms),
+            max: Duration::from_millis(config.max_restart_delay_ms),
+            max_attempts: config.max_restarts,
+            attempts: 0,
+        }
+    }
+
+    /// Delay before the next attempt, or `None` once attempts are exhausted.
+    pub(crate) fn next_delay(&mut self) -> Option<Duration> {
+        if self.attempts >= self.max_attempts {
+            return None;
+        }
+        let delay = self
+            .initial
+            .saturating_mul(2u32.saturating_pow(self.attempts))
+            .min(self.max);
+        self.attempts += 1;
+        Some(delay)
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.attempts = 0;
+    }
+}
+
+// ── SupervisedEngine ───────────────────────────────────────────
+
+/// An input's engine, restarted from its recipe when calls keep failing or
+/// its result stream closes. Its results are completed by the input's
+/// [`UtteranceClock`].
+pub(crate) struct SupervisedEngine {
+    input_id: String,
+    clock: UtteranceClock,
+    recipe: EngineRecipe,
+    /// `None` while restarting and once failed.
+    engine: Option<Box<dyn AsrSession>>,
+    results: Option<ResultStream>,
+    conditioner: AudioConditioner,
+    max_errors: u32,
+    healthy_after: Duration,
+    backoff: Backoff,
+    errors: u32,
+    started: Instant,
+    monitor: EngineMonitor,
+}
+
+impl SupervisedEngine {
+    pub(crate) fn new(
+        input_id: &str,
+        recipe: EngineRecipe,
+        engine: Box<dyn AsrSession>,
+        results: ResultStream,
+        config: &AsrSupervisorConfig,
+        monitor: EngineMonitor,
+    ) -> Self {
+        Self {
+            input_id: input_id.to_string(),
+            clock: UtteranceClock::new(input_id, &recipe.engine_name, &recipe.config),
+            recipe,
+            conditioner: AudioConditioner::new(&engine.capabilities()),
+            engine: Some(engine),
+            results: Some(results),
+            max_errors: config.max_errors.max(1),
+            healthy_after: Duration::from_millis(config.healthy_after_ms),
+            backoff: Backoff::new(config),
+            errors: 0,
+            started: Instant::now(),
+            monitor,
+        }
+    }
+
+    /// Account for a chunk read from the input's tap, before segmenting it.
+    pub(crate) fn receive(&mut self, chunk: &AudioChunk) {
+        self.clock.advance(chunk);
+    }
+
+    /// Next result from the engine; `None` means its stream closed. Pends
+    /// forever while there is no engine.
+    pub(crate) async fn next_result(&mut self) -> Option<RecognitionResult> {
+        let mut result = match self.results.as_mut() {
+            Some(results) => results.recv().await?,
+            None => std::future::pending().await,
+        };
+        self.clock.stamp(&mut result);
+        Some(result)
+    }
+
+    /// Forward one segmenter event in the engine's preferred format. Returns
+    /// the reason once failures call for a restart.
+    pub(crate) async fn dispatch(&mut self, event: VadEvent) -> Option<String> {
+        match event {
+            VadEvent::SpeechStart => self.clock.speech_start(),
+            VadEvent::SpeechEnd => self.clock.speech_end(),
+            VadEvent::Audio(_) => {}
+        }
+        let engine = self.engine.as_mut()?;
+        let mut results = Vec::new();
+        match event {
+            VadEvent::SpeechStart => results.push(engine.begin_utterance().await),
+            VadEvent::Audio(audio) => {
+                for chunk in self.conditioner.process(audio) {
+                    results.push(engine.push_audio(chunk).await);
+                }
+            }
+            VadEvent::SpeechEnd => {
+                if let Some(chunk) = self.conditioner.finish() {
+                    results.push(engine.push_audio(chunk).await);
+                }
+                results.push(engine.end_utterance().await);
+            }
+        }
+        let mut last_error = None;
+        for result in results {
+            match result {
+                Ok(()) => self.errors = 0,
+                Err(e) => {
+                    tracing::error!(input_id = %self.input_id, "engine feed error: {e}");
+                    self.errors += 1;
+                    last_error = Some(e.to_string());
+                }
+            }
+        }
+        match last_error {
+            Some(e) if self.errors >= self.max_errors => {
+                Some(format!("{} consecutive errors, last: {e}", self.errors))
+            }
+            Some(e) => {
+                self.monitor.update(|h| {
+                    h.state = EngineState::Degraded;
+                    h.last_error = Some(e);
+                });
+                None
+            }
+            None => {
+                if self.errors == 0 {
+                    self.monitor.update(|h| {
+                        if h.state == EngineState::Degraded {
+                            h.state = EngineState::Running;
+                        }
+                    });
+                }
+                None
+            }
+        }
+    }
+
+    /// Replace the engine, waiting out the backoff delay between attempts
+    /// and discarding the input's audio meanwhile. Marks the engine failed
+    /// once restarts are exhausted. Returns `false` if the tap closed.
+    pub(crate) async fn restart(&mut self, tap_rx: &mut TapReceiver, reason: &str) -> bool {
+        if let Some(mut engine) = self.engine.take() {
+            let _ = engine.shutdown().await;
+        }
+        self.results = None;
+        if self.started.elapsed() >= self.healthy_after {
+            self.backoff.reset();
+        }
+        let mut reason = reason.to_string();
+        loop {
+            let Some(delay) = self.backoff.next_delay() else {
+                tracing::error!(
+                    input_id = %self.input_id,
+                    engine = %self.recipe.engine_name,
+                    "engine failed, giving up: {reason}"
+                );
+                self.monitor.update(|h| {
+                    h.state = EngineState::Failed;
+                    h.last_error = Some(reason);
+                });
+                return true;
+            };
+            tracing::warn!(
+                input_id = %self.input_id,
+                engine = %self.recipe.engine_name,
+                "restarting engine in {} ms: {reason}",
+                delay.as_millis()
+            );
+            self.monitor.update(|h| {
+                h.state = EngineState::Restarting;
+                h.restarts += 1;
+                h.last_error = Some(reason.clone());
+            });
+
+            let sleep = tokio::time::sleep(delay);
+            tokio::pin!(sleep);
+            loop {
+                tokio::select! {
+                    _ = &mut sleep => break,
+                    chunk = tap_rx.recv() => match chunk {
+                        Some(chunk) => self.clock.advance(&chunk),
+                        None => return false,
+                    },
+                }
+            }
+
+            match self.recipe.build().await {
+                Ok((engine, results)) => {
+                    tracing::info!(input_id = %self.input_id, "engine restarted");
+                    self.conditioner = AudioConditioner::new(&engine.capabilities());
+                    self.engine = Some(engine);
+                    self.results = Some(results);
+                    self.errors = 0;
+                    self.started = Instant::now();
+                    self.monitor.update(|h| h.state = EngineState::Running);
+                    return true;
+                }
+                Err(e) => reason = e.to_string(),
+            }
+        }
+    }
+
+    /// Push any buffered audio, flush and shut the engine down, returning
+    /// the results it still produced.
+    pub(crate) async fn finish(&mut self) -> Vec<RecognitionResult> {
+        let Some(mut engine) = self.engine.take() else {
+            return Vec::new();
+        };
+        if let Some(rest) = self.conditioner.finish() {
+            if let Err(e) = engine.push_audio(rest).await {
+                tracing::error!(input_id = %self.input_id, "engine feed error: {e}");
+            }
+        }
+        if let Err(e) = engine.flush().await {
+            tracing::error!(input_id = %self.input_id, "engine flush error: {e}");
+        }
+        let _ = engine.shutdown().await;
+        drop(engine);
+        let mut remaining = Vec::new();
+        if let Some(mut results) = self.results.take() {
+            while let Some(mut result) = results.recv().await {
+                self.clock.stamp(&mut result);
+                remaining.push(result);
+            }
+        }
+        remaining
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let mut backoff = Backoff::new(&AsrSupervisorConfig {
+            restart_delay_ms: 100,
+            max_restart_delay_ms: 300,
+            max_restarts: 4,
+            ..Default::default()
+        });
+        let delays: Vec<_> = std::iter::from_fn(|| backoff.next_delay()).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 300, 300].map(Duration::from_millis).to_vec()
+        );
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_backoff_zero_restarts_gives_up_immediately() {
+        let mut backoff = Backoff::new(&AsrSupervisorConfig {
+            max_restarts: 0,
+            ..Default::default()
+        });
+        assert_eq!(backoff.next_delay(), None);
+    }
+}
@@ -0,0 +1,149 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use voxmux_core::{AudioChunk, TtsError};
+
+/// Text-to-speech backend. Produces audio at the engine's own sample rate and
+/// channel count; callers convert it to the mixer format.
+#[async_trait]
+pub trait TtsEngine: Send + Sync {
+    fn name(&self) -> &str;
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), TtsError>;
+    async fn synthesize(&self, text: &str) -> Result<AudioChunk, TtsError>;
+}
+
+// ── TtsRegistry ────────────────────────────────────────────────
+
+pub struct TtsRegistry {
+    factories: HashMap<String, fn() -> Box<dyn TtsEngine>>,
+}
+
+impl TtsRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            factories: HashMap::new(),
+        };
+        registry.register("null", || Box::new(NullTts::new()));
+        registry.register("exec", || Box::new(crate::exec_tts::ExecTts::new()));
+        registry
+    }
+
+    pub fn register(&mut self, name: &str, factory: fn() -> Box<dyn TtsEngine>) {
+        self.factories.insert(name.to_string(), factory);
+    }
+
+    pub fn create(&self, name: &str) -> Result<Box<dyn TtsEngine>, TtsError> {
+        self.factories
+            .get(name)
+            .map(|f| f())
+            .ok_or_else(|| TtsError::EngineNotFound(name.to_string()))
+    }
+
+    pub fn list_engines(&self) -> Vec<&str> {
+        self.factories.keys().map(|s| s.as_str()).collect()
+    }
+}
+
+impl Default for TtsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── NullTts ────────────────────────────────────────────────────
+
+/// Sample rate of [`NullTts`] output.
+pub const NULL_TTS_SAMPLE_RATE: u32 = 16000;
+
+/// Test engine: 50 ms of silence per character of text.
+pub struct NullTts {
+    synth_count: AtomicUsize,
+}
+
+impl NullTts {
+    pub fn new() -> Self {
+        Self {
+            synth_count: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn synth_count(&self) -> usize {
+        self.synth_count.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for NullTts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TtsEngine for NullTts {
+    fn name(&self) -> &str {
+        "null"
+    }
+
+    async fn initialize(&mut self, _config: toml::Value) -> Result<(), TtsError> {
+        Ok(())
+    }
+
+    async fn synthesize(&self, text: &str) -> Result<AudioChunk, TtsError> {
+        self.synth_count.fetch_add(1, Ordering::Relaxed);
+        let samples = text.chars().count() * NULL_TTS_SAMPLE_RATE as usize / 20;
+        Ok(AudioChunk {
+            samples: vec![0.0; samples],
+            sample_rate: NULL_TTS_SAMPLE_RATE,
+            channels: 1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tts_registry_has_builtin_engines() {
+        let registry = TtsRegistry::new();
+        let mut engines = registry.list_engines();
+        engines.sort();
+        assert_eq!(engines, vec!["exec", "null"]);
+        assert_eq!(registry.create("exec").unwrap().name(), "exec");
+    }
+
+    #[test]
+    fn test_tts_registry_unknown_engine() {
+        let registry = TtsRegistry::new();
+        match registry.create("nope") {
+            Err(TtsError::EngineNotFound(name)) => assert_eq!(name, "nope"),
+            _ => panic!("expected EngineNotFound error"),
+        }
+    }
+
+    #[test]
+    fn test_tts_registry_register_custom_engine() {
+        let mut registry = TtsRegistry::new();
+        registry.register("custom", || Box::new(NullTts::new()));
+        assert_eq!(registry.create("custom").unwrap().name(), "null");
+    }
+
+    #[tokio::test]
+    async fn test_null_tts_synthesizes_silence() {
+        let tts = NullTts::new();
+        let chunk = tts.synthesize("hello").await.unwrap();
+        assert_eq!(chunk.sample_rate, NULL_TTS_SAMPLE_RATE);
+        assert_eq!(chunk.channels, 1);
+        // 5 chars × 50 ms
+        assert_eq!(chunk.samples.len(), 4000);
+        assert!(chunk.samples.iter().all(|&s| s == 0.0));
+        assert_eq!(tts.synth_count(), 1);
+    }
+
+    #[test]
+    fn test_tts_engines_implement_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<NullTts>();
+        assert_send_sync::<crate::exec_tts::ExecTts>();
+    }
+}
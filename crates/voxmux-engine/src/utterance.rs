@@ -0,0 +1,190 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use voxmux_core::{AudioChunk, RecognitionResult};
+
+fn unix_now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+/// Tracks an input's stream position and utterance boundaries, and completes
+/// the results of its engine with them.
+///
+/// Results count as one utterance up to and including a final. Its span is
+/// the last VAD segment if VAD is on, otherwise the audio received since the
+/// previous final.
+pub(crate) struct UtteranceClock {
+    input_id: String,
+    engine: String,
+    language: Option<String>,
+    /// Wall-clock time at stream position 0.
+    epoch: Option<f64>,
+    /// Seconds of audio received.
+    position: f64,
+    /// Position at the start of the latest chunk.
+    chunk_start: f64,
+    utterance_id: u64,
+    /// Start of the VAD segment being fed.
+    open: Option<f64>,
+    /// Last complete VAD segment not yet finalised.
+    closed: Option<(f64, f64)>,
+    /// End of the last finalised utterance.
+    last_end: f64,
+}
+
+impl UtteranceClock {
+    pub(crate) fn new(input_id: &str, engine: &str, config: &toml::Value) -> Self {
+        Self {
+            input_id: input_id.to_string(),
+            engine: engine.to_string(),
+            language: config
+                .get("language")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            epoch: None,
+            position: 0.0,
+            chunk_start: 0.0,
+            utterance_id: 1,
+            open: None,
+            closed: None,
+            last_end: 0.0,
+        }
+    }
+
+    /// Account for a chunk read from the input's tap.
+    pub(crate) fn advance(&mut self, chunk: &AudioChunk) {
+        let frames = chunk.samples.len() / chunk.channels.max(1) as usize;
+        let secs = frames as f64 / chunk.sample_rate.max(1) as f64;
+        self.chunk_start = self.position;
+        self.position += secs;
+        if self.epoch.is_none() {
+            self.epoch = Some(unix_now() - secs);
+        }
+    }
+
+    /// VAD detected speech in the latest chunk.
+    pub(crate) fn speech_start(&mut self) {
+        self.open = Some(self.chunk_start);
+    }
+
+    /// VAD closed the current segment at the end of the latest chunk.
+    pub(crate) fn speech_end(&mut self) {
+        if let Some(start) = self.open.take() {
+            self.closed = Some((start, self.position));
+        }
+    }
+
+    /// Fill in what the engine left unset. Engine-reported timing is kept.
+    pub(crate) fn stamp(&mut self, result: &mut RecognitionResult) {
+        let (start, end) = self
+            .closed
+            .or(self.open.map(|start| (start, self.position)))
+            .unwrap_or((self.last_end, self.position));
+
+        result.input_id = self.input_id.clone();
+        if result.utterance_id == 0 {
+            result.utterance_id = self.utterance_id;
+        }
+        if result.timestamp == 0.0 {
+            result.offset = start;
+            result.timestamp = end;
+        }
+        if let Some(epoch) = self.epoch {
+            result.started_at.get_or_insert(epoch + result.offset);
+            result.ended_at.get_or_insert(epoch + result.timestamp);
+        }
+        if result.engine.is_empty() {
+            result.engine = self.engine.clone();
+        }
+        if result.language.is_none() {
+            result.language = self.language.clone();
+        }
+
+        if result.is_final {
+            self.utterance_id += 1;
+            self.last_end = end;
+            self.closed = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(secs: f64) -> AudioChunk {
+        AudioChunk {
+            samples: vec![0.0; (secs * 16000.0) as usize],
+            sample_rate: 16000,
+            channels: 1,
+        }
+    }
+
+    fn result(text: &str, is_final: bool) -> RecognitionResult {
+        RecognitionResult {
+            text: text.to_string(),
+            is_final,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_clock_numbers_utterances_and_spans_audio() {
+        let config: toml::Value = toml::from_str("language = \"en\"").unwrap();
+        let mut clock = UtteranceClock::new("mic1", "null", &config);
+        clock.advance(&chunk(0.5));
+
+        let mut partial = result("hel", false);
+        clock.stamp(&mut partial);
+        clock.advance(&chunk(0.5));
+        let mut first = result("hello", true);
+        clock.stamp(&mut first);
+        clock.advance(&chunk(0.25));
+        let mut second = result("again", true);
+        clock.stamp(&mut second);
+
+        assert_eq!((partial.utterance_id, first.utterance_id), (1, 1));
+        assert_eq!(second.utterance_id, 2);
+        assert_eq!((first.offset, first.timestamp), (0.0, 1.0));
+        assert_eq!((second.offset, second.timestamp), (1.0, 1.25));
+        assert_eq!(first.input_id, "mic1");
+        assert_eq!(first.engine, "null");
+        assert_eq!(first.language.as_deref(), Some("en"));
+        let (start, end) = (first.started_at.unwrap(), first.ended_at.unwrap());
+        assert!((end - start - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_clock_uses_vad_segment() {
+        let mut clock = UtteranceClock::new("mic1", "null", &toml::Value::Table(Default::default()));
+        clock.advance(&chunk(1.0));
+        clock.advance(&chunk(0.5));
+        clock.speech_start();
+        clock.advance(&chunk(0.5));
+        clock.speech_end();
+        clock.advance(&chunk(1.0));
+
+        let mut r = result("roger", true);
+        clock.stamp(&mut r);
+        assert_eq!((r.offset, r.timestamp), (1.0, 2.0));
+        assert_eq!(r.language, None);
+    }
+
+    #[test]
+    fn test_clock_keeps_engine_values() {
+        let mut clock = UtteranceClock::new("mic1", "null", &toml::Value::Table(Default::default()));
+        clock.advance(&chunk(2.0));
+        let mut r = RecognitionResult {
+            offset: 0.5,
+            timestamp: 1.5,
+            language: Some("de".to_string()),
+            engine: "custom".to_string(),
+            ..result("hallo", true)
+        };
+        clock.stamp(&mut r);
+        assert_eq!((r.offset, r.timestamp), (0.5, 1.5));
+        assert_eq!(r.language.as_deref(), Some("de"));
+        assert_eq!(r.engine, "custom");
+    }
+}
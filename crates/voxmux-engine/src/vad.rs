@@ -0,0 +1,530 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use voxmux_core::config::VadConfig;
+use voxmux_core::{AsrError, AudioChunk, VadState};
+
+/// Analysis frame length for speech detection.
+pub const FRAME_MS: u32 = 20;
+
+// ── SpeechDetector ─────────────────────────────────────────────
+
+/// Scores audio frames for speech. Implementations may be simple signal
+/// statistics or wrap a trained model.
+pub trait SpeechDetector: Send {
+    /// Returns the detector's plugin name (e.g. `"energy"`).
+    fn name(&self) -> &str;
+    /// One-time configuration from the detector-specific keys of `[asr.vad]`.
+    fn configure(&mut self, _config: &toml::Value) -> Result<(), AsrError> {
+        Ok(())
+    }
+    /// Probability (0.0–1.0) that a mono frame of `FRAME_MS` contains speech.
+    fn speech_probability(&mut self, frame: &[f32], sample_rate: u32) -> f32;
+}
+
+// ── EnergyDetector ─────────────────────────────────────────────
+
+/// Frame energy against an adaptive noise floor, discounted for noise-like
+/// (high zero-crossing rate) spectra.
+pub struct EnergyDetector {
+    /// Required level above the noise floor.
+    snr_db: f32,
+    /// Frames quieter than this are never speech.
+    floor_dbfs: f32,
+    noise_db: f32,
+}
+
+/// How fast the noise floor follows louder frames (per frame).
+const NOISE_RISE_DB: f32 = 0.05;
+/// Zero-crossing rate above which a frame looks like broadband noise.
+const NOISY_ZCR: f32 = 0.4;
+
+impl EnergyDetector {
+    pub fn new() -> Self {
+        Self {
+            snr_db: 10.0,
+            floor_dbfs: -50.0,
+            noise_db: -120.0,
+        }
+    }
+}
+
+impl Default for EnergyDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpeechDetector for EnergyDetector {
+    fn name(&self) -> &str {
+        "energy"
+    }
+
+    fn configure(&mut self, config: &toml::Value) -> Result<(), AsrError> {
+        let float = |key: &str| -> Result<Option<f32>, AsrError> {
+            match config.get(key) {
+                None => Ok(None),
+                Some(v) => v
+                    .as_float()
+                    .or_else(|| v.as_integer().map(|i| i as f64))
+                    .map(|f| Some(f as f32))
+                    .ok_or_else(|| {
+                        AsrError::InitializationFailed(format!("VAD '{key}' must be a number"))
+                    }),
+            }
+        };
+        if let Some(snr) = float("snr_db")? {
+            self.snr_db = snr;
+        }
+        if let Some(floor) = float("floor_dbfs")? {
+            self.floor_dbfs = floor;
+        }
+        Ok(())
+    }
+
+    fn speech_probability(&mut self, frame: &[f32], _sample_rate: u32) -> f32 {
+        if frame.is_empty() {
+            return 0.0;
+        }
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        let db = 20.0 * rms.max(1e-6).log10();
+
+        // Track the quietest recent level: drop immediately, rise slowly
+        self.noise_db = if db > self.noise_db {
+            self.noise_db + NOISE_RISE_DB
+        } else {
+            db
+        };
+
+        let threshold = (self.noise_db + self.snr_db).max(self.floor_dbfs);
+        let mut p = ((db - threshold) / 10.0 + 0.5).clamp(0.0, 1.0);
+
+        let crossings = frame
+            .windows(2)
+            .filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0))
+            .count();
+        if crossings as f32 / frame.len() as f32 > NOISY_ZCR {
+            p *= 0.5;
+        }
+        p
+    }
+}
+
+// ── VadRegistry ────────────────────────────────────────────────
+
+pub struct VadRegistry {
+    factories: HashMap<String, fn() -> Box<dyn SpeechDetector>>,
+}
+
+impl VadRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            factories: HashMap::new(),
+        };
+        registry.register("energy", || Box::new(EnergyDetector::new()));
+        registry
+    }
+
+    pub fn register(&mut self, name: &str, factory: fn() -> Box<dyn SpeechDetector>) {
+        self.factories.insert(name.to_string(), factory);
+    }
+
+    /// Create and configure the detector named in `config`.
+    pub fn create(&self, config: &VadConfig) -> Result<Box<dyn SpeechDetector>, AsrError> {
+        let factory = self.factories.get(&config.detector).ok_or_else(|| {
+            AsrError::InitializationFailed(format!("unknown VAD detector: {}", config.detector))
+        })?;
+        let mut detector = factory();
+        detector.configure(&config.extra)?;
+        Ok(detector)
+    }
+}
+
+impl Default for VadRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── VadMonitor ─────────────────────────────────────────────────
+
+const STATE_SILENCE: u8 = 0;
+const STATE_SPEECH: u8 = 1;
+
+/// Read-only view of an input's VAD state, for the TUI.
+#[derive(Clone)]
+pub struct VadMonitor {
+    state: Arc<AtomicU8>,
+}
+
+impl VadMonitor {
+    pub fn state(&self) -> VadState {
+        match self.state.load(Ordering::Relaxed) {
+            STATE_SPEECH => VadState::Speech,
+            _ => VadState::Silence,
+        }
+    }
+}
+
+// ── Vad ────────────────────────────────────────────────────────
+
+/// Output of the segmenter, in stream order.
+#[derive(Debug, Clone)]
+pub enum VadEvent {
+    SpeechStart,
+    Audio(AudioChunk),
+    SpeechEnd,
+}
+
+/// Splits an input's audio stream into utterances.
+pub struct Vad {
+    detector: Box<dyn SpeechDetector>,
+    config: VadConfig,
+    state: Arc<AtomicU8>,
+    sample_rate: u32,
+    channels: u16,
+    /// Samples not yet forming a whole frame.
+    pending: Vec<f32>,
+    /// Recent audio kept while silent, replayed when speech starts.
+    pre_roll: VecDeque<f32>,
+    speech_ms: u32,
+    in_speech: bool,
+    utterance_ms: u32,
+    silence_ms: u32,
+}
+
+impl Vad {
+    pub fn new(config: &VadConfig, detector: Box<dyn SpeechDetector>) -> Self {
+        Self {
+            detector,
+            config: config.clone(),
+            state: Arc::new(AtomicU8::new(STATE_SILENCE)),
+            sample_rate: 0,
+            channels: 0,
+            pending: Vec::new(),
+            pre_roll: VecDeque::new(),
+            speech_ms: 0,
+            in_speech: false,
+            utterance_ms: 0,
+            silence_ms: 0,
+        }
+    }
+
+    pub fn monitor(&self) -> VadMonitor {
+        VadMonitor {
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    /// Feed raw audio; returns the resulting segment events.
+    pub fn process(&mut self, chunk: AudioChunk) -> Vec<VadEvent> {
+        let mut events = Vec::new();
+        let mut speech = Vec::new();
+        if chunk.sample_rate != self.sample_rate || chunk.channels != self.channels {
+            self.end_utterance(&mut speech, &mut events);
+            self.sample_rate = chunk.sample_rate;
+            self.channels = chunk.channels.max(1);
+            self.pending.clear();
+            self.pre_roll.clear();
+        }
+
+        self.pending.extend_from_slice(&chunk.samples);
+        let frame_len = self.frame_len();
+        let frames = self.pending.len() / frame_len;
+        let input: Vec<f32> = self.pending.drain(..frames * frame_len).collect();
+        for frame in input.chunks(frame_len) {
+            self.process_frame(frame, &mut speech, &mut events);
+        }
+        self.flush_audio(&mut speech, &mut events);
+        events
+    }
+
+    /// Close any open utterance, e.g. when the input stream ends.
+    pub fn flush(&mut self) -> Vec<VadEvent> {
+        let mut events = Vec::new();
+        let mut speech = std::mem::take(&mut self.pending);
+        if !self.in_speech {
+            speech.clear();
+        }
+        self.end_utterance(&mut speech, &mut events);
+        events
+    }
+
+    fn frame_len(&self) -> usize {
+        (self.sample_rate * FRAME_MS / 1000).max(1) as usize * self.channels as usize
+    }
+
+    fn ms_to_samples(&self, ms: u32) -> usize {
+        (self.sample_rate as u64 * ms as u64 / 1000) as usize * self.channels as usize
+    }
+
+    fn process_frame(&mut self, frame: &[f32], speech: &mut Vec<f32>, events: &mut Vec<VadEvent>) {
+        let channels = self.channels as usize;
+        let mono: Vec<f32> = frame
+            .chunks(channels)
+            .map(|f| f.iter().sum::<f32>() / channels as f32)
+            .collect();
+        let is_speech =
+            self.detector.speech_probability(&mono, self.sample_rate) >= self.config.threshold;
+
+        if !self.in_speech {
+            self.pre_roll.extend(frame);
+            let keep = self.ms_to_samples(self.config.pre_roll_ms + self.config.min_speech_ms);
+            let excess = self.pre_roll.len().saturating_sub(keep);
+            self.pre_roll.drain(..excess);
+
+            self.speech_ms = if is_speech { self.speech_ms + FRAME_MS } else { 0 };
+            if self.speech_ms >= self.config.min_speech_ms.max(FRAME_MS) {
+                self.start_utterance(events);
+                speech.extend(self.pre_roll.drain(..));
+            }
+            return;
+        }
+
+        speech.extend_from_slice(frame);
+        self.utterance_ms += FRAME_MS;
+        self.silence_ms = if is_speech { 0 } else { self.silence_ms + FRAME_MS };
+
+        if self.silence_ms >= self.config.hangover_ms {
+            self.end_utterance(speech, events);
+        } else if self.utterance_ms >= self.config.max_utterance_ms {
+            // Split long speech; the next utterance continues immediately
+            self.end_utterance(speech, events);
+            self.start_utterance(events);
+        }
+    }
+
+    fn start_utterance(&mut self, events: &mut Vec<VadEvent>) {
+        events.push(VadEvent::SpeechStart);
+        self.in_speech = true;
+        self.utterance_ms = 0;
+        self.silence_ms = 0;
+        self.state.store(STATE_SPEECH, Ordering::Relaxed);
+    }
+
+    fn end_utterance(&mut self, speech: &mut Vec<f32>, events: &mut Vec<VadEvent>) {
+        if !self.in_speech {
+            return;
+        }
+        self.flush_audio(speech, events);
+        events.push(VadEvent::SpeechEnd);
+        self.in_speech = false;
+        self.speech_ms = 0;
+        self.state.store(STATE_SILENCE, Ordering::Relaxed);
+    }
+
+    fn flush_audio(&self, speech: &mut Vec<f32>, events: &mut Vec<VadEvent>) {
+        if speech.is_empty() {
+            return;
+        }
+        events.push(VadEvent::Audio(AudioChunk {
+            samples: std::mem::take(speech),
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16000;
+
+    fn tone(ms: u32, amplitude: f32) -> Vec<f32> {
+        (0..RATE * ms / 1000)
+            .map(|i| (i as f32 * 0.12).sin() * amplitude)
+            .collect()
+    }
+
+    fn chunk(samples: Vec<f32>) -> AudioChunk {
+        AudioChunk {
+            samples,
+            sample_rate: RATE,
+            channels: 1,
+        }
+    }
+
+    fn vad(config: VadConfig) -> Vad {
+        let detector = VadRegistry::new().create(&config).unwrap();
+        Vad::new(&config, detector)
+    }
+
+    /// Summarise events as S (start), E (end) and audio lengths in ms.
+    fn summary(events: &[VadEvent]) -> Vec<String> {
+        events
+            .iter()
+            .map(|e| match e {
+                VadEvent::SpeechStart => "S".to_string(),
+                VadEvent::SpeechEnd => "E".to_string(),
+                VadEvent::Audio(c) => (c.samples.len() as u32 * 1000 / RATE).to_string(),
+            })
+            .collect()
+    }
+
+    fn run(vad: &mut Vad, parts: Vec<Vec<f32>>) -> Vec<VadEvent> {
+        parts.into_iter().flat_map(|p| vad.process(chunk(p))).collect()
+    }
+
+    #[test]
+    fn test_energy_detector_speech_vs_silence() {
+        let mut d = EnergyDetector::new();
+        let quiet = vec![0.0005f32; 320];
+        for _ in 0..10 {
+            assert!(d.speech_probability(&quiet, RATE) < 0.5);
+        }
+        assert!(d.speech_probability(&tone(20, 0.3), RATE) > 0.9);
+    }
+
+    #[test]
+    fn test_energy_detector_adapts_to_noise_floor() {
+        let mut d = EnergyDetector::new();
+        let hum = tone(20, 0.05);
+        // A steady loud hum stops counting as speech once the floor catches up
+        let first = d.speech_probability(&hum, RATE);
+        let later = (0..2000).map(|_| d.speech_probability(&hum, RATE)).last().unwrap();
+        assert!(first < 0.5 || later < first);
+        assert!(later < 0.5);
+    }
+
+    #[test]
+    fn test_energy_detector_discounts_noise_like_frames() {
+        let mut d = EnergyDetector::new();
+        let alternating: Vec<f32> = (0..320).map(|i| if i % 2 == 0 { 0.02 } else { -0.02 }).collect();
+        let smooth: Vec<f32> = tone(20, 0.02);
+        let p_noise = d.speech_probability(&alternating, RATE);
+        let mut d = EnergyDetector::new();
+        let p_tone = d.speech_probability(&smooth, RATE);
+        assert!(p_noise < p_tone);
+    }
+
+    #[test]
+    fn test_registry_unknown_detector() {
+        let config = VadConfig {
+            detector: "silero".to_string(),
+            ..Default::default()
+        };
+        match VadRegistry::new().create(&config) {
+            Err(AsrError::InitializationFailed(msg)) => assert!(msg.contains("silero")),
+            _ => panic!("expected InitializationFailed"),
+        }
+    }
+
+    #[test]
+    fn test_registry_configures_detector() {
+        let config = VadConfig {
+            extra: toml::toml! { snr_db = "loud" }.into(),
+            ..Default::default()
+        };
+        assert!(VadRegistry::new().create(&config).is_err());
+    }
+
+    #[test]
+    fn test_vad_silence_only_emits_nothing() {
+        let mut vad = vad(VadConfig::default());
+        let events = run(&mut vad, vec![vec![0.0; 16000]]);
+        assert!(events.is_empty());
+        assert_eq!(vad.monitor().state(), VadState::Silence);
+    }
+
+    #[test]
+    fn test_vad_segments_utterance_with_pre_roll_and_hangover() {
+        let mut vad = vad(VadConfig {
+            pre_roll_ms: 200,
+            hangover_ms: 300,
+            min_speech_ms: 40,
+            ..Default::default()
+        });
+        let events = run(
+            &mut vad,
+            vec![vec![0.0; 16000], tone(500, 0.3), vec![0.0; 16000]],
+        );
+        // 200 ms pre-roll + 500 ms speech, then 300 ms hangover from the next chunk
+        assert_eq!(summary(&events), vec!["S", "700", "300", "E"]);
+        assert_eq!(vad.monitor().state(), VadState::Silence);
+    }
+
+    #[test]
+    fn test_vad_reports_speech_state() {
+        let mut vad = vad(VadConfig::default());
+        let monitor = vad.monitor();
+        run(&mut vad, vec![vec![0.0; 1600], tone(200, 0.3)]);
+        assert_eq!(monitor.state(), VadState::Speech);
+    }
+
+    #[test]
+    fn test_vad_short_pause_stays_in_utterance() {
+        let mut vad = vad(VadConfig {
+            hangover_ms: 300,
+            ..Default::default()
+        });
+        let events = run(
+            &mut vad,
+            vec![tone(300, 0.3), vec![0.0; 3200], tone(300, 0.3), vec![0.0; 8000]],
+        );
+        let s = summary(&events);
+        assert_eq!(s.iter().filter(|e| *e == "S").count(), 1);
+        assert_eq!(s.last().unwrap(), "E");
+    }
+
+    #[test]
+    fn test_vad_splits_at_max_length() {
+        let mut vad = vad(VadConfig {
+            max_utterance_ms: 1000,
+            pre_roll_ms: 0,
+            min_speech_ms: 20,
+            ..Default::default()
+        });
+        let events = run(&mut vad, vec![tone(2500, 0.3)]);
+        // The first utterance also carries the 20 ms frame that confirmed speech
+        assert_eq!(
+            summary(&events),
+            vec!["S", "1020", "E", "S", "1000", "E", "S", "480"]
+        );
+    }
+
+    #[test]
+    fn test_vad_ignores_clicks_shorter_than_min_speech() {
+        let mut vad = vad(VadConfig {
+            min_speech_ms: 100,
+            ..Default::default()
+        });
+        let events = run(&mut vad, vec![vec![0.0; 1600], tone(40, 0.3), vec![0.0; 8000]]);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_vad_flush_closes_open_utterance() {
+        let mut vad = vad(VadConfig::default());
+        let events = run(&mut vad, vec![tone(300, 0.3)]);
+        assert_eq!(summary(&events)[0], "S");
+        let events = vad.flush();
+        assert_eq!(summary(&events).last().unwrap(), "E");
+        assert!(vad.flush().is_empty());
+    }
+
+    #[test]
+    fn test_vad_handles_unaligned_chunks() {
+        let mut vad = vad(VadConfig {
+            pre_roll_ms: 0,
+            hangover_ms: 100,
+            min_speech_ms: 20,
+            ..Default::default()
+        });
+        let signal: Vec<f32> = tone(400, 0.3).into_iter().chain(vec![0.0; 4800]).collect();
+        let events: Vec<VadEvent> = signal
+            .chunks(123)
+            .flat_map(|c| vad.process(chunk(c.to_vec())))
+            .collect();
+        let audio: usize = events
+            .iter()
+            .map(|e| match e {
+                VadEvent::Audio(c) => c.samples.len(),
+                _ => 0,
+            })
+            .sum();
+        // 400 ms of speech plus 100 ms hangover
+        assert_eq!(audio, 8000);
+        assert!(matches!(events.last(), Some(VadEvent::SpeechEnd)));
+    }
+}
@@ -0,0 +1,508 @@
+use crate::capabilities::{EngineCapabilities, ProcessingMode};
+use crate::session::{AsrSession, ResultStream};
+use async_trait::async_trait;
+use std::path::Path;
+use tokio::sync::mpsc;
+use voxmux_core::{AsrError, AudioChunk, RecognitionResult};
+
+/// Sample rate Vosk models are trained on.
+const VOSK_SAMPLE_RATE: u32 = 16000;
+
+/// The calls the session makes on a Vosk recognizer.
+trait Decoder: Send {
+    /// Decode 16-bit PCM. Returns `true` when an utterance was finalised
+    /// (endpoint detected), so `result` holds its text.
+    fn accept(&mut self, pcm: &[i16]) -> Result<bool, AsrError>;
+    fn partial(&mut self) -> String;
+    fn result(&mut self) -> String;
+    /// Finalise whatever has been decoded so far.
+    fn final_result(&mut self) -> String;
+    fn reset(&mut self);
+}
+
+/// A `vosk::Recognizer` and the model it decodes with.
+struct VoskDecoder {
+    recognizer: vosk::Recognizer,
+    _model: vosk::Model,
+}
+
+impl VoskDecoder {
+    /// Load the model (slow for large models) and create a recognizer,
+    /// restricted to `grammar` when it is not empty.
+    fn load(model_path: &str, grammar: &[String]) -> Result<Self, AsrError> {
+        let model = vosk::Model::new(model_path).ok_or_else(|| {
+            AsrError::InitializationFailed(format!(
+                "failed to load vosk model from '{model_path}'"
+            ))
+        })?;
+        let rate = VOSK_SAMPLE_RATE as f32;
+        let recognizer = if grammar.is_empty() {
+            vosk::Recognizer::new(&model, rate)
+        } else {
+            vosk::Recognizer::new_with_grammar(&model, rate, grammar)
+        }
+        .ok_or_else(|| {
+            AsrError::InitializationFailed("failed to create vosk recognizer".to_string())
+        })?;
+        Ok(Self {
+            recognizer,
+            _model: model,
+        })
+    }
+}
+
+fn complete_text(result: vosk::CompleteResult) -> String {
+    result
+        .single()
+        .map(|r| r.text.to_string())
+        .unwrap_or_default()
+}
+
+impl Decoder for VoskDecoder {
+    fn accept(&mut self, pcm: &[i16]) -> Result<bool, AsrError> {
+        match self.recognizer.accept_waveform(pcm) {
+            Ok(vosk::DecodingState::Finalized) => Ok(true),
+            Ok(vosk::DecodingState::Running) => Ok(false),
+            Ok(vosk::DecodingState::Failed) => Err(AsrError::ProcessingFailed(
+                "vosk failed to decode audio".to_string(),
+            )),
+            Err(e) => Err(AsrError::ProcessingFailed(e.to_string())),
+        }
+    }
+    fn partial(&mut self) -> String {
+        self.recognizer.partial_result().partial.to_string()
+    }
+    fn result(&mut self) -> String {
+        complete_text(self.recognizer.result())
+    }
+    fn final_result(&mut self) -> String {
+        complete_text(self.recognizer.final_result())
+    }
+    fn reset(&mut self) {
+        self.recognizer.reset();
+    }
+}
+
+/// Low-latency offline streaming recognition with partial results.
+///
+/// Config: `model_path` (a Vosk model directory), optional `grammar` (phrases
+/// to restrict recognition to; add `"[unk]"` to allow other words) and
+/// `partial_results` (default `true`).
+pub struct VoskSession {
+    decoder: Option<Box<dyn Decoder>>,
+    grammar: Vec<String>,
+    partial_results: bool,
+    /// Last partial sent, to report only changes.
+    last_partial: String,
+    tx: mpsc::UnboundedSender<RecognitionResult>,
+    rx: Option<ResultStream>,
+}
+
+impl VoskSession {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            decoder: None,
+            grammar: Vec::new(),
+            partial_results: true,
+            last_partial: String::new(),
+            tx,
+            rx: Some(rx),
+        }
+    }
+
+    /// Phrases recognition is restricted to; empty means the full vocabulary.
+    pub fn grammar(&self) -> &[String] {
+        &self.grammar
+    }
+
+    fn decoder(&mut self) -> Result<&mut Box<dyn Decoder>, AsrError> {
+        self.decoder
+            .as_mut()
+            .ok_or_else(|| AsrError::ProcessingFailed("vosk session not initialized".to_string()))
+    }
+
+    fn emit(&mut self, text: String, is_final: bool) {
+        if is_final {
+            self.last_partial.clear();
+        } else if text == self.last_partial {
+            return;
+        } else {
+            self.last_partial = text.clone();
+        }
+        if text.is_empty() {
+            return;
+        }
+        let _ = self.tx.send(RecognitionResult {
+            text,
+            input_id: String::new(),
+            timestamp: 0.0,
+            is_final,
+            ..Default::default()
+        });
+    }
+
+    /// Emit the decoder's final result for the current utterance.
+    fn finalize(&mut self) -> Result<(), AsrError> {
+        let text = self.decoder()?.final_result();
+        self.emit(text, true);
+        Ok(())
+    }
+}
+
+impl Default for VoskSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl AsrSession for VoskSession {
+    fn name(&self) -> &str {
+        "vosk"
+    }
+
+    /// 16 kHz mono in 100 ms chunks.
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            sample_rate: Some(VOSK_SAMPLE_RATE),
+            channels: Some(1),
+            mode: ProcessingMode::Streaming { chunk_ms: 100 },
+            partial_results: self.partial_results,
+            max_segment_ms: None,
+            languages: Vec::new(),
+        }
+    }
+
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError> {
+        let model_path = config
+            .get("model_path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                AsrError::InitializationFailed("missing 'model_path' in vosk config".to_string())
+            })?;
+        if !Path::new(model_path).is_dir() {
+            return Err(AsrError::InitializationFailed(format!(
+                "vosk model directory '{model_path}' not found"
+            )));
+        }
+
+        let invalid_grammar = || {
+            AsrError::InitializationFailed(
+                "vosk 'grammar' must be a list of strings without quotes".to_string(),
+            )
+        };
+        let grammar: Vec<String> = match config.get("grammar") {
+            None => Vec::new(),
+            Some(toml::Value::Array(phrases)) => phrases
+                .iter()
+                .map(|p| {
+                    // Phrases are passed to Vosk as a JSON list, unescaped
+                    p.as_str()
+                        .filter(|p| !p.contains(['"', '\\']))
+                        .map(str::to_string)
+                        .ok_or_else(invalid_grammar)
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err(invalid_grammar()),
+        };
+        let partial_results = config
+            .get("partial_results")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(self.partial_results);
+
+        let path = model_path.to_string();
+        let phrases = grammar.clone();
+        let decoder = tokio::task::spawn_blocking(move || VoskDecoder::load(&path, &phrases))
+            .await
+            .map_err(|e| AsrError::InitializationFailed(e.to_string()))??;
+
+        tracing::info!(
+            model_path = %model_path,
+            grammar = grammar.len(),
+            "VoskSession initialized"
+        );
+        self.grammar = grammar;
+        self.partial_results = partial_results;
+        self.decoder = Some(Box::new(decoder));
+        Ok(())
+    }
+
+    fn take_results(&mut self) -> Option<ResultStream> {
+        self.rx.take()
+    }
+
+    async fn push_audio(&mut self, chunk: AudioChunk) -> Result<(), AsrError> {
+        let pcm: Vec<i16> = chunk
+            .samples
+            .iter()
+            .map(|&s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+            .collect();
+        let partial_results = self.partial_results;
+        let decoder = self.decoder()?;
+        if decoder.accept(&pcm)? {
+            let text = decoder.result();
+            self.emit(text, true);
+        } else if partial_results {
+            let text = decoder.partial();
+            self.emit(text, false);
+        }
+        Ok(())
+    }
+
+    async fn end_utterance(&mut self) -> Result<(), AsrError> {
+        self.finalize()
+    }
+
+    async fn flush(&mut self) -> Result<(), AsrError> {
+        self.finalize()
+    }
+
+    async fn reset(&mut self) -> Result<(), AsrError> {
+        self.decoder()?.reset();
+        self.last_partial.clear();
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<(), AsrError> {
+        self.decoder = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Recognises one word per 1600 samples and finalises every third word,
+    /// like an endpointing recognizer.
+    struct ScriptedDecoder {
+        words: Vec<&'static str>,
+        samples: usize,
+    }
+
+    impl ScriptedDecoder {
+        fn text(&self) -> String {
+            self.words[..(self.samples / 1600).min(self.words.len())].join(" ")
+        }
+    }
+
+    impl Decoder for ScriptedDecoder {
+        fn accept(&mut self, pcm: &[i16]) -> Result<bool, AsrError> {
+            self.samples += pcm.len();
+            Ok(self.samples >= 3 * 1600)
+        }
+        fn partial(&mut self) -> String {
+            self.text()
+        }
+        fn result(&mut self) -> String {
+            let text = self.text();
+            self.samples = 0;
+            text
+        }
+        fn final_result(&mut self) -> String {
+            self.result()
+        }
+        fn reset(&mut self) {
+            self.samples = 0;
+        }
+    }
+
+    fn scripted() -> VoskSession {
+        let mut session = VoskSession::new();
+        session.decoder = Some(Box::new(ScriptedDecoder {
+            words: vec!["alpha", "bravo", "charlie"],
+            samples: 0,
+        }));
+        session
+    }
+
+    fn chunk(len: usize) -> AudioChunk {
+        AudioChunk {
+            samples: vec![0.1; len],
+            sample_rate: VOSK_SAMPLE_RATE,
+            channels: 1,
+        }
+    }
+
+    fn drain(results: &mut ResultStream) -> Vec<(String, bool)> {
+        let mut out = Vec::new();
+        while let Ok(r) = results.try_recv() {
+            out.push((r.text, r.is_final));
+        }
+        out
+    }
+
+    fn table(entries: &[(&str, toml::Value)]) -> toml::Value {
+        toml::Value::Table(
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_vosk_emits_changed_partials_then_final() {
+        let mut session = scripted();
+        let mut results = session.take_results().unwrap();
+        for _ in 0..6 {
+            session.push_audio(chunk(800)).await.unwrap();
+        }
+        assert_eq!(
+            drain(&mut results),
+            vec![
+                ("alpha".to_string(), false),
+                ("alpha bravo".to_string(), false),
+                ("alpha bravo charlie".to_string(), true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vosk_end_utterance_finalizes() {
+        let mut session = scripted();
+        let mut results = session.take_results().unwrap();
+        session.push_audio(chunk(1600)).await.unwrap();
+        session.end_utterance().await.unwrap();
+        // Nothing pending: no empty final
+        session.flush().await.unwrap();
+        assert_eq!(
+            drain(&mut results),
+            vec![("alpha".to_string(), false), ("alpha".to_string(), true)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vosk_partials_can_be_disabled_and_reset() {
+        let mut session = scripted();
+        session.partial_results = false;
+        let mut results = session.take_results().unwrap();
+        session.push_audio(chunk(3200)).await.unwrap();
+        session.reset().await.unwrap();
+        session.flush().await.unwrap();
+        assert!(drain(&mut results).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_vosk_requires_initialization() {
+        let mut session = VoskSession::new();
+        assert!(matches!(
+            session.push_audio(chunk(160)).await,
+            Err(AsrError::ProcessingFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_vosk_config_validation() {
+        let mut session = VoskSession::new();
+        match session.initialize(table(&[])).await {
+            Err(AsrError::InitializationFailed(msg)) => assert!(msg.contains("model_path")),
+            _ => panic!("expected InitializationFailed"),
+        }
+        let missing = table(&[("model_path", "/nonexistent/vosk-model".into())]);
+        assert!(session.initialize(missing).await.is_err());
+
+        let dir = std::env::temp_dir().display().to_string();
+        for grammar in [vec![1.into()], vec!["say \"hi\"".into()]] {
+            let bad_grammar = table(&[
+                ("model_path", dir.clone().into()),
+                ("grammar", toml::Value::Array(grammar)),
+            ]);
+            assert!(session.initialize(bad_grammar).await.is_err());
+        }
+
+        // A directory that holds no model
+        match session.initialize(table(&[("model_path", dir.into())])).await {
+            Err(AsrError::InitializationFailed(msg)) => assert!(msg.contains("load"), "{msg}"),
+            _ => panic!("expected InitializationFailed"),
+        }
+        assert!(session.decoder.is_none());
+    }
+
+    #[test]
+    fn test_vosk_capabilities() {
+        let caps = VoskSession::new().capabilities();
+        assert_eq!(caps.sample_rate, Some(16000));
+        assert_eq!(caps.channels, Some(1));
+        assert_eq!(caps.mode, ProcessingMode::Streaming { chunk_ms: 100 });
+        assert!(caps.partial_results);
+    }
+
+    /// Transcript of `python/example/test.wav` from the vosk-api repository
+    /// with `vosk-model-small-en-us-0.15`.
+    const FIXTURE_TEXT: &str = "one zero zero zero one nine oh two one oh zero one eight zero three";
+
+    fn fixture_audio() -> Vec<f32> {
+        let path = std::env::var("VOSK_TEST_WAV").expect("VOSK_TEST_WAV not set");
+        let mut reader = hound::WavReader::open(path).unwrap();
+        assert_eq!(reader.spec().sample_rate, VOSK_SAMPLE_RATE);
+        assert_eq!(reader.spec().channels, 1);
+        reader
+            .samples::<i16>()
+            .map(|s| s.unwrap() as f32 / i16::MAX as f32)
+            .collect()
+    }
+
+    async fn transcribe(config: toml::Value) -> Vec<(String, bool)> {
+        let mut session = VoskSession::new();
+        let mut results = session.take_results().unwrap();
+        session.initialize(config).await.unwrap();
+        for samples in fixture_audio().chunks(1600) {
+            session
+                .push_audio(AudioChunk {
+                    samples: samples.to_vec(),
+                    sample_rate: VOSK_SAMPLE_RATE,
+                    channels: 1,
+                })
+                .await
+                .unwrap();
+        }
+        session.flush().await.unwrap();
+        drain(&mut results)
+    }
+
+    fn finals(results: &[(String, bool)]) -> String {
+        results
+            .iter()
+            .filter(|(_, is_final)| *is_final)
+            .map(|(text, _)| text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Run with a model and the vosk-api example recording:
+    /// `VOSK_TEST_MODEL=./models/vosk-model-small-en-us-0.15 VOSK_TEST_WAV=./test.wav
+    /// cargo test -p voxmux-engine --features vosk -- --ignored`
+    #[tokio::test]
+    #[ignore = "needs VOSK_TEST_MODEL and VOSK_TEST_WAV"]
+    async fn test_vosk_model_fixture() {
+        let model_path = std::env::var("VOSK_TEST_MODEL").expect("VOSK_TEST_MODEL not set");
+
+        let results = transcribe(table(&[("model_path", model_path.clone().into())])).await;
+        assert!(
+            results.iter().any(|(text, is_final)| !is_final && !text.is_empty()),
+            "no partial in {results:?}"
+        );
+        assert_eq!(finals(&results), FIXTURE_TEXT);
+
+        // A grammar limits recognition to its phrases
+        let grammar = ["one", "zero", "[unk]"];
+        let results = transcribe(table(&[
+            ("model_path", model_path.into()),
+            (
+                "grammar",
+                toml::Value::Array(grammar.iter().map(|&p| p.into()).collect()),
+            ),
+            ("partial_results", false.into()),
+        ]))
+        .await;
+        assert!(results.iter().all(|(_, is_final)| *is_final));
+        let text = finals(&results);
+        assert!(text.split_whitespace().any(|w| w == "one"), "{text}");
+        assert!(
+            text.split_whitespace().all(|w| grammar.contains(&w)),
+            "{text}"
+        );
+    }
+}
@@ -1,12 +1,24 @@
-use crate::engine_trait::AsrEngine;
-use voxmux_core::{AsrError, AudioChunk, RecognitionResult};
+use crate::capabilities::{EngineCapabilities, ProcessingMode};
+use crate::shared::SharedEngine;
+use voxmux_core::{AsrError, AudioChunk};
 use async_trait::async_trait;
-use tokio::sync::mpsc;
 
+/// Language codes accepted by multilingual Whisper models.
+const WHISPER_LANGUAGES: &[&str] = &[
+    "en", "zh", "de", "es", "ru", "ko", "fr", "ja", "pt", "tr", "pl", "ca", "nl", "ar", "sv",
+    "it", "id", "hi", "fi", "vi", "he", "uk", "el", "ms", "cs", "ro", "da", "hu", "ta", "no",
+    "th", "ur", "hr", "bg", "lt", "la", "mi", "ml", "cy", "sk", "te", "fa", "lv", "bn", "sr",
+    "az", "sl", "kn", "et", "mk", "br", "eu", "is", "hy", "ne", "mn", "bs", "kk", "sq", "sw",
+    "gl", "mr", "pa", "si", "km", "sn", "yo", "so", "af", "oc", "ka", "be", "tg", "sd", "gu",
+    "am", "yi", "lo", "uz", "fo", "ht", "ps", "tk", "nn", "mt", "sa", "lb", "my", "bo", "tl",
+    "mg", "as", "tt", "haw", "ln", "ha", "ba", "jw", "su", "auto",
+];
+
+/// Shared between inputs: one loaded model per distinct configuration.
 pub struct WhisperEngine {
     model_path: Option<String>,
     language: Option<String>,
-    result_sender: std::sync::Mutex<Option<mpsc::UnboundedSender<RecognitionResult>>>,
+    prompt: Option<String>,
 }
 
 impl WhisperEngine {
@@ -14,7 +26,7 @@ impl WhisperEngine {
         Self {
             model_path: None,
             language: None,
-            result_sender: std::sync::Mutex::new(None),
+            prompt: None,
         }
     }
 }
@@ -26,11 +38,23 @@ impl Default for WhisperEngine {
 }
 
 #[async_trait]
-impl AsrEngine for WhisperEngine {
+impl SharedEngine for WhisperEngine {
     fn name(&self) -> &str {
         "whisper"
     }
 
+    /// 16 kHz mono, one utterance (at most 30 s) at a time.
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            sample_rate: Some(16000),
+            channels: Some(1),
+            mode: ProcessingMode::Batch,
+            partial_results: false,
+            max_segment_ms: Some(30_000),
+            languages: WHISPER_LANGUAGES.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
     async fn initialize(&mut self, config: toml::Value) -> Result<(), AsrError> {
         let model_path = config
             .get("model_path")
@@ -45,25 +69,23 @@ impl AsrEngine for WhisperEngine {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        self.prompt = config
+            .get("prompt")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
         tracing::info!(
             model_path = %model_path,
             language = ?self.language,
+            prompt = ?self.prompt,
             "WhisperEngine initialized (stub — model not loaded)"
         );
         Ok(())
     }
 
-    async fn feed_audio(&self, _chunk: AudioChunk) -> Result<(), AsrError> {
+    async fn transcribe(&self, _segment: AudioChunk) -> Result<String, AsrError> {
         // Stub: real inference deferred to when whisper-rs is actually wired
-        Ok(())
-    }
-
-    fn set_result_sender(&mut self, sender: mpsc::UnboundedSender<RecognitionResult>) {
-        *self.result_sender.lock().unwrap() = Some(sender);
-    }
-
-    async fn shutdown(&self) -> Result<(), AsrError> {
-        Ok(())
+        Ok(String::new())
     }
 }
 
@@ -103,8 +125,23 @@ mod tests {
             "language".to_string(),
             toml::Value::String("ja".to_string()),
         );
+        table.insert(
+            "prompt".to_string(),
+            toml::Value::String("Kanto net".to_string()),
+        );
         let result = engine.initialize(toml::Value::Table(table)).await;
         assert!(result.is_ok());
+        assert_eq!(engine.prompt.as_deref(), Some("Kanto net"));
+    }
+
+    #[test]
+    fn test_whisper_engine_capabilities() {
+        let caps = WhisperEngine::new().capabilities();
+        assert_eq!(caps.sample_rate, Some(16000));
+        assert_eq!(caps.channels, Some(1));
+        assert_eq!(caps.mode, ProcessingMode::Batch);
+        assert!(caps.supports_language("ja"));
+        assert!(!caps.supports_language("xx"));
     }
 
     #[test]
@@ -0,0 +1,54 @@
+#!/usr/bin/env python3
+"""Example voxmux `exec` engine: reports how much audio it has heard.
+
+Protocol (see README, "Exec engine"): one JSON object per line on stdin;
+an "audio" line is followed by `samples` little-endian int16 values.
+Results are written to stdout as JSON lines.
+
+Settings read from the `config` table in the hello message:
+  crash_after  exit with status 1 after this many audio frames
+"""
+import json
+import sys
+
+
+def emit(text, final):
+    sys.stdout.write(json.dumps({"text": text, "final": final}) + "\n")
+    sys.stdout.flush()
+
+
+def main():
+    stdin = sys.stdin.buffer
+    hello = json.loads(stdin.readline())
+    config = hello.get("config", {})
+    crash_after = config.get("crash_after")
+    print(f"ready: {hello['sample_rate']} Hz, {hello['channels']} ch", file=sys.stderr, flush=True)
+
+    frames = 0
+    heard = 0
+    while True:
+        line = stdin.readline()
+        if not line:
+            break
+        msg = json.loads(line)
+        kind = msg["type"]
+        if kind == "audio":
+            stdin.read(msg["samples"] * 2)
+            frames += 1
+            heard += msg["samples"]
+            if crash_after is not None and frames >= crash_after:
+                print("crashing on purpose", file=sys.stderr, flush=True)
+                sys.exit(1)
+            emit(f"{heard} samples", False)
+        elif kind in ("end", "flush"):
+            if heard:
+                emit(f"{heard} samples", True)
+            heard = 0
+        elif kind == "reset":
+            heard = 0
+        elif kind == "shutdown":
+            break
+
+
+if __name__ == "__main__":
+    main()
@@ -1,5 +1,7 @@
 use voxmux_core::AudioChunk;
 use voxmux_engine::{AsrHost, PluginRegistry};
+use voxmux_plugin::host::Plugin;
+use voxmux_plugin::test_support;
 
 #[tokio::test]
 async fn test_full_pipeline_null_engine() {
@@ -128,3 +130,115 @@ async fn test_full_pipeline_large_chunk() {
     drop(tx);
     host.shutdown().await;
 }
+
+#[tokio::test]
+async fn test_full_pipeline_script_engine() {
+    let registry = PluginRegistry::new();
+    let mut host = AsrHost::new();
+    let mut rx = host.take_result_receiver().unwrap();
+
+    let mut config = toml::Table::new();
+    config.insert(
+        "path".into(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/script.jsonl").into(),
+    );
+    let tx = host
+        .add_input("mic1", "script", toml::Value::Table(config), &registry)
+        .await
+        .unwrap();
+    host.start();
+
+    // Two seconds of audio in 100 ms chunks
+    for _ in 0..20 {
+        tx.send(AudioChunk {
+            samples: vec![0.0; 4800],
+            sample_rate: 48000,
+            channels: 1,
+        })
+        .unwrap();
+    }
+
+    let mut results = Vec::new();
+    while let Ok(Some(r)) = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv()).await {
+        assert_eq!(r.input_id, "mic1");
+        let done = r.is_final;
+        results.push(r.text);
+        if done {
+            break;
+        }
+    }
+    assert_eq!(results, ["CQ CQ", "CQ CQ this is", "CQ CQ this is Kilo Seven"]);
+
+    drop(tx);
+    host.shutdown().await;
+}
+
+// ── Plugins ────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn test_full_pipeline_plugin_engine() {
+    let library = test_support::build_example_plugin(env!("CARGO_TARGET_TMPDIR"));
+    let plugin = Plugin::load(&library).unwrap();
+    assert_eq!(plugin.name(), "example");
+    assert_eq!(plugin.version(), "0.1.0");
+
+    let mut registry = PluginRegistry::new();
+    registry.register_plugin(&plugin);
+    assert!(registry.list_engines().contains(&"example-counter"));
+
+    let mut host = AsrHost::new();
+    let mut rx = host.take_result_receiver().unwrap();
+    let mut config = toml::Table::new();
+    config.insert("label".into(), "ex: ".into());
+    let tx = host
+        .add_input("mic1", "example-counter", toml::Value::Table(config), &registry)
+        .await
+        .unwrap();
+    host.start();
+
+    // 100 ms at the engine's own format passes through unchanged
+    tx.send(AudioChunk {
+        samples: vec![0.0; 1600],
+        sample_rate: 16000,
+        channels: 1,
+    })
+    .unwrap();
+    drop(tx);
+
+    let mut results = Vec::new();
+    while let Ok(Some(r)) = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv()).await {
+        assert_eq!(r.input_id, "mic1");
+        let done = r.is_final;
+        results.push((r.text, r.is_final));
+        if done {
+            break;
+        }
+    }
+    assert_eq!(
+        results,
+        vec![
+            ("ex: 1600 samples".to_string(), false),
+            ("ex: 1600 samples".to_string(), true),
+        ]
+    );
+    host.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_plugin_engine_rejects_bad_config() {
+    let library = test_support::build_example_plugin(env!("CARGO_TARGET_TMPDIR"));
+    let plugin = Plugin::load(&library).unwrap();
+    let mut registry = PluginRegistry::new();
+    registry.register_plugin(&plugin);
+
+    let mut host = AsrHost::new();
+    let mut config = toml::Table::new();
+    config.insert("label".into(), 1.into());
+    match host
+        .add_input("mic1", "example-counter", toml::Value::Table(config), &registry)
+        .await
+    {
+        Err(e) => assert!(e.to_string().contains("'label' must be a string")),
+        Ok(_) => panic!("expected an initialization error"),
+    }
+}
@@ -0,0 +1,117 @@
+//! Example voxmux plugin, loaded from a shared library.
+//!
+//! Build with `cargo build -p voxmux-example-plugin` and copy
+//! `libvoxmux_example_plugin.so` into the `[plugins] dir`. Provides:
+//! - engine `example-counter`: reports how much audio it has heard
+//! - destination `example-log`: appends `input<TAB>prefix+text` lines to `path`
+
+use std::io::Write;
+use voxmux_plugin::{Capabilities, DestinationPlugin, EnginePlugin};
+
+fn parse(config: &str) -> Result<toml::Table, String> {
+    config.parse::<toml::Table>().map_err(|e| e.to_string())
+}
+
+/// Emits "N samples" as a partial per chunk and as a final per utterance.
+/// Config: optional `label` prepended to every result.
+pub struct CounterEngine {
+    label: String,
+    heard: usize,
+    pending: Vec<(String, bool)>,
+}
+
+impl CounterEngine {
+    fn report(&mut self, is_final: bool) {
+        let text = format!("{}{} samples", self.label, self.heard);
+        self.pending.push((text, is_final));
+    }
+}
+
+impl EnginePlugin for CounterEngine {
+    fn new(config: &str) -> Result<Self, String> {
+        let config = parse(config)?;
+        let label = match config.get("label") {
+            None => String::new(),
+            Some(toml::Value::String(label)) => label.clone(),
+            Some(_) => return Err("'label' must be a string".to_string()),
+        };
+        Ok(Self {
+            label,
+            heard: 0,
+            pending: Vec::new(),
+        })
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            sample_rate: 16000,
+            channels: 1,
+            batch: false,
+            chunk_ms: 100,
+            partial_results: true,
+        }
+    }
+
+    fn push_audio(
+        &mut self,
+        samples: &[f32],
+        _sample_rate: u32,
+        _channels: u16,
+    ) -> Result<(), String> {
+        self.heard += samples.len();
+        self.report(false);
+        Ok(())
+    }
+
+    fn end_utterance(&mut self) -> Result<(), String> {
+        if self.heard > 0 {
+            self.report(true);
+        }
+        self.heard = 0;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        self.end_utterance()
+    }
+
+    fn reset(&mut self) -> Result<(), String> {
+        self.heard = 0;
+        Ok(())
+    }
+
+    fn poll_result(&mut self) -> Option<(String, bool)> {
+        (!self.pending.is_empty()).then(|| self.pending.remove(0))
+    }
+}
+
+/// Appends one line per message to the file at `path`.
+pub struct LogDestination {
+    file: std::fs::File,
+}
+
+impl DestinationPlugin for LogDestination {
+    fn new(config: &str) -> Result<Self, String> {
+        let config = parse(config)?;
+        let path = config
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or("missing 'path' in config")?;
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("{path}: {e}"))?;
+        Ok(Self { file })
+    }
+
+    fn send_text(&mut self, text: &str, input_id: &str, prefix: &str) -> Result<(), String> {
+        writeln!(self.file, "{input_id}\t{prefix}{text}").map_err(|e| e.to_string())
+    }
+}
+
+voxmux_plugin::declare_plugin! {
+    name: "example",
+    engines: ["example-counter" => CounterEngine],
+    destinations: ["example-log" => LogDestination],
+}
@@ -0,0 +1,149 @@
+//! `extern "C"` shims behind [`EngineVTable::of`](crate::EngineVTable::of)
+//! and [`DestinationVTable::of`](crate::DestinationVTable::of). Panics are
+//! caught here and reported as errors, since unwinding across the ABI is
+//! undefined behaviour.
+
+use crate::{Capabilities, DestinationPlugin, EnginePlugin, ErrorString};
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+fn into_c_string(s: String) -> *mut c_char {
+    CString::new(s.replace('\0', " "))
+        .expect("NUL bytes were replaced")
+        .into_raw()
+}
+
+fn guard<T>(f: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| Err("plugin panicked".to_string()))
+}
+
+fn status(result: Result<(), String>) -> ErrorString {
+    match result {
+        Ok(()) => ptr::null_mut(),
+        Err(msg) => into_c_string(msg),
+    }
+}
+
+unsafe fn str_arg<'a>(s: *const c_char) -> Result<&'a str, String> {
+    if s.is_null() {
+        return Ok("");
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|e| format!("invalid UTF-8 argument: {e}"))
+}
+
+unsafe fn create<T>(
+    config: *const c_char,
+    error: *mut ErrorString,
+    new: impl FnOnce(&str) -> Result<T, String>,
+) -> *mut c_void {
+    match guard(|| new(str_arg(config)?)) {
+        Ok(instance) => Box::into_raw(Box::new(instance)).cast(),
+        Err(msg) => {
+            if !error.is_null() {
+                *error = into_c_string(msg);
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe fn instance<'a, T>(handle: *mut c_void) -> &'a mut T {
+    &mut *handle.cast::<T>()
+}
+
+pub unsafe extern "C" fn free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+pub unsafe extern "C" fn destroy<T>(handle: *mut c_void) {
+    if !handle.is_null() {
+        let _ = catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(handle.cast::<T>()))));
+    }
+}
+
+// ── Engines ────────────────────────────────────────────────────
+
+pub unsafe extern "C" fn engine_create<E: EnginePlugin>(
+    config: *const c_char,
+    error: *mut ErrorString,
+) -> *mut c_void {
+    create(config, error, E::new)
+}
+
+pub unsafe extern "C" fn engine_capabilities<E: EnginePlugin>(handle: *mut c_void) -> Capabilities {
+    let engine = instance::<E>(handle);
+    guard(|| Ok(engine.capabilities())).unwrap_or_default()
+}
+
+pub unsafe extern "C" fn engine_push_audio<E: EnginePlugin>(
+    handle: *mut c_void,
+    samples: *const f32,
+    len: usize,
+    sample_rate: u32,
+    channels: u16,
+) -> ErrorString {
+    let engine = instance::<E>(handle);
+    let samples = if len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(samples, len)
+    };
+    status(guard(|| engine.push_audio(samples, sample_rate, channels)))
+}
+
+pub unsafe extern "C" fn engine_end_utterance<E: EnginePlugin>(handle: *mut c_void) -> ErrorString {
+    let engine = instance::<E>(handle);
+    status(guard(|| engine.end_utterance()))
+}
+
+pub unsafe extern "C" fn engine_flush<E: EnginePlugin>(handle: *mut c_void) -> ErrorString {
+    let engine = instance::<E>(handle);
+    status(guard(|| engine.flush()))
+}
+
+pub unsafe extern "C" fn engine_reset<E: EnginePlugin>(handle: *mut c_void) -> ErrorString {
+    let engine = instance::<E>(handle);
+    status(guard(|| engine.reset()))
+}
+
+pub unsafe extern "C" fn engine_poll_result<E: EnginePlugin>(
+    handle: *mut c_void,
+    is_final: *mut bool,
+) -> *mut c_char {
+    let engine = instance::<E>(handle);
+    match guard(|| Ok(engine.poll_result())) {
+        Ok(Some((text, fin))) => {
+            if !is_final.is_null() {
+                *is_final = fin;
+            }
+            into_c_string(text)
+        }
+        _ => ptr::null_mut(),
+    }
+}
+
+// ── Destinations ───────────────────────────────────────────────
+
+pub unsafe extern "C" fn destination_create<D: DestinationPlugin>(
+    config: *const c_char,
+    error: *mut ErrorString,
+) -> *mut c_void {
+    create(config, error, D::new)
+}
+
+pub unsafe extern "C" fn destination_send_text<D: DestinationPlugin>(
+    handle: *mut c_void,
+    text: *const c_char,
+    input_id: *const c_char,
+    prefix: *const c_char,
+) -> ErrorString {
+    let destination = instance::<D>(handle);
+    status(guard(|| {
+        destination.send_text(str_arg(text)?, str_arg(input_id)?, str_arg(prefix)?)
+    }))
+}
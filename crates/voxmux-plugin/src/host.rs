@@ -0,0 +1,575 @@
+//! Loading plugins and calling them through safe wrappers.
+
+use crate::{
+    Capabilities, DeclarationFn, DestinationVTable, EngineVTable, ErrorString, PluginDeclaration,
+    ABI_VERSION, DECLARATION_SYMBOL,
+};
+use libloading::Library;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::sync::Arc;
+use voxmux_core::PluginError;
+
+/// A declaration and the library it lives in.
+struct Loaded {
+    declaration: *const PluginDeclaration,
+    /// `None` for plugins linked into the host (tests).
+    _library: Option<Library>,
+}
+
+// SAFETY: the declaration is immutable and valid while the library is loaded,
+// which `Arc<Loaded>` guarantees for every handle derived from it.
+unsafe impl Send for Loaded {}
+unsafe impl Sync for Loaded {}
+
+impl Loaded {
+    fn declaration(&self) -> &PluginDeclaration {
+        unsafe { &*self.declaration }
+    }
+
+    fn engine(&self, index: usize) -> &EngineVTable {
+        unsafe { &(*self.declaration().engines.add(index)).vtable }
+    }
+
+    fn destination(&self, index: usize) -> &DestinationVTable {
+        unsafe { &(*self.declaration().destinations.add(index)).vtable }
+    }
+
+    /// Copy and release a string returned by the plugin.
+    fn take_string(&self, s: *mut c_char) -> Option<String> {
+        if s.is_null() {
+            return None;
+        }
+        let text = unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned();
+        unsafe { (self.declaration().free_string)(s) };
+        Some(text)
+    }
+
+    fn status(&self, error: ErrorString) -> Result<(), String> {
+        match self.take_string(error) {
+            None => Ok(()),
+            Some(msg) => Err(msg),
+        }
+    }
+
+    fn create(
+        &self,
+        create: unsafe extern "C" fn(*const c_char, *mut ErrorString) -> *mut c_void,
+        config: &str,
+    ) -> Result<*mut c_void, String> {
+        let config = CString::new(config).map_err(|e| e.to_string())?;
+        let mut error = ptr::null_mut();
+        let handle = unsafe { create(config.as_ptr(), &mut error) };
+        if handle.is_null() {
+            return Err(self
+                .take_string(error)
+                .unwrap_or_else(|| "plugin returned no instance".to_string()));
+        }
+        Ok(handle)
+    }
+}
+
+unsafe fn declared_str(ptr: *const c_char, what: &str, path: &str) -> Result<String, PluginError> {
+    if ptr.is_null() {
+        return Err(PluginError::Invalid {
+            path: path.to_string(),
+            reason: format!("missing {what}"),
+        });
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(str::to_string)
+        .map_err(|_| PluginError::Invalid {
+            path: path.to_string(),
+            reason: format!("{what} is not UTF-8"),
+        })
+}
+
+// ── Plugin ─────────────────────────────────────────────────────
+
+/// A loaded plugin library and the engines and destinations it provides.
+pub struct Plugin {
+    name: String,
+    version: String,
+    path: PathBuf,
+    engines: Vec<PluginEngine>,
+    destinations: Vec<PluginDestination>,
+}
+
+impl Plugin {
+    /// Load the shared library at `path`, refusing it unless it declares
+    /// [`ABI_VERSION`].
+    pub fn load(path: &Path) -> Result<Self, PluginError> {
+        let display = path.display().to_string();
+        let load_failed = |reason: String| PluginError::LoadFailed {
+            path: display.clone(),
+            reason,
+        };
+        let library = unsafe { Library::new(path) }.map_err(|e| load_failed(e.to_string()))?;
+        let declare: DeclarationFn = unsafe {
+            *library
+                .get::<DeclarationFn>(DECLARATION_SYMBOL)
+                .map_err(|_| PluginError::Invalid {
+                    path: display.clone(),
+                    reason: "no voxmux_plugin_declaration export".to_string(),
+                })?
+        };
+        let declaration = unsafe { declare() };
+        unsafe { Self::from_declaration(path, declaration, Some(library)) }
+    }
+
+    /// Validate a declaration and wrap its entries.
+    ///
+    /// # Safety
+    /// `declaration` must be null or point to a declaration that stays valid
+    /// as long as `library` (or the process, if `None`).
+    unsafe fn from_declaration(
+        path: &Path,
+        declaration: *const PluginDeclaration,
+        library: Option<Library>,
+    ) -> Result<Self, PluginError> {
+        let display = path.display().to_string();
+        if declaration.is_null() {
+            return Err(PluginError::Invalid {
+                path: display,
+                reason: "null declaration".to_string(),
+            });
+        }
+        // Only the version is read until it is known to match
+        let found = ptr::addr_of!((*declaration).abi_version).read();
+        if found != ABI_VERSION {
+            return Err(PluginError::IncompatibleAbi {
+                path: display,
+                found,
+                expected: ABI_VERSION,
+            });
+        }
+
+        let decl = &*declaration;
+        let name = declared_str(decl.name, "plugin name", &display)?;
+        let version = declared_str(decl.version, "plugin version", &display)?;
+        if (decl.engine_count > 0 && decl.engines.is_null())
+            || (decl.destination_count > 0 && decl.destinations.is_null())
+        {
+            return Err(PluginError::Invalid {
+                path: display,
+                reason: "null entry table".to_string(),
+            });
+        }
+        let mut engine_names = Vec::with_capacity(decl.engine_count);
+        for i in 0..decl.engine_count {
+            engine_names.push(declared_str(
+                (*decl.engines.add(i)).name,
+                "engine name",
+                &display,
+            )?);
+        }
+        let mut destination_names = Vec::with_capacity(decl.destination_count);
+        for i in 0..decl.destination_count {
+            destination_names.push(declared_str(
+                (*decl.destinations.add(i)).name,
+                "destination name",
+                &display,
+            )?);
+        }
+
+        let loaded = Arc::new(Loaded {
+            declaration,
+            _library: library,
+        });
+        Ok(Self {
+            name,
+            version,
+            path: path.to_path_buf(),
+            engines: engine_names
+                .into_iter()
+                .enumerate()
+                .map(|(index, name)| PluginEngine {
+                    loaded: loaded.clone(),
+                    index,
+                    name,
+                })
+                .collect(),
+            destinations: destination_names
+                .into_iter()
+                .enumerate()
+                .map(|(index, name)| PluginDestination {
+                    loaded: loaded.clone(),
+                    index,
+                    name,
+                })
+                .collect(),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn engines(&self) -> &[PluginEngine] {
+        &self.engines
+    }
+
+    pub fn destinations(&self) -> &[PluginDestination] {
+        &self.destinations
+    }
+}
+
+/// Load every shared library in `dir`, in file name order. Each entry is the
+/// plugin or the reason it was refused.
+pub fn load_dir(dir: &Path) -> Vec<Result<Plugin, PluginError>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            return vec![Err(PluginError::LoadFailed {
+                path: dir.display().to_string(),
+                reason: e.to_string(),
+            })]
+        }
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension()
+                .is_some_and(|ext| ext == std::env::consts::DLL_EXTENSION)
+        })
+        .collect();
+    paths.sort();
+    paths.iter().map(|p| Plugin::load(p)).collect()
+}
+
+// ── Engines ────────────────────────────────────────────────────
+
+/// An engine provided by a plugin; cheap to clone.
+#[derive(Clone)]
+pub struct PluginEngine {
+    loaded: Arc<Loaded>,
+    index: usize,
+    name: String,
+}
+
+impl PluginEngine {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Create an instance from its TOML configuration.
+    pub fn create(&self, config: &str) -> Result<EngineInstance, String> {
+        let handle = self
+            .loaded
+            .create(self.loaded.engine(self.index).create, config)?;
+        Ok(EngineInstance {
+            loaded: self.loaded.clone(),
+            index: self.index,
+            handle,
+        })
+    }
+}
+
+/// A live engine instance, destroyed on drop.
+pub struct EngineInstance {
+    loaded: Arc<Loaded>,
+    index: usize,
+    handle: *mut c_void,
+}
+
+// SAFETY: plugin engines are `Send`, and `&mut self` serialises calls.
+unsafe impl Send for EngineInstance {}
+
+impl EngineInstance {
+    fn vtable(&self) -> &EngineVTable {
+        self.loaded.engine(self.index)
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        unsafe { (self.vtable().capabilities)(self.handle) }
+    }
+
+    pub fn push_audio(
+        &mut self,
+        samples: &[f32],
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<(), String> {
+        let error = unsafe {
+            (self.vtable().push_audio)(
+                self.handle,
+                samples.as_ptr(),
+                samples.len(),
+                sample_rate,
+                channels,
+            )
+        };
+        self.loaded.status(error)
+    }
+
+    pub fn end_utterance(&mut self) -> Result<(), String> {
+        let error = unsafe { (self.vtable().end_utterance)(self.handle) };
+        self.loaded.status(error)
+    }
+
+    pub fn flush(&mut self) -> Result<(), String> {
+        let error = unsafe { (self.vtable().flush)(self.handle) };
+        self.loaded.status(error)
+    }
+
+    pub fn reset(&mut self) -> Result<(), String> {
+        let error = unsafe { (self.vtable().reset)(self.handle) };
+        self.loaded.status(error)
+    }
+
+    /// Next pending result as `(text, is_final)`.
+    pub fn poll_result(&mut self) -> Option<(String, bool)> {
+        let mut is_final = false;
+        let text = unsafe { (self.vtable().poll_result)(self.handle, &mut is_final) };
+        self.loaded.take_string(text).map(|t| (t, is_final))
+    }
+}
+
+impl Drop for EngineInstance {
+    fn drop(&mut self) {
+        unsafe { (self.vtable().destroy)(self.handle) };
+    }
+}
+
+// ── Destinations ───────────────────────────────────────────────
+
+/// A destination provided by a plugin; cheap to clone.
+#[derive(Clone)]
+pub struct PluginDestination {
+    loaded: Arc<Loaded>,
+    index: usize,
+    name: String,
+}
+
+impl PluginDestination {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Create an instance from its TOML configuration.
+    pub fn create(&self, config: &str) -> Result<DestinationInstance, String> {
+        let handle = self
+            .loaded
+            .create(self.loaded.destination(self.index).create, config)?;
+        Ok(DestinationInstance {
+            loaded: self.loaded.clone(),
+            index: self.index,
+            handle,
+        })
+    }
+}
+
+/// A live destination instance, destroyed on drop.
+pub struct DestinationInstance {
+    loaded: Arc<Loaded>,
+    index: usize,
+    handle: *mut c_void,
+}
+
+// SAFETY: plugin destinations are `Send`, and `&mut self` serialises calls.
+unsafe impl Send for DestinationInstance {}
+
+impl DestinationInstance {
+    fn vtable(&self) -> &DestinationVTable {
+        self.loaded.destination(self.index)
+    }
+
+    pub fn send_text(&mut self, text: &str, input_id: &str, prefix: &str) -> Result<(), String> {
+        let text = CString::new(text).map_err(|e| e.to_string())?;
+        let input_id = CString::new(input_id).map_err(|e| e.to_string())?;
+        let prefix = CString::new(prefix).map_err(|e| e.to_string())?;
+        let error = unsafe {
+            (self.vtable().send_text)(
+                self.handle,
+                text.as_ptr(),
+                input_id.as_ptr(),
+                prefix.as_ptr(),
+            )
+        };
+        self.loaded.status(error)
+    }
+}
+
+impl Drop for DestinationInstance {
+    fn drop(&mut self) {
+        unsafe { (self.vtable().destroy)(self.handle) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DestinationPlugin, EnginePlugin};
+
+    /// Echoes the config as a partial, then a final on `end_utterance`.
+    struct EchoEngine {
+        config: String,
+        pending: Vec<(String, bool)>,
+    }
+
+    impl EnginePlugin for EchoEngine {
+        fn new(config: &str) -> Result<Self, String> {
+            if config.contains("fail") {
+                return Err("bad config".to_string());
+            }
+            Ok(Self {
+                config: config.to_string(),
+                pending: Vec::new(),
+            })
+        }
+        fn capabilities(&self) -> Capabilities {
+            Capabilities {
+                sample_rate: 16000,
+                channels: 1,
+                ..Default::default()
+            }
+        }
+        fn push_audio(
+            &mut self,
+            samples: &[f32],
+            _rate: u32,
+            _channels: u16,
+        ) -> Result<(), String> {
+            if samples.is_empty() {
+                panic!("no samples");
+            }
+            self.pending
+                .push((format!("{} {}", self.config, samples.len()), false));
+            Ok(())
+        }
+        fn end_utterance(&mut self) -> Result<(), String> {
+            self.pending.push((self.config.clone(), true));
+            Ok(())
+        }
+        fn poll_result(&mut self) -> Option<(String, bool)> {
+            (!self.pending.is_empty()).then(|| self.pending.remove(0))
+        }
+    }
+
+    struct RejectingDestination;
+
+    impl DestinationPlugin for RejectingDestination {
+        fn new(_config: &str) -> Result<Self, String> {
+            Ok(Self)
+        }
+        fn send_text(&mut self, text: &str, input_id: &str, prefix: &str) -> Result<(), String> {
+            Err(format!("{prefix}{text} from {input_id}"))
+        }
+    }
+
+    crate::declare_plugin! {
+        name: "test",
+        engines: ["echo" => EchoEngine],
+        destinations: ["reject" => RejectingDestination],
+    }
+
+    fn linked() -> Plugin {
+        unsafe { Plugin::from_declaration(Path::new("linked"), voxmux_plugin_declaration(), None) }
+            .unwrap()
+    }
+
+    #[test]
+    fn test_declaration_reports_name_version_and_entries() {
+        let plugin = linked();
+        assert_eq!(plugin.name(), "test");
+        assert_eq!(plugin.version(), env!("CARGO_PKG_VERSION"));
+        assert_eq!(plugin.engines()[0].name(), "echo");
+        assert_eq!(plugin.destinations()[0].name(), "reject");
+    }
+
+    #[test]
+    fn test_engine_calls_cross_the_abi() {
+        let plugin = linked();
+        let engine = &plugin.engines()[0];
+        assert_eq!(engine.create("fail").err().as_deref(), Some("bad config"));
+
+        let mut instance = engine.create("hi").unwrap();
+        assert_eq!(instance.capabilities().sample_rate, 16000);
+        instance.push_audio(&[0.0; 160], 16000, 1).unwrap();
+        instance.end_utterance().unwrap();
+        assert_eq!(instance.poll_result(), Some(("hi 160".to_string(), false)));
+        assert_eq!(instance.poll_result(), Some(("hi".to_string(), true)));
+        assert_eq!(instance.poll_result(), None);
+        // Panics are reported as errors instead of unwinding into the host
+        assert_eq!(
+            instance.push_audio(&[], 16000, 1).unwrap_err(),
+            "plugin panicked"
+        );
+    }
+
+    #[test]
+    fn test_destination_errors_cross_the_abi() {
+        let plugin = linked();
+        let mut instance = plugin.destinations()[0].create("").unwrap();
+        assert_eq!(
+            instance.send_text("hello", "mic1", "[A] ").unwrap_err(),
+            "[A] hello from mic1"
+        );
+    }
+
+    #[test]
+    fn test_incompatible_abi_is_refused() {
+        static FUTURE: PluginDeclaration = PluginDeclaration {
+            abi_version: ABI_VERSION + 1,
+            name: ptr::null(),
+            version: ptr::null(),
+            engines: ptr::null(),
+            engine_count: 0,
+            destinations: ptr::null(),
+            destination_count: 0,
+            free_string: crate::export::free_string,
+        };
+        match unsafe { Plugin::from_declaration(Path::new("future.so"), &FUTURE, None) } {
+            Err(PluginError::IncompatibleAbi {
+                found, expected, ..
+            }) => {
+                assert_eq!((found, expected), (ABI_VERSION + 1, ABI_VERSION))
+            }
+            _ => panic!("expected IncompatibleAbi"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_declaration_is_refused() {
+        static NAMELESS: PluginDeclaration = PluginDeclaration {
+            abi_version: ABI_VERSION,
+            name: ptr::null(),
+            version: ptr::null(),
+            engines: ptr::null(),
+            engine_count: 0,
+            destinations: ptr::null(),
+            destination_count: 0,
+            free_string: crate::export::free_string,
+        };
+        let result = unsafe { Plugin::from_declaration(Path::new("x.so"), &NAMELESS, None) };
+        assert!(matches!(result, Err(PluginError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_load_rejects_non_libraries() {
+        let dir = std::env::temp_dir().join("voxmux_plugin_load_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let fake = dir.join(format!("fake.{}", std::env::consts::DLL_EXTENSION));
+        std::fs::write(&fake, b"not a library").unwrap();
+        std::fs::write(dir.join("README.txt"), b"ignored").unwrap();
+
+        let results = load_dir(&dir);
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0], Err(PluginError::LoadFailed { .. })));
+        assert!(matches!(
+            load_dir(&dir.join("missing"))[..],
+            [Err(PluginError::LoadFailed { .. })]
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
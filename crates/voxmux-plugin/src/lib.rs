@@ -0,0 +1,228 @@
+//! Stable C ABI between voxmux and engines/destinations built as shared
+//! libraries.
+//!
+//! A plugin exports one function, `voxmux_plugin_declaration`, returning a
+//! [`PluginDeclaration`] that lists its engines and destinations as tables of
+//! `extern "C"` functions. Plugins normally implement [`EnginePlugin`] or
+//! [`DestinationPlugin`] and generate the exports with [`declare_plugin!`]:
+//!
+//! ```ignore
+//! voxmux_plugin::declare_plugin! {
+//!     name: "example",
+//!     engines: ["counter" => CounterEngine],
+//!     destinations: ["log" => LogDestination],
+//! }
+//! ```
+//!
+//! Any change to the types in this module bumps [`ABI_VERSION`]; the host
+//! refuses plugins declaring a different version.
+
+use std::ffi::{c_char, c_void};
+
+#[doc(hidden)]
+pub mod export;
+#[cfg(feature = "host")]
+pub mod host;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+
+/// Version of the plugin ABI described by this crate.
+pub const ABI_VERSION: u32 = 1;
+
+/// Name of the function every plugin exports, NUL-terminated.
+pub const DECLARATION_SYMBOL: &[u8] = b"voxmux_plugin_declaration\0";
+
+/// Signature of [`DECLARATION_SYMBOL`].
+pub type DeclarationFn = unsafe extern "C" fn() -> *const PluginDeclaration;
+
+/// Error message returned across the ABI: NUL-terminated, allocated by the
+/// plugin and released with [`PluginDeclaration::free_string`]. Null means
+/// success.
+pub type ErrorString = *mut c_char;
+
+// ── Declaration ────────────────────────────────────────────────
+
+/// Everything a plugin provides. Strings are NUL-terminated UTF-8 and, like
+/// the tables, live as long as the library is loaded.
+#[repr(C)]
+pub struct PluginDeclaration {
+    /// Must stay the first field: the host checks it before reading the rest.
+    pub abi_version: u32,
+    pub name: *const c_char,
+    pub version: *const c_char,
+    pub engines: *const EngineEntry,
+    pub engine_count: usize,
+    pub destinations: *const DestinationEntry,
+    pub destination_count: usize,
+    /// Releases strings returned by the plugin.
+    pub free_string: unsafe extern "C" fn(*mut c_char),
+}
+
+// SAFETY: declarations are immutable static data.
+unsafe impl Sync for PluginDeclaration {}
+
+#[repr(C)]
+pub struct EngineEntry {
+    pub name: *const c_char,
+    pub vtable: EngineVTable,
+}
+
+// SAFETY: entries are immutable static data.
+unsafe impl Sync for EngineEntry {}
+
+#[repr(C)]
+pub struct DestinationEntry {
+    pub name: *const c_char,
+    pub vtable: DestinationVTable,
+}
+
+// SAFETY: entries are immutable static data.
+unsafe impl Sync for DestinationEntry {}
+
+// ── Engines ────────────────────────────────────────────────────
+
+/// Audio format an engine wants; mirrors `voxmux_engine::EngineCapabilities`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Preferred sample rate, 0 = as captured.
+    pub sample_rate: u32,
+    /// Preferred channel count, 0 = as captured.
+    pub channels: u16,
+    /// Whole utterances instead of a stream.
+    pub batch: bool,
+    /// Streaming chunk length, 0 = as captured.
+    pub chunk_ms: u32,
+    pub partial_results: bool,
+}
+
+/// An engine instance is an opaque handle created from a TOML config string.
+/// The host calls it from one thread at a time.
+#[repr(C)]
+pub struct EngineVTable {
+    /// Returns null and sets `*error` on failure.
+    pub create: unsafe extern "C" fn(config: *const c_char, error: *mut ErrorString) -> *mut c_void,
+    pub capabilities: unsafe extern "C" fn(handle: *mut c_void) -> Capabilities,
+    pub push_audio: unsafe extern "C" fn(
+        handle: *mut c_void,
+        samples: *const f32,
+        len: usize,
+        sample_rate: u32,
+        channels: u16,
+    ) -> ErrorString,
+    pub end_utterance: unsafe extern "C" fn(handle: *mut c_void) -> ErrorString,
+    pub flush: unsafe extern "C" fn(handle: *mut c_void) -> ErrorString,
+    pub reset: unsafe extern "C" fn(handle: *mut c_void) -> ErrorString,
+    /// Next pending result, or null when there is none.
+    pub poll_result: unsafe extern "C" fn(handle: *mut c_void, is_final: *mut bool) -> *mut c_char,
+    pub destroy: unsafe extern "C" fn(handle: *mut c_void),
+}
+
+/// A streaming recognizer implemented in a plugin.
+pub trait EnginePlugin: Send + Sized + 'static {
+    /// Create an instance from the engine's TOML configuration.
+    fn new(config: &str) -> Result<Self, String>;
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::default()
+    }
+    fn push_audio(
+        &mut self,
+        samples: &[f32],
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<(), String>;
+    fn end_utterance(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+    fn flush(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+    fn reset(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+    /// Next pending result as `(text, is_final)`; polled after every call.
+    fn poll_result(&mut self) -> Option<(String, bool)>;
+}
+
+impl EngineVTable {
+    pub const fn of<E: EnginePlugin>() -> Self {
+        Self {
+            create: export::engine_create::<E>,
+            capabilities: export::engine_capabilities::<E>,
+            push_audio: export::engine_push_audio::<E>,
+            end_utterance: export::engine_end_utterance::<E>,
+            flush: export::engine_flush::<E>,
+            reset: export::engine_reset::<E>,
+            poll_result: export::engine_poll_result::<E>,
+            destroy: export::destroy::<E>,
+        }
+    }
+}
+
+// ── Destinations ───────────────────────────────────────────────
+
+/// A destination instance is an opaque handle created from a TOML config
+/// string. The host calls it from one thread at a time.
+#[repr(C)]
+pub struct DestinationVTable {
+    /// Returns null and sets `*error` on failure.
+    pub create: unsafe extern "C" fn(config: *const c_char, error: *mut ErrorString) -> *mut c_void,
+    pub send_text: unsafe extern "C" fn(
+        handle: *mut c_void,
+        text: *const c_char,
+        input_id: *const c_char,
+        prefix: *const c_char,
+    ) -> ErrorString,
+    pub destroy: unsafe extern "C" fn(handle: *mut c_void),
+}
+
+/// A text destination implemented in a plugin.
+pub trait DestinationPlugin: Send + Sized + 'static {
+    /// Create an instance from the destination's TOML configuration.
+    fn new(config: &str) -> Result<Self, String>;
+    fn send_text(&mut self, text: &str, input_id: &str, prefix: &str) -> Result<(), String>;
+}
+
+impl DestinationVTable {
+    pub const fn of<D: DestinationPlugin>() -> Self {
+        Self {
+            create: export::destination_create::<D>,
+            send_text: export::destination_send_text::<D>,
+            destroy: export::destroy::<D>,
+        }
+    }
+}
+
+/// Export a plugin's declaration. The version reported is the plugin
+/// crate's `CARGO_PKG_VERSION`.
+#[macro_export]
+macro_rules! declare_plugin {
+    (
+        name: $name:literal,
+        engines: [$($engine:literal => $engine_ty:ty),* $(,)?],
+        destinations: [$($dest:literal => $dest_ty:ty),* $(,)?] $(,)?
+    ) => {
+        #[no_mangle]
+        pub extern "C" fn voxmux_plugin_declaration() -> *const $crate::PluginDeclaration {
+            const ENGINES: &[$crate::EngineEntry] = &[$($crate::EngineEntry {
+                name: concat!($engine, "\0").as_ptr().cast(),
+                vtable: $crate::EngineVTable::of::<$engine_ty>(),
+            }),*];
+            const DESTINATIONS: &[$crate::DestinationEntry] = &[$($crate::DestinationEntry {
+                name: concat!($dest, "\0").as_ptr().cast(),
+                vtable: $crate::DestinationVTable::of::<$dest_ty>(),
+            }),*];
+            static DECLARATION: $crate::PluginDeclaration = $crate::PluginDeclaration {
+                abi_version: $crate::ABI_VERSION,
+                name: concat!($name, "\0").as_ptr().cast(),
+                version: concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast(),
+                engines: ENGINES.as_ptr(),
+                engine_count: ENGINES.len(),
+                destinations: DESTINATIONS.as_ptr(),
+                destination_count: DESTINATIONS.len(),
+                free_string: $crate::export::free_string,
+            };
+            &DECLARATION
+        }
+    };
+}
@@ -0,0 +1,28 @@
+//! Helpers for tests that load real plugins.
+
+use std::path::{Path, PathBuf};
+
+/// Build the `voxmux-example-plugin` crate under `tmp_dir` and return the
+/// path of its library. Integration tests pass `env!("CARGO_TARGET_TMPDIR")`.
+pub fn build_example_plugin(tmp_dir: impl AsRef<Path>) -> PathBuf {
+    let target_dir = tmp_dir.as_ref().join("example-plugin");
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = std::process::Command::new(cargo)
+        .args([
+            "build",
+            "--quiet",
+            "-p",
+            "voxmux-example-plugin",
+            "--target-dir",
+        ])
+        .arg(&target_dir)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "building the example plugin failed");
+    target_dir.join("debug").join(format!(
+        "{}voxmux_example_plugin.{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_EXTENSION
+    ))
+}
@@ -0,0 +1,445 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use voxmux_core::config::AlertRuleConfig;
+use voxmux_core::{AlertEvent, RecognitionResult, TextError};
+
+/// Scripts written without spaces (Thai, kana and CJK ideographs), where
+/// every character counts as a word.
+fn is_unspaced(c: char) -> bool {
+    matches!(c,
+        '\u{0E00}'..='\u{0E7F}'
+        | '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF66}'..='\u{FF9F}')
+}
+
+/// The lower-cased words of `text`: runs of letters and digits, so
+/// punctuation doesn't matter.
+fn words(text: &str) -> Vec<Vec<char>> {
+    let mut words = Vec::new();
+    let mut word = Vec::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if !c.is_alphanumeric() || is_unspaced(c) {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            if is_unspaced(c) {
+                words.push(vec![c]);
+            }
+            continue;
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Whether some run of whole, consecutive `words`, joined without spaces,
+/// is within `max_edits` substituted, inserted or deleted characters of
+/// `pattern`. Matches start and end on word boundaries, so a keyword never
+/// matches part of a word, while "may day" still matches "mayday".
+fn fuzzy_match(words: &[Vec<char>], pattern: &[char], max_edits: usize) -> bool {
+    if pattern.is_empty() {
+        return false;
+    }
+    let max_len = pattern.len() + max_edits;
+    let mut prev = vec![0; pattern.len() + 1];
+    let mut cur = vec![0; pattern.len() + 1];
+    for start in 0..words.len() {
+        // Edit distances between the joined words so far and each prefix of
+        // the pattern
+        prev.iter_mut().enumerate().for_each(|(j, d)| *d = j);
+        let mut len = 0;
+        for word in &words[start..] {
+            len += word.len();
+            if len > max_len {
+                break;
+            }
+            for &c in word {
+                cur[0] = prev[0] + 1;
+                for j in 1..=pattern.len() {
+                    let substitute = prev[j - 1] + usize::from(pattern[j - 1] != c);
+                    cur[j] = substitute.min(prev[j] + 1).min(cur[j - 1] + 1);
+                }
+                std::mem::swap(&mut prev, &mut cur);
+            }
+            if prev[pattern.len()] <= max_edits {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+struct Keyword {
+    text: String,
+    chars: Vec<char>,
+    max_edits: usize,
+}
+
+struct AlertRule {
+    config: AlertRuleConfig,
+    keywords: Vec<Keyword>,
+    patterns: Vec<Regex>,
+    last_fired: Option<Instant>,
+    /// Utterance already alerted on, per input.
+    alerted: HashMap<String, u64>,
+}
+
+impl AlertRule {
+    fn new(config: &AlertRuleConfig) -> Result<Self, TextError> {
+        let invalid =
+            |msg: String| TextError::InvalidConfig(format!("alert '{}': {msg}", config.name));
+        let keywords = config
+            .keywords
+            .iter()
+            .map(|k| {
+                let chars = words(k).concat();
+                if chars.is_empty() {
+                    return Err(invalid(format!("keyword {k:?} has no letters or digits")));
+                }
+                Ok(Keyword {
+                    text: k.clone(),
+                    max_edits: (config.fuzzy as usize).min(chars.len() / 4),
+                    chars,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let patterns = config
+            .patterns
+            .iter()
+            .map(|p| Regex::new(p).map_err(|e| invalid(e.to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
+        if keywords.is_empty() && patterns.is_empty() {
+            return Err(invalid("needs keywords or patterns".to_string()));
+        }
+        Ok(Self {
+            config: config.clone(),
+            keywords,
+            patterns,
+            last_fired: None,
+            alerted: HashMap::new(),
+        })
+    }
+
+    /// The keyword or pattern `text` matches, if any.
+    fn find(&self, text: &str) -> Option<String> {
+        let words = words(text);
+        self.keywords
+            .iter()
+            .find(|k| fuzzy_match(&words, &k.chars, k.max_edits))
+            .map(|k| k.text.clone())
+            .or_else(|| {
+                self.patterns
+                    .iter()
+                    .find_map(|p| p.find(text).map(|m| m.as_str().to_string()))
+            })
+    }
+
+    fn check(&mut self, result: &RecognitionResult, now: Instant) -> Option<AlertEvent> {
+        let config = &self.config;
+        if result.text.is_empty() || (!result.is_final && !config.partials) {
+            return None;
+        }
+        if !config.inputs.is_empty() && !config.inputs.contains(&result.input_id) {
+            return None;
+        }
+        if result.utterance_id != 0
+            && self.alerted.get(&result.input_id) == Some(&result.utterance_id)
+        {
+            return None;
+        }
+        let interval = Duration::from_millis(config.min_interval_ms as u64);
+        if self
+            .last_fired
+            .is_some_and(|t| now.duration_since(t) < interval)
+        {
+            return None;
+        }
+        let matched = self.find(&result.text)?;
+
+        self.last_fired = Some(now);
+        if result.utterance_id != 0 {
+            self.alerted
+                .insert(result.input_id.clone(), result.utterance_id);
+        }
+        Some(AlertEvent {
+            rule: config.name.clone(),
+            input_id: result.input_id.clone(),
+            text: result.text.clone(),
+            matched,
+            is_final: result.is_final,
+        })
+    }
+}
+
+/// An alert and what to do with it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub event: AlertEvent,
+    pub highlight: bool,
+    pub warn: bool,
+    pub speak: bool,
+}
+
+/// Compiled `[[alerts]]` rules, checked against each recognition result.
+pub struct AlertRules {
+    rules: Vec<AlertRule>,
+}
+
+impl AlertRules {
+    pub fn new(configs: &[AlertRuleConfig]) -> Result<Self, TextError> {
+        let mut rules: Vec<AlertRule> = Vec::with_capacity(configs.len());
+        for config in configs {
+            if rules.iter().any(|r| r.config.name == config.name) {
+                return Err(TextError::InvalidConfig(format!(
+                    "duplicate alert name '{}'",
+                    config.name
+                )));
+            }
+            rules.push(AlertRule::new(config)?);
+        }
+        Ok(Self { rules })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Replace the rules. Rate limiting carries over for rules that keep
+    /// their name. On error the current rules stay in place.
+    pub fn reload(&mut self, configs: &[AlertRuleConfig]) -> Result<(), TextError> {
+        let mut new = Self::new(configs)?;
+        for rule in &mut new.rules {
+            if let Some(old) = self
+                .rules
+                .iter_mut()
+                .find(|r| r.config.name == rule.config.name)
+            {
+                rule.last_fired = old.last_fired;
+                rule.alerted = std::mem::take(&mut old.alerted);
+            }
+        }
+        *self = new;
+        Ok(())
+    }
+
+    /// Alerts raised by `result`, one per matching rule.
+    pub fn check(&mut self, result: &RecognitionResult) -> Vec<Alert> {
+        self.check_at(result, Instant::now())
+    }
+
+    pub fn check_at(&mut self, result: &RecognitionResult, now: Instant) -> Vec<Alert> {
+        self.rules
+            .iter_mut()
+            .filter_map(|rule| {
+                rule.check(result, now).map(|event| Alert {
+                    event,
+                    highlight: rule.config.highlight,
+                    warn: rule.config.warn,
+                    speak: rule.config.speak,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(toml_str: &str) -> Result<AlertRules, TextError> {
+        let config = voxmux_core::AppConfig::from_toml_str(toml_str).unwrap();
+        AlertRules::new(&config.alerts)
+    }
+
+    fn result(input_id: &str, text: &str, is_final: bool, utterance_id: u64) -> RecognitionResult {
+        RecognitionResult {
+            input_id: input_id.to_string(),
+            text: text.to_string(),
+            is_final,
+            utterance_id,
+            ..Default::default()
+        }
+    }
+
+    fn matches(text: &str, keyword: &str, max_edits: usize) -> bool {
+        fuzzy_match(&words(text), &words(keyword).concat(), max_edits)
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        let text = "This is May-Day, over";
+        assert!(matches(text, "mayday", 0));
+        assert!(!matches(text, "maydey", 0));
+        assert!(matches(text, "maydey", 1));
+        assert!(matches("JA 1 XYZ", "ja1xyz", 0));
+        assert!(matches("メーデー、メーデー", "メーデー", 0));
+        assert!(matches("緊急メーデーです", "メーデー", 0));
+        assert!(!fuzzy_match(&words(text), &[], 3));
+    }
+
+    #[test]
+    fn test_fuzzy_match_respects_word_boundaries() {
+        // Neither inside a word nor across the gap between two
+        assert!(!matches("chaos some", "sos", 0));
+        assert!(!matches("also", "sos", 0));
+        assert!(!matches("sosa", "sos", 0));
+        assert!(matches("s o s", "sos", 0));
+        // An edit cannot stretch a keyword over part of a neighbouring word
+        assert!(!matches("also over", "sos", 1));
+        assert!(matches("may days", "mayday", 1));
+        assert!(matches("mayday's", "mayday", 0));
+    }
+
+    #[test]
+    fn test_alert_keywords_patterns_and_fuzzy() {
+        let mut rules = rules(
+            r#"
+[[alerts]]
+name = "distress"
+keywords = ["mayday", "emergency"]
+fuzzy = 1
+
+[[alerts]]
+name = "callsign"
+patterns = ['(?i)\bJA1\w+']
+highlight = false
+speak = true
+"#,
+        )
+        .unwrap();
+        assert_eq!(rules.len(), 2);
+
+        let alerts = rules.check(&result("radio1", "emergancy, JA1XYZ calling", true, 1));
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].event.rule, "distress");
+        assert_eq!(alerts[0].event.matched, "emergency");
+        assert!(alerts[0].highlight && alerts[0].warn && !alerts[0].speak);
+        assert_eq!(alerts[1].event.matched, "JA1XYZ");
+        assert!(!alerts[1].highlight && alerts[1].speak);
+
+        assert!(rules
+            .check(&result("radio1", "all quiet", true, 2))
+            .is_empty());
+        // Fuzziness is capped at a quarter of the keyword length
+        let mut short =
+            self::rules("[[alerts]]\nname = \"k7\"\nkeywords = [\"K7\"]\nfuzzy = 2").unwrap();
+        assert!(short.check(&result("radio1", "k8", true, 1)).is_empty());
+        assert_eq!(short.check(&result("radio1", "k 7", true, 2)).len(), 1);
+        let mut loose =
+            self::rules("[[alerts]]\nname = \"d\"\nkeywords = [\"sos\", \"mayday\"]\nfuzzy = 2")
+                .unwrap();
+        for text in ["so what", "chaos some", "see you monday", "close the doors"] {
+            assert!(
+                loose.check(&result("radio1", text, true, 3)).is_empty(),
+                "{text}"
+            );
+        }
+        assert_eq!(
+            loose.check(&result("radio1", "maydy maydy", true, 4)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_alert_filters_and_rate_limits() {
+        let mut rules = rules(
+            r#"
+[[alerts]]
+name = "distress"
+keywords = ["mayday"]
+partials = true
+inputs = ["radio1"]
+min_interval_ms = 1000
+
+[[alerts]]
+name = "finals"
+keywords = ["mayday"]
+"#,
+        )
+        .unwrap();
+        let start = Instant::now();
+        let names = |alerts: Vec<Alert>| -> Vec<String> {
+            alerts.into_iter().map(|a| a.event.rule).collect()
+        };
+
+        // A partial fires the partial rule; its final doesn't fire it again
+        assert_eq!(
+            names(rules.check_at(&result("radio1", "may", false, 1), start)),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            names(rules.check_at(&result("radio1", "mayday", false, 1), start)),
+            ["distress"]
+        );
+        assert_eq!(
+            names(rules.check_at(&result("radio1", "mayday", true, 1), start)),
+            ["finals"]
+        );
+        // Other inputs only match the unrestricted rule
+        assert_eq!(
+            names(rules.check_at(&result("radio2", "mayday", true, 1), start)),
+            ["finals"]
+        );
+
+        let soon = start + Duration::from_millis(500);
+        assert_eq!(
+            names(rules.check_at(&result("radio1", "mayday", true, 2), soon)),
+            ["finals"]
+        );
+        let later = start + Duration::from_millis(1500);
+        assert_eq!(
+            names(rules.check_at(&result("radio1", "mayday", true, 3), later)),
+            ["distress", "finals"]
+        );
+    }
+
+    #[test]
+    fn test_alert_ignores_emptied_finals() {
+        let mut rules = rules("[[alerts]]\nname = \"any\"\npatterns = ['^']").unwrap();
+        assert!(rules.check(&result("radio1", "", true, 1)).is_empty());
+        assert_eq!(rules.check(&result("radio1", "copy", true, 2)).len(), 1);
+    }
+
+    #[test]
+    fn test_alert_reload_keeps_rate_limit_and_rejects_bad_rules() {
+        let config = |keywords: &str| {
+            voxmux_core::AppConfig::from_toml_str(&format!(
+                "[[alerts]]\nname = \"distress\"\nkeywords = {keywords}\nmin_interval_ms = 60000"
+            ))
+            .unwrap()
+            .alerts
+        };
+        let mut rules = AlertRules::new(&config("[\"mayday\"]")).unwrap();
+        assert_eq!(rules.check(&result("radio1", "mayday", true, 1)).len(), 1);
+
+        rules.reload(&config("[\"mayday\", \"pan pan\"]")).unwrap();
+        assert!(rules
+            .check(&result("radio1", "pan pan", true, 2))
+            .is_empty());
+
+        assert!(rules.reload(&config("[\"--\"]")).is_err());
+        assert_eq!(rules.len(), 1);
+    }
+
+    #[test]
+    fn test_alert_config_errors() {
+        assert!(rules("[[alerts]]\nname = \"empty\"").is_err());
+        assert!(rules("[[alerts]]\nname = \"bad\"\npatterns = ['(']").is_err());
+        assert!(matches!(
+            rules("[[alerts]]\nname = \"a\"\nkeywords = [\"x\"]\n[[alerts]]\nname = \"a\"\nkeywords = [\"y\"]"),
+            Err(TextError::InvalidConfig(msg)) if msg.contains("duplicate")
+        ));
+        assert!(rules("").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+use crate::processor::{settings, TextProcessor};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use voxmux_core::TextError;
+
+#[derive(Deserialize)]
+struct Settings {
+    /// Replacements, e.g. `{ Yankee = "Y" }`.
+    #[serde(default)]
+    words: HashMap<String, String>,
+    /// Words deleted outright, e.g. fillers.
+    #[serde(default)]
+    remove: Vec<String>,
+    /// Words replaced by asterisks, e.g. profanity.
+    #[serde(default)]
+    mask: Vec<String>,
+    #[serde(default = "default_true")]
+    ignore_case: bool,
+    /// Only match whole words. Turn off for languages written without
+    /// spaces, such as Japanese.
+    #[serde(default = "default_true")]
+    whole_words: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+enum Action {
+    Replace(String),
+    Remove,
+    Mask,
+}
+
+/// Word list replacements: vocabulary fixes, filler removal and masking.
+/// Longer entries win over shorter ones they contain.
+pub struct DictionaryReplace {
+    pattern: Option<Regex>,
+    entries: HashMap<String, Action>,
+    ignore_case: bool,
+}
+
+impl DictionaryReplace {
+    pub fn new() -> Self {
+        Self {
+            pattern: None,
+            entries: HashMap::new(),
+            ignore_case: true,
+        }
+    }
+
+    fn key(&self, word: &str) -> String {
+        if self.ignore_case {
+            word.to_lowercase()
+        } else {
+            word.to_string()
+        }
+    }
+}
+
+impl Default for DictionaryReplace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextProcessor for DictionaryReplace {
+    fn name(&self) -> &str {
+        "dictionary"
+    }
+
+    fn configure(&mut self, table: &toml::Table) -> Result<(), TextError> {
+        let config: Settings = settings("dictionary", table)?;
+        self.ignore_case = config.ignore_case;
+        let entries = config
+            .words
+            .into_iter()
+            .map(|(from, to)| (from, Action::Replace(to)))
+            .chain(config.remove.into_iter().map(|w| (w, Action::Remove)))
+            .chain(config.mask.into_iter().map(|w| (w, Action::Mask)));
+        self.entries.clear();
+        for (word, action) in entries {
+            if word.is_empty() {
+                return Err(TextError::InvalidConfig(
+                    "dictionary: empty word".to_string(),
+                ));
+            }
+            self.entries.insert(self.key(&word), action);
+        }
+        if self.entries.is_empty() {
+            self.pattern = None;
+            return Ok(());
+        }
+
+        let mut words: Vec<&String> = self.entries.keys().collect();
+        words.sort_by(|a, b| b.chars().count().cmp(&a.chars().count()).then(a.cmp(b)));
+        let alternation = words
+            .iter()
+            .map(|w| regex::escape(w))
+            .collect::<Vec<_>>()
+            .join("|");
+        let flags = if self.ignore_case { "(?i)" } else { "" };
+        let pattern = if config.whole_words {
+            format!(r"{flags}\b(?:{alternation})\b")
+        } else {
+            format!("{flags}(?:{alternation})")
+        };
+        self.pattern = Some(
+            Regex::new(&pattern)
+                .map_err(|e| TextError::InvalidConfig(format!("dictionary: {e}")))?,
+        );
+        Ok(())
+    }
+
+    fn process(&self, text: &str) -> String {
+        let Some(pattern) = &self.pattern else {
+            return text.to_string();
+        };
+        pattern
+            .replace_all(text, |caps: &regex::Captures| {
+                let word = &caps[0];
+                match self.entries.get(&self.key(word)) {
+                    Some(Action::Replace(to)) => to.clone(),
+                    Some(Action::Remove) => String::new(),
+                    Some(Action::Mask) => "*".repeat(word.chars().count()),
+                    None => word.to_string(),
+                }
+            })
+            .into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processor(settings: &str) -> DictionaryReplace {
+        let mut p = DictionaryReplace::new();
+        p.configure(&toml::from_str(settings).unwrap()).unwrap();
+        p
+    }
+
+    #[test]
+    fn test_dictionary_replaces_removes_and_masks() {
+        let p = processor(
+            r#"
+words = { Yankee = "Y", "Kilo Seven" = "K7", Kilo = "K" }
+remove = ["um", "uh"]
+mask = ["darn"]
+"#,
+        );
+        assert_eq!(
+            p.process("um yankee kilo seven this is Kilo, DARN yankees"),
+            " Y K7 this is K, **** yankees"
+        );
+    }
+
+    #[test]
+    fn test_dictionary_case_and_word_options() {
+        let p = processor("words = { Yankee = \"Y\" }\nignore_case = false");
+        assert_eq!(p.process("yankee Yankee"), "yankee Y");
+
+        let p = processor(
+            "words = { \"えーと\" = \"\", \"了解\" = \"りょうかい\" }\nwhole_words = false",
+        );
+        assert_eq!(p.process("えーと了解です"), "りょうかいです");
+    }
+
+    #[test]
+    fn test_dictionary_empty_is_identity() {
+        assert_eq!(processor("").process("as is"), "as is");
+        let mut p = DictionaryReplace::new();
+        assert!(p
+            .configure(&toml::from_str("remove = [\"\"]").unwrap())
+            .is_err());
+    }
+}
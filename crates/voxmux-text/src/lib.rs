@@ -0,0 +1,23 @@
+pub mod alert;
+pub mod dictionary;
+#[cfg(feature = "http")]
+pub mod libretranslate;
+pub mod normalize;
+pub mod numbers;
+pub mod pipeline;
+pub mod processor;
+pub mod regex_replace;
+pub mod translate;
+pub mod width;
+
+pub use alert::{Alert, AlertRules};
+pub use dictionary::DictionaryReplace;
+#[cfg(feature = "http")]
+pub use libretranslate::LibreTranslate;
+pub use normalize::Normalize;
+pub use numbers::NumberWords;
+pub use pipeline::TextPipeline;
+pub use processor::{ProcessorChain, TextProcessor, TextProcessorRegistry};
+pub use regex_replace::RegexReplace;
+pub use translate::{Translator, TranslatorRegistry};
+pub use width::WidthConvert;
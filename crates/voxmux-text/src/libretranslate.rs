@@ -0,0 +1,249 @@
+use crate::translate::{base_language, Translator};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use voxmux_core::TranslationError;
+
+/// `[translation.libretranslate]` settings.
+#[derive(Debug, Clone, Deserialize)]
+struct LibreTranslateConfig {
+    /// The `/translate` endpoint, e.g. `http://localhost:5000/translate`.
+    url: String,
+    #[serde(default)]
+    api_key: Option<String>,
+    #[serde(default = "default_timeout_ms")]
+    timeout_ms: u64,
+}
+
+fn default_timeout_ms() -> u64 {
+    10_000
+}
+
+#[derive(Serialize)]
+struct TranslateRequest<'a> {
+    q: &'a str,
+    source: &'a str,
+    target: &'a str,
+    format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateResponse {
+    translated_text: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Translates through a LibreTranslate (or Argos Translate) compatible
+/// server. The source language is detected when the engine reports none.
+pub struct LibreTranslate {
+    config: Option<LibreTranslateConfig>,
+    client: reqwest::Client,
+}
+
+impl LibreTranslate {
+    pub fn new() -> Self {
+        Self {
+            config: None,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+impl Default for LibreTranslate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Translator for LibreTranslate {
+    fn name(&self) -> &str {
+        "libretranslate"
+    }
+
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), TranslationError> {
+        let config: LibreTranslateConfig = config.try_into().map_err(|e| {
+            TranslationError::InitializationFailed(format!(
+                "invalid [translation.libretranslate]: {e}"
+            ))
+        })?;
+        self.client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .map_err(|e| TranslationError::InitializationFailed(e.to_string()))?;
+        tracing::info!(url = %config.url, "LibreTranslate initialized");
+        self.config = Some(config);
+        Ok(())
+    }
+
+    async fn translate(
+        &self,
+        text: &str,
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<String, TranslationError> {
+        let config = self.config.as_ref().ok_or_else(|| {
+            TranslationError::Failed("libretranslate not initialized".to_string())
+        })?;
+        let source = source
+            .map(base_language)
+            .unwrap_or_else(|| "auto".to_string());
+        let target = base_language(target);
+        let request = TranslateRequest {
+            q: text,
+            source: &source,
+            target: &target,
+            format: "text",
+            api_key: config.api_key.as_deref(),
+        };
+
+        let failed = |e: String| TranslationError::Failed(format!("{}: {e}", config.url));
+        let response = self
+            .client
+            .post(&config.url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| failed(e.to_string()))?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(|e| failed(e.to_string()))?;
+        if !status.is_success() {
+            let message = serde_json::from_slice::<ErrorResponse>(&body)
+                .map(|e| e.error)
+                .unwrap_or_else(|_| String::from_utf8_lossy(&body).trim().to_string());
+            return Err(failed(format!("{status}: {message}")));
+        }
+        let parsed: TranslateResponse =
+            serde_json::from_slice(&body).map_err(|e| failed(format!("invalid response: {e}")))?;
+        Ok(parsed.translated_text.trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serves the canned `(status, body)` replies in order, one per
+    /// connection, and records each request body.
+    async fn mock_server(replies: Vec<(u16, &'static str)>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/translate", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            for (status, body) in replies {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let request = read_body(&mut stream).await;
+                recorded.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\ncontent-type: application/json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    async fn read_body(stream: &mut tokio::net::TcpStream) -> String {
+        let mut data = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            data.extend_from_slice(&buf[..n]);
+            if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+                let head = String::from_utf8_lossy(&data[..end]).to_ascii_lowercase();
+                let length: usize = head
+                    .lines()
+                    .find_map(|l| l.strip_prefix("content-length:"))
+                    .map(|v| v.trim().parse().unwrap())
+                    .unwrap_or(0);
+                while data.len() < end + 4 + length {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    data.extend_from_slice(&buf[..n]);
+                }
+                return String::from_utf8_lossy(&data[end + 4..end + 4 + length]).to_string();
+            }
+        }
+    }
+
+    async fn translator(url: &str, extra: &str) -> LibreTranslate {
+        let config: toml::Value = toml::from_str(&format!("url = \"{url}\"\n{extra}")).unwrap();
+        let mut translator = LibreTranslate::new();
+        translator.initialize(config).await.unwrap();
+        translator
+    }
+
+    #[tokio::test]
+    async fn test_libretranslate_request_and_response() {
+        let (url, requests) = mock_server(vec![
+            (200, r#"{"translatedText": " Roger, over. "}"#),
+            (200, r#"{"translatedText": "Hello"}"#),
+        ])
+        .await;
+        let translator = translator(&url, "api_key = \"secret\"").await;
+
+        let text = translator
+            .translate("了解、どうぞ", Some("ja-JP"), "en")
+            .await;
+        assert_eq!(text.unwrap(), "Roger, over.");
+        assert_eq!(
+            translator.translate("Hallo", None, "en-US").await.unwrap(),
+            "Hello"
+        );
+
+        let requests = requests.lock().unwrap();
+        let first: serde_json::Value = serde_json::from_str(&requests[0]).unwrap();
+        assert_eq!(first["q"], "了解、どうぞ");
+        assert_eq!(first["source"], "ja");
+        assert_eq!(first["target"], "en");
+        assert_eq!(first["format"], "text");
+        assert_eq!(first["api_key"], "secret");
+        let second: serde_json::Value = serde_json::from_str(&requests[1]).unwrap();
+        assert_eq!(second["source"], "auto");
+    }
+
+    #[tokio::test]
+    async fn test_libretranslate_reports_server_errors() {
+        let (url, _) = mock_server(vec![
+            (400, r#"{"error": "xx is not supported"}"#),
+            (200, "<html>not json</html>"),
+        ])
+        .await;
+        let translator = translator(&url, "").await;
+
+        match translator.translate("hi", Some("en"), "xx").await {
+            Err(TranslationError::Failed(msg)) => {
+                assert!(
+                    msg.contains("400") && msg.contains("xx is not supported"),
+                    "{msg}"
+                )
+            }
+            other => panic!("expected Failed, got {other:?}"),
+        }
+        assert!(translator.translate("hi", Some("en"), "de").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_libretranslate_config_validation() {
+        let mut translator = LibreTranslate::new();
+        assert!(matches!(
+            translator
+                .initialize(toml::Value::Table(Default::default()))
+                .await,
+            Err(TranslationError::InitializationFailed(_))
+        ));
+        assert!(translator.translate("hi", None, "en").await.is_err());
+    }
+}
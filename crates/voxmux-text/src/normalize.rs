@@ -0,0 +1,165 @@
+use crate::processor::{settings, TextProcessor};
+use serde::Deserialize;
+use voxmux_core::TextError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Case {
+    #[default]
+    Keep,
+    Lower,
+    Upper,
+    /// Capitalise the first letter of each sentence.
+    Sentence,
+}
+
+#[derive(Deserialize)]
+struct Settings {
+    #[serde(default)]
+    case: Case,
+    /// Collapse runs of whitespace, trim, and drop spaces before punctuation.
+    #[serde(default = "default_true")]
+    spacing: bool,
+    /// Appended when the text does not already end in punctuation.
+    #[serde(default)]
+    end_punctuation: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+const CLOSING: &[char] = &[
+    ',', '.', '!', '?', ';', ':', ')', ']', '}', '、', '。', '！', '？',
+];
+const TERMINAL: &[char] = &['.', '!', '?', '。', '！', '？', '…'];
+
+/// Case, spacing and sentence-end normalisation.
+pub struct Normalize {
+    case: Case,
+    spacing: bool,
+    end_punctuation: Option<String>,
+}
+
+impl Normalize {
+    pub fn new() -> Self {
+        Self {
+            case: Case::Keep,
+            spacing: true,
+            end_punctuation: None,
+        }
+    }
+}
+
+impl Default for Normalize {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn fix_spacing(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for word in text.split_whitespace() {
+        if !out.is_empty() && !word.starts_with(CLOSING) {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+    out
+}
+
+fn sentence_case(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut start = true;
+    for c in text.chars() {
+        if start && c.is_alphabetic() {
+            out.extend(c.to_uppercase());
+            start = false;
+        } else {
+            out.push(c);
+            if TERMINAL.contains(&c) {
+                start = true;
+            } else if !c.is_whitespace() {
+                start = false;
+            }
+        }
+    }
+    out
+}
+
+impl TextProcessor for Normalize {
+    fn name(&self) -> &str {
+        "normalize"
+    }
+
+    fn configure(&mut self, table: &toml::Table) -> Result<(), TextError> {
+        let config: Settings = settings("normalize", table)?;
+        self.case = config.case;
+        self.spacing = config.spacing;
+        self.end_punctuation = config.end_punctuation;
+        Ok(())
+    }
+
+    fn process(&self, text: &str) -> String {
+        let mut text = if self.spacing {
+            fix_spacing(text)
+        } else {
+            text.to_string()
+        };
+        text = match self.case {
+            Case::Keep => text,
+            Case::Lower => text.to_lowercase(),
+            Case::Upper => text.to_uppercase(),
+            Case::Sentence => sentence_case(&text),
+        };
+        if let Some(end) = &self.end_punctuation {
+            let trimmed = text.trim_end();
+            if !trimmed.is_empty() && !trimmed.ends_with(TERMINAL) {
+                text.truncate(trimmed.len());
+                text.push_str(end);
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processor(settings: &str) -> Normalize {
+        let mut p = Normalize::new();
+        p.configure(&toml::from_str(settings).unwrap()).unwrap();
+        p
+    }
+
+    #[test]
+    fn test_normalize_spacing_by_default() {
+        let p = processor("");
+        assert_eq!(p.process("  this  is ,  K7 . over \t"), "this is, K7. over");
+        assert_eq!(p.process("   "), "");
+    }
+
+    #[test]
+    fn test_normalize_case_and_end_punctuation() {
+        let p = processor("case = \"sentence\"\nend_punctuation = \".\"");
+        assert_eq!(p.process("roger. copy that"), "Roger. Copy that.");
+        assert_eq!(p.process("done!"), "Done!");
+        assert_eq!(p.process(""), "");
+
+        assert_eq!(processor("case = \"upper\"").process("cq dx"), "CQ DX");
+        assert_eq!(processor("case = \"lower\"").process("CQ DX"), "cq dx");
+        assert_eq!(
+            processor("spacing = false\nend_punctuation = \"。\"").process(" 了解 "),
+            " 了解。"
+        );
+    }
+
+    #[test]
+    fn test_normalize_rejects_unknown_case() {
+        let mut p = Normalize::new();
+        assert!(p
+            .configure(&toml::from_str("case = \"title\"").unwrap())
+            .is_err());
+    }
+}
@@ -0,0 +1,340 @@
+use crate::processor::{settings, TextProcessor};
+use serde::Deserialize;
+use voxmux_core::TextError;
+
+#[derive(Deserialize)]
+struct Settings {
+    /// Run numbers spoken one after another together: "one two three" → "123".
+    #[serde(default = "default_true")]
+    join: bool,
+    /// Also convert a digit word on its own: "one of us" → "1 of us".
+    #[serde(default)]
+    single_digits: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Word {
+    Digit(u64),
+    Teen(u64),
+    Tens(u64),
+    Hundred,
+    Scale(u64),
+    /// "and" in "one hundred and five".
+    And,
+    /// The decimal point in "one four six point five two".
+    Point,
+}
+
+fn word(w: &str) -> Option<Word> {
+    use Word::*;
+    Some(match w {
+        "zero" => Digit(0),
+        "one" => Digit(1),
+        "two" => Digit(2),
+        "three" => Digit(3),
+        "four" => Digit(4),
+        "five" => Digit(5),
+        "six" => Digit(6),
+        "seven" => Digit(7),
+        "eight" => Digit(8),
+        "nine" | "niner" => Digit(9),
+        "ten" => Teen(10),
+        "eleven" => Teen(11),
+        "twelve" => Teen(12),
+        "thirteen" => Teen(13),
+        "fourteen" => Teen(14),
+        "fifteen" => Teen(15),
+        "sixteen" => Teen(16),
+        "seventeen" => Teen(17),
+        "eighteen" => Teen(18),
+        "nineteen" => Teen(19),
+        "twenty" => Tens(20),
+        "thirty" => Tens(30),
+        "forty" => Tens(40),
+        "fifty" => Tens(50),
+        "sixty" => Tens(60),
+        "seventy" => Tens(70),
+        "eighty" => Tens(80),
+        "ninety" => Tens(90),
+        "hundred" => Hundred,
+        "thousand" => Scale(1_000),
+        "million" => Scale(1_000_000),
+        "billion" => Scale(1_000_000_000),
+        "and" => And,
+        "point" => Point,
+        _ => return None,
+    })
+}
+
+/// The words of one token, e.g. "Twenty-five" → [20, 5].
+fn words(core: &str) -> Option<Vec<Word>> {
+    core.to_lowercase().split('-').map(word).collect()
+}
+
+/// Reads a run of number words into digits.
+#[derive(Clone)]
+struct Reader {
+    out: String,
+    join: bool,
+    /// Value of the scales already read ("two thousand …").
+    total: u64,
+    /// Value below the last scale.
+    current: u64,
+    /// Smallest scale read so far.
+    scale: u64,
+    last: Option<Word>,
+    decimal: bool,
+    words: usize,
+}
+
+impl Reader {
+    fn new(join: bool) -> Self {
+        Self {
+            out: String::new(),
+            join,
+            total: 0,
+            current: 0,
+            scale: u64::MAX,
+            last: None,
+            decimal: false,
+            words: 0,
+        }
+    }
+
+    /// Whether `w` continues the number being read.
+    fn extends(&self, w: Word) -> bool {
+        use Word::*;
+        match (self.last, w) {
+            (Some(Tens(_)), Digit(d)) => d > 0 && self.current.is_multiple_of(10),
+            (Some(Hundred | Scale(_) | And), Digit(d)) => d > 0,
+            (Some(Hundred | Scale(_) | And), Teen(_) | Tens(_)) => true,
+            (Some(Digit(_) | Teen(_) | Tens(_)), Hundred) => (1..100).contains(&self.current),
+            (Some(Digit(_) | Teen(_) | Tens(_) | Hundred), Scale(s)) => {
+                self.current > 0 && s < self.scale
+            }
+            (Some(Hundred | Scale(_)), And) => true,
+            _ => false,
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.last.is_some() && !self.decimal {
+            self.out.push_str(&(self.total + self.current).to_string());
+        }
+    }
+
+    /// Read `w`, or return false if it can't be part of this run.
+    fn push(&mut self, w: Word) -> bool {
+        use Word::*;
+        if self.decimal {
+            let Digit(d) = w else {
+                return false;
+            };
+            self.out.push_str(&d.to_string());
+        } else if self.extends(w) {
+            match w {
+                Digit(n) | Teen(n) | Tens(n) => self.current += n,
+                Hundred => self.current *= 100,
+                Scale(s) => {
+                    self.total += self.current * s;
+                    self.current = 0;
+                    self.scale = s;
+                }
+                And | Point => {}
+            }
+        } else {
+            match (self.last, w) {
+                (Some(_), Point) => {
+                    self.flush();
+                    self.out.push('.');
+                    self.decimal = true;
+                }
+                (_, Digit(n) | Teen(n) | Tens(n)) => {
+                    self.flush();
+                    if !self.out.is_empty() && !self.join {
+                        self.out.push(' ');
+                    }
+                    self.total = 0;
+                    self.current = n;
+                    self.scale = u64::MAX;
+                }
+                _ => return false,
+            }
+        }
+        self.last = Some(w);
+        self.words += 1;
+        true
+    }
+
+    /// A complete number ends here, not on a dangling "and" or "point".
+    fn complete(&self) -> bool {
+        !matches!(self.last, None | Some(Word::And | Word::Point))
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        self.out
+    }
+}
+
+/// Spelled-out English numbers to digits: "one four six point five two" →
+/// "146.52", "two thousand and five" → "2005".
+pub struct NumberWords {
+    join: bool,
+    single_digits: bool,
+}
+
+impl NumberWords {
+    pub fn new() -> Self {
+        Self {
+            join: true,
+            single_digits: false,
+        }
+    }
+}
+
+impl Default for NumberWords {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Byte spans of the whitespace-separated tokens in `text`.
+fn tokens(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+    spans
+}
+
+/// Split a token into leading punctuation, word and trailing punctuation.
+fn parts(token: &str) -> (&str, &str, &str) {
+    let start = token.find(char::is_alphanumeric).unwrap_or(token.len());
+    let end = token.rfind(char::is_alphanumeric).map_or(start, |i| {
+        i + token[i..].chars().next().map_or(0, char::len_utf8)
+    });
+    (&token[..start], &token[start..end], &token[end..])
+}
+
+impl TextProcessor for NumberWords {
+    fn name(&self) -> &str {
+        "numbers"
+    }
+
+    fn configure(&mut self, table: &toml::Table) -> Result<(), TextError> {
+        let config: Settings = settings("numbers", table)?;
+        self.join = config.join;
+        self.single_digits = config.single_digits;
+        Ok(())
+    }
+
+    fn process(&self, text: &str) -> String {
+        let spans = tokens(text);
+        let token = |i: usize| parts(&text[spans[i].0..spans[i].1]);
+        let mut out = String::with_capacity(text.len());
+        let mut copied = 0;
+        let mut i = 0;
+        while i < spans.len() {
+            let mut reader = Reader::new(self.join);
+            let mut run = None;
+            for j in i..spans.len() {
+                let (lead, core, _) = token(j);
+                if j > i && (!lead.is_empty() || !token(j - 1).2.is_empty()) {
+                    break;
+                }
+                let Some(words) = words(core) else {
+                    break;
+                };
+                let mut next = reader.clone();
+                if !words.into_iter().all(|w| next.push(w)) {
+                    break;
+                }
+                reader = next;
+                if reader.complete() {
+                    run = Some((reader.clone(), j + 1));
+                }
+            }
+            let lone_digit = |r: &Reader| r.words == 1 && matches!(r.last, Some(Word::Digit(_)));
+            match run {
+                Some((reader, end)) if self.single_digits || !lone_digit(&reader) => {
+                    let (lead, _, _) = token(i);
+                    let (_, _, trail) = token(end - 1);
+                    out.push_str(&text[copied..spans[i].0 + lead.len()]);
+                    out.push_str(&reader.finish());
+                    copied = spans[end - 1].1 - trail.len();
+                    i = end;
+                }
+                _ => i += 1,
+            }
+        }
+        out.push_str(&text[copied..]);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processor(settings: &str) -> NumberWords {
+        let mut p = NumberWords::new();
+        p.configure(&toml::from_str(settings).unwrap()).unwrap();
+        p
+    }
+
+    #[test]
+    fn test_numbers_cardinals() {
+        let p = processor("");
+        assert_eq!(p.process("twenty five"), "25");
+        assert_eq!(p.process("Twenty-five."), "25.");
+        assert_eq!(p.process("one hundred and five"), "105");
+        assert_eq!(p.process("two thousand three hundred forty"), "2340");
+        assert_eq!(p.process("nineteen hundred"), "1900");
+        assert_eq!(p.process("one million two hundred thousand"), "1200000");
+    }
+
+    #[test]
+    fn test_numbers_digit_strings_and_decimals() {
+        let p = processor("");
+        assert_eq!(
+            p.process("tune to one four six point five two"),
+            "tune to 146.52"
+        );
+        assert_eq!(p.process("zero zero seven"), "007");
+        assert_eq!(p.process("niner one one"), "911");
+        assert_eq!(p.process("five twenty"), "520");
+        assert_eq!(processor("join = false").process("one two three"), "1 2 3");
+    }
+
+    #[test]
+    fn test_numbers_leave_other_text_alone() {
+        let p = processor("");
+        assert_eq!(p.process("one of us, two  more"), "one of us, two  more");
+        assert_eq!(p.process("one, two, three"), "one, two, three");
+        assert_eq!(p.process("bread and butter"), "bread and butter");
+        assert_eq!(p.process("a hundred ways"), "a hundred ways");
+        assert_eq!(p.process("ninety and rising"), "90 and rising");
+        assert_eq!(p.process("set point five"), "set point five");
+        assert_eq!(p.process("(twelve) done"), "(12) done");
+        assert_eq!(
+            processor("single_digits = true").process("one of us"),
+            "1 of us"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::processor::{ProcessorChain, TextProcessorRegistry};
+use std::collections::HashMap;
+use voxmux_core::config::AppConfig;
+use voxmux_core::{RecognitionResult, TextError};
+
+/// The global chain plus each input's own chain, built from `[text]` and
+/// `[input.text]`.
+pub struct TextPipeline {
+    global: ProcessorChain,
+    /// Per input: (run the global chain first, own chain).
+    inputs: HashMap<String, (bool, ProcessorChain)>,
+}
+
+impl TextPipeline {
+    pub fn new(config: &AppConfig, registry: &TextProcessorRegistry) -> Result<Self, TextError> {
+        let global = registry.chain(&config.text.processors)?;
+        let mut inputs = HashMap::new();
+        for input in &config.input {
+            if let Some(text) = &input.text {
+                inputs.insert(
+                    input.id.clone(),
+                    (text.inherit, registry.chain(&text.processors)?),
+                );
+            }
+        }
+        Ok(Self { global, inputs })
+    }
+
+    /// Whether any chain has processors.
+    pub fn is_empty(&self) -> bool {
+        self.global.is_empty() && self.inputs.values().all(|(_, chain)| chain.is_empty())
+    }
+
+    pub fn process(&self, input_id: &str, text: &str) -> String {
+        match self.inputs.get(input_id) {
+            Some((true, chain)) => chain.apply(&self.global.apply(text)),
+            Some((false, chain)) => chain.apply(text),
+            None => self.global.apply(text),
+        }
+    }
+
+    /// Rewrite `result.text` in place. Returns false for a partial left
+    /// with nothing but whitespace, which should be dropped. An emptied
+    /// final is kept with empty text, as it still closes its utterance.
+    pub fn apply(&self, result: &mut RecognitionResult) -> bool {
+        result.text = self.process(&result.input_id, &result.text);
+        if result.text.trim().is_empty() {
+            result.text.clear();
+            return result.is_final;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[[text.processors]]
+processor = "dictionary"
+remove = ["um"]
+
+[[text.processors]]
+processor = "normalize"
+
+[[input]]
+id = "ja"
+device_name = "default"
+[input.text]
+inherit = false
+[[input.text.processors]]
+processor = "width"
+
+[[input]]
+id = "en"
+device_name = "default"
+[[input.text.processors]]
+processor = "normalize"
+case = "upper"
+
+[[input]]
+id = "plain"
+device_name = "default"
+"#;
+
+    fn pipeline() -> TextPipeline {
+        let config = AppConfig::from_toml_str(CONFIG).unwrap();
+        TextPipeline::new(&config, &TextProcessorRegistry::new()).unwrap()
+    }
+
+    #[test]
+    fn test_pipeline_global_and_per_input_chains() {
+        let p = pipeline();
+        assert!(!p.is_empty());
+        assert_eq!(p.process("plain", "um  roger um"), "roger");
+        assert_eq!(p.process("en", "um  roger"), "ROGER");
+        assert_eq!(p.process("ja", "ＵＭ　了解"), "UM 了解");
+        assert_eq!(p.process("unknown", "um copy"), "copy");
+    }
+
+    #[test]
+    fn test_pipeline_drops_emptied_partials_and_keeps_emptied_finals() {
+        let p = pipeline();
+        let mut result = RecognitionResult {
+            input_id: "plain".to_string(),
+            text: " um ".to_string(),
+            is_final: false,
+            ..Default::default()
+        };
+        assert!(!p.apply(&mut result));
+
+        result.text = " um ".to_string();
+        result.is_final = true;
+        assert!(p.apply(&mut result));
+        assert_eq!(result.text, "");
+
+        result.text = "um roger".to_string();
+        assert!(p.apply(&mut result));
+        assert_eq!(result.text, "roger");
+    }
+
+    #[test]
+    fn test_pipeline_unknown_processor_fails() {
+        let config =
+            AppConfig::from_toml_str("[[text.processors]]\nprocessor = \"nope\"\n").unwrap();
+        assert!(matches!(
+            TextPipeline::new(&config, &TextProcessorRegistry::new()),
+            Err(TextError::ProcessorNotFound(_))
+        ));
+        let empty = AppConfig::from_toml_str("").unwrap();
+        assert!(TextPipeline::new(&empty, &TextProcessorRegistry::new())
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use voxmux_core::config::TextProcessorConfig;
+use voxmux_core::TextError;
+
+/// A step that rewrites recognised text, e.g. vocabulary fixes or
+/// normalisation.
+pub trait TextProcessor: Send + Sync {
+    fn name(&self) -> &str;
+    /// Apply the settings from the processor's config table. Called once,
+    /// before any text.
+    fn configure(&mut self, settings: &toml::Table) -> Result<(), TextError>;
+    fn process(&self, text: &str) -> String;
+}
+
+/// Deserialize a processor's settings table into `T`.
+pub(crate) fn settings<T: serde::de::DeserializeOwned>(
+    processor: &str,
+    settings: &toml::Table,
+) -> Result<T, TextError> {
+    toml::Value::Table(settings.clone())
+        .try_into()
+        .map_err(|e| TextError::InvalidConfig(format!("{processor}: {e}")))
+}
+
+// ── ProcessorChain ─────────────────────────────────────────────
+
+/// Processors applied in order, each to the previous one's output.
+#[derive(Default)]
+pub struct ProcessorChain {
+    processors: Vec<Box<dyn TextProcessor>>,
+}
+
+impl ProcessorChain {
+    pub fn new(processors: Vec<Box<dyn TextProcessor>>) -> Self {
+        Self { processors }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.processors.iter().map(|p| p.name()).collect()
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        self.processors
+            .iter()
+            .fold(text.to_string(), |text, p| p.process(&text))
+    }
+}
+
+// ── TextProcessorRegistry ──────────────────────────────────────
+
+pub struct TextProcessorRegistry {
+    factories: HashMap<String, fn() -> Box<dyn TextProcessor>>,
+}
+
+impl TextProcessorRegistry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            factories: HashMap::new(),
+        };
+        registry.register("regex", || {
+            Box::new(crate::regex_replace::RegexReplace::new())
+        });
+        registry.register("dictionary", || {
+            Box::new(crate::dictionary::DictionaryReplace::new())
+        });
+        registry.register("normalize", || Box::new(crate::normalize::Normalize::new()));
+        registry.register("numbers", || Box::new(crate::numbers::NumberWords::new()));
+        registry.register("width", || Box::new(crate::width::WidthConvert::new()));
+        registry
+    }
+
+    pub fn register(&mut self, name: &str, factory: fn() -> Box<dyn TextProcessor>) {
+        self.factories.insert(name.to_string(), factory);
+    }
+
+    /// Create and configure the processor named in `config`.
+    pub fn create(
+        &self,
+        config: &TextProcessorConfig,
+    ) -> Result<Box<dyn TextProcessor>, TextError> {
+        let factory = self
+            .factories
+            .get(&config.processor)
+            .ok_or_else(|| TextError::ProcessorNotFound(config.processor.clone()))?;
+        let mut processor = factory();
+        processor.configure(&config.settings)?;
+        Ok(processor)
+    }
+
+    pub fn chain(&self, configs: &[TextProcessorConfig]) -> Result<ProcessorChain, TextError> {
+        configs
+            .iter()
+            .map(|c| self.create(c))
+            .collect::<Result<_, _>>()
+            .map(ProcessorChain::new)
+    }
+
+    pub fn list(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(|s| s.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl Default for TextProcessorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Suffix(String);
+
+    impl TextProcessor for Suffix {
+        fn name(&self) -> &str {
+            "suffix"
+        }
+        fn configure(&mut self, settings: &toml::Table) -> Result<(), TextError> {
+            self.0 = settings
+                .get("text")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| TextError::InvalidConfig("suffix: missing 'text'".to_string()))?
+                .to_string();
+            Ok(())
+        }
+        fn process(&self, text: &str) -> String {
+            format!("{text}{}", self.0)
+        }
+    }
+
+    fn config(processor: &str, settings: &str) -> TextProcessorConfig {
+        TextProcessorConfig {
+            processor: processor.to_string(),
+            settings: toml::from_str(settings).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_registry_lists_builtins() {
+        assert_eq!(
+            TextProcessorRegistry::new().list(),
+            ["dictionary", "normalize", "numbers", "regex", "width"]
+        );
+    }
+
+    #[test]
+    fn test_registry_unknown_processor_fails() {
+        let registry = TextProcessorRegistry::new();
+        assert!(matches!(
+            registry.create(&config("nope", "")),
+            Err(TextError::ProcessorNotFound(name)) if name == "nope"
+        ));
+    }
+
+    #[test]
+    fn test_chain_applies_in_order() {
+        let mut registry = TextProcessorRegistry::new();
+        registry.register("suffix", || Box::new(Suffix(String::new())));
+        let chain = registry
+            .chain(&[
+                config("suffix", "text = \"!\""),
+                config("suffix", "text = \"?\""),
+            ])
+            .unwrap();
+        assert_eq!(chain.names(), ["suffix", "suffix"]);
+        assert_eq!(chain.apply("hi"), "hi!?");
+        assert!(ProcessorChain::default().is_empty());
+
+        assert!(matches!(
+            registry.chain(&[config("suffix", "")]),
+            Err(TextError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::processor::{settings, TextProcessor};
+use regex::Regex;
+use serde::Deserialize;
+use voxmux_core::TextError;
+
+#[derive(Deserialize)]
+struct Settings {
+    rules: Vec<Rule>,
+}
+
+#[derive(Deserialize)]
+struct Rule {
+    pattern: String,
+    /// May refer to capture groups as `$1` or `${name}`.
+    #[serde(default)]
+    replace: String,
+}
+
+/// Regular expression substitutions, applied in order.
+pub struct RegexReplace {
+    rules: Vec<(Regex, String)>,
+}
+
+impl RegexReplace {
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+}
+
+impl Default for RegexReplace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextProcessor for RegexReplace {
+    fn name(&self) -> &str {
+        "regex"
+    }
+
+    fn configure(&mut self, table: &toml::Table) -> Result<(), TextError> {
+        let config: Settings = settings("regex", table)?;
+        self.rules = config
+            .rules
+            .into_iter()
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .map(|re| (re, rule.replace))
+                    .map_err(|e| TextError::InvalidConfig(format!("regex: {e}")))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
+    fn process(&self, text: &str) -> String {
+        self.rules
+            .iter()
+            .fold(text.to_string(), |text, (re, replace)| {
+                re.replace_all(&text, replace.as_str()).into_owned()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processor(settings: &str) -> Result<RegexReplace, TextError> {
+        let mut p = RegexReplace::new();
+        p.configure(&toml::from_str(settings).unwrap())?;
+        Ok(p)
+    }
+
+    #[test]
+    fn test_regex_rules_apply_in_order_with_captures() {
+        let p = processor(
+            r#"
+rules = [
+    { pattern = '(\d+) point (\d+)', replace = '$1.$2' },
+    { pattern = '(?i)\bover and out\b', replace = 'out' },
+    { pattern = '\s*\[noise\]' },
+]
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            p.process("one four 7 point 5 [noise] Over and Out"),
+            "one four 7.5 out"
+        );
+    }
+
+    #[test]
+    fn test_regex_invalid_pattern_fails() {
+        assert!(matches!(
+            processor("rules = [{ pattern = '(' }]"),
+            Err(TextError::InvalidConfig(msg)) if msg.starts_with("regex:")
+        ));
+        assert!(processor("").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use voxmux_core::TranslationError;
+
+/// Machine translation of recognised text.
+#[async_trait]
+pub trait Translator: Send + Sync {
+    fn name(&self) -> &str;
+    async fn initialize(&mut self, config: toml::Value) -> Result<(), TranslationError>;
+    /// Translate `text` into `target`. `source` is the language reported by
+    /// the engine, if any; translators should detect it when `None`.
+    async fn translate(
+        &self,
+        text: &str,
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<String, TranslationError>;
+}
+
+/// The primary subtag of a language code: `"en-US"` → `"en"`.
+pub fn base_language(code: &str) -> String {
+    code.split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Whether text in `source` needs no translation into `target`.
+pub fn same_language(source: Option<&str>, target: &str) -> bool {
+    source.is_some_and(|s| base_language(s) == base_language(target))
+}
+
+// ── TranslatorRegistry ─────────────────────────────────────────
+
+pub struct TranslatorRegistry {
+    factories: HashMap<String, fn() -> Box<dyn Translator>>,
+}
+
+impl TranslatorRegistry {
+    pub fn new() -> Self {
+        #[allow(unused_mut)]
+        let mut registry = Self {
+            factories: HashMap::new(),
+        };
+        #[cfg(feature = "http")]
+        registry.register("libretranslate", || {
+            Box::new(crate::libretranslate::LibreTranslate::new())
+        });
+        registry
+    }
+
+    pub fn register(&mut self, name: &str, factory: fn() -> Box<dyn Translator>) {
+        self.factories.insert(name.to_string(), factory);
+    }
+
+    pub fn create(&self, name: &str) -> Result<Box<dyn Translator>, TranslationError> {
+        self.factories
+            .get(name)
+            .map(|factory| factory())
+            .ok_or_else(|| TranslationError::NotFound(name.to_string()))
+    }
+
+    pub fn list(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.factories.keys().map(|s| s.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl Default for TranslatorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Upper;
+
+    #[async_trait]
+    impl Translator for Upper {
+        fn name(&self) -> &str {
+            "upper"
+        }
+        async fn initialize(&mut self, _config: toml::Value) -> Result<(), TranslationError> {
+            Ok(())
+        }
+        async fn translate(
+            &self,
+            text: &str,
+            _source: Option<&str>,
+            _target: &str,
+        ) -> Result<String, TranslationError> {
+            Ok(text.to_uppercase())
+        }
+    }
+
+    #[test]
+    fn test_language_codes() {
+        assert_eq!(base_language("en-US"), "en");
+        assert_eq!(base_language("pt_BR"), "pt");
+        assert_eq!(base_language("JA"), "ja");
+        assert!(same_language(Some("en-GB"), "en"));
+        assert!(!same_language(Some("ja"), "en"));
+        assert!(!same_language(None, "en"));
+    }
+
+    #[tokio::test]
+    async fn test_registry_creates_registered_translators() {
+        let mut registry = TranslatorRegistry::new();
+        registry.register("upper", || Box::new(Upper));
+        assert!(registry.list().contains(&"upper"));
+
+        let translator = registry.create("upper").unwrap();
+        assert_eq!(
+            translator
+                .translate("roger", Some("en"), "de")
+                .await
+                .unwrap(),
+            "ROGER"
+        );
+        assert!(matches!(
+            registry.create("nope"),
+            Err(TranslationError::NotFound(name)) if name == "nope"
+        ));
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_registry_lists_libretranslate() {
+        assert_eq!(TranslatorRegistry::new().list(), ["libretranslate"]);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::processor::{settings, TextProcessor};
+use serde::Deserialize;
+use voxmux_core::TextError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Width {
+    /// Full-width ASCII to half-width, half-width katakana to full-width.
+    #[default]
+    Half,
+    /// Half-width ASCII to full-width.
+    Full,
+}
+
+#[derive(Deserialize)]
+struct Settings {
+    #[serde(default)]
+    to: Width,
+}
+
+/// Full-width forms of U+FF61..=U+FF9F.
+const KATAKANA: &str = "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン゛゜";
+
+/// Full-/half-width conversion, mainly for Japanese output.
+pub struct WidthConvert {
+    to: Width,
+}
+
+impl WidthConvert {
+    pub fn new() -> Self {
+        Self { to: Width::Half }
+    }
+}
+
+impl Default for WidthConvert {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn katakana(c: char) -> Option<char> {
+    let index = (c as u32).checked_sub(0xFF61)?;
+    KATAKANA.chars().nth(index as usize)
+}
+
+/// Combine a full-width kana with a half-width (han)dakuten.
+fn voiced(base: char, mark: char) -> Option<char> {
+    match mark {
+        '\u{FF9E}' if base == 'ウ' => Some('ヴ'),
+        '\u{FF9E}' if "カキクケコサシスセソタチツテトハヒフヘホ".contains(base) => {
+            char::from_u32(base as u32 + 1)
+        }
+        '\u{FF9F}' if "ハヒフヘホ".contains(base) => char::from_u32(base as u32 + 2),
+        _ => None,
+    }
+}
+
+fn to_half(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\u{FF01}'..='\u{FF5E}' => out.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)),
+            '\u{3000}' => out.push(' '),
+            '\u{FF9E}' | '\u{FF9F}' => match out.pop() {
+                Some(base) => match voiced(base, c) {
+                    Some(v) => out.push(v),
+                    None => {
+                        out.push(base);
+                        out.extend(katakana(c));
+                    }
+                },
+                None => out.extend(katakana(c)),
+            },
+            '\u{FF61}'..='\u{FF9D}' => out.push(katakana(c).unwrap_or(c)),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn to_full(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '!'..='~' => char::from_u32(c as u32 + 0xFEE0).unwrap_or(c),
+            ' ' => '\u{3000}',
+            _ => c,
+        })
+        .collect()
+}
+
+impl TextProcessor for WidthConvert {
+    fn name(&self) -> &str {
+        "width"
+    }
+
+    fn configure(&mut self, table: &toml::Table) -> Result<(), TextError> {
+        let config: Settings = settings("width", table)?;
+        self.to = config.to;
+        Ok(())
+    }
+
+    fn process(&self, text: &str) -> String {
+        match self.to {
+            Width::Half => to_half(text),
+            Width::Full => to_full(text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn processor(settings: &str) -> WidthConvert {
+        let mut p = WidthConvert::new();
+        p.configure(&toml::from_str(settings).unwrap()).unwrap();
+        p
+    }
+
+    #[test]
+    fn test_width_half_converts_ascii_and_katakana() {
+        let p = processor("");
+        assert_eq!(p.process("ＪＡ１ＸＹＺ　です！"), "JA1XYZ です!");
+        assert_eq!(p.process("ｺﾝﾃｽﾄ ｶﾞﾊﾟｳﾞｫ｡"), "コンテスト ガパヴォ。");
+        assert_eq!(p.process("ﾞｱﾟ"), "゛ア゜");
+    }
+
+    #[test]
+    fn test_width_full_converts_ascii() {
+        let p = processor("to = \"full\"");
+        assert_eq!(p.process("CQ 599!"), "ＣＱ　５９９！");
+        assert_eq!(p.process("了解"), "了解");
+    }
+}
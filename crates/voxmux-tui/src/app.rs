@@ -2,7 +2,10 @@ use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
 use crossterm::event::{KeyCode, KeyEvent};
-use voxmux_core::tui_types::{RouterState, UiCommand};
+use voxmux_core::tui_types::{RouterState, ToneSignal, ToneTarget, UiCommand};
+
+/// Spectrum rows kept for the waterfall.
+pub const WATERFALL_ROWS: usize = 64;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tab {
@@ -27,6 +30,11 @@ pub struct App {
     pub logs: Arc<Mutex<VecDeque<String>>>,
     pub log_scroll: usize,
     pub log_auto_scroll: bool,
+    /// Inputs tab shows the selected input's detail view.
+    pub input_detail: bool,
+    /// Spectrum history of the selected input, newest first.
+    pub waterfall: VecDeque<Vec<f32>>,
+    waterfall_seq: u64,
 }
 
 impl App {
@@ -39,6 +47,9 @@ impl App {
             logs,
             log_scroll: 0,
             log_auto_scroll: true,
+            input_detail: false,
+            waterfall: VecDeque::new(),
+            waterfall_seq: 0,
         }
     }
 
@@ -48,6 +59,26 @@ impl App {
         if !self.state.inputs.is_empty() && self.selected_input >= self.state.inputs.len() {
             self.selected_input = self.state.inputs.len() - 1;
         }
+        let spectrum = self
+            .state
+            .inputs
+            .get(self.selected_input)
+            .and_then(|i| i.spectrum.as_ref());
+        if let Some(spectrum) = spectrum {
+            if spectrum.seq != self.waterfall_seq {
+                self.waterfall_seq = spectrum.seq;
+                self.waterfall.push_front(spectrum.bands_db.clone());
+                self.waterfall.truncate(WATERFALL_ROWS);
+            }
+        }
+    }
+
+    fn select_input(&mut self, index: usize) {
+        if index != self.selected_input {
+            self.selected_input = index;
+            self.waterfall.clear();
+            self.waterfall_seq = 0;
+        }
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> AppAction {
@@ -93,16 +124,31 @@ impl App {
         match key.code {
             KeyCode::Up => {
                 if self.selected_input > 0 {
-                    self.selected_input -= 1;
+                    self.select_input(self.selected_input - 1);
                 }
                 AppAction::None
             }
             KeyCode::Down => {
                 if self.selected_input + 1 < self.state.inputs.len() {
-                    self.selected_input += 1;
+                    self.select_input(self.selected_input + 1);
                 }
                 AppAction::None
             }
+            KeyCode::Enter => {
+                self.input_detail = !self.input_detail;
+                AppAction::None
+            }
+            KeyCode::Esc => {
+                self.input_detail = false;
+                AppAction::None
+            }
+            KeyCode::Char('f') => {
+                let input = &self.state.inputs[self.selected_input];
+                AppAction::Command(UiCommand::SetSpectrum {
+                    input_id: input.id.clone(),
+                    enabled: input.spectrum.is_none(),
+                })
+            }
             KeyCode::Right => {
                 let input = &self.state.inputs[self.selected_input];
                 let new_vol = (input.volume + 0.05).min(1.0);
@@ -133,6 +179,24 @@ impl App {
                     enabled: !input.enabled,
                 })
             }
+            KeyCode::Char('l') => {
+                let input = &self.state.inputs[self.selected_input];
+                if self.state.tone.measuring_input.as_ref() == Some(&input.id) {
+                    AppAction::Command(UiCommand::StopTone)
+                } else {
+                    AppAction::Command(UiCommand::MeasureLoopback {
+                        input_id: input.id.clone(),
+                    })
+                }
+            }
+            KeyCode::Char('r') => {
+                let id = self.state.inputs[self.selected_input].id.clone();
+                self.replay_action(Some(id))
+            }
+            KeyCode::Char('w') => {
+                let id = self.state.inputs[self.selected_input].id.clone();
+                self.export_action(Some(id))
+            }
             _ => AppAction::None,
         }
     }
@@ -142,10 +206,56 @@ impl App {
             KeyCode::Char(' ') => AppAction::Command(UiCommand::SetPlayMixedInput(
                 !self.state.output.play_mixed_input,
             )),
+            // Cycle: off → 1 kHz reference → pink noise → off
+            KeyCode::Char('t') => {
+                let tone = &self.state.tone;
+                let next = match tone.signal {
+                    _ if tone.measuring_input.is_some() => None,
+                    None => Some(ToneSignal::Reference1k),
+                    Some(ToneSignal::Reference1k) => Some(ToneSignal::PinkNoise {
+                        level_dbfs: -20.0,
+                    }),
+                    Some(_) => None,
+                };
+                AppAction::Command(match next {
+                    Some(signal) => UiCommand::StartTone {
+                        signal,
+                        target: ToneTarget::Mix,
+                    },
+                    None => UiCommand::StopTone,
+                })
+            }
+            KeyCode::Char('c') => {
+                AppAction::Command(UiCommand::SetCuesMuted(!self.state.cues_muted))
+            }
+            KeyCode::Char('r') => self.replay_action(None),
+            KeyCode::Char('w') => self.export_action(None),
             _ => AppAction::None,
         }
     }
 
+    /// Replay the full history of an input (`None` = mix), or stop if it
+    /// is already playing.
+    fn replay_action(&self, input_id: Option<String>) -> AppAction {
+        let replay = &self.state.replay;
+        let source = input_id.as_deref().unwrap_or("mix");
+        if replay.playing.as_deref() == Some(source) {
+            AppAction::Command(UiCommand::StopReplay)
+        } else {
+            AppAction::Command(UiCommand::Replay {
+                input_id,
+                seconds: replay.history_secs as f32,
+            })
+        }
+    }
+
+    fn export_action(&self, input_id: Option<String>) -> AppAction {
+        AppAction::Command(UiCommand::ExportReplay {
+            input_id,
+            seconds: self.state.replay.history_secs as f32,
+        })
+    }
+
     fn handle_logs_key(&mut self, key: KeyEvent) -> AppAction {
         match key.code {
             KeyCode::Up => {
@@ -171,7 +281,7 @@ impl App {
 mod tests {
     use super::*;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-    use voxmux_core::tui_types::InputState;
+    use voxmux_core::tui_types::{InputState, Spectrum};
 
     fn key(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code, KeyModifiers::NONE)
@@ -409,4 +519,199 @@ mod tests {
         assert_eq!(app.log_scroll, 0);
         assert!(app.log_auto_scroll);
     }
+
+    #[test]
+    fn test_app_tone_cycle() {
+        let mut app = make_app();
+        app.tab = Tab::Outputs;
+        let action = app.handle_key(key(KeyCode::Char('t')));
+        assert_eq!(
+            action,
+            AppAction::Command(UiCommand::StartTone {
+                signal: ToneSignal::Reference1k,
+                target: ToneTarget::Mix,
+            })
+        );
+
+        app.state.tone.signal = Some(ToneSignal::Reference1k);
+        let action = app.handle_key(key(KeyCode::Char('t')));
+        assert!(matches!(
+            action,
+            AppAction::Command(UiCommand::StartTone {
+                signal: ToneSignal::PinkNoise { .. },
+                ..
+            })
+        ));
+
+        app.state.tone.signal = Some(ToneSignal::PinkNoise { level_dbfs: -20.0 });
+        let action = app.handle_key(key(KeyCode::Char('t')));
+        assert_eq!(action, AppAction::Command(UiCommand::StopTone));
+    }
+
+    #[test]
+    fn test_app_loopback_toggle() {
+        let mut app = make_app_with_inputs(vec![InputState {
+            id: "mic1".into(),
+            ..Default::default()
+        }]);
+        app.tab = Tab::Inputs;
+        let action = app.handle_key(key(KeyCode::Char('l')));
+        assert_eq!(
+            action,
+            AppAction::Command(UiCommand::MeasureLoopback {
+                input_id: "mic1".into(),
+            })
+        );
+
+        app.state.tone.measuring_input = Some("mic1".into());
+        let action = app.handle_key(key(KeyCode::Char('l')));
+        assert_eq!(action, AppAction::Command(UiCommand::StopTone));
+    }
+
+    #[test]
+    fn test_app_replay_input_toggle() {
+        let mut app = make_app_with_inputs(vec![InputState {
+            id: "mic1".into(),
+            ..Default::default()
+        }]);
+        app.state.replay.history_secs = 30;
+        app.tab = Tab::Inputs;
+        let action = app.handle_key(key(KeyCode::Char('r')));
+        assert_eq!(
+            action,
+            AppAction::Command(UiCommand::Replay {
+                input_id: Some("mic1".into()),
+                seconds: 30.0,
+            })
+        );
+
+        app.state.replay.playing = Some("mic1".into());
+        let action = app.handle_key(key(KeyCode::Char('r')));
+        assert_eq!(action, AppAction::Command(UiCommand::StopReplay));
+
+        let action = app.handle_key(key(KeyCode::Char('w')));
+        assert_eq!(
+            action,
+            AppAction::Command(UiCommand::ExportReplay {
+                input_id: Some("mic1".into()),
+                seconds: 30.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_app_replay_mix() {
+        let mut app = make_app();
+        app.state.replay.history_secs = 10;
+        app.state.replay.playing = Some("mic1".into());
+        app.tab = Tab::Outputs;
+        // Another source playing: replaying the mix replaces it
+        let action = app.handle_key(key(KeyCode::Char('r')));
+        assert_eq!(
+            action,
+            AppAction::Command(UiCommand::Replay {
+                input_id: None,
+                seconds: 10.0,
+            })
+        );
+        let action = app.handle_key(key(KeyCode::Char('w')));
+        assert_eq!(
+            action,
+            AppAction::Command(UiCommand::ExportReplay {
+                input_id: None,
+                seconds: 10.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_app_cues_mute_toggle() {
+        let mut app = make_app();
+        app.tab = Tab::Outputs;
+        let action = app.handle_key(key(KeyCode::Char('c')));
+        assert_eq!(action, AppAction::Command(UiCommand::SetCuesMuted(true)));
+        app.state.cues_muted = true;
+        let action = app.handle_key(key(KeyCode::Char('c')));
+        assert_eq!(action, AppAction::Command(UiCommand::SetCuesMuted(false)));
+    }
+
+    #[test]
+    fn test_app_input_detail_toggle() {
+        let mut app = make_app_with_inputs(vec![InputState {
+            id: "mic1".into(),
+            ..Default::default()
+        }]);
+        app.tab = Tab::Inputs;
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.input_detail);
+        app.handle_key(key(KeyCode::Esc));
+        assert!(!app.input_detail);
+    }
+
+    #[test]
+    fn test_app_spectrum_toggle() {
+        let mut app = make_app_with_inputs(vec![InputState {
+            id: "mic1".into(),
+            ..Default::default()
+        }]);
+        app.tab = Tab::Inputs;
+        let action = app.handle_key(key(KeyCode::Char('f')));
+        assert_eq!(
+            action,
+            AppAction::Command(UiCommand::SetSpectrum {
+                input_id: "mic1".into(),
+                enabled: true,
+            })
+        );
+
+        app.state.inputs[0].spectrum = Some(Spectrum::default());
+        let action = app.handle_key(key(KeyCode::Char('f')));
+        assert_eq!(
+            action,
+            AppAction::Command(UiCommand::SetSpectrum {
+                input_id: "mic1".into(),
+                enabled: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_app_waterfall_history() {
+        let spectrum_input = |id: &str, seq: u64| InputState {
+            id: id.into(),
+            spectrum: Some(Spectrum {
+                bands_db: vec![-(seq as f32)],
+                max_hz: 4000.0,
+                seq,
+            }),
+            ..Default::default()
+        };
+        let mut app = make_app();
+        for seq in [1, 1, 2, 3] {
+            app.update_state(RouterState {
+                inputs: vec![spectrum_input("a", seq), spectrum_input("b", 10)],
+                ..Default::default()
+            });
+        }
+        // Repeated frames are recorded once, newest first
+        assert_eq!(app.waterfall, vec![vec![-3.0], vec![-2.0], vec![-1.0]]);
+
+        for seq in 4..(4 + WATERFALL_ROWS as u64) {
+            app.update_state(RouterState {
+                inputs: vec![spectrum_input("a", seq), spectrum_input("b", 10)],
+                ..Default::default()
+            });
+        }
+        assert_eq!(app.waterfall.len(), WATERFALL_ROWS);
+
+        // Selecting another input starts a fresh history
+        app.tab = Tab::Inputs;
+        app.handle_key(key(KeyCode::Down));
+        assert!(app.waterfall.is_empty());
+        app.update_state(RouterState {
+            inputs: vec![spectrum_input("a", 99), spectrum_input("b", 10)],
+            ..Default::default()
+        });
+        assert_eq!(app.waterfall, vec![vec![-10.0]]);
+    }
 }
@@ -1,10 +1,26 @@
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Tabs};
+use ratatui::widgets::{
+    Bar, BarChart, BarGroup, Block, Borders, Gauge, List, ListItem, Paragraph, Tabs,
+};
 use ratatui::Frame;
 
 use crate::app::{App, Tab};
+use voxmux_core::tui_types::{EngineState, InputState, ToneTarget, VadState};
+
+/// Levels shown in the spectrum views, in dBFS.
+const SPECTRUM_MIN_DB: f32 = -100.0;
+const SPECTRUM_MAX_DB: f32 = 0.0;
+/// Waterfall colours from quietest to loudest.
+const WATERFALL_PALETTE: [Color; 6] = [
+    Color::Black,
+    Color::Blue,
+    Color::Cyan,
+    Color::Green,
+    Color::Yellow,
+    Color::Red,
+];
 
 pub fn draw(frame: &mut Frame, app: &App) {
     let [tabs_area, main_area] =
@@ -61,10 +77,34 @@ fn draw_dashboard(frame: &mut Frame, app: &App, area: Rect) {
     let areas = Layout::vertical(constraints).split(area);
 
     for (i, input) in app.state.inputs.iter().enumerate() {
+        let lag = if input.asr_queue.is_falling_behind() {
+            format!(" [ASR +{:.1}s]", input.asr_queue.queued_ms as f32 / 1000.0)
+        } else {
+            String::new()
+        };
+        let mut squelch = input
+            .ctcss_hz
+            .map(|hz| format!(" [CTCSS {:.1}]", hz))
+            .unwrap_or_default();
+        if let Some(code) = input.dcs_code {
+            squelch.push_str(&format!(" [DCS {:03o}N]", code));
+        }
+        let speech = if input.vad == VadState::Speech { " [SPEECH]" } else { "" };
+        let engine = match input.asr_engine.as_ref().map(|e| e.state) {
+            Some(EngineState::Degraded) => " [ASR DEGRADED]",
+            Some(EngineState::Restarting) => " [ASR RESTARTING]",
+            Some(EngineState::Failed) => " [ASR FAILED]",
+            Some(EngineState::Running) | None => "",
+        };
         let label = format!(
-            "{} {}",
+            "{} {}{}{}{}{}{}",
             input.id,
-            if input.muted { "[M]" } else { "" }
+            if input.muted { "[M]" } else { "" },
+            if input.transmitting { " [TX]" } else { "" },
+            speech,
+            engine,
+            lag,
+            squelch,
         );
         let ratio = input.peak_level.clamp(0.0, 1.0) as f64;
         let gauge = Gauge::default()
@@ -96,7 +136,17 @@ fn draw_dashboard(frame: &mut Frame, app: &App, area: Rect) {
         .iter()
         .rev()
         .take(10)
-        .map(|s| ListItem::new(s.as_str()))
+        .map(|s| {
+            // Lines that raised a highlighted alert stand out
+            if app.state.is_alert(s) {
+                ListItem::new(Span::styled(
+                    s.as_str(),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                ListItem::new(s.as_str())
+            }
+        })
         .collect();
     let recog_list = List::new(recog_items)
         .block(Block::default().borders(Borders::ALL).title("Recent ASR"));
@@ -104,7 +154,14 @@ fn draw_dashboard(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn draw_inputs(frame: &mut Frame, app: &App, area: Rect) {
-    let items: Vec<ListItem> = app
+    if app.input_detail {
+        if let Some(input) = app.state.inputs.get(app.selected_input) {
+            draw_input_detail(frame, app, input, area);
+            return;
+        }
+    }
+
+    let mut items: Vec<ListItem> = app
         .state
         .inputs
         .iter()
@@ -113,6 +170,48 @@ fn draw_inputs(frame: &mut Frame, app: &App, area: Rect) {
             let marker = if i == app.selected_input { ">" } else { " " };
             let mute_str = if input.muted { " [MUTED]" } else { "" };
             let enabled_str = if input.enabled { "" } else { " (disabled)" };
+            let asr_str = if input.asr_queue.capacity_ms > 0 {
+                format!(
+                    "  asr q:{}ms drop:{}ms",
+                    input.asr_queue.queued_ms, input.asr_queue.dropped_ms
+                )
+            } else {
+                String::new()
+            };
+            let pool_str = if input.asr_segments > 0 {
+                format!("  seg:{}", input.asr_segments)
+            } else {
+                String::new()
+            };
+            let vad_str = match input.vad {
+                VadState::Off => "",
+                VadState::Silence => "  vad:silence",
+                VadState::Speech => "  vad:speech",
+            };
+            let engine_str = match &input.asr_engine {
+                Some(engine) if engine.restarts > 0 || engine.state != EngineState::Running => {
+                    format!("  engine:{} restarts:{}", engine.state, engine.restarts)
+                }
+                _ => String::new(),
+            };
+            let heard_str = match &input.last_heard {
+                Some(tx) => format!(
+                    "  heard:{} ago ({:.1}s)",
+                    format_age(unix_now() - (tx.started_at + tx.duration_secs)),
+                    tx.duration_secs
+                ),
+                None => String::new(),
+            };
+            let mut tone_str = String::new();
+            if let Some(hz) = input.ctcss_hz {
+                tone_str.push_str(&format!("  ctcss:{:.1}Hz", hz));
+            }
+            if let Some(code) = input.dcs_code {
+                tone_str.push_str(&format!("  dcs:{:03o}N", code));
+            }
+            if !input.dtmf_digits.is_empty() {
+                tone_str.push_str(&format!("  dtmf:{}", input.dtmf_digits));
+            }
             let line = Line::from(vec![
                 Span::raw(format!("{} ", marker)),
                 Span::styled(
@@ -124,34 +223,208 @@ fn draw_inputs(frame: &mut Frame, app: &App, area: Rect) {
                     },
                 ),
                 Span::raw(format!(
-                    "  vol:{:.0}%{}{}",
+                    "  vol:{:.0}%{}{}{}{}{}{}{}{}",
                     input.volume * 100.0,
                     mute_str,
                     enabled_str,
+                    asr_str,
+                    pool_str,
+                    vad_str,
+                    engine_str,
+                    heard_str,
+                    tone_str,
                 )),
             ]);
             ListItem::new(line)
         })
         .collect();
+    if let Some(pool) = &app.state.asr_pool {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Span::styled(
+            format!(
+                "  ASR pool: {}/{} busy  queued:{}  done:{}  dropped:{}  latency last:{}ms avg:{}ms max:{}ms",
+                pool.busy,
+                pool.workers,
+                pool.queued,
+                pool.completed,
+                pool.dropped,
+                pool.last_latency_ms,
+                pool.avg_latency_ms,
+                pool.max_latency_ms,
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
 
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .title("Inputs (Up/Down=select, Left/Right=vol, m=mute, e=enable)"),
+            .title(
+                "Inputs (Up/Down=select, Left/Right=vol, m=mute, e=enable, l=loopback, \
+                 f=spectrum, r=replay, w=export, Enter=detail)",
+            ),
     );
     frame.render_widget(list, area);
 }
 
+fn draw_input_detail(frame: &mut Frame, app: &App, input: &InputState, area: Rect) {
+    let [header_area, chart_area, waterfall_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Fill(1),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+
+    let spectrum_str = match &input.spectrum {
+        Some(spectrum) => format!("spectrum 0-{:.0} Hz (f=off)", spectrum.max_hz),
+        None => "spectrum off (f=on)".to_string(),
+    };
+    let header = Paragraph::new(format!(
+        "{}  vol:{:.0}%  {}",
+        input.device_name,
+        input.volume * 100.0,
+        spectrum_str
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Input '{}' (Esc=back)", input.id)),
+    );
+    frame.render_widget(header, header_area);
+
+    let chart_block = Block::default().borders(Borders::ALL).title("Spectrum");
+    let columns = chart_area.width.saturating_sub(2) as usize;
+    if let Some(spectrum) = &input.spectrum {
+        let bands = fit_bands(&spectrum.bands_db, columns);
+        let bar_width = (columns / bands.len().max(1)).max(1) as u16;
+        let bars: Vec<Bar> = bands
+            .iter()
+            .map(|&db| Bar::default().value(spectrum_level(db)).text_value(String::new()))
+            .collect();
+        let chart = BarChart::default()
+            .block(chart_block)
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(bar_width)
+            .bar_gap(0)
+            .max(100)
+            .bar_style(Style::default().fg(Color::Green));
+        frame.render_widget(chart, chart_area);
+    } else {
+        frame.render_widget(chart_block, chart_area);
+    }
+
+    let columns = waterfall_area.width.saturating_sub(2) as usize;
+    let rows = waterfall_area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = app
+        .waterfall
+        .iter()
+        .take(rows)
+        .map(|row| {
+            let bands = fit_bands(row, columns);
+            let repeat = (columns / bands.len().max(1)).max(1);
+            Line::from(
+                bands
+                    .iter()
+                    .map(|&db| {
+                        let level = spectrum_level(db) as usize;
+                        let color = WATERFALL_PALETTE
+                            [(level * WATERFALL_PALETTE.len() / 101).min(WATERFALL_PALETTE.len() - 1)];
+                        Span::styled("█".repeat(repeat), Style::default().fg(color))
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    let waterfall = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Waterfall"));
+    frame.render_widget(waterfall, waterfall_area);
+}
+
+fn unix_now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+/// Compact age such as `42s`, `5m` or `3h`.
+fn format_age(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        _ => format!("{}h", secs / 3600),
+    }
+}
+
+/// Map a band level onto 0-100 for display.
+fn spectrum_level(db: f32) -> u64 {
+    let clamped = db.clamp(SPECTRUM_MIN_DB, SPECTRUM_MAX_DB);
+    ((clamped - SPECTRUM_MIN_DB) / (SPECTRUM_MAX_DB - SPECTRUM_MIN_DB) * 100.0).round() as u64
+}
+
+/// Merge bands (keeping the loudest) until they fit in `width` columns.
+fn fit_bands(bands: &[f32], width: usize) -> Vec<f32> {
+    if bands.len() <= width || width == 0 {
+        return bands.to_vec();
+    }
+    (0..width)
+        .map(|c| {
+            let lo = c * bands.len() / width;
+            let hi = ((c + 1) * bands.len() / width).max(lo + 1);
+            bands[lo..hi].iter().copied().fold(f32::MIN, f32::max)
+        })
+        .collect()
+}
+
 fn draw_outputs(frame: &mut Frame, app: &App, area: Rect) {
     let play_str = if app.state.output.play_mixed_input {
         "ON"
     } else {
         "OFF"
     };
-    let text = format!(
-        "Output device: {}\nPlay mixed input: {} (Space to toggle)",
-        app.state.output.device_name, play_str,
+    let tone = &app.state.tone;
+    let tone_str = match (&tone.signal, &tone.measuring_input) {
+        (_, Some(id)) => format!("loopback bursts → measuring '{}'", id),
+        (Some(signal), None) => match &tone.target {
+            ToneTarget::Mix => format!("{} → mix", signal),
+            ToneTarget::AsrTap(id) => format!("{} → ASR '{}'", signal, id),
+        },
+        (None, None) => "off".to_string(),
+    };
+    let mut text = format!(
+        "Output device: {}\nPlay mixed input: {} (Space to toggle)\nTest tone: {} (t to cycle)",
+        app.state.output.device_name, play_str, tone_str,
     );
+    if let Some(m) = &tone.measurement {
+        text.push_str(&format!(
+            "\nLoopback: latency {:.1} ms, level {:+.1} dB",
+            m.latency_ms, m.level_db
+        ));
+    } else if tone.measuring_input.is_some() {
+        text.push_str("\nLoopback: waiting for burst...");
+    }
+    let replay = &app.state.replay;
+    match &replay.playing {
+        Some(source) => text.push_str(&format!(
+            "\nReplay: {} {:.1}s left (r to stop)",
+            source, replay.remaining_secs
+        )),
+        None => text.push_str(&format!(
+            "\nReplay: last {}s (r=replay mix, w=export WAV)",
+            replay.history_secs
+        )),
+    }
+    if app.state.cue_count > 0 {
+        text.push_str(&format!(
+            "\nSound cues: {} configured, {} (c to toggle)",
+            app.state.cue_count,
+            if app.state.cues_muted { "MUTED" } else { "on" }
+        ));
+    }
+    if let Some(announcement) = &app.state.announcement {
+        text.push_str(&format!("\nAnnouncing: \"{}\"", announcement));
+    }
     let block = Block::default()
         .borders(Borders::ALL)
         .title("Output");
@@ -191,7 +464,11 @@ mod tests {
     use ratatui::buffer::Buffer;
     use std::collections::VecDeque;
     use std::sync::{Arc, Mutex};
-    use voxmux_core::tui_types::{InputState, RouterState};
+    use voxmux_core::tui_types::{
+        AsrPoolState, AsrQueueState, EngineHealth, InputState, LoopbackMeasurement, ReplayState, RouterState, Spectrum,
+        ToneSignal, ToneState,
+    };
+    use voxmux_core::{AlertEvent, TransmissionEvent, TransmissionPhase};
 
     fn buffer_text(buf: &Buffer) -> String {
         let area = buf.area();
@@ -321,6 +598,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dashboard_highlights_alerts() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(60, 20);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            inputs: vec![InputState {
+                id: "radio1".into(),
+                device_name: "Rig".into(),
+                enabled: true,
+                ..Default::default()
+            }],
+            latest_recognitions: vec![
+                "[radio1] all quiet".to_string(),
+                "[radio1] mayday mayday".to_string(),
+            ],
+            alerts: vec![AlertEvent {
+                rule: "distress".to_string(),
+                input_id: "radio1".to_string(),
+                text: "mayday mayday".to_string(),
+                matched: "mayday".to_string(),
+                is_final: true,
+            }],
+            ..Default::default()
+        });
+        app.tab = Tab::Dashboard;
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+
+        let buf = terminal.backend().buffer();
+        let text = buffer_text(buf);
+        let line_style = |needle: &str| {
+            let y = text.lines().position(|l| l.contains(needle)).unwrap() as u16;
+            buf.cell((2, y)).unwrap().style()
+        };
+        assert_eq!(line_style("mayday").fg, Some(Color::Yellow));
+        assert!(line_style("mayday").add_modifier.contains(Modifier::BOLD));
+        assert_ne!(line_style("all quiet").fg, Some(Color::Yellow));
+    }
+
     #[test]
     fn test_inputs_tab_renders_device_list() {
         use ratatui::backend::TestBackend;
@@ -361,6 +683,442 @@ mod tests {
         assert!(text.contains("DeviceGamma"), "missing DeviceGamma:\n{}", text);
     }
 
+    #[test]
+    fn test_dashboard_flags_asr_lag() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(60, 20);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            inputs: vec![InputState {
+                id: "radio1".into(),
+                enabled: true,
+                asr_queue: AsrQueueState {
+                    queued_ms: 3500,
+                    capacity_ms: 5000,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        app.tab = Tab::Dashboard;
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(
+            text.contains("ASR +3.5s"),
+            "expected ASR lag marker in dashboard, got:\n{}",
+            text,
+        );
+    }
+
+    #[test]
+    fn test_inputs_tab_renders_asr_queue() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(100, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            inputs: vec![InputState {
+                id: "a".into(),
+                device_name: "DeviceAlpha".into(),
+                asr_queue: AsrQueueState {
+                    queued_ms: 40,
+                    capacity_ms: 5000,
+                    dropped_ms: 1200,
+                    dropped_chunks: 3,
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        app.tab = Tab::Inputs;
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("q:40ms"), "missing queue depth:\n{}", text);
+        assert!(text.contains("drop:1200ms"), "missing drop count:\n{}", text);
+    }
+
+    #[test]
+    fn test_inputs_tab_renders_asr_pool() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(140, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            inputs: vec![InputState {
+                id: "a".into(),
+                device_name: "DeviceAlpha".into(),
+                asr_segments: 2,
+                ..Default::default()
+            }],
+            asr_pool: Some(AsrPoolState {
+                workers: 2,
+                busy: 1,
+                queued: 2,
+                completed: 40,
+                last_latency_ms: 900,
+                avg_latency_ms: 850,
+                max_latency_ms: 2100,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        app.tab = Tab::Inputs;
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("seg:2"), "missing input backlog:\n{}", text);
+        assert!(text.contains("ASR pool: 1/2 busy"), "missing pool load:\n{}", text);
+        assert!(text.contains("avg:850ms max:2100ms"), "missing latency:\n{}", text);
+    }
+
+    #[test]
+    fn test_decoded_tones_rendered() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(100, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            inputs: vec![InputState {
+                id: "radio1".into(),
+                device_name: "Radio".into(),
+                ctcss_hz: Some(88.5),
+                dcs_code: Some(0o023),
+                dtmf_digits: "123#".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("[CTCSS 88.5]"), "missing dashboard tone:\n{}", text);
+        assert!(text.contains("[DCS 023N]"), "missing dashboard code:\n{}", text);
+
+        app.tab = Tab::Inputs;
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("ctcss:88.5Hz"), "missing ctcss:\n{}", text);
+        assert!(text.contains("dcs:023N"), "missing dcs:\n{}", text);
+        assert!(text.contains("dtmf:123#"), "missing digits:\n{}", text);
+    }
+
+    #[test]
+    fn test_vad_state_rendered() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(100, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            inputs: vec![InputState {
+                id: "mic1".into(),
+                device_name: "Mic".into(),
+                vad: VadState::Speech,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("[SPEECH]"), "missing dashboard speech marker:\n{}", text);
+
+        app.tab = Tab::Inputs;
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("vad:speech"), "missing vad state:\n{}", text);
+    }
+
+    #[test]
+    fn test_engine_health_rendered() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(100, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            inputs: vec![InputState {
+                id: "mic1".into(),
+                device_name: "Mic".into(),
+                asr_engine: Some(EngineHealth {
+                    state: EngineState::Restarting,
+                    restarts: 2,
+                    last_error: Some("broken".into()),
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("[ASR RESTARTING]"), "missing dashboard marker:\n{}", text);
+
+        app.tab = Tab::Inputs;
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(
+            text.contains("engine:restarting restarts:2"),
+            "missing engine state:\n{}",
+            text
+        );
+    }
+
+    #[test]
+    fn test_input_detail_renders_spectrum_and_waterfall() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(60, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        let mut bands_db = vec![-110.0; 16];
+        bands_db[3] = -6.0;
+        for seq in 1..=3 {
+            app.update_state(RouterState {
+                inputs: vec![InputState {
+                    id: "rx".into(),
+                    device_name: "Radio".into(),
+                    spectrum: Some(Spectrum {
+                        bands_db: bands_db.clone(),
+                        max_hz: 4000.0,
+                        seq,
+                    }),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            });
+        }
+        app.tab = Tab::Inputs;
+        app.input_detail = true;
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        let text = buffer_text(buf);
+        assert!(text.contains("Input 'rx'"), "missing header:\n{}", text);
+        assert!(text.contains("spectrum 0-4000 Hz"), "missing range:\n{}", text);
+        assert!(text.contains("Waterfall"), "missing waterfall:\n{}", text);
+
+        // Three waterfall rows; the loud band is drawn red
+        let lines: Vec<&str> = text.lines().collect();
+        let title = lines.iter().position(|l| l.contains("Waterfall")).unwrap();
+        for row in 1..=3 {
+            assert!(lines[title + row].contains('█'), "row {row} empty:\n{}", text);
+        }
+        assert!(!lines[title + 4].contains('█'));
+        let y = (title + 1) as u16;
+        let red = (0..buf.area.width).any(|x| buf[(x, y)].fg == Color::Red);
+        assert!(red, "loud band not highlighted");
+    }
+
+    #[test]
+    fn test_input_detail_spectrum_off() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(60, 20);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            inputs: vec![InputState {
+                id: "rx".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        app.tab = Tab::Inputs;
+        app.input_detail = true;
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("spectrum off (f=on)"), "missing hint:\n{}", text);
+    }
+
+    #[test]
+    fn test_transmission_activity_rendered() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(120, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            inputs: vec![InputState {
+                id: "rx1".into(),
+                device_name: "Radio".into(),
+                transmitting: true,
+                last_heard: Some(TransmissionEvent {
+                    input_id: "rx1".into(),
+                    phase: TransmissionPhase::End,
+                    started_at: unix_now() - 130.0,
+                    duration_secs: 3.2,
+                    peak_dbfs: -8.0,
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("rx1  [TX]"), "missing TX marker:\n{}", text);
+
+        app.tab = Tab::Inputs;
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("heard:2m ago (3.2s)"), "missing last heard:\n{}", text);
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(-1.0), "0s");
+        assert_eq!(format_age(59.9), "59s");
+        assert_eq!(format_age(61.0), "1m");
+        assert_eq!(format_age(7200.0), "2h");
+    }
+
+    #[test]
+    fn test_fit_bands_keeps_peaks() {
+        assert_eq!(fit_bands(&[-1.0, -2.0], 10), vec![-1.0, -2.0]);
+        assert_eq!(
+            fit_bands(&[-50.0, -10.0, -80.0, -70.0], 2),
+            vec![-10.0, -70.0]
+        );
+        assert_eq!(spectrum_level(-120.0), 0);
+        assert_eq!(spectrum_level(-50.0), 50);
+        assert_eq!(spectrum_level(3.0), 100);
+    }
+
+    #[test]
+    fn test_outputs_tab_renders_tone_and_loopback() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(100, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            tone: ToneState {
+                signal: Some(ToneSignal::Reference1k),
+                target: ToneTarget::Mix,
+                measuring_input: Some("mic".into()),
+                measurement: Some(LoopbackMeasurement {
+                    latency_ms: 42.5,
+                    level_db: -6.0,
+                }),
+            },
+            ..Default::default()
+        });
+        app.tab = Tab::Outputs;
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("measuring 'mic'"), "missing tone status:\n{}", text);
+        assert!(text.contains("latency 42.5 ms"), "missing latency:\n{}", text);
+        assert!(text.contains("level -6.0 dB"), "missing level:\n{}", text);
+    }
+
+    #[test]
+    fn test_outputs_tab_renders_replay() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(100, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            replay: ReplayState {
+                history_secs: 30,
+                playing: Some("mic1".into()),
+                remaining_secs: 12.46,
+            },
+            ..Default::default()
+        });
+        app.tab = Tab::Outputs;
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(text.contains("Replay: mic1 12.5s left"), "missing replay:\n{}", text);
+    }
+
+    #[test]
+    fn test_outputs_tab_renders_announcement() {
+        use ratatui::backend::TestBackend;
+        use ratatui::Terminal;
+
+        let backend = TestBackend::new(100, 14);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Arc::new(Mutex::new(VecDeque::new())));
+        app.update_state(RouterState {
+            announcement: Some("input mic1 lost signal".into()),
+            cue_count: 3,
+            cues_muted: true,
+            ..Default::default()
+        });
+        app.tab = Tab::Outputs;
+
+        terminal
+            .draw(|frame| draw(frame, &app))
+            .unwrap();
+
+        let text = buffer_text(terminal.backend().buffer());
+        assert!(
+            text.contains("Announcing: \"input mic1 lost signal\""),
+            "missing announcement:\n{}",
+            text
+        );
+        assert!(text.contains("Sound cues: 3 configured, MUTED"), "missing cues:\n{}", text);
+    }
+
     #[test]
     fn test_logs_tab_renders_log_lines() {
         use ratatui::backend::TestBackend;
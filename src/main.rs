@@ -1,14 +1,53 @@
 use anyhow::{Context, Result};
 use clap::Parser;
 use std::collections::VecDeque;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::EnvFilter;
 
 const RECOGNITION_BUFFER_CAPACITY: usize = 50;
 
+/// How long a dropped-audio warning stays on the dashboard after the last drop.
+const ASR_DROP_WARNING_HOLD: Duration = Duration::from_secs(5);
+
+/// Number of recent alerts kept for the TUI.
+const ALERT_LOG_CAPACITY: usize = 20;
+
+/// How long an alert warning stays on the dashboard.
+const ALERT_WARNING_HOLD: Duration = Duration::from_secs(30);
+
+/// Mixer input id of the built-in test tone generator.
+const TONE_INPUT_ID: &str = "__tone";
+
+/// Mixer input id of the instant replay player.
+const REPLAY_INPUT_ID: &str = "__replay";
+
+/// Mixer input id of spoken announcements.
+const ANNOUNCE_INPUT_ID: &str = "__announce";
+
+/// Mixer input id of the sound cue player.
+const CUE_INPUT_ID: &str = "__cues";
+
+/// Number of recent DTMF digits kept per input for the TUI.
+const DTMF_DIGITS_SHOWN: usize = 16;
+
+/// Latest decoded tones on an input, for the TUI.
+#[derive(Debug, Default)]
+struct DecodedTones {
+    ctcss_hz: Option<f32>,
+    dcs_code: Option<u16>,
+    dtmf_digits: String,
+}
+
+/// Keyed-up state of an input, for the TUI.
+#[derive(Debug, Default)]
+struct Activity {
+    transmitting: bool,
+    last_heard: Option<voxmux_core::TransmissionEvent>,
+}
+
 #[derive(Parser)]
 #[command(name = "voxmux", about = "Audio mixing router with ASR")]
 struct Cli {
@@ -69,6 +108,28 @@ async fn main() -> Result<()> {
     // Create mixer with output producer
     let mut mixer = voxmux_audio::Mixer::new(out_producer, buffer_size as usize);
 
+    // Test tone generator: a virtual input that can also feed ASR taps
+    let (tone_prod, tone_cons) = voxmux_audio::create_ring_buffer(ring_capacity);
+    let mut tone_generator = voxmux_audio::ToneGenerator::new(sample_rate, channels, tone_prod);
+
+    // Rolling history of every input and the mix bus, for instant replay
+    let mut replay_buffers =
+        voxmux_audio::ReplayBuffers::new(sample_rate, channels, config.replay.seconds);
+    mixer.add_bus_analyzer(replay_buffers.recorder(None));
+
+    // Sound cues played into the mix on configured events
+    let (cue_prod, cue_cons) = voxmux_audio::create_ring_buffer(ring_capacity);
+    let cue_player = voxmux_audio::ClipPlayer::new(sample_rate, channels, cue_prod);
+    let cues = voxmux_audio::CueBank::load(&config.cues, sample_rate, channels, cue_player.handle())
+        .context("failed to load sound cues")?;
+    let _cue_input = mixer.add_input(CUE_INPUT_ID, cue_cons, 1.0, false);
+
+    // Engines and destinations from shared libraries
+    let plugins = match config.plugins.dir {
+        Some(ref dir) => load_plugins(Path::new(dir)),
+        None => Vec::new(),
+    };
+
     // Create a CaptureNode + ring buffer for each enabled input
     let enabled_inputs: Vec<_> = config.input.iter().filter(|i| i.enabled).collect();
     if enabled_inputs.is_empty() {
@@ -78,37 +139,78 @@ async fn main() -> Result<()> {
     // Recognition buffer for TUI display (shared across ASR + broadcast tasks)
     let recognition_buf = Arc::new(Mutex::new(VecDeque::<String>::new()));
 
+    // Alert rules, checked on every processed result and swapped on reload
+    let alert_rules = Arc::new(Mutex::new(
+        voxmux_text::AlertRules::new(&config.alerts).context("invalid alert rules")?,
+    ));
+    let alert_log = Arc::new(Mutex::new(VecDeque::<(voxmux_text::Alert, Instant)>::new()));
+    // Alerts to speak, relayed to the announcer once it has started
+    let (alert_speech_tx, mut alert_speech_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+
     // Set up ASR if configured
     let mut asr_host = None;
     let mut dest_host_handle: Option<voxmux_destination::DestinationHost> = None;
     let mut tap_senders = std::collections::HashMap::new();
+    let mut tap_stats = std::collections::HashMap::new();
+    let mut vad_monitors = std::collections::HashMap::new();
+    let mut engine_monitors = std::collections::HashMap::new();
+    let mut asr_pool = None;
+    let (tone_event_tx, mut tone_event_rx) =
+        tokio::sync::mpsc::unbounded_channel::<voxmux_core::ToneEvent>();
+    let mut dest_tone_tx = None;
+    let (transmission_tx, mut transmission_rx) =
+        tokio::sync::mpsc::unbounded_channel::<voxmux_core::TransmissionEvent>();
 
     if let Some(ref asr_config) = config.asr {
-        let registry = voxmux_engine::PluginRegistry::new();
-        let mut host = voxmux_engine::AsrHost::new();
+        let mut registry = voxmux_engine::PluginRegistry::new();
+        for plugin in &plugins {
+            registry.register_plugin(plugin);
+        }
+        let mut host = voxmux_engine::AsrHost::with_queue_config(asr_config.queue.clone());
+        if let Some(ref vad_config) = asr_config.vad {
+            host.set_vad(vad_config.clone(), voxmux_engine::VadRegistry::new());
+        }
+        host.set_pool(asr_config.pool.clone());
+        host.set_supervisor(asr_config.supervisor.clone());
+
+        // Text processors run on every result before cues and destinations
+        let text_pipeline = Arc::new(
+            voxmux_text::TextPipeline::new(&config, &voxmux_text::TextProcessorRegistry::new())
+                .context("invalid text processing settings")?,
+        );
 
+        // Resolve every input's engine first so a typo fails before any engine loads
+        let mut resolved = Vec::with_capacity(enabled_inputs.len());
         for input_cfg in &enabled_inputs {
-            let engine_config = match asr_config.engine.as_str() {
-                "whisper" => {
-                    if let Some(ref whisper_cfg) = asr_config.whisper {
-                        toml::Value::try_from(whisper_cfg)
-                            .context("failed to serialize whisper config")?
-                    } else {
-                        toml::Value::Table(Default::default())
-                    }
-                }
-                _ => toml::Value::Table(Default::default()),
-            };
+            let asr = asr_config
+                .resolve(input_cfg)
+                .with_context(|| format!("invalid ASR settings for input '{}'", input_cfg.id))?;
+            check_engine(&input_cfg.id, &asr.engine, &registry.list_engines())?;
+            resolved.push(asr);
+        }
 
+        for (input_cfg, asr) in enabled_inputs.iter().zip(resolved) {
             let tap_tx = host
-                .add_input(&input_cfg.id, &asr_config.engine, engine_config, &registry)
+                .add_input(&input_cfg.id, &asr.engine, asr.config, &registry)
                 .await
                 .with_context(|| {
                     format!(
                         "failed to add ASR input '{}' with engine '{}'",
-                        input_cfg.id, asr_config.engine
+                        input_cfg.id, asr.engine
                     )
                 })?;
+            tracing::info!(input = %input_cfg.id, engine = %asr.engine, "ASR input added");
+            if let Some(priority) = input_cfg.asr.as_ref().map(|a| a.priority) {
+                host.set_priority(&input_cfg.id, priority);
+            }
+            tap_stats.insert(input_cfg.id.clone(), tap_tx.stats());
+            if let Some(monitor) = host.vad_monitor(&input_cfg.id) {
+                vad_monitors.insert(input_cfg.id.clone(), monitor);
+            }
+            if let Some(monitor) = host.engine_monitor(&input_cfg.id) {
+                engine_monitors.insert(input_cfg.id.clone(), monitor);
+            }
+            tone_generator.add_tap(&input_cfg.id, tap_tx.clone());
             tap_senders.insert(input_cfg.id.clone(), tap_tx);
         }
 
@@ -116,7 +218,8 @@ async fn main() -> Result<()> {
         if let Some(result_rx) = host.take_result_receiver() {
             let has_destinations = enabled_inputs
                 .iter()
-                .any(|i| !i.destinations.is_empty());
+                .any(|i| !i.destinations.is_empty())
+                || config.alerts.iter().any(|a| !a.destinations.is_empty());
 
             if has_destinations {
                 // Create a forwarder channel: result_rx → forwarder → dest_host
@@ -124,60 +227,140 @@ async fn main() -> Result<()> {
                     tokio::sync::mpsc::unbounded_channel::<voxmux_core::RecognitionResult>();
 
                 let mut dest_host = voxmux_destination::DestinationHost::new(fwd_rx);
+                for plugin in &plugins {
+                    dest_host.register_plugin(plugin);
+                }
+                if let Some(ref translation) = config.translation {
+                    let mut translator = voxmux_text::TranslatorRegistry::new()
+                        .create(&translation.translator)
+                        .context("failed to create translator")?;
+                    translator
+                        .initialize(translation.translator_config())
+                        .await
+                        .with_context(|| {
+                            format!("failed to initialize translator '{}'", translation.translator)
+                        })?;
+                    tracing::info!("translation active (translator '{}')", translation.translator);
+                    dest_host.set_translator(Arc::from(translator));
+                }
 
                 for input_cfg in &enabled_inputs {
                     for route_cfg in &input_cfg.destinations {
-                        // Merge global destination config with per-route extra
-                        let mut merged = match config.destinations {
-                            Some(ref dests) => dests
-                                .get(&route_cfg.plugin)
-                                .cloned()
-                                .unwrap_or_else(|| toml::Value::Table(Default::default())),
-                            None => toml::Value::Table(Default::default()),
+                        let merged = route_config(&config, route_cfg);
+                        let added = match route_cfg.translate {
+                            Some(ref translate) => {
+                                dest_host
+                                    .add_route_with_translation(
+                                        &input_cfg.id,
+                                        &route_cfg.plugin,
+                                        &route_cfg.prefix,
+                                        translate.clone(),
+                                        merged,
+                                    )
+                                    .await
+                            }
+                            None => {
+                                dest_host
+                                    .add_route(
+                                        &input_cfg.id,
+                                        &route_cfg.plugin,
+                                        &route_cfg.prefix,
+                                        merged,
+                                    )
+                                    .await
+                            }
                         };
+                        added.with_context(|| {
+                            format!(
+                                "failed to add destination route '{}' for input '{}'",
+                                route_cfg.plugin, input_cfg.id
+                            )
+                        })?;
 
-                        // Overlay per-route extra fields
-                        if let (Some(base), Some(extra)) =
-                            (merged.as_table_mut(), route_cfg.extra.as_table())
-                        {
-                            for (k, v) in extra {
-                                base.insert(k.clone(), v.clone());
-                            }
-                        }
+                        tracing::info!(
+                            "routed input '{}' → destination '{}' (prefix: {:?})",
+                            input_cfg.id,
+                            route_cfg.plugin,
+                            route_cfg.prefix,
+                        );
+                    }
+                }
 
+                for rule in &config.alerts {
+                    for route_cfg in &rule.destinations {
                         dest_host
-                            .add_route(
-                                &input_cfg.id,
+                            .add_alert_route(
+                                &rule.name,
                                 &route_cfg.plugin,
                                 &route_cfg.prefix,
-                                merged,
+                                route_config(&config, route_cfg),
                             )
                             .await
                             .with_context(|| {
                                 format!(
-                                    "failed to add destination route '{}' for input '{}'",
-                                    route_cfg.plugin, input_cfg.id
+                                    "failed to add destination route '{}' for alert '{}'",
+                                    route_cfg.plugin, rule.name
                                 )
                             })?;
-
                         tracing::info!(
-                            "routed input '{}' → destination '{}' (prefix: {:?})",
-                            input_cfg.id,
+                            "routed alert '{}' → destination '{}' (prefix: {:?})",
+                            rule.name,
                             route_cfg.plugin,
                             route_cfg.prefix,
                         );
                     }
                 }
 
+                let (tone_fwd_tx, tone_fwd_rx) = tokio::sync::mpsc::unbounded_channel();
+                dest_host.set_tone_receiver(tone_fwd_rx);
+                dest_tone_tx = Some(tone_fwd_tx);
+
+                let (alert_fwd_tx, alert_fwd_rx) = tokio::sync::mpsc::unbounded_channel();
+                dest_host.set_alert_receiver(alert_fwd_rx);
+
+                let (failure_tx, mut failure_rx) = tokio::sync::mpsc::unbounded_channel::<
+                    voxmux_destination::DestinationFailure,
+                >();
+                dest_host.set_failure_sender(failure_tx);
+                let failure_cues = cues.clone();
+                tokio::spawn(async move {
+                    while let Some(failure) = failure_rx.recv().await {
+                        failure_cues.trigger(&voxmux_audio::CueEvent::DestinationError {
+                            destination: &failure.destination,
+                        });
+                    }
+                });
+
                 dest_host.start();
                 dest_host_handle = Some(dest_host);
 
                 // Forwarder task: copies to recognition buffer + forwards to DestinationHost
                 let fwd_recog_buf = Arc::clone(&recognition_buf);
+                let fwd_cues = cues.clone();
+                let fwd_pipeline = Arc::clone(&text_pipeline);
+                let fwd_alert_rules = Arc::clone(&alert_rules);
+                let fwd_alert_log = Arc::clone(&alert_log);
+                let fwd_speech_tx = alert_speech_tx.clone();
                 tokio::spawn(async move {
                     let mut rx = result_rx;
-                    while let Some(result) = rx.recv().await {
-                        if result.is_final {
+                    while let Some(mut result) = rx.recv().await {
+                        if !fwd_pipeline.apply(&mut result) {
+                            continue;
+                        }
+                        for alert in check_alerts(&fwd_alert_rules, &fwd_alert_log, &result) {
+                            fwd_cues.trigger(&voxmux_audio::CueEvent::Alert {
+                                rule: &alert.event.rule,
+                            });
+                            if alert.speak {
+                                let _ = fwd_speech_tx.send(alert_speech(&alert.event));
+                            }
+                            let _ = alert_fwd_tx.send(alert.event);
+                        }
+                        if result.is_final && !result.text.is_empty() {
+                            fwd_cues.trigger(&voxmux_audio::CueEvent::Keyword {
+                                input_id: &result.input_id,
+                                text: &result.text,
+                            });
                             let text =
                                 format!("[{}] {}", result.input_id, result.text);
                             push_recognition(&fwd_recog_buf, text);
@@ -188,16 +371,38 @@ async fn main() -> Result<()> {
             } else {
                 // Fallback: log ASR results + push to recognition buffer
                 let fallback_recog_buf = Arc::clone(&recognition_buf);
+                let fallback_cues = cues.clone();
+                let fallback_pipeline = Arc::clone(&text_pipeline);
+                let fallback_alert_rules = Arc::clone(&alert_rules);
+                let fallback_alert_log = Arc::clone(&alert_log);
+                let fallback_speech_tx = alert_speech_tx.clone();
                 tokio::spawn(async move {
                     let mut rx = result_rx;
-                    while let Some(result) = rx.recv().await {
+                    while let Some(mut result) = rx.recv().await {
+                        if !fallback_pipeline.apply(&mut result) {
+                            continue;
+                        }
+                        for alert in
+                            check_alerts(&fallback_alert_rules, &fallback_alert_log, &result)
+                        {
+                            fallback_cues.trigger(&voxmux_audio::CueEvent::Alert {
+                                rule: &alert.event.rule,
+                            });
+                            if alert.speak {
+                                let _ = fallback_speech_tx.send(alert_speech(&alert.event));
+                            }
+                        }
                         tracing::info!(
                             input_id = %result.input_id,
                             is_final = result.is_final,
                             "ASR: {}",
                             result.text,
                         );
-                        if result.is_final {
+                        if result.is_final && !result.text.is_empty() {
+                            fallback_cues.trigger(&voxmux_audio::CueEvent::Keyword {
+                                input_id: &result.input_id,
+                                text: &result.text,
+                            });
                             let text =
                                 format!("[{}] {}", result.input_id, result.text);
                             push_recognition(&fallback_recog_buf, text);
@@ -208,14 +413,17 @@ async fn main() -> Result<()> {
         }
 
         host.start();
-        tracing::info!("ASR engine '{}' active", asr_config.engine);
+        let pool = host.pool();
+        asr_pool = pool.is_used().then_some(pool);
+        tracing::info!("ASR active (default engine '{}')", asr_config.engine);
         asr_host = Some(host);
     }
 
-    // Keep capture nodes alive for the duration of the program
-    let mut _captures = Vec::new();
+    // Keep capture nodes alive until shutdown
+    let mut captures = Vec::new();
     let mut input_handles = Vec::new();
     let mut capture_handles = Vec::new();
+    let mut spectrum_handles = std::collections::HashMap::new();
 
     for input_cfg in &enabled_inputs {
         tracing::info!(
@@ -238,9 +446,44 @@ async fn main() -> Result<()> {
         let (in_prod, in_cons) = voxmux_audio::create_ring_buffer(ring_capacity);
 
         let handle = mixer.add_input(&input_cfg.id, in_cons, input_cfg.volume, input_cfg.muted);
+        mixer.add_analyzer(&input_cfg.id, tone_generator.loopback_analyzer(&input_cfg.id));
+        mixer.add_analyzer(&input_cfg.id, replay_buffers.recorder(Some(&input_cfg.id)));
+        let spectrum = voxmux_audio::SpectrumAnalyzer::new(
+            sample_rate,
+            channels,
+            &input_cfg.spectrum.clone().unwrap_or(voxmux_core::config::SpectrumConfig {
+                enabled: false,
+                ..Default::default()
+            }),
+        );
+        spectrum_handles.insert(input_cfg.id.clone(), spectrum.handle());
+        mixer.add_analyzer(&input_cfg.id, Box::new(spectrum));
+        if let Some(ref tx_cfg) = input_cfg.transmission {
+            mixer.add_analyzer(
+                &input_cfg.id,
+                Box::new(voxmux_audio::TransmissionDetector::new(
+                    &input_cfg.id,
+                    sample_rate,
+                    channels,
+                    tx_cfg,
+                    transmission_tx.clone(),
+                )),
+            );
+        }
         input_handles.push(handle);
 
-        let asr_tap = tap_senders.remove(&input_cfg.id);
+        let taps = voxmux_audio::CaptureTaps {
+            asr: tap_senders.remove(&input_cfg.id),
+            tone_decoder: input_cfg.tones.as_ref().map(|tones| {
+                voxmux_audio::ToneDecoder::new(
+                    &input_cfg.id,
+                    sample_rate,
+                    channels,
+                    tones,
+                    tone_event_tx.clone(),
+                )
+            }),
+        };
 
         let (capture, capture_handle) = voxmux_audio::CaptureNode::new(
             &input_device,
@@ -248,15 +491,69 @@ async fn main() -> Result<()> {
             sample_rate,
             channels,
             buffer_size,
-            asr_tap,
+            taps,
             &input_cfg.id,
         )
         .with_context(|| format!("failed to create capture node for '{}'", input_cfg.id))?;
 
-        _captures.push(capture);
+        captures.push(capture);
         capture_handles.push(capture_handle);
     }
 
+    // Capture nodes hold the only remaining senders
+    drop(tone_event_tx);
+
+    // Tone event forwarder: logs, updates TUI state, feeds DestinationHost
+    let decoded_tones = Arc::new(Mutex::new(
+        std::collections::HashMap::<String, DecodedTones>::new(),
+    ));
+    let fwd_decoded_tones = Arc::clone(&decoded_tones);
+    tokio::spawn(async move {
+        while let Some(event) = tone_event_rx.recv().await {
+            tracing::info!(input_id = %event.input_id, "tone: {}", event);
+            if let Ok(mut tones) = fwd_decoded_tones.lock() {
+                apply_tone_event(tones.entry(event.input_id.clone()).or_default(), &event);
+            }
+            if let Some(ref tx) = dest_tone_tx {
+                let _ = tx.send(event);
+            }
+        }
+    });
+
+    // Transmission forwarder: logs, appends to the activity log, updates TUI state
+    drop(transmission_tx);
+    let mut activity_log = match config.general.activity_log {
+        Some(ref path) => Some(
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("failed to open activity log '{}'", path))?,
+        ),
+        None => None,
+    };
+    let activity = Arc::new(Mutex::new(
+        std::collections::HashMap::<String, Activity>::new(),
+    ));
+    let fwd_activity = Arc::clone(&activity);
+    tokio::spawn(async move {
+        while let Some(event) = transmission_rx.recv().await {
+            tracing::info!(input_id = %event.input_id, "{}", event);
+            if let Some(ref mut file) = activity_log {
+                use std::io::Write;
+                if let Err(e) = writeln!(file, "{}", format_activity_line(&event)) {
+                    tracing::warn!("failed to write activity log: {e}");
+                }
+            }
+            if let Ok(mut activity) = fwd_activity.lock() {
+                apply_transmission_event(
+                    activity.entry(event.input_id.clone()).or_default(),
+                    event,
+                );
+            }
+        }
+    });
+
     // Start output node
     let (_output, output_handle) = voxmux_audio::OutputNode::new(
         &output_device,
@@ -275,8 +572,86 @@ async fn main() -> Result<()> {
         buffer_size,
     );
 
+    // Kept out of `input_handles`, which mirrors the configured inputs
+    let _tone_input = mixer.add_input(TONE_INPUT_ID, tone_cons, 1.0, false);
+    let (replay_prod, replay_cons) = voxmux_audio::create_ring_buffer(ring_capacity);
+    let _replay_input = mixer.add_ducking_input(
+        REPLAY_INPUT_ID,
+        replay_cons,
+        1.0,
+        voxmux_audio::tone::dbfs_to_amplitude(config.replay.duck_db),
+    );
+    let replay_player = voxmux_audio::ClipPlayer::new(sample_rate, channels, replay_prod);
+    let announce_player = config.tts.as_ref().map(|tts_config| {
+        let (announce_prod, announce_cons) = voxmux_audio::create_ring_buffer(ring_capacity);
+        mixer.add_ducking_input(
+            ANNOUNCE_INPUT_ID,
+            announce_cons,
+            tts_config.volume,
+            voxmux_audio::tone::dbfs_to_amplitude(tts_config.duck_db),
+        );
+        voxmux_audio::ClipPlayer::new(sample_rate, channels, announce_prod)
+    });
+
     // Start mixer thread (1ms poll interval)
     let mixer_handle = mixer.start(Duration::from_millis(1));
+    let tone_generator_handle = tone_generator.start();
+    let tone_handle = tone_generator_handle.handle();
+    let replay_player_handle = replay_player.start("replay");
+    let cue_player_handle = cue_player.start("cues");
+    let replay_handle = replay_player_handle.handle();
+    let announce_player_handle = announce_player.map(|player| player.start("announce"));
+    let announce_handle = announce_player_handle.as_ref().map(|h| h.handle());
+    let announce_tx = match (&config.tts, &announce_handle) {
+        (Some(tts_config), Some(player)) => {
+            let tx = spawn_announcer(tts_config, player.clone(), sample_rate, channels)
+                .await
+                .with_context(|| format!("failed to start TTS engine '{}'", tts_config.engine))?;
+            tracing::info!("TTS engine '{}' active", tts_config.engine);
+            Some(tx)
+        }
+        _ => None,
+    };
+
+    if let (Some(tts_config), Some(tx)) = (&config.tts, &announce_tx) {
+        // Start every scheduled announcement, failing on a bad entry first
+        let now = unix_now();
+        let timings = tts_config
+            .schedule
+            .iter()
+            .map(|entry| entry.timing(now))
+            .collect::<Result<Vec<_>, _>>()
+            .context("invalid announcement schedule")?;
+        for (entry, (delay, period)) in tts_config.schedule.iter().zip(timings) {
+            let tx = tx.clone();
+            let text = entry.text.clone();
+            tokio::spawn(async move {
+                let start = tokio::time::Instant::now() + delay;
+                let mut interval = tokio::time::interval_at(start, period);
+                loop {
+                    interval.tick().await;
+                    if tx.send(text.clone()).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+
+    match &announce_tx {
+        Some(tx) => {
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                while let Some(text) = alert_speech_rx.recv().await {
+                    let _ = tx.send(text);
+                }
+            });
+        }
+        None if config.alerts.iter().any(|a| a.speak) => {
+            tracing::warn!("alert rules with speak = true need a [tts] section");
+        }
+        None => {}
+    }
 
     // Set up TUI communication channels
     let (state_tx, state_rx) =
@@ -296,10 +671,31 @@ async fn main() -> Result<()> {
     let broadcast_capture_handles = capture_handles.clone();
     let broadcast_output_handle = output_handle.clone();
     let broadcast_recog_buf = Arc::clone(&recognition_buf);
+    let broadcast_tone_handle = tone_handle.clone();
+    let broadcast_decoded_tones = Arc::clone(&decoded_tones);
+    let broadcast_spectrum_handles = spectrum_handles.clone();
+    let broadcast_activity = Arc::clone(&activity);
+    let broadcast_replay_handle = replay_handle.clone();
+    let broadcast_announce_handle = announce_handle.clone();
+    let broadcast_cues = cues.clone();
+    let broadcast_alert_log = Arc::clone(&alert_log);
+    // Only set when input failures should be spoken
+    let error_announce_tx = announce_tx
+        .clone()
+        .filter(|_| config.tts.as_ref().is_some_and(|t| t.announce_input_errors));
+    let replay_history_secs = replay_buffers.seconds();
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_millis(33));
+        // Per input: dropped-chunk count seen last tick and when it last grew
+        let mut last_drops: std::collections::HashMap<
+            String,
+            (u64, Option<std::time::Instant>),
+        > = std::collections::HashMap::new();
+        let mut failed_inputs = std::collections::HashSet::new();
         loop {
             interval.tick().await;
+            let decoded = broadcast_decoded_tones.lock().ok();
+            let activity = broadcast_activity.lock().ok();
             let inputs: Vec<voxmux_core::InputState> = broadcast_handles
                 .iter()
                 .zip(input_configs.iter())
@@ -318,9 +714,45 @@ async fn main() -> Result<()> {
                         muted: handle.is_muted(),
                         peak_level: handle.peak_level(),
                         status,
+                        asr_queue: tap_stats
+                            .get(id)
+                            .map(|stats| stats.queue_state())
+                            .unwrap_or_default(),
+                        asr_segments: asr_pool.as_ref().map(|p| p.pending(id)).unwrap_or(0),
+                        ctcss_hz: decoded
+                            .as_ref()
+                            .and_then(|d| d.get(id))
+                            .and_then(|t| t.ctcss_hz),
+                        dcs_code: decoded
+                            .as_ref()
+                            .and_then(|d| d.get(id))
+                            .and_then(|t| t.dcs_code),
+                        dtmf_digits: decoded
+                            .as_ref()
+                            .and_then(|d| d.get(id))
+                            .map(|t| t.dtmf_digits.clone())
+                            .unwrap_or_default(),
+                        vad: vad_monitors
+                            .get(id)
+                            .map(|m| m.state())
+                            .unwrap_or_default(),
+                        asr_engine: engine_monitors.get(id).map(|m| m.health()),
+                        spectrum: broadcast_spectrum_handles
+                            .get(id)
+                            .and_then(|h| h.latest()),
+                        transmitting: activity
+                            .as_ref()
+                            .and_then(|a| a.get(id))
+                            .is_some_and(|a| a.transmitting),
+                        last_heard: activity
+                            .as_ref()
+                            .and_then(|a| a.get(id))
+                            .and_then(|a| a.last_heard.clone()),
                     }
                 })
                 .collect();
+            drop(decoded);
+            drop(activity);
 
             // Collect warnings from unhealthy devices
             let mut warnings = Vec::new();
@@ -329,12 +761,68 @@ async fn main() -> Result<()> {
             {
                 if cap_handle.status() == voxmux_core::InputStatus::Error {
                     warnings.push(format!("Input '{}' stream error", id));
+                    if failed_inputs.insert(id.clone()) {
+                        broadcast_cues.trigger(&voxmux_audio::CueEvent::InputError {
+                            input_id: id,
+                        });
+                        if let Some(tx) = &error_announce_tx {
+                            let _ = tx.send(format!("input {} lost signal", id));
+                        }
+                    }
+                } else {
+                    failed_inputs.remove(id);
                 }
             }
             if broadcast_output_handle.status() == voxmux_core::InputStatus::Error {
                 warnings.push("Output stream error".to_string());
             }
+            for input in &inputs {
+                let queue = input.asr_queue;
+                if queue.is_falling_behind() {
+                    warnings.push(format!(
+                        "Input '{}' ASR behind real time ({} ms queued)",
+                        input.id, queue.queued_ms
+                    ));
+                }
+                let entry = last_drops.entry(input.id.clone()).or_insert((0, None));
+                if queue.dropped_chunks > entry.0 {
+                    *entry = (queue.dropped_chunks, Some(std::time::Instant::now()));
+                }
+                if entry.1.is_some_and(|t| t.elapsed() < ASR_DROP_WARNING_HOLD) {
+                    warnings.push(format!(
+                        "Input '{}' ASR dropped audio ({} ms total)",
+                        input.id, queue.dropped_ms
+                    ));
+                }
+                if let Some(engine) = &input.asr_engine {
+                    if engine.state != voxmux_core::EngineState::Running {
+                        warnings.push(format!(
+                            "Input '{}' ASR engine {}: {}",
+                            input.id,
+                            engine.state,
+                            engine.last_error.as_deref().unwrap_or("unknown error")
+                        ));
+                    }
+                }
+            }
+
+            // Highlighted alerts, with a warning while each is recent
+            let mut alerts = Vec::new();
+            if let Ok(log) = broadcast_alert_log.lock() {
+                for (alert, raised) in log.iter() {
+                    if alert.highlight {
+                        alerts.push(alert.event.clone());
+                    }
+                    if alert.warn && raised.elapsed() < ALERT_WARNING_HOLD {
+                        warnings.push(format!(
+                            "Alert '{}' on '{}': {}",
+                            alert.event.rule, alert.event.input_id, alert.event.text
+                        ));
+                    }
+                }
+            }
 
+            let now_playing = broadcast_replay_handle.now_playing();
             let recognitions = broadcast_recog_buf
                 .lock()
                 .map(|q| q.iter().cloned().collect())
@@ -347,8 +835,22 @@ async fn main() -> Result<()> {
                     play_mixed_input: broadcast_output_handle.is_playing(),
                 },
                 latest_recognitions: recognitions,
+                alerts,
                 warnings,
                 is_running: true,
+                tone: broadcast_tone_handle.state(),
+                replay: voxmux_core::ReplayState {
+                    history_secs: replay_history_secs,
+                    playing: now_playing.as_ref().map(|(label, _)| label.clone()),
+                    remaining_secs: now_playing.map(|(_, secs)| secs).unwrap_or_default(),
+                },
+                announcement: broadcast_announce_handle
+                    .as_ref()
+                    .and_then(|h| h.now_playing())
+                    .map(|(text, _)| text),
+                cue_count: broadcast_cues.len(),
+                cues_muted: broadcast_cues.is_muted(),
+                asr_pool: asr_pool.as_ref().map(|p| p.metrics()),
             };
 
             if state_tx.send(state).is_err() {
@@ -361,6 +863,13 @@ async fn main() -> Result<()> {
     let cmd_handles = input_handles.clone();
     let cmd_capture_handles = capture_handles.clone();
     let cmd_output_handle = output_handle.clone();
+    let cmd_tone_handle = tone_handle.clone();
+    let cmd_spectrum_handles = spectrum_handles;
+    let cmd_replay_handle = replay_handle;
+    let cmd_announce_tx = announce_tx;
+    let cmd_cues = cues;
+    let cmd_replay_buffers = replay_buffers;
+    let replay_export_dir = std::path::PathBuf::from(&config.replay.export_dir);
     tokio::spawn(async move {
         while let Some(cmd) = cmd_rx.recv().await {
             match cmd {
@@ -384,6 +893,61 @@ async fn main() -> Result<()> {
                 voxmux_core::UiCommand::SetPlayMixedInput(play) => {
                     cmd_output_handle.set_playing(play);
                 }
+                voxmux_core::UiCommand::StartTone { signal, target } => {
+                    tracing::info!("test tone: {} → {:?}", signal, target);
+                    cmd_tone_handle.start_tone(signal, target);
+                }
+                voxmux_core::UiCommand::StopTone => {
+                    tracing::info!("test tone stopped");
+                    cmd_tone_handle.stop_tone();
+                }
+                voxmux_core::UiCommand::MeasureLoopback { input_id } => {
+                    tracing::info!("measuring loopback on input '{}'", input_id);
+                    cmd_tone_handle.measure_loopback(&input_id);
+                }
+                voxmux_core::UiCommand::SetSpectrum { input_id, enabled } => {
+                    if let Some(h) = cmd_spectrum_handles.get(&input_id) {
+                        h.set_enabled(enabled);
+                    }
+                }
+                voxmux_core::UiCommand::Replay { input_id, seconds } => {
+                    let source = input_id.as_deref().unwrap_or("mix");
+                    match cmd_replay_buffers.snapshot(input_id.as_deref(), seconds) {
+                        Some(samples) if !samples.is_empty() => {
+                            tracing::info!("replaying last {:.0}s of '{}'", seconds, source);
+                            cmd_replay_handle.play_now(source, samples);
+                        }
+                        _ => tracing::warn!("no replay history for '{}'", source),
+                    }
+                }
+                voxmux_core::UiCommand::StopReplay => {
+                    cmd_replay_handle.stop();
+                }
+                voxmux_core::UiCommand::ExportReplay { input_id, seconds } => {
+                    let buffers = cmd_replay_buffers.clone();
+                    let path = replay_export_dir.join(replay_file_name(
+                        input_id.as_deref().unwrap_or("mix"),
+                        unix_now(),
+                    ));
+                    // WAV writing does file I/O; keep it off the command loop
+                    tokio::task::spawn_blocking(move || {
+                        match buffers.export_wav(input_id.as_deref(), seconds, &path) {
+                            Ok(secs) => {
+                                tracing::info!("exported {:.1}s replay to {}", secs, path.display())
+                            }
+                            Err(e) => tracing::error!("replay export failed: {}", e),
+                        }
+                    });
+                }
+                voxmux_core::UiCommand::Announce { text } => match &cmd_announce_tx {
+                    Some(tx) => {
+                        let _ = tx.send(text);
+                    }
+                    None => tracing::warn!("announcement ignored: no [tts] engine configured"),
+                },
+                voxmux_core::UiCommand::SetCuesMuted(muted) => {
+                    cmd_cues.set_muted(muted);
+                }
                 voxmux_core::UiCommand::Quit => {
                     break;
                 }
@@ -397,6 +961,7 @@ async fn main() -> Result<()> {
     let reload_capture_handles = capture_handles.clone();
     let reload_output_handle = output_handle.clone();
     let reload_config = config.clone();
+    let reload_alert_rules = Arc::clone(&alert_rules);
     tokio::spawn(async move {
         use notify::{Event, RecursiveMode, Watcher};
 
@@ -455,6 +1020,14 @@ async fn main() -> Result<()> {
                 reload_output_handle.set_playing(play);
                 tracing::info!("reloaded: play_mixed_input → {}", play);
             }
+            if let Some(ref alerts) = diff.alerts_change {
+                if let Ok(mut rules) = reload_alert_rules.lock() {
+                    match rules.reload(alerts) {
+                        Ok(()) => tracing::info!("reloaded: {} alert rules", rules.len()),
+                        Err(e) => tracing::warn!("alert rules not reloaded: {}", e),
+                    }
+                }
+            }
 
             // Log non-reloadable changes as warnings
             for warning in &diff.non_reloadable {
@@ -490,6 +1063,15 @@ async fn main() -> Result<()> {
     tracing::info!("shutting down");
     mixer_handle.stop();
 
+    // Release every ASR tap sender so the engine tasks see end-of-stream
+    tone_generator_handle.stop();
+    replay_player_handle.stop();
+    cue_player_handle.stop();
+    if let Some(handle) = announce_player_handle {
+        handle.stop();
+    }
+    drop(captures);
+
     if let Some(mut host) = asr_host {
         host.shutdown().await;
     }
@@ -501,6 +1083,185 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Start the TTS engine and a task that speaks each text sent to the returned
+/// channel, queueing the audio on the announcement player.
+async fn spawn_announcer(
+    tts_config: &voxmux_core::config::TtsConfig,
+    player: voxmux_audio::PlayerHandle,
+    sample_rate: u32,
+    channels: u16,
+) -> Result<tokio::sync::mpsc::UnboundedSender<String>> {
+    let mut engine = voxmux_engine::TtsRegistry::new().create(&tts_config.engine)?;
+    let engine_config = match tts_config.engine.as_str() {
+        "exec" => toml::Value::try_from(tts_config.exec.clone().unwrap_or_default())
+            .context("failed to serialize exec TTS config")?,
+        _ => toml::Value::Table(Default::default()),
+    };
+    engine.initialize(engine_config).await?;
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    tokio::spawn(async move {
+        while let Some(text) = rx.recv().await {
+            match engine.synthesize(&text).await {
+                Ok(chunk) => {
+                    tracing::info!("announcing: {}", text);
+                    let samples = voxmux_audio::convert_clip(
+                        &chunk.samples,
+                        chunk.sample_rate,
+                        chunk.channels,
+                        sample_rate,
+                        channels,
+                    );
+                    player.enqueue(&text, samples);
+                }
+                Err(e) => tracing::warn!("announcement '{}' failed: {}", text, e),
+            }
+        }
+    });
+    Ok(tx)
+}
+
+/// Load every plugin in `dir`. Plugins that fail to load, or were built
+/// for another ABI version, are logged and skipped.
+fn load_plugins(dir: &Path) -> Vec<voxmux_plugin::host::Plugin> {
+    let mut plugins = Vec::new();
+    for result in voxmux_plugin::host::load_dir(dir) {
+        match result {
+            Ok(plugin) => {
+                let names = |items: Vec<&str>| match items.join(", ") {
+                    s if s.is_empty() => "none".to_string(),
+                    s => s,
+                };
+                tracing::info!(
+                    "loaded plugin '{}' {} from {} (engines: {}; destinations: {})",
+                    plugin.name(),
+                    plugin.version(),
+                    plugin.path().display(),
+                    names(plugin.engines().iter().map(|e| e.name()).collect()),
+                    names(plugin.destinations().iter().map(|d| d.name()).collect()),
+                );
+                plugins.push(plugin);
+            }
+            Err(e) => tracing::error!("{}", e),
+        }
+    }
+    plugins
+}
+
+/// Reject engine names the registry does not know, listing the ones it does.
+fn check_engine(input_id: &str, engine: &str, available: &[&str]) -> Result<()> {
+    if !available.contains(&engine) {
+        anyhow::bail!(
+            "input '{}': unknown ASR engine '{}' (available: {})",
+            input_id,
+            engine,
+            available.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Fold a tone event into an input's displayed tone state.
+fn apply_tone_event(tones: &mut DecodedTones, event: &voxmux_core::ToneEvent) {
+    match event.kind {
+        voxmux_core::ToneEventKind::Dtmf { digit } => {
+            if tones.dtmf_digits.len() >= DTMF_DIGITS_SHOWN {
+                tones.dtmf_digits.remove(0);
+            }
+            tones.dtmf_digits.push(digit);
+        }
+        voxmux_core::ToneEventKind::CtcssStart { frequency_hz } => {
+            tones.ctcss_hz = Some(frequency_hz);
+        }
+        voxmux_core::ToneEventKind::CtcssEnd { frequency_hz } => {
+            if tones.ctcss_hz == Some(frequency_hz) {
+                tones.ctcss_hz = None;
+            }
+        }
+        voxmux_core::ToneEventKind::DcsStart { code } => tones.dcs_code = Some(code),
+        voxmux_core::ToneEventKind::DcsEnd { code } => {
+            if tones.dcs_code == Some(code) {
+                tones.dcs_code = None;
+            }
+        }
+    }
+}
+
+/// Fold a transmission event into an input's displayed activity.
+fn apply_transmission_event(
+    activity: &mut Activity,
+    event: voxmux_core::TransmissionEvent,
+) {
+    match event.phase {
+        voxmux_core::TransmissionPhase::Start => activity.transmitting = true,
+        voxmux_core::TransmissionPhase::End => {
+            activity.transmitting = false;
+            activity.last_heard = Some(event);
+        }
+    }
+}
+
+/// One tab-separated activity log line: UTC start time, input, event.
+fn format_activity_line(event: &voxmux_core::TransmissionEvent) -> String {
+    format!(
+        "{}\t{}\t{}",
+        format_utc(event.started_at),
+        event.input_id,
+        event
+    )
+}
+
+/// Format Unix seconds as an RFC 3339 UTC timestamp with milliseconds.
+fn format_utc(unix_secs: f64) -> String {
+    let millis = (unix_secs * 1000.0).round() as i64;
+    let (days, ms_of_day) = (millis.div_euclid(86_400_000), millis.rem_euclid(86_400_000));
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        ms_of_day / 3_600_000,
+        ms_of_day / 60_000 % 60,
+        ms_of_day / 1000 % 60,
+        ms_of_day % 1000
+    )
+}
+
+/// File name for a replay export, e.g. `replay-mic1-20261018T115045Z.wav`.
+fn replay_file_name(source: &str, unix_secs: f64) -> String {
+    let stamp: String = format_utc(unix_secs.floor())
+        .chars()
+        .filter(|c| !matches!(c, '-' | ':'))
+        .collect();
+    // Drop the milliseconds: "...T115045.000Z" → "...T115045Z"
+    let stamp = stamp.replace(".000Z", "Z");
+    let source: String = source
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!("replay-{}-{}.wav", source, stamp)
+}
+
+/// Current time as Unix seconds.
+fn unix_now() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
 /// Push a recognition string into the bounded buffer, dropping oldest if full.
 fn push_recognition(buf: &Arc<Mutex<VecDeque<String>>>, text: String) {
     if let Ok(mut q) = buf.lock() {
@@ -511,10 +1272,73 @@ fn push_recognition(buf: &Arc<Mutex<VecDeque<String>>>, text: String) {
     }
 }
 
+/// Check `result` against the alert rules, logging each alert and keeping it
+/// for the TUI. Returns the raised alerts.
+fn check_alerts(
+    rules: &Mutex<voxmux_text::AlertRules>,
+    log: &Mutex<VecDeque<(voxmux_text::Alert, Instant)>>,
+    result: &voxmux_core::RecognitionResult,
+) -> Vec<voxmux_text::Alert> {
+    let alerts = match rules.lock() {
+        Ok(mut rules) => rules.check(result),
+        Err(_) => return Vec::new(),
+    };
+    for alert in &alerts {
+        tracing::warn!(
+            rule = %alert.event.rule,
+            input_id = %alert.event.input_id,
+            matched = %alert.event.matched,
+            "alert: {}",
+            alert.event.text,
+        );
+        if let Ok(mut q) = log.lock() {
+            if q.len() >= ALERT_LOG_CAPACITY {
+                q.pop_front();
+            }
+            q.push_back((alert.clone(), Instant::now()));
+        }
+    }
+    alerts
+}
+
+/// What the announcer says for an alert.
+fn alert_speech(event: &voxmux_core::AlertEvent) -> String {
+    format!("alert {} on {}: {}", event.rule, event.input_id, event.text)
+}
+
+/// The global `[destinations]` settings for a route's plugin, overlaid with
+/// the route's own fields.
+fn route_config(
+    config: &voxmux_core::AppConfig,
+    route: &voxmux_core::config::DestinationRouteConfig,
+) -> toml::Value {
+    let mut merged = config
+        .destinations
+        .as_ref()
+        .and_then(|dests| dests.get(&route.plugin).cloned())
+        .unwrap_or_else(|| toml::Value::Table(Default::default()));
+    if let (Some(base), Some(extra)) = (merged.as_table_mut(), route.extra.as_table()) {
+        for (k, v) in extra {
+            base.insert(k.clone(), v.clone());
+        }
+    }
+    merged
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_engine() {
+        assert!(check_engine("mic1", "null", &["null", "whisper"]).is_ok());
+        let err = check_engine("rx", "wisper", &["null", "whisper"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "input 'rx': unknown ASR engine 'wisper' (available: null, whisper)"
+        );
+    }
+
     #[test]
     fn test_recognition_buffer_bounded() {
         let buf = Arc::new(Mutex::new(VecDeque::<String>::new()));
@@ -528,6 +1352,42 @@ mod tests {
         assert_eq!(q.back().unwrap(), "msg54");
     }
 
+    #[test]
+    fn test_apply_tone_event() {
+        let event = |kind| voxmux_core::ToneEvent {
+            input_id: "radio1".to_string(),
+            timestamp: 0.0,
+            kind,
+        };
+        let mut tones = DecodedTones::default();
+        for digit in "0123456789*#ABCD12".chars() {
+            apply_tone_event(&mut tones, &event(voxmux_core::ToneEventKind::Dtmf { digit }));
+        }
+        assert_eq!(tones.dtmf_digits, "23456789*#ABCD12");
+
+        apply_tone_event(
+            &mut tones,
+            &event(voxmux_core::ToneEventKind::CtcssStart { frequency_hz: 88.5 }),
+        );
+        assert_eq!(tones.ctcss_hz, Some(88.5));
+        apply_tone_event(
+            &mut tones,
+            &event(voxmux_core::ToneEventKind::CtcssEnd { frequency_hz: 88.5 }),
+        );
+        assert_eq!(tones.ctcss_hz, None);
+
+        apply_tone_event(
+            &mut tones,
+            &event(voxmux_core::ToneEventKind::DcsStart { code: 0o023 }),
+        );
+        assert_eq!(tones.dcs_code, Some(0o023));
+        apply_tone_event(
+            &mut tones,
+            &event(voxmux_core::ToneEventKind::DcsEnd { code: 0o023 }),
+        );
+        assert_eq!(tones.dcs_code, None);
+    }
+
     #[tokio::test]
     async fn test_recognition_forwarder() {
         let buf = Arc::new(Mutex::new(VecDeque::<String>::new()));
@@ -553,6 +1413,7 @@ mod tests {
                 input_id: "mic1".to_string(),
                 timestamp: 0.0,
                 is_final: true,
+                ..Default::default()
             })
             .unwrap();
 
@@ -570,4 +1431,152 @@ mod tests {
         assert_eq!(forwarded.text, "hello world");
         assert_eq!(forwarded.input_id, "mic1");
     }
+
+    #[test]
+    fn test_check_alerts_logs_bounded() {
+        let config = voxmux_core::AppConfig::from_toml_str(
+            "[[alerts]]\nname = \"distress\"\nkeywords = [\"mayday\"]\nwarn = false",
+        )
+        .unwrap();
+        let rules = Mutex::new(voxmux_text::AlertRules::new(&config.alerts).unwrap());
+        let log = Mutex::new(VecDeque::new());
+        let result = |utterance_id| voxmux_core::RecognitionResult {
+            input_id: "radio1".to_string(),
+            text: "mayday mayday".to_string(),
+            is_final: true,
+            utterance_id,
+            ..Default::default()
+        };
+
+        let alerts = check_alerts(&rules, &log, &result(1));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].event.rule, "distress");
+        assert_eq!(
+            alert_speech(&alerts[0].event),
+            "alert distress on radio1: mayday mayday"
+        );
+        assert!(check_alerts(&rules, &log, &result(1)).is_empty());
+
+        for id in 2..(ALERT_LOG_CAPACITY as u64 + 5) {
+            check_alerts(&rules, &log, &result(id));
+        }
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), ALERT_LOG_CAPACITY);
+        assert!(log.iter().all(|(alert, _)| alert.highlight && !alert.warn));
+    }
+
+    #[test]
+    fn test_route_config_overlays_route_fields() {
+        let config = voxmux_core::AppConfig::from_toml_str(
+            r#"
+[destinations.file]
+path = "all.txt"
+append = true
+
+[[alerts]]
+name = "distress"
+keywords = ["mayday"]
+destinations = [{ plugin = "file", path = "alerts.txt" }]
+"#,
+        )
+        .unwrap();
+        let merged = route_config(&config, &config.alerts[0].destinations[0]);
+        assert_eq!(merged["path"].as_str(), Some("alerts.txt"));
+        assert_eq!(merged["append"].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn test_transmission_event_updates_activity() {
+        let event = |phase| voxmux_core::TransmissionEvent {
+            input_id: "rx".to_string(),
+            phase,
+            started_at: 100.0,
+            duration_secs: 2.5,
+            peak_dbfs: -10.0,
+        };
+        let mut activity = Activity::default();
+        apply_transmission_event(&mut activity, event(voxmux_core::TransmissionPhase::Start));
+        assert!(activity.transmitting);
+        assert!(activity.last_heard.is_none());
+
+        apply_transmission_event(&mut activity, event(voxmux_core::TransmissionPhase::End));
+        assert!(!activity.transmitting);
+        assert_eq!(activity.last_heard.unwrap().duration_secs, 2.5);
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(0.0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format_utc(951_782_400.5), "2000-02-29T00:00:00.500Z");
+        assert_eq!(format_utc(1_792_324_245.25), "2026-10-18T11:50:45.250Z");
+    }
+
+    #[tokio::test]
+    async fn test_announcer_queues_synthesized_speech() {
+        let (prod, _cons) = voxmux_audio::create_ring_buffer(1024);
+        let player = voxmux_audio::ClipPlayer::new(48000, 2, prod);
+        let handle = player.handle();
+        let tts_config = voxmux_core::config::TtsConfig {
+            engine: "null".to_string(),
+            duck_db: -12.0,
+            volume: 1.0,
+            announce_input_errors: true,
+            exec: None,
+            schedule: Vec::new(),
+        };
+        let tx = spawn_announcer(&tts_config, handle.clone(), 48000, 2)
+            .await
+            .unwrap();
+        tx.send("hi".to_string()).unwrap();
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(2);
+        while !handle.is_playing() && std::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        // Null engine: 2 chars × 50 ms
+        let (label, secs) = handle.now_playing().unwrap();
+        assert_eq!(label, "hi");
+        assert!((secs - 0.1).abs() < 1e-3, "{secs}");
+    }
+
+    #[tokio::test]
+    async fn test_announcer_unknown_engine() {
+        let (prod, _cons) = voxmux_audio::create_ring_buffer(16);
+        let player = voxmux_audio::ClipPlayer::new(48000, 1, prod);
+        let tts_config = voxmux_core::config::TtsConfig {
+            engine: "nope".to_string(),
+            duck_db: -12.0,
+            volume: 1.0,
+            announce_input_errors: false,
+            exec: None,
+            schedule: Vec::new(),
+        };
+        assert!(spawn_announcer(&tts_config, player.handle(), 48000, 1)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_replay_file_name() {
+        assert_eq!(
+            replay_file_name("mic1", 1_792_324_245.75),
+            "replay-mic1-20261018T115045Z.wav"
+        );
+        assert_eq!(replay_file_name("a/b c", 0.0), "replay-a_b_c-19700101T000000Z.wav");
+    }
+
+    #[test]
+    fn test_format_activity_line() {
+        let event = voxmux_core::TransmissionEvent {
+            input_id: "rx".to_string(),
+            phase: voxmux_core::TransmissionPhase::End,
+            started_at: 0.0,
+            duration_secs: 3.0,
+            peak_dbfs: -6.0,
+        };
+        assert_eq!(
+            format_activity_line(&event),
+            "1970-01-01T00:00:00.000Z\trx\tTX end 3.0s peak -6.0 dBFS"
+        );
+    }
 }